@@ -0,0 +1,67 @@
+use bytes::{Bytes, BytesMut};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use nimblecache::resp::{frame::RespCommandFrame, types::RespType};
+use tokio_util::codec::Decoder;
+
+/// Number of bytes delivered to the decoder at a time, like the reads from a socket.
+const READ_SIZE: usize = 64 * 1024;
+
+/// Encodes the given command as an array of bulk strings.
+fn command(args: &[&[u8]]) -> Vec<u8> {
+    RespType::Array(
+        args.iter()
+            .map(|arg| RespType::BulkString(Bytes::copy_from_slice(arg)))
+            .collect(),
+    )
+    .to_bytes()
+    .to_vec()
+}
+
+/// Builds a pipeline of SET and GET commands, with values of the given size.
+fn pipeline(num_cmds: usize, value_size: usize) -> Vec<u8> {
+    let value = vec![b'x'; value_size];
+    let mut input = vec![];
+    for i in 0..num_cmds / 2 {
+        let key = format!("key:{}", i);
+        input.extend(command(&[b"SET", key.as_bytes(), &value]));
+        input.extend(command(&[b"GET", key.as_bytes()]));
+    }
+
+    input
+}
+
+/// Decodes all the commands in the input, which is delivered to the decoder in chunks.
+fn decode_all(input: &[u8]) -> usize {
+    let mut codec = RespCommandFrame::new();
+    let mut src = BytesMut::new();
+    let mut num_cmds = 0;
+
+    for chunk in input.chunks(READ_SIZE) {
+        src.extend_from_slice(chunk);
+        while let Some(cmd) = codec.decode(&mut src).unwrap() {
+            num_cmds += 1;
+            criterion::black_box(cmd);
+        }
+    }
+
+    num_cmds
+}
+
+fn pipelined_set_get(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipelined_set_get");
+
+    // (name, number of commands, size of the values)
+    let cases = [("small", 10_000, 16), ("1mb", 20, 1024 * 1024)];
+    for (name, num_cmds, value_size) in cases {
+        let input = pipeline(num_cmds, value_size);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &input, |b, input| {
+            b.iter(|| assert_eq!(decode_all(input), num_cmds))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, pipelined_set_get);
+criterion_main!(benches);
@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use bytes::Bytes;
+
+use crate::resp::types::RespType;
+
+use super::CommandError;
+
+/// Reads a command argument as an UTF-8 string.
+///
+/// # Arguments
+///
+/// * `arg` - The argument, which must be a `BulkString`.
+///
+/// * `name` - Name of the argument. This is used in the error messages.
+///
+/// # Returns
+///
+/// * `Ok(String)` if the argument is a bulk string with valid UTF-8 data.
+/// * `Err(CommandError)` otherwise.
+pub fn parse_string(arg: &RespType, name: &str) -> Result<String, CommandError> {
+    let bytes = parse_bytes(arg, name)?;
+
+    match String::from_utf8(bytes.to_vec()) {
+        Ok(s) => Ok(s),
+        Err(_) => Err(CommandError::Other(format!(
+            "Invalid argument. {} must be a valid UTF-8 string",
+            name
+        ))),
+    }
+}
+
+/// Reads a command argument as raw bytes. This is used for binary safe values.
+///
+/// # Arguments
+///
+/// * `arg` - The argument, which must be a `BulkString`.
+///
+/// * `name` - Name of the argument. This is used in the error messages.
+pub fn parse_bytes(arg: &RespType, name: &str) -> Result<Bytes, CommandError> {
+    match arg {
+        RespType::BulkString(b) => Ok(b.clone()),
+        _ => Err(CommandError::Other(format!(
+            "Invalid argument. {} must be a bulk string",
+            name
+        ))),
+    }
+}
+
+/// Reads a command argument as an integer. Any integer type which implements `FromStr`
+/// can be parsed, and the range of the type is validated as part of the parsing.
+///
+/// # Arguments
+///
+/// * `arg` - The argument, which must be a `BulkString`.
+///
+/// * `name` - Name of the argument. This is used in the error messages.
+pub fn parse_int<T: FromStr>(arg: &RespType, name: &str) -> Result<T, CommandError> {
+    let s = parse_string(arg, name)?;
+
+    match s.parse::<T>() {
+        Ok(n) => Ok(n),
+        Err(_) => Err(CommandError::Other(format!(
+            "{} should be an integer or is out of range",
+            name
+        ))),
+    }
+}
+
+/// Reads a command argument as a floating point number. `NaN` is not accepted.
+///
+/// # Arguments
+///
+/// * `arg` - The argument, which must be a `BulkString`.
+///
+/// * `name` - Name of the argument. This is used in the error messages.
+pub fn parse_float(arg: &RespType, name: &str) -> Result<f64, CommandError> {
+    let s = parse_string(arg, name)?;
+
+    match s.parse::<f64>() {
+        Ok(n) if !n.is_nan() => Ok(n),
+        _ => Err(CommandError::Other(format!(
+            "{} is not a valid float",
+            name
+        ))),
+    }
+}
+
+/// Builds the error returned when a command is invoked with the wrong number of arguments.
+pub fn wrong_num_args(cmd: &str) -> CommandError {
+    CommandError::Other(format!(
+        "Wrong number of arguments specified for '{}' command",
+        cmd
+    ))
+}
@@ -0,0 +1,93 @@
+use crate::resp::types::RespType;
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    client::ClientState,
+    CommandError,
+};
+
+/// The only user, whose password is set by the `requirepass` flag.
+const DEFAULT_USER: &str = "default";
+
+/// Represents the AUTH command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Auth {
+    username: Option<String>,
+    password: String,
+}
+
+impl Auth {
+    /// Creates a new `Auth` instance from the given arguments.
+    ///
+    /// Syntax: `AUTH [username] password`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the AUTH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Auth)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Auth, CommandError> {
+        let (username, password) = match args.len() {
+            1 => (None, parse_string(&args[0], "Password")?),
+            2 => (
+                Some(parse_string(&args[0], "Username")?),
+                parse_string(&args[1], "Password")?,
+            ),
+            _ => return Err(wrong_num_args("AUTH")),
+        };
+
+        Ok(Auth { username, password })
+    }
+
+    /// Executes the AUTH command.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The state of the connection, which is marked as authenticated.
+    ///
+    /// * `requirepass` - The password of the default user, if any.
+    ///
+    /// # Returns
+    ///
+    /// "OK" as a `SimpleString` if the credentials are valid, or an error otherwise.
+    pub fn apply(&self, client: &mut ClientState, requirepass: Option<&str>) -> RespType {
+        if self.username.is_none() && requirepass.is_none() {
+            return RespType::SimpleError(String::from(
+                "AUTH <password> called without any password configured for the default user. Are you sure your configuration is correct?",
+            ));
+        }
+
+        let username = self.username.as_deref().unwrap_or(DEFAULT_USER);
+        match check_credentials(username, &self.password, requirepass) {
+            Ok(_) => {
+                client.authenticated = true;
+                RespType::SimpleString(String::from("OK"))
+            }
+            Err(e) => RespType::SimpleError(e),
+        }
+    }
+}
+
+/// Checks the credentials of a user. Any password is accepted for the default user if
+/// there's no password configured.
+///
+/// # Returns
+///
+/// * `Ok(())` - If the credentials are valid.
+/// * `Err(String)` - The error to be sent to the client otherwise.
+pub fn check_credentials(
+    username: &str,
+    password: &str,
+    requirepass: Option<&str>,
+) -> Result<(), String> {
+    if username != DEFAULT_USER || requirepass.is_some_and(|p| p != password) {
+        return Err(String::from(
+            "WRONGPASS invalid username-password pair or user is disabled.",
+        ));
+    }
+
+    Ok(())
+}
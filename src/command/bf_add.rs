@@ -0,0 +1,92 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the BF.ADD and BF.MADD commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct BfAdd {
+    key: String,
+    items: Vec<Vec<u8>>,
+    multi: bool,
+}
+
+impl BfAdd {
+    /// Creates a new `BfAdd` instance from the given arguments.
+    ///
+    /// Syntax: `BF.ADD key item` or `BF.MADD key item [item ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `multi` - Set to `true` for BF.MADD command, which accepts multiple items.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BfAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, multi: bool) -> Result<BfAdd, CommandError> {
+        let cmd_name = if multi { "BF.MADD" } else { "BF.ADD" };
+        if args.len() < 2 || (!multi && args.len() != 2) {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut items = vec![];
+        for arg in args[1..].iter() {
+            items.push(parse_bytes(arg, "Item")?.to_vec());
+        }
+
+        Ok(BfAdd { key, items, multi })
+    }
+
+    /// Executes the BF.ADD or BF.MADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For each item, `true` is returned as a `Boolean` (1 as an `Integer` in RESP2) if the item
+    /// was added, or `false` if it may have been added already. An error is returned in place of the items which couldn't be added
+    /// since the filter is full. BF.ADD returns the result of its item, while BF.MADD returns an
+    /// `Array` with the results of all the items.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let results = match db.bf_add(&self.key, &self.items) {
+            Ok(results) => results,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut replies = results.into_iter().map(|added| match added {
+            Some(added) => RespType::Boolean(added),
+            None => RespType::SimpleError(String::from("Non scaling filter is full")),
+        });
+
+        if self.multi {
+            RespType::Array(replies.collect())
+        } else {
+            replies.next().unwrap_or(RespType::NullBulkString)
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let cmd_name = if self.multi { "BF.MADD" } else { "BF.ADD" };
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from(cmd_name)),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        for item in self.items.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(item.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
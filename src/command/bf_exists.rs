@@ -0,0 +1,72 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the BF.EXISTS and BF.MEXISTS commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct BfExists {
+    key: String,
+    items: Vec<Vec<u8>>,
+    multi: bool,
+}
+
+impl BfExists {
+    /// Creates a new `BfExists` instance from the given arguments.
+    ///
+    /// Syntax: `BF.EXISTS key item` or `BF.MEXISTS key item [item ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `multi` - Set to `true` for BF.MEXISTS command, which accepts multiple items.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BfExists)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, multi: bool) -> Result<BfExists, CommandError> {
+        let cmd_name = if multi { "BF.MEXISTS" } else { "BF.EXISTS" };
+        if args.len() < 2 || (!multi && args.len() != 2) {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut items = vec![];
+        for arg in args[1..].iter() {
+            items.push(parse_bytes(arg, "Item")?.to_vec());
+        }
+
+        Ok(BfExists { key, items, multi })
+    }
+
+    /// Executes the BF.EXISTS or BF.MEXISTS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For each item, `true` is returned as a `Boolean` (1 as an `Integer` in RESP2) if the item
+    /// may have been added, or `false` if it was definitely not added. BF.EXISTS returns the result of its item, while BF.MEXISTS
+    /// returns an `Array` with the results of all the items.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let results = match db.bf_exists(&self.key, &self.items) {
+            Ok(results) => results,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut replies = results.into_iter().map(RespType::Boolean);
+
+        if self.multi {
+            RespType::Array(replies.collect())
+        } else {
+            replies.next().unwrap_or(RespType::Boolean(false))
+        }
+    }
+}
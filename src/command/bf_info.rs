@@ -0,0 +1,117 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the BF.INFO command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct BfInfo {
+    key: String,
+    field: Option<BfInfoField>,
+}
+
+/// The field requested by BF.INFO.
+#[derive(Debug, Clone, Copy)]
+enum BfInfoField {
+    Capacity,
+    Size,
+    Filters,
+    Items,
+    Expansion,
+}
+
+impl BfInfo {
+    /// Creates a new `BfInfo` instance from the given arguments.
+    ///
+    /// Syntax: `BF.INFO key [CAPACITY | SIZE | FILTERS | ITEMS | EXPANSION]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BF.INFO command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BfInfo)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BfInfo, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(wrong_num_args("BF.INFO"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let field = match args.get(1) {
+            Some(arg) => Some(match parse_string(arg, "Field")?.to_lowercase().as_str() {
+                "capacity" => BfInfoField::Capacity,
+                "size" => BfInfoField::Size,
+                "filters" => BfInfoField::Filters,
+                "items" => BfInfoField::Items,
+                "expansion" => BfInfoField::Expansion,
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid information field",
+                    )))
+                }
+            }),
+            None => None,
+        };
+
+        Ok(BfInfo { key, field })
+    }
+
+    /// Executes the BF.INFO command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the names and values of all the fields, or an `Array` with just
+    /// the value if a field is requested. Expansion rate is returned as `NullBulkString` for
+    /// non-scaling filters. An error is returned if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let info = match db.bf_info(&self.key) {
+            Ok(Some(info)) => info,
+            Ok(None) => return RespType::SimpleError(String::from("Not found")),
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let expansion = match info.expansion {
+            Some(expansion) => RespType::Integer(expansion as i64),
+            None => RespType::NullBulkString,
+        };
+
+        let fields = [
+            ("Capacity", RespType::Integer(info.capacity as i64)),
+            ("Size", RespType::Integer(info.size as i64)),
+            ("Number of filters", RespType::Integer(info.filters as i64)),
+            (
+                "Number of items inserted",
+                RespType::Integer(info.items as i64),
+            ),
+            ("Expansion rate", expansion),
+        ];
+
+        let idx = match self.field {
+            Some(BfInfoField::Capacity) => 0,
+            Some(BfInfoField::Size) => 1,
+            Some(BfInfoField::Filters) => 2,
+            Some(BfInfoField::Items) => 3,
+            Some(BfInfoField::Expansion) => 4,
+            None => {
+                let mut reply = vec![];
+                for (name, value) in fields {
+                    reply.push(RespType::SimpleString(String::from(name)));
+                    reply.push(value);
+                }
+                return RespType::Array(reply);
+            }
+        };
+
+        let (_, value) = fields[idx].clone();
+        RespType::Array(vec![value])
+    }
+}
@@ -0,0 +1,132 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_float, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the BF.RESERVE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct BfReserve {
+    key: String,
+    error_rate: f64,
+    capacity: u64,
+    /// `None` for non-scaling filters.
+    expansion: Option<u32>,
+}
+
+impl BfReserve {
+    /// Creates a new `BfReserve` instance from the given arguments.
+    ///
+    /// Syntax: `BF.RESERVE key error_rate capacity [EXPANSION expansion] [NONSCALING]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BF.RESERVE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BfReserve)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BfReserve, CommandError> {
+        if args.len() < 3 {
+            return Err(wrong_num_args("BF.RESERVE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let error_rate = parse_float(&args[1], "Error rate")?;
+        if error_rate <= 0.0 || error_rate >= 1.0 {
+            return Err(CommandError::Other(String::from(
+                "Error rate must be between 0 and 1",
+            )));
+        }
+
+        let capacity = parse_int::<u64>(&args[2], "Capacity")?;
+        if capacity == 0 {
+            return Err(CommandError::Other(String::from(
+                "Capacity must be a positive integer",
+            )));
+        }
+
+        let mut expansion = Some(2);
+        let mut nonscaling = false;
+        let mut idx = 3;
+
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "expansion" => {
+                    let arg = args
+                        .get(idx + 1)
+                        .ok_or_else(|| wrong_num_args("BF.RESERVE"))?;
+                    let rate = parse_int::<u32>(arg, "Expansion")?;
+                    if rate == 0 {
+                        return Err(CommandError::Other(String::from(
+                            "Expansion must be a positive integer",
+                        )));
+                    }
+                    expansion = Some(rate);
+                    idx += 2;
+                }
+                "nonscaling" => {
+                    nonscaling = true;
+                    idx += 1;
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        if nonscaling {
+            expansion = None;
+        }
+
+        Ok(BfReserve {
+            key,
+            error_rate,
+            capacity,
+            expansion,
+        })
+    }
+
+    /// Executes the BF.RESERVE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 'OK' as a `SimpleString` if the filter is created, or an error if the key
+    /// already exists.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.bf_reserve(&self.key, self.error_rate, self.capacity, self.expansion) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("BF.RESERVE")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.error_rate.to_string())),
+            RespType::BulkString(Bytes::from(self.capacity.to_string())),
+        ];
+
+        match self.expansion {
+            Some(expansion) => {
+                cmd.push(RespType::BulkString(Bytes::from("EXPANSION")));
+                cmd.push(RespType::BulkString(Bytes::from(expansion.to_string())));
+            }
+            None => cmd.push(RespType::BulkString(Bytes::from("NONSCALING"))),
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    resp::types::RespType,
+    storage::{
+        bitmap::{BitRange, BitRangeUnit},
+        db::DB,
+    },
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the BITCOUNT command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct BitCount {
+    key: String,
+    range: Option<BitRange>,
+}
+
+impl BitCount {
+    /// Creates a new `BitCount` instance from the given arguments.
+    ///
+    /// Syntax: `BITCOUNT key [start end [BYTE | BIT]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BITCOUNT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BitCount)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BitCount, CommandError> {
+        if args.is_empty() || args.len() == 2 || args.len() > 4 {
+            return Err(wrong_num_args("BITCOUNT"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut range = None;
+        if args.len() > 1 {
+            let start = parse_int::<i64>(&args[1], "Start index")?;
+            let end = parse_int::<i64>(&args[2], "End index")?;
+            let unit = match args.get(3) {
+                Some(arg) => parse_range_unit(arg)?,
+                None => BitRangeUnit::Byte,
+            };
+
+            range = Some(BitRange {
+                start,
+                end: Some(end),
+                unit,
+            });
+        }
+
+        Ok(BitCount { key, range })
+    }
+
+    /// Executes the BITCOUNT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of bits set to 1 as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.bitcount(&self.key, self.range) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
+
+/// Parse the unit (`BYTE` or `BIT`) in which the indices of a bit range are specified.
+pub fn parse_range_unit(arg: &RespType) -> Result<BitRangeUnit, CommandError> {
+    let unit = parse_string(arg, "Range unit")?;
+
+    match unit.to_lowercase().as_str() {
+        "byte" => Ok(BitRangeUnit::Byte),
+        "bit" => Ok(BitRangeUnit::Bit),
+        _ => Err(CommandError::Other(String::from(
+            "Invalid range unit. Range unit should be either BYTE or BIT",
+        ))),
+    }
+}
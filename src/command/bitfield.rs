@@ -0,0 +1,250 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        bitmap::{BitfieldOp, BitfieldOverflow, BitfieldType, MAX_BIT_OFFSET},
+        db::DB,
+    },
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the BITFIELD and BITFIELD_RO commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Bitfield {
+    key: String,
+    ops: Vec<BitfieldOp>,
+}
+
+impl Bitfield {
+    /// Creates a new `Bitfield` instance from the given arguments.
+    ///
+    /// Syntax: `BITFIELD key [GET encoding offset | [OVERFLOW <WRAP | SAT | FAIL>]
+    /// <SET encoding offset value | INCRBY encoding offset increment> [...]]`
+    ///
+    /// Encoding is the type of the field, prefixed with `i` for signed and `u` for unsigned
+    /// integers (e.g. `i8`, `u4`). Offset can be prefixed with `#`, in which case it's
+    /// multiplied by the width of the field.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `read_only` - Set to `true` for BITFIELD_RO command, which only accepts GET operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Bitfield)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, read_only: bool) -> Result<Bitfield, CommandError> {
+        let cmd_name = if read_only { "BITFIELD_RO" } else { "BITFIELD" };
+        if args.is_empty() {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut ops = vec![];
+        let mut overflow = BitfieldOverflow::Wrap;
+        let mut idx = 1;
+
+        while idx < args.len() {
+            let sub_cmd = parse_string(&args[idx], "Subcommand")?.to_lowercase();
+            let num_args = match sub_cmd.as_str() {
+                "overflow" => 1,
+                "get" => 2,
+                "set" | "incrby" => 3,
+                _ => {
+                    return Err(CommandError::Other(format!(
+                        "Invalid subcommand '{}' for '{}' command",
+                        sub_cmd, cmd_name
+                    )))
+                }
+            };
+
+            if read_only && sub_cmd != "get" {
+                return Err(CommandError::Other(String::from(
+                    "BITFIELD_RO only supports the GET subcommand",
+                )));
+            }
+
+            if idx + num_args >= args.len() {
+                return Err(wrong_num_args(cmd_name));
+            }
+
+            if sub_cmd == "overflow" {
+                overflow = match parse_string(&args[idx + 1], "Overflow")?
+                    .to_lowercase()
+                    .as_str()
+                {
+                    "wrap" => BitfieldOverflow::Wrap,
+                    "sat" => BitfieldOverflow::Sat,
+                    "fail" => BitfieldOverflow::Fail,
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Invalid OVERFLOW type specified",
+                        )))
+                    }
+                };
+                idx += num_args + 1;
+                continue;
+            }
+
+            let ty = parse_type(&args[idx + 1])?;
+            let offset = parse_offset(&args[idx + 2], ty)?;
+
+            let op = match sub_cmd.as_str() {
+                "get" => BitfieldOp::Get { ty, offset },
+                "set" => BitfieldOp::Set {
+                    ty,
+                    offset,
+                    value: parse_int::<i64>(&args[idx + 3], "Value")?,
+                    overflow,
+                },
+                _ => BitfieldOp::IncrBy {
+                    ty,
+                    offset,
+                    incr: parse_int::<i64>(&args[idx + 3], "Increment")?,
+                    overflow,
+                },
+            };
+
+            ops.push(op);
+            idx += num_args + 1;
+        }
+
+        Ok(Bitfield { key, ops })
+    }
+
+    /// Executes the BITFIELD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the result of each operation. GET returns the value of the
+    /// field, SET returns the old value of the field and INCRBY returns the new value of the field.
+    /// A `NullBulkString` is returned for the operations that failed due to an overflow.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.bitfield(&self.key, &self.ops) {
+            Ok(results) => RespType::Array(
+                results
+                    .into_iter()
+                    .map(|r| match r {
+                        Some(v) => RespType::Integer(v),
+                        None => RespType::NullBulkString,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Checks if the command modifies the string value (ie, has SET or INCRBY operations).
+    pub fn is_write(&self) -> bool {
+        self.ops
+            .iter()
+            .any(|op| !matches!(op, BitfieldOp::Get { .. }))
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("BITFIELD")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        for op in self.ops.iter() {
+            let (name, ty, offset, arg, overflow) = match *op {
+                BitfieldOp::Get { ty, offset } => ("GET", ty, offset, None, None),
+                BitfieldOp::Set {
+                    ty,
+                    offset,
+                    value,
+                    overflow,
+                } => ("SET", ty, offset, Some(value), Some(overflow)),
+                BitfieldOp::IncrBy {
+                    ty,
+                    offset,
+                    incr,
+                    overflow,
+                } => ("INCRBY", ty, offset, Some(incr), Some(overflow)),
+            };
+
+            if let Some(overflow) = overflow {
+                let overflow = match overflow {
+                    BitfieldOverflow::Wrap => "WRAP",
+                    BitfieldOverflow::Sat => "SAT",
+                    BitfieldOverflow::Fail => "FAIL",
+                };
+                cmd.push(RespType::BulkString(Bytes::from("OVERFLOW")));
+                cmd.push(RespType::BulkString(Bytes::from(overflow)));
+            }
+
+            let ty = format!("{}{}", if ty.signed { "i" } else { "u" }, ty.bits);
+            cmd.push(RespType::BulkString(Bytes::from(name)));
+            cmd.push(RespType::BulkString(Bytes::from(ty)));
+            cmd.push(RespType::BulkString(Bytes::from(offset.to_string())));
+            if let Some(arg) = arg {
+                cmd.push(RespType::BulkString(Bytes::from(arg.to_string())));
+            }
+        }
+
+        RespType::Array(cmd)
+    }
+}
+
+/// Parse the encoding of a field, like `i8` or `u16`.
+fn parse_type(arg: &RespType) -> Result<BitfieldType, CommandError> {
+    let invalid_type_err = || {
+        CommandError::Other(String::from(
+            "Invalid bitfield type. Use something like i16 u8. Note that u64 is not supported but i64 is",
+        ))
+    };
+
+    let ty = parse_string(arg, "Encoding")?.to_lowercase();
+    let signed = match ty.chars().next() {
+        Some('i') => true,
+        Some('u') => false,
+        _ => return Err(invalid_type_err()),
+    };
+
+    let bits = match ty[1..].parse::<u8>() {
+        Ok(b) => b,
+        Err(_) => return Err(invalid_type_err()),
+    };
+
+    let max_bits = if signed { 64 } else { 63 };
+    if bits < 1 || bits > max_bits {
+        return Err(invalid_type_err());
+    }
+
+    Ok(BitfieldType { signed, bits })
+}
+
+/// Parse the bit offset of a field. Offsets prefixed with `#` are multiplied by the width of the field.
+fn parse_offset(arg: &RespType, ty: BitfieldType) -> Result<u64, CommandError> {
+    let invalid_offset_err =
+        || CommandError::Other(String::from("Bit offset is not an integer or out of range"));
+
+    let offset = parse_string(arg, "Offset")?;
+    let offset = match offset.strip_prefix('#') {
+        Some(o) => o.parse::<u64>().map(|o| o.saturating_mul(ty.bits as u64)),
+        None => offset.parse::<u64>(),
+    };
+
+    match offset {
+        Ok(o)
+            if o.checked_add(ty.bits as u64)
+                .is_some_and(|end| end <= MAX_BIT_OFFSET) =>
+        {
+            Ok(o)
+        }
+        _ => Err(invalid_offset_err()),
+    }
+}
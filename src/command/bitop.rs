@@ -0,0 +1,104 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{bitmap::BitOp as Op, db::DB},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the BITOP command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct BitOp {
+    op: Op,
+    dest_key: String,
+    keys: Vec<String>,
+}
+
+impl BitOp {
+    /// Creates a new `BitOp` instance from the given arguments.
+    ///
+    /// Syntax: `BITOP <AND | OR | XOR | NOT> destkey key [key ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BITOP command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BitOp)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BitOp, CommandError> {
+        if args.len() < 3 {
+            return Err(wrong_num_args("BITOP"));
+        }
+
+        let op = parse_string(&args[0], "Operation")?;
+        let op = match op.to_lowercase().as_str() {
+            "and" => Op::And,
+            "or" => Op::Or,
+            "xor" => Op::Xor,
+            "not" => Op::Not,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid operation. Operation should be one of AND, OR, XOR or NOT",
+                )))
+            }
+        };
+
+        let dest_key = parse_string(&args[1], "Destination key")?;
+
+        let mut keys = vec![];
+        for arg in args[2..].iter() {
+            keys.push(parse_string(arg, "Key")?);
+        }
+
+        if matches!(op, Op::Not) && keys.len() != 1 {
+            return Err(CommandError::Other(String::from(
+                "BITOP NOT must be called with a single source key",
+            )));
+        }
+
+        Ok(BitOp { op, dest_key, keys })
+    }
+
+    /// Executes the BITOP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the length of the string stored in the destination key as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.bitop(self.op, &self.dest_key, &self.keys) {
+            Ok(len) => RespType::Integer(len as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let op = match self.op {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+            Op::Not => "NOT",
+        };
+
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("BITOP")),
+            RespType::BulkString(Bytes::from(op)),
+            RespType::BulkString(Bytes::from(self.dest_key.clone())),
+        ];
+
+        for key in self.keys.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(key.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
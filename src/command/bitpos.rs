@@ -0,0 +1,86 @@
+use crate::{
+    resp::types::RespType,
+    storage::{
+        bitmap::{BitRange, BitRangeUnit},
+        db::DB,
+    },
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    bitcount::parse_range_unit,
+    CommandError,
+};
+
+/// Represents the BITPOS command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct BitPos {
+    key: String,
+    bit: u8,
+    range: Option<BitRange>,
+}
+
+impl BitPos {
+    /// Creates a new `BitPos` instance from the given arguments.
+    ///
+    /// Syntax: `BITPOS key bit [start [end [BYTE | BIT]]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the BITPOS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BitPos)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<BitPos, CommandError> {
+        if args.len() < 2 || args.len() > 5 {
+            return Err(wrong_num_args("BITPOS"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let bit = match parse_int::<u8>(&args[1], "Bit") {
+            Ok(b) if b <= 1 => b,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "The bit argument must be 1 or 0",
+                )))
+            }
+        };
+
+        let mut range = None;
+        if args.len() > 2 {
+            let start = parse_int::<i64>(&args[2], "Start index")?;
+            let end = match args.get(3) {
+                Some(arg) => Some(parse_int::<i64>(arg, "End index")?),
+                None => None,
+            };
+            let unit = match args.get(4) {
+                Some(arg) => parse_range_unit(arg)?,
+                None => BitRangeUnit::Byte,
+            };
+
+            range = Some(BitRange { start, end, unit });
+        }
+
+        Ok(BitPos { key, bit, range })
+    }
+
+    /// Executes the BITPOS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the position of the first bit set to the specified value as an `Integer`,
+    /// or -1 if no such bit is found.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.bitpos(&self.key, self.bit, self.range) {
+            Ok(pos) => RespType::Integer(pos),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
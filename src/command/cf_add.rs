@@ -0,0 +1,69 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CF.ADD and CF.ADDNX commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CfAdd {
+    key: String,
+    item: Vec<u8>,
+    nx: bool,
+}
+
+impl CfAdd {
+    /// Creates a new `CfAdd` instance from the given arguments.
+    ///
+    /// Syntax: `CF.ADD key item` or `CF.ADDNX key item`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `nx` - Set to `true` for CF.ADDNX command, which adds the item only if it doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CfAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, nx: bool) -> Result<CfAdd, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args(if nx { "CF.ADDNX" } else { "CF.ADD" }));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let item = parse_bytes(&args[1], "Item")?.to_vec();
+
+        Ok(CfAdd { key, item, nx })
+    }
+
+    /// Executes the CF.ADD or CF.ADDNX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns `true` as a `Boolean` (1 as an `Integer` in RESP2) if the item was added, or
+    /// `false` if CF.ADDNX finds that the item may exist already. An error is returned if the
+    /// filter is full.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cf_add(&self.key, &self.item, self.nx) {
+            Ok(added) => RespType::Boolean(added),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from(if self.nx { "CF.ADDNX" } else { "CF.ADD" })),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.item.clone())),
+        ])
+    }
+}
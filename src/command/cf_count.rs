@@ -0,0 +1,55 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CF.COUNT command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CfCount {
+    key: String,
+    item: Vec<u8>,
+}
+
+impl CfCount {
+    /// Creates a new `CfCount` instance from the given arguments.
+    ///
+    /// Syntax: `CF.COUNT key item`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CF.COUNT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CfCount)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CfCount, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("CF.COUNT"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let item = parse_bytes(&args[1], "Item")?.to_vec();
+
+        Ok(CfCount { key, item })
+    }
+
+    /// Executes the CF.COUNT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of times the item may have been added as an `Integer`. The count
+    /// can be higher than the actual number, since other items may share the fingerprint.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cf_count(&self.key, &self.item) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
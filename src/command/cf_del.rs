@@ -0,0 +1,65 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CF.DEL command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CfDel {
+    key: String,
+    item: Vec<u8>,
+}
+
+impl CfDel {
+    /// Creates a new `CfDel` instance from the given arguments.
+    ///
+    /// Syntax: `CF.DEL key item`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CF.DEL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CfDel)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CfDel, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("CF.DEL"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let item = parse_bytes(&args[1], "Item")?.to_vec();
+
+        Ok(CfDel { key, item })
+    }
+
+    /// Executes the CF.DEL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 1 as an `Integer` if an occurrence of the item was deleted, or 0 if the item
+    /// is not found. An error is returned if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cf_del(&self.key, &self.item) {
+            Ok(deleted) => RespType::Integer(deleted as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("CF.DEL")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.item.clone())),
+        ])
+    }
+}
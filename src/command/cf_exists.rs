@@ -0,0 +1,55 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CF.EXISTS command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CfExists {
+    key: String,
+    item: Vec<u8>,
+}
+
+impl CfExists {
+    /// Creates a new `CfExists` instance from the given arguments.
+    ///
+    /// Syntax: `CF.EXISTS key item`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CF.EXISTS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CfExists)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CfExists, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("CF.EXISTS"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let item = parse_bytes(&args[1], "Item")?.to_vec();
+
+        Ok(CfExists { key, item })
+    }
+
+    /// Executes the CF.EXISTS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns `true` as a `Boolean` (1 as an `Integer` in RESP2) if the item may have been
+    /// added, or `false` if it was definitely not added.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cf_exists(&self.key, &self.item) {
+            Ok(exists) => RespType::Boolean(exists),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
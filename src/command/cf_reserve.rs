@@ -0,0 +1,147 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        cuckoo::{CF_DEFAULT_BUCKET_SIZE, CF_DEFAULT_EXPANSION, CF_DEFAULT_MAX_ITERATIONS},
+        db::DB,
+    },
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Max number of items in each bucket of a Cuckoo filter.
+const CF_MAX_BUCKET_SIZE: usize = 255;
+/// Max growth rate of the sub-filters of a Cuckoo filter.
+const CF_MAX_EXPANSION: u32 = 32768;
+
+/// Represents the CF.RESERVE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CfReserve {
+    key: String,
+    capacity: u64,
+    bucket_size: usize,
+    max_iterations: u32,
+    expansion: u32,
+}
+
+impl CfReserve {
+    /// Creates a new `CfReserve` instance from the given arguments.
+    ///
+    /// Syntax: `CF.RESERVE key capacity [BUCKETSIZE bucketsize] [MAXITERATIONS maxiterations]
+    /// [EXPANSION expansion]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CF.RESERVE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CfReserve)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CfReserve, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("CF.RESERVE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let capacity = parse_int::<u64>(&args[1], "Capacity")?;
+        if capacity == 0 {
+            return Err(CommandError::Other(String::from(
+                "Capacity must be a positive integer",
+            )));
+        }
+
+        let mut bucket_size = CF_DEFAULT_BUCKET_SIZE;
+        let mut max_iterations = CF_DEFAULT_MAX_ITERATIONS;
+        let mut expansion = CF_DEFAULT_EXPANSION;
+
+        for opt in args[2..].chunks(2) {
+            let name = parse_string(&opt[0], "Option")?.to_lowercase();
+            let value = opt.get(1).ok_or_else(|| wrong_num_args("CF.RESERVE"))?;
+
+            match name.as_str() {
+                "bucketsize" => {
+                    bucket_size = parse_int(value, "Bucket size")?;
+                    if bucket_size == 0 || bucket_size > CF_MAX_BUCKET_SIZE {
+                        return Err(CommandError::Other(format!(
+                            "Bucket size must be between 1 and {}",
+                            CF_MAX_BUCKET_SIZE
+                        )));
+                    }
+                }
+                "maxiterations" => {
+                    max_iterations = parse_int(value, "Max iterations")?;
+                    if max_iterations == 0 {
+                        return Err(CommandError::Other(String::from(
+                            "Max iterations must be a positive integer",
+                        )));
+                    }
+                }
+                "expansion" => {
+                    expansion = parse_int(value, "Expansion")?;
+                    if expansion > CF_MAX_EXPANSION {
+                        return Err(CommandError::Other(format!(
+                            "Expansion must be between 0 and {}",
+                            CF_MAX_EXPANSION
+                        )));
+                    }
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        Ok(CfReserve {
+            key,
+            capacity,
+            bucket_size,
+            max_iterations,
+            expansion,
+        })
+    }
+
+    /// Executes the CF.RESERVE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 'OK' as a `SimpleString` if the filter is created, or an error if the key
+    /// already exists.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cf_reserve(
+            &self.key,
+            self.capacity,
+            self.bucket_size,
+            self.max_iterations,
+            self.expansion,
+        ) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("CF.RESERVE")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.capacity.to_string())),
+            RespType::BulkString(Bytes::from("BUCKETSIZE")),
+            RespType::BulkString(Bytes::from(self.bucket_size.to_string())),
+            RespType::BulkString(Bytes::from("MAXITERATIONS")),
+            RespType::BulkString(Bytes::from(self.max_iterations.to_string())),
+            RespType::BulkString(Bytes::from("EXPANSION")),
+            RespType::BulkString(Bytes::from(self.expansion.to_string())),
+        ])
+    }
+}
@@ -0,0 +1,299 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::{ProtocolVersion, RespType},
+    tracking::{Tracking, TrackingOptions},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// The state of a client connection, which is managed by the CLIENT, HELLO and AUTH
+/// commands.
+#[derive(Debug)]
+pub struct ClientState {
+    /// The unique id of the connection.
+    pub id: u64,
+    /// The name of the connection, set by CLIENT SETNAME or HELLO.
+    pub name: Option<String>,
+    /// Set to `true` once the connection is authenticated, or if no password is required.
+    pub authenticated: bool,
+    /// The version of RESP used by the connection.
+    pub version: ProtocolVersion,
+    /// Set by CLIENT CACHING, for the next command of the connection.
+    pub caching: Option<bool>,
+}
+
+/// Represents the CLIENT command in Nimblecache, which manages the state of the connection.
+#[derive(Debug, Clone)]
+pub struct Client {
+    sub_cmd: SubCommand,
+}
+
+/// The subcommands of the CLIENT command.
+#[derive(Debug, Clone)]
+enum SubCommand {
+    /// The id of the connection.
+    Id,
+    /// Sets the name of the connection, or removes it if the name is empty.
+    SetName(String),
+    /// The name of the connection.
+    GetName,
+    /// Enables tracking of the keys cached by the client with the options, or disables it.
+    Tracking(Option<TrackingOptions>),
+    /// Whether the keys read by the next command are tracked (OPTIN and OPTOUT modes).
+    Caching(bool),
+    /// The id of the client to which the invalidation messages are redirected.
+    GetRedir,
+    /// The tracking options of the connection.
+    TrackingInfo,
+}
+
+impl Client {
+    /// Creates a new `Client` instance from the given arguments.
+    ///
+    /// Syntax: `CLIENT ID`, `CLIENT SETNAME connection-name`, `CLIENT GETNAME`,
+    /// `CLIENT TRACKING <ON | OFF> [REDIRECT client-id] [PREFIX prefix [PREFIX prefix ...]]
+    /// [BCAST] [OPTIN] [OPTOUT] [NOLOOP]`, `CLIENT CACHING <YES | NO>`, `CLIENT GETREDIR` or
+    /// `CLIENT TRACKINGINFO`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CLIENT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Client)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Client, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("CLIENT"));
+        }
+
+        let sub_cmd = parse_string(&args[0], "Subcommand")?;
+        let sub_cmd = match sub_cmd.to_lowercase().as_str() {
+            "id" if args.len() == 1 => SubCommand::Id,
+            "setname" if args.len() == 2 => SubCommand::SetName(parse_name(&args[1])?),
+            "getname" if args.len() == 1 => SubCommand::GetName,
+            "getredir" if args.len() == 1 => SubCommand::GetRedir,
+            "trackinginfo" if args.len() == 1 => SubCommand::TrackingInfo,
+            "tracking" if args.len() >= 2 => SubCommand::Tracking(parse_tracking(&args[1..])?),
+            "caching" if args.len() == 2 => {
+                let mode = parse_string(&args[1], "Mode")?;
+                match mode.to_lowercase().as_str() {
+                    "yes" => SubCommand::Caching(true),
+                    "no" => SubCommand::Caching(false),
+                    _ => return Err(CommandError::Other(String::from("syntax error"))),
+                }
+            }
+            "id" | "setname" | "getname" | "getredir" | "trackinginfo" | "tracking" | "caching" => {
+                return Err(wrong_num_args(&format!(
+                    "CLIENT {}",
+                    sub_cmd.to_uppercase()
+                )))
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Invalid subcommand '{}' for 'CLIENT' command",
+                    sub_cmd
+                )))
+            }
+        };
+
+        Ok(Client { sub_cmd })
+    }
+
+    /// Executes the CLIENT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The state of the connection.
+    ///
+    /// * `tracking` - The keys cached by the clients.
+    ///
+    /// # Returns
+    ///
+    /// * ID - The id of the connection as an `Integer`.
+    /// * SETNAME - "OK" as a `SimpleString`.
+    /// * GETNAME - The name of the connection as a `BulkString`, or `Null` if it has none.
+    /// * TRACKING and CACHING - "OK" as a `SimpleString`, or an error if the options are
+    ///   invalid.
+    /// * GETREDIR - The id of the client to which the invalidation messages are redirected
+    ///   as an `Integer`, `0` if they're not redirected, or `-1` if tracking is disabled.
+    /// * TRACKINGINFO - A `Map` of the tracking flags (as a `Set`), the id of the client to
+    ///   which the invalidation messages are redirected (as in GETREDIR), and the prefixes.
+    pub fn apply(&self, client: &mut ClientState, tracking: &Tracking) -> RespType {
+        let client_id = client.id;
+        match &self.sub_cmd {
+            SubCommand::Id => RespType::Integer(client_id as i64),
+            SubCommand::SetName(name) => {
+                client.name = Some(name.clone()).filter(|name| !name.is_empty());
+                RespType::SimpleString(String::from("OK"))
+            }
+            SubCommand::GetName => match &client.name {
+                Some(name) => RespType::BulkString(Bytes::from(name.clone())),
+                None => RespType::Null,
+            },
+            SubCommand::Tracking(Some(options)) => {
+                match tracking.enable(client_id, options.clone()) {
+                    Ok(_) => RespType::SimpleString(String::from("OK")),
+                    Err(e) => RespType::SimpleError(e),
+                }
+            }
+            SubCommand::Tracking(None) => {
+                tracking.disable(client_id);
+                RespType::SimpleString(String::from("OK"))
+            }
+            SubCommand::Caching(yes) => match tracking.options(client_id) {
+                Some(options) if options.optin && *yes => {
+                    client.caching = Some(true);
+                    RespType::SimpleString(String::from("OK"))
+                }
+                Some(options) if options.optout && !*yes => {
+                    client.caching = Some(false);
+                    RespType::SimpleString(String::from("OK"))
+                }
+                Some(options) if options.optin || options.optout => {
+                    RespType::SimpleError(String::from(
+                        "CLIENT CACHING YES is only valid in OPTIN mode, and CLIENT CACHING NO in OPTOUT mode",
+                    ))
+                }
+                _ => RespType::SimpleError(String::from(
+                    "CLIENT CACHING can be called only when the client is in tracking mode with OPTIN or OPTOUT mode enabled",
+                )),
+            },
+            SubCommand::GetRedir => match tracking.options(client_id) {
+                Some(options) => RespType::Integer(options.redirect.unwrap_or(0) as i64),
+                None => RespType::Integer(-1),
+            },
+            SubCommand::TrackingInfo => tracking_info(client, tracking),
+        }
+    }
+}
+
+/// Reads the name of the connection. Names can't contain spaces, newlines or special
+/// characters, since they're listed along with the other connections.
+pub fn parse_name(arg: &RespType) -> Result<String, CommandError> {
+    let name = parse_string(arg, "Connection name")?;
+    if name.chars().any(|c| !('!'..='~').contains(&c)) {
+        return Err(CommandError::Other(String::from(
+            "Client names cannot contain spaces, newlines or special characters.",
+        )));
+    }
+
+    Ok(name)
+}
+
+/// Builds the reply of the TRACKINGINFO subcommand.
+fn tracking_info(client: &ClientState, tracking: &Tracking) -> RespType {
+    let options = match tracking.options(client.id) {
+        Some(options) => options,
+        None => {
+            return RespType::Map(vec![
+                (
+                    RespType::BulkString(Bytes::from("flags")),
+                    RespType::Set(vec![RespType::BulkString(Bytes::from("off"))]),
+                ),
+                (
+                    RespType::BulkString(Bytes::from("redirect")),
+                    RespType::Integer(-1),
+                ),
+                (
+                    RespType::BulkString(Bytes::from("prefixes")),
+                    RespType::Array(vec![]),
+                ),
+            ])
+        }
+    };
+
+    let mut flags = vec!["on"];
+    if options.bcast {
+        flags.push("bcast");
+    }
+    if options.optin {
+        flags.push("optin");
+        if client.caching == Some(true) {
+            flags.push("caching-yes");
+        }
+    }
+    if options.optout {
+        flags.push("optout");
+        if client.caching == Some(false) {
+            flags.push("caching-no");
+        }
+    }
+    if options.noloop {
+        flags.push("noloop");
+    }
+    if options
+        .redirect
+        .is_some_and(|id| !tracking.is_connected(id))
+    {
+        flags.push("broken_redirect");
+    }
+
+    RespType::Map(vec![
+        (
+            RespType::BulkString(Bytes::from("flags")),
+            RespType::Set(
+                flags
+                    .into_iter()
+                    .map(|f| RespType::BulkString(Bytes::from(f)))
+                    .collect(),
+            ),
+        ),
+        (
+            RespType::BulkString(Bytes::from("redirect")),
+            RespType::Integer(options.redirect.unwrap_or(0) as i64),
+        ),
+        (
+            RespType::BulkString(Bytes::from("prefixes")),
+            RespType::Array(
+                options
+                    .prefixes
+                    .into_iter()
+                    .map(|p| RespType::BulkString(Bytes::from(p)))
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+/// Reads the arguments of the TRACKING subcommand.
+///
+/// # Returns
+///
+/// The tracking options if tracking is turned on, or `None` if it's turned off.
+fn parse_tracking(args: &[RespType]) -> Result<Option<TrackingOptions>, CommandError> {
+    let on = match parse_string(&args[0], "Status")?.to_lowercase().as_str() {
+        "on" => true,
+        "off" => false,
+        _ => return Err(CommandError::Other(String::from("syntax error"))),
+    };
+
+    let mut options = TrackingOptions::default();
+    let mut i = 1;
+    while i < args.len() {
+        let opt = parse_string(&args[i], "Option")?;
+        match opt.to_lowercase().as_str() {
+            "redirect" if i + 1 < args.len() => {
+                options.redirect = Some(parse_int(&args[i + 1], "Client ID")?);
+                i += 1;
+            }
+            "prefix" if i + 1 < args.len() => {
+                options.prefixes.push(parse_string(&args[i + 1], "Prefix")?);
+                i += 1;
+            }
+            "bcast" => options.bcast = true,
+            "optin" => options.optin = true,
+            "optout" => options.optout = true,
+            "noloop" => options.noloop = true,
+            _ => return Err(CommandError::Other(String::from("syntax error"))),
+        }
+        i += 1;
+    }
+
+    Ok(on.then_some(options))
+}
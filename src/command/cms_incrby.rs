@@ -0,0 +1,82 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CMS.INCRBY command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CmsIncrBy {
+    key: String,
+    items: Vec<(Vec<u8>, u64)>,
+}
+
+impl CmsIncrBy {
+    /// Creates a new `CmsIncrBy` instance from the given arguments.
+    ///
+    /// Syntax: `CMS.INCRBY key item increment [item increment ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CMS.INCRBY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CmsIncrBy)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CmsIncrBy, CommandError> {
+        let pairs = args.get(1..).unwrap_or_default().chunks_exact(2);
+        if pairs.len() == 0 || !pairs.remainder().is_empty() {
+            return Err(wrong_num_args("CMS.INCRBY"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut items = vec![];
+        for pair in pairs {
+            let item = parse_bytes(&pair[0], "Item")?.to_vec();
+            let incr = parse_int::<u64>(&pair[1], "Increment")?;
+            items.push((item, incr));
+        }
+
+        Ok(CmsIncrBy { key, items })
+    }
+
+    /// Executes the CMS.INCRBY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the new count of each item as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cms_incrby(&self.key, &self.items) {
+            Ok(counts) => RespType::Array(
+                counts
+                    .into_iter()
+                    .map(|c| RespType::Integer(c as i64))
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("CMS.INCRBY")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        for (item, incr) in self.items.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(item.clone())));
+            cmd.push(RespType::BulkString(Bytes::from(incr.to_string())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
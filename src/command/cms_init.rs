@@ -0,0 +1,104 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{count_min_sketch::CountMinSketch, db::DB},
+};
+
+use super::{
+    args::{parse_float, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CMS.INITBYDIM and CMS.INITBYPROB commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CmsInit {
+    key: String,
+    width: usize,
+    depth: usize,
+}
+
+impl CmsInit {
+    /// Creates a new `CmsInit` instance from the given arguments.
+    ///
+    /// Syntax: `CMS.INITBYDIM key width depth` or `CMS.INITBYPROB key error probability`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `by_prob` - Set to `true` for CMS.INITBYPROB command, where the dimensions are derived
+    ///   from the acceptable overestimation (as a fraction of the total count) and the probability
+    ///   of exceeding it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CmsInit)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, by_prob: bool) -> Result<CmsInit, CommandError> {
+        if args.len() != 3 {
+            return Err(wrong_num_args(if by_prob {
+                "CMS.INITBYPROB"
+            } else {
+                "CMS.INITBYDIM"
+            }));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let (width, depth) = if by_prob {
+            let error = parse_float(&args[1], "Error")?;
+            let probability = parse_float(&args[2], "Probability")?;
+            if error <= 0.0 || error >= 1.0 || probability <= 0.0 || probability >= 1.0 {
+                return Err(CommandError::Other(String::from(
+                    "Error and probability must be between 0 and 1",
+                )));
+            }
+            CountMinSketch::dimensions_for(error, probability)
+        } else {
+            let width = parse_int::<usize>(&args[1], "Width")?;
+            let depth = parse_int::<usize>(&args[2], "Depth")?;
+            if width == 0 || depth == 0 {
+                return Err(CommandError::Other(String::from(
+                    "Width and depth must be positive integers",
+                )));
+            }
+            (width, depth)
+        };
+
+        if width.checked_mul(depth).is_none() {
+            return Err(CommandError::Other(String::from(
+                "Width and depth are too large",
+            )));
+        }
+
+        Ok(CmsInit { key, width, depth })
+    }
+
+    /// Executes the CMS.INITBYDIM or CMS.INITBYPROB command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 'OK' as a `SimpleString` if the sketch is created, or an error if the key
+    /// already exists.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cms_init(&self.key, self.width, self.depth) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Both the commands are replicated as CMS.INITBYDIM, with the dimensions computed on master.
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("CMS.INITBYDIM")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.width.to_string())),
+            RespType::BulkString(Bytes::from(self.depth.to_string())),
+        ])
+    }
+}
@@ -0,0 +1,102 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CMS.MERGE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CmsMerge {
+    dest: String,
+    /// The source keys and their weights.
+    sources: Vec<(String, u64)>,
+}
+
+impl CmsMerge {
+    /// Creates a new `CmsMerge` instance from the given arguments.
+    ///
+    /// Syntax: `CMS.MERGE destination numkeys source [source ...] [WEIGHTS weight [weight ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CMS.MERGE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CmsMerge)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CmsMerge, CommandError> {
+        if args.len() < 3 {
+            return Err(wrong_num_args("CMS.MERGE"));
+        }
+
+        let dest = parse_string(&args[0], "Destination")?;
+
+        let num_keys = parse_int::<usize>(&args[1], "Number of keys")?;
+        if num_keys == 0 || args.len() < 2 + num_keys {
+            return Err(wrong_num_args("CMS.MERGE"));
+        }
+
+        let mut sources = vec![];
+        for arg in args[2..2 + num_keys].iter() {
+            sources.push((parse_string(arg, "Source")?, 1));
+        }
+
+        let rest = &args[2 + num_keys..];
+        if !rest.is_empty() {
+            if parse_string(&rest[0], "Option")?.to_lowercase() != "weights" {
+                return Err(CommandError::Other(String::from(
+                    "Invalid option specified",
+                )));
+            }
+            if rest.len() != num_keys + 1 {
+                return Err(wrong_num_args("CMS.MERGE"));
+            }
+
+            for (source, arg) in sources.iter_mut().zip(rest[1..].iter()) {
+                source.1 = parse_int(arg, "Weight")?;
+            }
+        }
+
+        Ok(CmsMerge { dest, sources })
+    }
+
+    /// Executes the CMS.MERGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 'OK' as a `SimpleString` if the sketches are merged, or an error if any of the
+    /// keys doesn't exist or if the dimensions of the sketches are not equal.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cms_merge(&self.dest, &self.sources) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("CMS.MERGE")),
+            RespType::BulkString(Bytes::from(self.dest.clone())),
+            RespType::BulkString(Bytes::from(self.sources.len().to_string())),
+        ];
+
+        for (source, _) in self.sources.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(source.clone())));
+        }
+
+        cmd.push(RespType::BulkString(Bytes::from("WEIGHTS")));
+        for (_, weight) in self.sources.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(weight.to_string())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the CMS.QUERY command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct CmsQuery {
+    key: String,
+    items: Vec<Vec<u8>>,
+}
+
+impl CmsQuery {
+    /// Creates a new `CmsQuery` instance from the given arguments.
+    ///
+    /// Syntax: `CMS.QUERY key item [item ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the CMS.QUERY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CmsQuery)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<CmsQuery, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("CMS.QUERY"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut items = vec![];
+        for arg in args[1..].iter() {
+            items.push(parse_bytes(arg, "Item")?.to_vec());
+        }
+
+        Ok(CmsQuery { key, items })
+    }
+
+    /// Executes the CMS.QUERY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the count of each item as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.cms_query(&self.key, &self.items) {
+            Ok(counts) => RespType::Array(
+                counts
+                    .into_iter()
+                    .map(|c| RespType::Integer(c as i64))
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{Storage, DB},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the COPY command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Copy {
+    src: String,
+    dest: String,
+    db_index: Option<usize>,
+    replace: bool,
+}
+
+impl Copy {
+    /// Creates a new `Copy` instance from the given arguments.
+    ///
+    /// Syntax: `COPY source destination [DB destination-db] [REPLACE]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the COPY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Copy)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Copy, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("COPY"));
+        }
+
+        let src = parse_string(&args[0], "Source")?;
+        let dest = parse_string(&args[1], "Destination")?;
+
+        let mut db_index = None;
+        let mut replace = false;
+        let mut idx = 2;
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "db" => {
+                    let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args("COPY"))?;
+                    db_index = Some(parse_int::<usize>(arg, "DB index")?);
+                    idx += 2;
+                }
+                "replace" => {
+                    replace = true;
+                    idx += 1;
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        Ok(Copy {
+            src,
+            dest,
+            db_index,
+            replace,
+        })
+    }
+
+    /// Executes the COPY command. The expiry of the source key is copied too.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The databases.
+    ///
+    /// * `db_index` - The number of the database selected by the connection.
+    ///
+    /// * `db` - The database selected by the connection.
+    ///
+    /// # Returns
+    ///
+    /// It returns `1` as an `Integer` if the value is copied, or `0` if the source key doesn't
+    /// exist or the destination key already exists (without REPLACE).
+    pub fn apply(&self, storage: &Storage, db_index: usize, db: &DB) -> RespType {
+        let res = match self.db_index {
+            Some(dest_db) if dest_db != db_index => {
+                storage.copy_to(db_index, &self.src, dest_db, &self.dest, self.replace)
+            }
+            _ => db.copy(&self.src, &self.dest, self.replace),
+        };
+
+        match res {
+            Ok(copied) => RespType::Integer(copied as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("COPY")),
+            RespType::BulkString(Bytes::from(self.src.clone())),
+            RespType::BulkString(Bytes::from(self.dest.clone())),
+        ];
+
+        if let Some(db_index) = self.db_index {
+            cmd.push(RespType::BulkString(Bytes::from("DB")));
+            cmd.push(RespType::BulkString(Bytes::from(db_index.to_string())));
+        }
+        if self.replace {
+            cmd.push(RespType::BulkString(Bytes::from("REPLACE")));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{args::wrong_num_args, CommandError};
+
+/// Represents the DBSIZE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct DbSize;
+
+impl DbSize {
+    /// Creates a new `DbSize` instance from the given arguments.
+    ///
+    /// Syntax: `DBSIZE`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the DBSIZE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DbSize)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<DbSize, CommandError> {
+        if !args.is_empty() {
+            return Err(wrong_num_args("DBSIZE"));
+        }
+
+        Ok(DbSize)
+    }
+
+    /// Executes the DBSIZE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of keys in the DB as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.dbsize() {
+            Ok(size) => RespType::Integer(size as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use bytes::Bytes;
+
 use crate::{resp::types::RespType, storage::db::DB};
 
-use super::CommandError;
+use super::{args::parse_string, CommandError};
 
 /// Represents the DEL command in Nimblecache.
 #[derive(Debug, Clone)]
@@ -29,13 +31,7 @@ impl Del {
         let mut keys: Vec<String> = vec![];
         for key in args.iter() {
             // validate if all keys are BulkStrings
-            if let RespType::BulkString(k) = key {
-                keys.push(k.clone());
-            } else {
-                return Err(CommandError::Other(
-                    "Invalid argument. Key must be a bulk string".to_string(),
-                ));
-            }
+            keys.push(parse_string(key, "Key")?);
         }
 
         Ok(Del { keys })
@@ -58,10 +54,10 @@ impl Del {
     }
 
     pub fn build_command(&self) -> RespType {
-        let mut cmd = vec![RespType::BulkString(String::from("DEL"))];
+        let mut cmd = vec![RespType::BulkString(Bytes::from("DEL"))];
 
         for key in self.keys.iter() {
-            cmd.push(RespType::BulkString(key.to_string()));
+            cmd.push(RespType::BulkString(Bytes::from(key.to_string())));
         }
 
         RespType::Array(cmd)
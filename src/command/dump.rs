@@ -0,0 +1,54 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the DUMP command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Dump {
+    key: String,
+}
+
+impl Dump {
+    /// Creates a new `Dump` instance from the given arguments.
+    ///
+    /// Syntax: `DUMP key`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the DUMP command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Dump)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Dump, CommandError> {
+        if args.len() != 1 {
+            return Err(wrong_num_args("DUMP"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        Ok(Dump { key })
+    }
+
+    /// Executes the DUMP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The serialized value as a `BulkString`, which can be passed to RESTORE, or a
+    /// `NullBulkString` if the key doesn't exist. The expiry of the key is not included.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.dump(&self.key) {
+            Ok(Some(payload)) => RespType::BulkString(payload.into()),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
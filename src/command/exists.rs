@@ -0,0 +1,56 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the EXISTS command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Exists {
+    keys: Vec<String>,
+}
+
+impl Exists {
+    /// Creates a new `Exists` instance from the given arguments.
+    ///
+    /// Syntax: `EXISTS key [key ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the EXISTS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Exists)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Exists, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("EXISTS"));
+        }
+
+        let keys = args
+            .iter()
+            .map(|arg| parse_string(arg, "Key"))
+            .collect::<Result<Vec<String>, CommandError>>()?;
+
+        Ok(Exists { keys })
+    }
+
+    /// Executes the EXISTS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of given keys which exist as an `Integer`. A key which is given
+    /// more than once is counted each time.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.exists(&self.keys) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
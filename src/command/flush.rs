@@ -0,0 +1,92 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::db::{Storage, DB},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the FLUSHDB and FLUSHALL commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Flush {
+    all: bool,
+    lazy: bool,
+}
+
+impl Flush {
+    /// Creates a new `Flush` instance from the given arguments.
+    ///
+    /// Syntax: `FLUSHDB [ASYNC | SYNC]` or `FLUSHALL [ASYNC | SYNC]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `all` - Set to `true` for FLUSHALL command, which deletes the keys of all the
+    ///   databases.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Flush)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, all: bool) -> Result<Flush, CommandError> {
+        let lazy = match args.as_slice() {
+            [] => false,
+            [mode] => match parse_string(mode, "Mode")?.to_lowercase().as_str() {
+                "async" => true,
+                "sync" => false,
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            },
+            _ => {
+                let cmd_name = if all { "FLUSHALL" } else { "FLUSHDB" };
+                return Err(wrong_num_args(cmd_name));
+            }
+        };
+
+        Ok(Flush { all, lazy })
+    }
+
+    /// Executes the FLUSHDB or FLUSHALL command. With ASYNC, the memory of the deleted keys
+    /// is freed in the background.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The databases.
+    ///
+    /// * `db` - The database selected by the connection.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` once the keys are deleted.
+    pub fn apply(&self, storage: &Storage, db: &DB) -> RespType {
+        let res = match self.all {
+            true => storage
+                .dbs()
+                .and_then(|dbs| dbs.iter().try_for_each(|db| db.flush(self.lazy))),
+            false => db.flush(self.lazy),
+        };
+
+        match res {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let cmd_name = if self.all { "FLUSHALL" } else { "FLUSHDB" };
+        let mut cmd = vec![RespType::BulkString(Bytes::from(cmd_name))];
+        if self.lazy {
+            cmd.push(RespType::BulkString(Bytes::from("ASYNC")));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,440 @@
+use std::ops::Bound;
+
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        search::{KnnQuery, QueryNode},
+        search_fields::tokenize,
+    },
+};
+
+use super::{
+    args::{parse_bytes, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Reads the `PARAMS nargs name value [name value ...]` option, starting at the `nargs`
+/// argument at `idx`. The index is moved past the parameters.
+pub fn parse_params(
+    args: &[RespType],
+    idx: &mut usize,
+    cmd: &str,
+) -> Result<Vec<(String, Bytes)>, CommandError> {
+    let nargs = args.get(*idx).ok_or_else(|| wrong_num_args(cmd))?;
+    let nargs = parse_int::<usize>(nargs, "Number of parameters")?;
+    let end = *idx + 1 + nargs;
+    if end > args.len() {
+        return Err(wrong_num_args(cmd));
+    }
+
+    let pairs = args[*idx + 1..end].chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(CommandError::Other(String::from(
+            "Each parameter must have a value",
+        )));
+    }
+
+    let mut params = vec![];
+    for pair in pairs {
+        params.push((
+            parse_string(&pair[0], "Parameter")?,
+            parse_bytes(&pair[1], "Parameter value")?,
+        ));
+    }
+    *idx = end;
+
+    Ok(params)
+}
+
+/// Reads a field referred to by a command argument, as `@field` or `field`.
+pub fn parse_field_ref(arg: &RespType) -> Result<String, CommandError> {
+    let field = parse_string(arg, "Field")?;
+    Ok(field.strip_prefix('@').unwrap_or(&field).to_string())
+}
+
+/// Parses a search query.
+///
+/// Terms are matched in all the TEXT fields, and documents must match all of them. The
+/// query syntax is:
+///
+/// * `a | b` matches either of the expressions, and `-a` negates an expression.
+/// * `(...)` groups expressions.
+/// * `"a b"` matches the terms as a phrase, and `ab*` matches the terms with the prefix.
+/// * `*` matches all the documents.
+/// * `@field:expr` matches a term, prefix, phrase or group in the TEXT field.
+/// * `@field:[min max]` matches the values in the range in the NUMERIC field, where `(`
+///   excludes a bound and `-inf` and `+inf` are the unbounded values.
+/// * `@field:{a | b}` matches any of the tags in the TAG field.
+/// * `$name` is replaced by the value of the parameter.
+///
+/// A query can end with a K nearest neighbours clause, as `query=>[KNN k @field $param
+/// [EF_RUNTIME ef] [AS scoreField]]`, where the query vector is passed as a parameter.
+///
+/// # Returns
+///
+/// * `Ok((QueryNode, Option<(KnnQuery, String)>))` - The query, and the KNN clause with the
+///   name of the score field, if any.
+/// * `Err(CommandError)` - if the query is invalid.
+pub fn parse_query(
+    query: &str,
+    params: &[(String, Bytes)],
+) -> Result<(QueryNode, Option<(KnnQuery, String)>), CommandError> {
+    let (filter, knn) = match query.split_once("=>") {
+        Some((filter, knn)) => (filter, Some(parse_knn(query, knn, params)?)),
+        None => (query, None),
+    };
+
+    let mut parser = QueryParser {
+        query,
+        chars: filter.chars().collect(),
+        pos: 0,
+        params,
+    };
+    let node = parser.parse_or(None)?;
+    parser.skip_whitespace();
+    if parser.pos < parser.chars.len() {
+        return Err(parser.syntax_err());
+    }
+
+    Ok((node, knn))
+}
+
+/// A recursive descent parser of the query syntax.
+struct QueryParser<'a> {
+    /// The whole query, for the error messages.
+    query: &'a str,
+    chars: Vec<char>,
+    pos: usize,
+    params: &'a [(String, Bytes)],
+}
+
+impl QueryParser<'_> {
+    /// Parses expressions separated by `|`. Text is searched in the field if given, else in
+    /// all the TEXT fields.
+    fn parse_or(&mut self, field: Option<&str>) -> Result<QueryNode, CommandError> {
+        let mut nodes = vec![self.parse_and(field)?];
+        while self.eat('|') {
+            nodes.push(self.parse_and(field)?);
+        }
+
+        Ok(match nodes.len() {
+            1 => nodes.remove(0),
+            _ => QueryNode::Or(nodes),
+        })
+    }
+
+    /// Parses a sequence of expressions, which must all match.
+    fn parse_and(&mut self, field: Option<&str>) -> Result<QueryNode, CommandError> {
+        let mut nodes = vec![];
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some(')') | Some('|') => break,
+                _ => nodes.push(self.parse_unary(field)?),
+            }
+        }
+
+        match nodes.len() {
+            0 => Err(self.syntax_err()),
+            1 => Ok(nodes.remove(0)),
+            _ => Ok(QueryNode::And(nodes)),
+        }
+    }
+
+    fn parse_unary(&mut self, field: Option<&str>) -> Result<QueryNode, CommandError> {
+        if self.eat('-') {
+            return Ok(QueryNode::Not(Box::new(self.parse_unary(field)?)));
+        }
+
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                let node = self.parse_or(field)?;
+                if !self.eat(')') {
+                    return Err(self.syntax_err());
+                }
+                Ok(node)
+            }
+            Some('"') => {
+                self.pos += 1;
+                let phrase = self.read_until('"')?;
+                let terms = tokenize(&phrase);
+                if terms.is_empty() {
+                    return Err(self.syntax_err());
+                }
+                Ok(QueryNode::Phrase(field.map(String::from), terms))
+            }
+            Some('*') => {
+                self.pos += 1;
+                Ok(QueryNode::All)
+            }
+            Some('@') if field.is_none() => {
+                self.pos += 1;
+                let name = self.read_word();
+                if name.is_empty() || !self.eat(':') {
+                    return Err(self.syntax_err());
+                }
+                self.parse_field_expr(&name)
+            }
+            _ => self.parse_term(field),
+        }
+    }
+
+    /// Parses the expression after `@field:`.
+    fn parse_field_expr(&mut self, field: &str) -> Result<QueryNode, CommandError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => {
+                self.pos += 1;
+                let range = self.read_until(']')?;
+                let bounds: Vec<&str> = range
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|b| !b.is_empty())
+                    .collect();
+                if bounds.len() != 2 {
+                    return Err(self.syntax_err());
+                }
+
+                Ok(QueryNode::Numeric(
+                    field.to_string(),
+                    self.parse_bound(bounds[0])?,
+                    self.parse_bound(bounds[1])?,
+                ))
+            }
+            Some('{') => {
+                self.pos += 1;
+                let tags = self.read_until('}')?;
+                let mut values = vec![];
+                for tag in split_tags(&tags) {
+                    let tag = self.resolve(tag.trim())?;
+                    if !tag.is_empty() {
+                        values.push(tag);
+                    }
+                }
+                if values.is_empty() {
+                    return Err(self.syntax_err());
+                }
+
+                Ok(QueryNode::Tag(field.to_string(), values))
+            }
+            _ => self.parse_unary(Some(field)),
+        }
+    }
+
+    /// Parses a term, or a prefix if the term ends with `*`. A term with several words (like
+    /// `well-known`) matches the words as a phrase.
+    fn parse_term(&mut self, field: Option<&str>) -> Result<QueryNode, CommandError> {
+        let word = self.read_word();
+        if word.is_empty() {
+            return Err(self.syntax_err());
+        }
+
+        let mut terms = tokenize(&self.resolve(&word)?);
+        let field = field.map(String::from);
+        if self.eat_adjacent('*') {
+            return match terms.len() {
+                1 => Ok(QueryNode::Prefix(field, terms.remove(0))),
+                _ => Err(self.syntax_err()),
+            };
+        }
+
+        match terms.len() {
+            0 => Err(self.syntax_err()),
+            1 => Ok(QueryNode::Term(field, terms.remove(0))),
+            _ => Ok(QueryNode::Phrase(field, terms)),
+        }
+    }
+
+    /// Parses a bound of a numeric range.
+    fn parse_bound(&self, bound: &str) -> Result<Bound<f64>, CommandError> {
+        let (bound, exclusive) = match bound.strip_prefix('(') {
+            Some(bound) => (bound, true),
+            None => (bound, false),
+        };
+        let bound = self.resolve(bound)?;
+
+        let value = match bound.to_lowercase().as_str() {
+            "-inf" | "+inf" | "inf" => return Ok(Bound::Unbounded),
+            _ => bound
+                .parse::<f64>()
+                .map_err(|_| CommandError::Other(format!("Bad numeric range bound '{}'", bound)))?,
+        };
+
+        Ok(match exclusive {
+            true => Bound::Excluded(value),
+            false => Bound::Included(value),
+        })
+    }
+
+    /// Returns the value of the parameter if the token is `$name`, else the token.
+    fn resolve(&self, token: &str) -> Result<String, CommandError> {
+        let name = match token.strip_prefix('$') {
+            Some(name) => name,
+            None => return Ok(token.to_string()),
+        };
+
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| String::from_utf8_lossy(v).to_string())
+            .ok_or_else(|| CommandError::Other(format!("No such parameter '{}'", name)))
+    }
+
+    /// Reads a word, which ends at whitespace or at a character with a meaning in the query
+    /// syntax. `\` escapes the next character.
+    fn read_word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '\\' if self.pos + 1 < self.chars.len() => {
+                    word.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                    continue;
+                }
+                '(' | ')' | '|' | '"' | '@' | '*' | '[' | ']' | '{' | '}' | ':' => break,
+                c if c.is_whitespace() => break,
+                c => word.push(c),
+            }
+            self.pos += 1;
+        }
+
+        word
+    }
+
+    /// Reads the characters till the delimiter, and moves past the delimiter.
+    fn read_until(&mut self, delimiter: char) -> Result<String, CommandError> {
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some('\\') if self.pos + 1 < self.chars.len() => {
+                    value.push('\\');
+                    value.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                }
+                Some(c) if c == delimiter => {
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(self.syntax_err()),
+            }
+        }
+    }
+
+    /// Moves past the character if it's next, after any whitespace.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        self.eat_adjacent(c)
+    }
+
+    /// Moves past the character if it's right at the current position.
+    fn eat_adjacent(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn syntax_err(&self) -> CommandError {
+        CommandError::Other(format!("Syntax error in query '{}'", self.query))
+    }
+}
+
+/// Splits the tags of a tag expression by `|`, and unescapes them.
+fn split_tags(tags: &str) -> Vec<String> {
+    let mut split = vec![String::new()];
+    let mut chars = tags.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => split.last_mut().unwrap().extend(chars.next()),
+            '|' => split.push(String::new()),
+            c => split.last_mut().unwrap().push(c),
+        }
+    }
+
+    split
+}
+
+/// Parses the K nearest neighbours clause, `[KNN k @field $param [EF_RUNTIME ef]
+/// [AS scoreField]]`, and returns it with the name of the score field.
+fn parse_knn(
+    query: &str,
+    knn: &str,
+    params: &[(String, Bytes)],
+) -> Result<(KnnQuery, String), CommandError> {
+    let syntax_err = || CommandError::Other(format!("Syntax error in query '{}'", query));
+
+    let knn = knn
+        .trim()
+        .strip_prefix('[')
+        .and_then(|k| k.strip_suffix(']'))
+        .ok_or_else(syntax_err)?;
+    let tokens: Vec<&str> = knn.split_whitespace().collect();
+    if tokens.len() < 4 || !tokens[0].eq_ignore_ascii_case("knn") {
+        return Err(syntax_err());
+    }
+
+    let param = |token: &str| -> Result<Bytes, CommandError> {
+        let name = token.strip_prefix('$').ok_or_else(syntax_err)?;
+        params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.clone())
+            .ok_or_else(|| CommandError::Other(format!("No such parameter '{}'", name)))
+    };
+    let number = |token: &str, name: &str| -> Result<usize, CommandError> {
+        let value = match token.starts_with('$') {
+            true => param(token)?,
+            false => Bytes::from(token.to_string()),
+        };
+        parse_int::<usize>(&RespType::BulkString(value), name)
+    };
+
+    let k = number(tokens[1], "K")?;
+    let field = tokens[2]
+        .strip_prefix('@')
+        .ok_or_else(syntax_err)?
+        .to_string();
+    let vector = param(tokens[3])?.to_vec();
+
+    let mut ef_runtime = None;
+    let mut score_field = format!("__{}_score", field);
+    let mut rest = tokens[4..].chunks_exact(2);
+    for pair in rest.by_ref() {
+        match pair[0].to_lowercase().as_str() {
+            "ef_runtime" => ef_runtime = Some(number(pair[1], "EF_RUNTIME")?),
+            "as" => score_field = pair[1].to_string(),
+            _ => return Err(syntax_err()),
+        }
+    }
+    if !rest.remainder().is_empty() {
+        return Err(syntax_err());
+    }
+
+    Ok((
+        KnnQuery {
+            field,
+            k,
+            vector,
+            ef_runtime,
+        },
+        score_field,
+    ))
+}
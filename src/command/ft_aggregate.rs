@@ -0,0 +1,292 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        search::{AggregateStep, QueryNode, ReduceFunction, Reducer, RowValue},
+    },
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    ft::{parse_field_ref, parse_params, parse_query},
+    CommandError,
+};
+
+/// Represents the FT.AGGREGATE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct FtAggregate {
+    index: String,
+    filter: QueryNode,
+    load: Vec<String>,
+    steps: Vec<AggregateStep>,
+}
+
+impl FtAggregate {
+    /// Creates a new `FtAggregate` instance from the given arguments.
+    ///
+    /// Syntax: `FT.AGGREGATE index query [LOAD {* | count field [field ...]}]
+    /// [GROUPBY nargs field [field ...] [REDUCE function nargs arg [arg ...] [AS name] ...]]
+    /// [SORTBY nargs field [ASC | DESC] [field [ASC | DESC] ...] [MAX num]]
+    /// [LIMIT offset num] [PARAMS nargs name value [name value ...]] [DIALECT dialect]`
+    ///
+    /// GROUPBY, SORTBY and LIMIT are the steps of the pipeline, which can be repeated and are
+    /// applied in order. Fields are referred to as `@field`.
+    ///
+    /// Reduce functions are `COUNT`, `COUNT_DISTINCT`, `SUM`, `MIN`, `MAX`, `AVG` and `TOLIST`,
+    /// which take the field to be reduced (except COUNT).
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the FT.AGGREGATE
+    ///   command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FtAggregate)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<FtAggregate, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("FT.AGGREGATE"));
+        }
+
+        let index = parse_string(&args[0], "Index")?;
+        let query = parse_string(&args[1], "Query")?;
+
+        let mut load = vec![];
+        let mut steps = vec![];
+        let mut params = vec![];
+
+        let mut idx = 2;
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "load" => {
+                    idx += 1;
+                    let all = args
+                        .get(idx)
+                        .map(|arg| parse_string(arg, "Field"))
+                        .transpose()?;
+                    if all.as_deref() == Some("*") {
+                        load.push(String::from("*"));
+                        idx += 1;
+                        continue;
+                    }
+
+                    let fields = counted_args(&args, &mut idx)?;
+                    for arg in fields.iter() {
+                        load.push(parse_field_ref(arg)?);
+                    }
+                }
+                "groupby" => {
+                    idx += 1;
+                    let fields = counted_args(&args, &mut idx)?
+                        .iter()
+                        .map(parse_field_ref)
+                        .collect::<Result<Vec<String>, CommandError>>()?;
+
+                    let mut reducers = vec![];
+                    while let Some(arg) = args.get(idx) {
+                        if parse_string(arg, "Option")?.to_lowercase() != "reduce" {
+                            break;
+                        }
+                        idx += 1;
+                        reducers.push(parse_reducer(&args, &mut idx)?);
+                    }
+
+                    steps.push(AggregateStep::GroupBy { fields, reducers });
+                }
+                "sortby" => {
+                    idx += 1;
+                    let mut fields: Vec<(String, bool)> = vec![];
+                    for arg in counted_args(&args, &mut idx)? {
+                        let arg = parse_string(arg, "Field")?;
+                        match (arg.to_lowercase().as_str(), fields.last_mut()) {
+                            ("asc", Some((_, asc))) => *asc = true,
+                            ("desc", Some((_, asc))) => *asc = false,
+                            _ => match arg.strip_prefix('@') {
+                                Some(field) => fields.push((field.to_string(), true)),
+                                None => {
+                                    return Err(CommandError::Other(format!(
+                                        "Bad SORTBY field '{}'",
+                                        arg
+                                    )))
+                                }
+                            },
+                        }
+                    }
+
+                    let mut max = None;
+                    if let Some(arg) = args.get(idx) {
+                        if parse_string(arg, "Option")?.to_lowercase() == "max" {
+                            let num = args
+                                .get(idx + 1)
+                                .ok_or_else(|| wrong_num_args("FT.AGGREGATE"))?;
+                            max = Some(parse_int::<usize>(num, "Max")?);
+                            idx += 2;
+                        }
+                    }
+
+                    steps.push(AggregateStep::SortBy { fields, max });
+                }
+                "limit" => {
+                    if args.len() < idx + 3 {
+                        return Err(wrong_num_args("FT.AGGREGATE"));
+                    }
+                    let offset = parse_int::<usize>(&args[idx + 1], "Offset")?;
+                    let num = parse_int::<usize>(&args[idx + 2], "Limit")?;
+                    steps.push(AggregateStep::Limit { offset, num });
+                    idx += 3;
+                }
+                "params" => {
+                    idx += 1;
+                    params = parse_params(&args, &mut idx, "FT.AGGREGATE")?;
+                }
+                "dialect" => {
+                    let dialect = args
+                        .get(idx + 1)
+                        .ok_or_else(|| wrong_num_args("FT.AGGREGATE"))?;
+                    parse_int::<u32>(dialect, "Dialect")?;
+                    idx += 2;
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        let filter = match parse_query(&query, &params)? {
+            (filter, None) => filter,
+            (_, Some(_)) => {
+                return Err(CommandError::Other(String::from(
+                    "KNN queries are not supported by FT.AGGREGATE",
+                )))
+            }
+        };
+
+        Ok(FtAggregate {
+            index,
+            filter,
+            load,
+            steps,
+        })
+    }
+
+    /// Executes the FT.AGGREGATE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the number of resulting rows as an `Integer`, followed by
+    /// each row as an `Array` of field-value pairs. Values are `BulkString`s, except the
+    /// results of TOLIST which are `Array`s of `BulkString`s.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let rows = match db.ft_aggregate(&self.index, &self.filter, &self.load, &self.steps) {
+            Ok(rows) => rows,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut reply = vec![RespType::Integer(rows.len() as i64)];
+        for row in rows {
+            reply.push(RespType::Array(
+                row.into_iter()
+                    .flat_map(|(field, value)| {
+                        let value = match value {
+                            RowValue::Value(value) => RespType::BulkString(Bytes::from(value)),
+                            RowValue::List(values) => RespType::Array(
+                                values
+                                    .into_iter()
+                                    .map(|v| RespType::BulkString(Bytes::from(v)))
+                                    .collect(),
+                            ),
+                        };
+                        [RespType::BulkString(Bytes::from(field)), value]
+                    })
+                    .collect(),
+            ));
+        }
+
+        RespType::Array(reply)
+    }
+}
+
+/// Parses `function nargs arg [arg ...] [AS name]` of a reducer, starting at `idx`. The index
+/// is moved past the reducer.
+fn parse_reducer(args: &[RespType], idx: &mut usize) -> Result<Reducer, CommandError> {
+    let name = args
+        .get(*idx)
+        .ok_or_else(|| wrong_num_args("FT.AGGREGATE"))?;
+    let name = parse_string(name, "Reduce function")?.to_lowercase();
+    *idx += 1;
+
+    let (function, nargs) = match name.as_str() {
+        "count" => (ReduceFunction::Count, 0),
+        "count_distinct" => (ReduceFunction::CountDistinct, 1),
+        "sum" => (ReduceFunction::Sum, 1),
+        "min" => (ReduceFunction::Min, 1),
+        "max" => (ReduceFunction::Max, 1),
+        "avg" => (ReduceFunction::Avg, 1),
+        "tolist" => (ReduceFunction::ToList, 1),
+        _ => {
+            return Err(CommandError::Other(format!(
+                "Unknown reduce function '{}'",
+                name
+            )))
+        }
+    };
+
+    let fn_args = counted_args(args, idx)?;
+    if fn_args.len() != nargs {
+        return Err(CommandError::Other(format!(
+            "Bad number of arguments for reduce function '{}'",
+            name
+        )));
+    }
+    let field = match fn_args.first() {
+        Some(arg) => Some(parse_field_ref(arg)?),
+        None => None,
+    };
+
+    let mut alias = format!(
+        "__generated_alias{}{}",
+        name,
+        field.as_deref().unwrap_or_default()
+    );
+    if let Some(arg) = args.get(*idx) {
+        if parse_string(arg, "Option")?.to_lowercase() == "as" {
+            let name = args
+                .get(*idx + 1)
+                .ok_or_else(|| wrong_num_args("FT.AGGREGATE"))?;
+            alias = parse_string(name, "Alias")?;
+            *idx += 2;
+        }
+    }
+
+    Ok(Reducer {
+        function,
+        field,
+        alias,
+    })
+}
+
+/// Returns the arguments counted by the argument at `idx`, and moves the index past them.
+fn counted_args<'a>(args: &'a [RespType], idx: &mut usize) -> Result<&'a [RespType], CommandError> {
+    let count = args
+        .get(*idx)
+        .ok_or_else(|| wrong_num_args("FT.AGGREGATE"))?;
+    let count = parse_int::<usize>(count, "Number of arguments")?;
+
+    let end = *idx + 1 + count;
+    if end > args.len() {
+        return Err(wrong_num_args("FT.AGGREGATE"));
+    }
+    let counted = &args[*idx + 1..end];
+    *idx = end;
+
+    Ok(counted)
+}
@@ -0,0 +1,421 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        search::{FieldKind, FieldSchema},
+        vector::{
+            DistanceMetric, VectorAlgorithm, VectorParams, HNSW_DEFAULT_EF_CONSTRUCTION,
+            HNSW_DEFAULT_EF_RUNTIME, HNSW_DEFAULT_M,
+        },
+    },
+};
+
+use super::{
+    args::{parse_float, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the FT.CREATE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct FtCreate {
+    index: String,
+    prefixes: Vec<String>,
+    schema: Vec<FieldSchema>,
+}
+
+impl FtCreate {
+    /// Creates a new `FtCreate` instance from the given arguments.
+    ///
+    /// Syntax: `FT.CREATE index [ON HASH] [PREFIX count prefix [prefix ...]] SCHEMA
+    /// field [AS alias] type [options ...] [field ...]`
+    ///
+    /// Field types are:
+    ///
+    /// * `TEXT [WEIGHT weight] [NOSTEM] [SORTABLE]`
+    /// * `TAG [SEPARATOR separator] [CASESENSITIVE] [SORTABLE]`, where the separator defaults
+    ///   to `,`.
+    /// * `NUMERIC [SORTABLE]`
+    /// * `VECTOR {FLAT | HNSW} nargs attribute value [attribute value ...]`
+    ///
+    /// NOSTEM is accepted for compatibility, since terms are not stemmed.
+    ///
+    /// Vector attributes are `TYPE FLOAT32`, `DIM dimensions` and
+    /// `DISTANCE_METRIC {L2 | IP | COSINE}`, which are required, and `M`, `EF_CONSTRUCTION`
+    /// and `EF_RUNTIME` for HNSW. `INITIAL_CAP`, `BLOCK_SIZE` and `EPSILON` are accepted for
+    /// compatibility, and ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the FT.CREATE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FtCreate)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<FtCreate, CommandError> {
+        if args.len() < 4 {
+            return Err(wrong_num_args("FT.CREATE"));
+        }
+
+        let index = parse_string(&args[0], "Index")?;
+
+        let mut prefixes = vec![];
+        let mut idx = 1;
+        loop {
+            let arg = args.get(idx).ok_or_else(|| wrong_num_args("FT.CREATE"))?;
+            match parse_string(arg, "Option")?.to_lowercase().as_str() {
+                "on" => {
+                    let on = args
+                        .get(idx + 1)
+                        .ok_or_else(|| wrong_num_args("FT.CREATE"))?;
+                    if parse_string(on, "Data type")?.to_lowercase() != "hash" {
+                        return Err(CommandError::Other(String::from(
+                            "Only HASH data type is supported",
+                        )));
+                    }
+                    idx += 2;
+                }
+                "prefix" => {
+                    let count = args
+                        .get(idx + 1)
+                        .ok_or_else(|| wrong_num_args("FT.CREATE"))?;
+                    let count = parse_int::<usize>(count, "Prefix count")?;
+                    let end = idx + 2 + count;
+                    if end > args.len() {
+                        return Err(wrong_num_args("FT.CREATE"));
+                    }
+                    for arg in args[idx + 2..end].iter() {
+                        prefixes.push(parse_string(arg, "Prefix")?);
+                    }
+                    idx = end;
+                }
+                "schema" => {
+                    idx += 1;
+                    break;
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        if prefixes.is_empty() {
+            prefixes.push(String::new());
+        }
+
+        let mut schema = vec![];
+        while idx < args.len() {
+            schema.push(parse_field(&args, &mut idx)?);
+        }
+        if schema.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "Fields arguments are missing",
+            )));
+        }
+
+        Ok(FtCreate {
+            index,
+            prefixes,
+            schema,
+        })
+    }
+
+    /// Executes the FT.CREATE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` if the index was created, or an error if an index
+    /// with the same name already exists.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ft_create(&self.index, self.prefixes.clone(), self.schema.clone()) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("FT.CREATE")),
+            RespType::BulkString(Bytes::from(self.index.clone())),
+            RespType::BulkString(Bytes::from("PREFIX")),
+            RespType::BulkString(Bytes::from(self.prefixes.len().to_string())),
+        ];
+        cmd.extend(
+            self.prefixes
+                .iter()
+                .map(|p| RespType::BulkString(Bytes::from(p.clone()))),
+        );
+        cmd.push(RespType::BulkString(Bytes::from("SCHEMA")));
+
+        for field in self.schema.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(field.name.clone())));
+            if let Some(alias) = &field.alias {
+                cmd.push(RespType::BulkString(Bytes::from("AS")));
+                cmd.push(RespType::BulkString(Bytes::from(alias.clone())));
+            }
+
+            match &field.kind {
+                FieldKind::Text { weight } => {
+                    cmd.push(RespType::BulkString(Bytes::from("TEXT")));
+                    cmd.push(RespType::BulkString(Bytes::from("WEIGHT")));
+                    cmd.push(RespType::BulkString(Bytes::from(weight.to_string())));
+                }
+                FieldKind::Tag {
+                    separator,
+                    case_sensitive,
+                } => {
+                    cmd.push(RespType::BulkString(Bytes::from("TAG")));
+                    cmd.push(RespType::BulkString(Bytes::from("SEPARATOR")));
+                    cmd.push(RespType::BulkString(Bytes::from(separator.to_string())));
+                    if *case_sensitive {
+                        cmd.push(RespType::BulkString(Bytes::from("CASESENSITIVE")));
+                    }
+                }
+                FieldKind::Numeric => cmd.push(RespType::BulkString(Bytes::from("NUMERIC"))),
+                FieldKind::Vector(params) => cmd.extend(vector_args(params)),
+            }
+            if field.sortable {
+                cmd.push(RespType::BulkString(Bytes::from("SORTABLE")));
+            }
+        }
+
+        RespType::Array(cmd)
+    }
+}
+
+/// Parses a field of the schema, starting at `idx`. The index is moved past the field.
+fn parse_field(args: &[RespType], idx: &mut usize) -> Result<FieldSchema, CommandError> {
+    let name = parse_string(&args[*idx], "Field")?;
+    *idx += 1;
+
+    let mut alias = None;
+    let mut field_type = parse_string(next_arg(args, idx)?, "Field type")?;
+    if field_type.to_lowercase() == "as" {
+        alias = Some(parse_string(next_arg(args, idx)?, "Alias")?);
+        field_type = parse_string(next_arg(args, idx)?, "Field type")?;
+    }
+
+    let mut kind = match field_type.to_lowercase().as_str() {
+        "text" => FieldKind::Text { weight: 1.0 },
+        "tag" => FieldKind::Tag {
+            separator: ',',
+            case_sensitive: false,
+        },
+        "numeric" => FieldKind::Numeric,
+        "vector" => FieldKind::Vector(parse_vector_params(args, idx)?),
+        _ => {
+            return Err(CommandError::Other(format!(
+                "Invalid field type for field '{}'",
+                name
+            )))
+        }
+    };
+
+    let mut sortable = false;
+    while let Some(arg) = args.get(*idx) {
+        match (
+            parse_string(arg, "Option")?.to_lowercase().as_str(),
+            &mut kind,
+        ) {
+            ("sortable", FieldKind::Text { .. } | FieldKind::Tag { .. } | FieldKind::Numeric) => {
+                sortable = true
+            }
+            ("nostem", FieldKind::Text { .. }) => {}
+            ("weight", FieldKind::Text { weight }) => {
+                *idx += 1;
+                *weight = parse_float(next_arg(args, idx)?, "Weight")?;
+                if !weight.is_finite() || *weight < 0.0 {
+                    return Err(CommandError::Other(String::from(
+                        "Weight must be a non-negative number",
+                    )));
+                }
+                continue;
+            }
+            ("separator", FieldKind::Tag { separator, .. }) => {
+                *idx += 1;
+                let value = parse_string(next_arg(args, idx)?, "Separator")?;
+                let mut chars = value.chars();
+                *separator = match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => {
+                        return Err(CommandError::Other(String::from(
+                            "Separator must be a single character",
+                        )))
+                    }
+                };
+                continue;
+            }
+            ("casesensitive", FieldKind::Tag { case_sensitive, .. }) => *case_sensitive = true,
+            // the argument is the name of the next field
+            _ => break,
+        }
+        *idx += 1;
+    }
+
+    Ok(FieldSchema {
+        name,
+        alias,
+        kind,
+        sortable,
+    })
+}
+
+/// Parses `{FLAT | HNSW} nargs attribute value [attribute value ...]` of a vector field.
+fn parse_vector_params(args: &[RespType], idx: &mut usize) -> Result<VectorParams, CommandError> {
+    let algorithm = parse_string(next_arg(args, idx)?, "Algorithm")?.to_lowercase();
+    let nargs = parse_int::<usize>(next_arg(args, idx)?, "Number of attributes")?;
+
+    let end = *idx + nargs;
+    if end > args.len() {
+        return Err(wrong_num_args("FT.CREATE"));
+    }
+    let attrs = args[*idx..end].chunks_exact(2);
+    if !attrs.remainder().is_empty() {
+        return Err(CommandError::Other(String::from(
+            "Each vector attribute must have a value",
+        )));
+    }
+    *idx = end;
+
+    let mut dim = None;
+    let mut metric = None;
+    let mut m = HNSW_DEFAULT_M;
+    let mut ef_construction = HNSW_DEFAULT_EF_CONSTRUCTION;
+    let mut ef_runtime = HNSW_DEFAULT_EF_RUNTIME;
+
+    for attr in attrs {
+        match parse_string(&attr[0], "Attribute")?.to_lowercase().as_str() {
+            "type" => {
+                if parse_string(&attr[1], "Type")?.to_lowercase() != "float32" {
+                    return Err(CommandError::Other(String::from(
+                        "Only FLOAT32 vectors are supported",
+                    )));
+                }
+            }
+            "dim" => dim = Some(parse_int::<usize>(&attr[1], "Dim")?),
+            "distance_metric" => {
+                metric = Some(
+                    match parse_string(&attr[1], "Distance metric")?
+                        .to_lowercase()
+                        .as_str()
+                    {
+                        "l2" => DistanceMetric::L2,
+                        "ip" => DistanceMetric::Ip,
+                        "cosine" => DistanceMetric::Cosine,
+                        _ => {
+                            return Err(CommandError::Other(String::from(
+                                "Unknown distance metric",
+                            )))
+                        }
+                    },
+                )
+            }
+            "m" => m = parse_int::<usize>(&attr[1], "M")?,
+            "ef_construction" => ef_construction = parse_int::<usize>(&attr[1], "EF_CONSTRUCTION")?,
+            "ef_runtime" => ef_runtime = parse_int::<usize>(&attr[1], "EF_RUNTIME")?,
+            "initial_cap" | "block_size" | "epsilon" => {}
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid vector attribute specified",
+                )))
+            }
+        }
+    }
+
+    let algorithm = match algorithm.as_str() {
+        "flat" => VectorAlgorithm::Flat,
+        "hnsw" => {
+            if m == 0 || ef_construction == 0 || ef_runtime == 0 {
+                return Err(CommandError::Other(String::from(
+                    "M, EF_CONSTRUCTION and EF_RUNTIME must be greater than zero",
+                )));
+            }
+            VectorAlgorithm::Hnsw {
+                m,
+                ef_construction,
+                ef_runtime,
+            }
+        }
+        _ => {
+            return Err(CommandError::Other(String::from(
+                "Unknown vector algorithm",
+            )))
+        }
+    };
+
+    match (dim, metric) {
+        (Some(dim), Some(metric)) if dim > 0 => Ok(VectorParams {
+            algorithm,
+            dim,
+            metric,
+        }),
+        _ => Err(CommandError::Other(String::from(
+            "DIM and DISTANCE_METRIC must be specified for vector fields",
+        ))),
+    }
+}
+
+/// Builds the arguments of a vector field, following its type.
+fn vector_args(params: &VectorParams) -> Vec<RespType> {
+    let metric = match params.metric {
+        DistanceMetric::L2 => "L2",
+        DistanceMetric::Ip => "IP",
+        DistanceMetric::Cosine => "COSINE",
+    };
+
+    let mut attrs = vec![
+        String::from("TYPE"),
+        String::from("FLOAT32"),
+        String::from("DIM"),
+        params.dim.to_string(),
+        String::from("DISTANCE_METRIC"),
+        String::from(metric),
+    ];
+
+    let algorithm = match params.algorithm {
+        VectorAlgorithm::Flat => "FLAT",
+        VectorAlgorithm::Hnsw {
+            m,
+            ef_construction,
+            ef_runtime,
+        } => {
+            attrs.extend([
+                String::from("M"),
+                m.to_string(),
+                String::from("EF_CONSTRUCTION"),
+                ef_construction.to_string(),
+                String::from("EF_RUNTIME"),
+                ef_runtime.to_string(),
+            ]);
+            "HNSW"
+        }
+    };
+
+    let mut args = vec![
+        RespType::BulkString(Bytes::from("VECTOR")),
+        RespType::BulkString(Bytes::from(algorithm)),
+        RespType::BulkString(Bytes::from(attrs.len().to_string())),
+    ];
+    args.extend(
+        attrs
+            .into_iter()
+            .map(|a| RespType::BulkString(Bytes::from(a))),
+    );
+
+    args
+}
+
+/// Returns the argument at `idx`, and moves the index to the next argument.
+fn next_arg<'a>(args: &'a [RespType], idx: &mut usize) -> Result<&'a RespType, CommandError> {
+    let arg = args.get(*idx).ok_or_else(|| wrong_num_args("FT.CREATE"))?;
+    *idx += 1;
+    Ok(arg)
+}
@@ -0,0 +1,78 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the FT.DROPINDEX command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct FtDropIndex {
+    index: String,
+    delete_docs: bool,
+}
+
+impl FtDropIndex {
+    /// Creates a new `FtDropIndex` instance from the given arguments.
+    ///
+    /// Syntax: `FT.DROPINDEX index [DD]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the FT.DROPINDEX command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FtDropIndex)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<FtDropIndex, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(wrong_num_args("FT.DROPINDEX"));
+        }
+
+        let index = parse_string(&args[0], "Index")?;
+
+        let delete_docs = match args.get(1) {
+            Some(arg) if parse_string(arg, "Option")?.to_lowercase() == "dd" => true,
+            Some(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid option specified",
+                )))
+            }
+            None => false,
+        };
+
+        Ok(FtDropIndex { index, delete_docs })
+    }
+
+    /// Executes the FT.DROPINDEX command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` if the index was dropped (along with the indexed
+    /// hashes, if DD is specified), or an error if the index doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ft_dropindex(&self.index, self.delete_docs) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("FT.DROPINDEX")),
+            RespType::BulkString(Bytes::from(self.index.clone())),
+        ];
+        if self.delete_docs {
+            cmd.push(RespType::BulkString(Bytes::from("DD")));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,130 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        search::{FieldKind, FieldSchema},
+    },
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the FT.INFO command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct FtInfo {
+    index: String,
+}
+
+impl FtInfo {
+    /// Creates a new `FtInfo` instance from the given arguments.
+    ///
+    /// Syntax: `FT.INFO index`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the FT.INFO command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FtInfo)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<FtInfo, CommandError> {
+        if args.len() != 1 {
+            return Err(wrong_num_args("FT.INFO"));
+        }
+
+        let index = parse_string(&args[0], "Index")?;
+
+        Ok(FtInfo { index })
+    }
+
+    /// Executes the FT.INFO command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` of name-value pairs with the name of the index, its definition
+    /// (the data type and the key prefixes), its attributes (the fields of the schema) and the
+    /// number of indexed documents, distinct terms and records. Returns an error if the index
+    /// doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let info = match db.ft_info(&self.index) {
+            Ok(info) => info,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let definition = RespType::Array(vec![
+            bulk_string("key_type"),
+            bulk_string("HASH"),
+            bulk_string("prefixes"),
+            RespType::Array(info.prefixes.iter().map(|p| bulk_string(p)).collect()),
+        ]);
+
+        RespType::Array(vec![
+            bulk_string("index_name"),
+            bulk_string(&self.index),
+            bulk_string("index_definition"),
+            definition,
+            bulk_string("attributes"),
+            RespType::Array(info.schema.iter().map(attribute_reply).collect()),
+            bulk_string("num_docs"),
+            RespType::Integer(info.num_docs as i64),
+            bulk_string("num_terms"),
+            RespType::Integer(info.num_terms as i64),
+            bulk_string("num_records"),
+            RespType::Integer(info.num_records as i64),
+        ])
+    }
+}
+
+/// Builds the reply for a field of the schema.
+fn attribute_reply(field: &FieldSchema) -> RespType {
+    let mut reply = vec![
+        bulk_string("identifier"),
+        bulk_string(&field.name),
+        bulk_string("attribute"),
+        bulk_string(field.attribute()),
+        bulk_string("type"),
+    ];
+
+    match &field.kind {
+        FieldKind::Text { weight } => {
+            reply.push(bulk_string("TEXT"));
+            reply.push(bulk_string("WEIGHT"));
+            reply.push(bulk_string(&weight.to_string()));
+        }
+        FieldKind::Tag {
+            separator,
+            case_sensitive,
+        } => {
+            reply.push(bulk_string("TAG"));
+            reply.push(bulk_string("SEPARATOR"));
+            reply.push(bulk_string(&separator.to_string()));
+            if *case_sensitive {
+                reply.push(bulk_string("CASESENSITIVE"));
+            }
+        }
+        FieldKind::Numeric => reply.push(bulk_string("NUMERIC")),
+        FieldKind::Vector(params) => {
+            reply.push(bulk_string("VECTOR"));
+            reply.push(bulk_string("dim"));
+            reply.push(RespType::Integer(params.dim as i64));
+        }
+    }
+    if field.sortable {
+        reply.push(bulk_string("SORTABLE"));
+    }
+
+    RespType::Array(reply)
+}
+
+fn bulk_string(s: &str) -> RespType {
+    RespType::BulkString(Bytes::from(s.to_string()))
+}
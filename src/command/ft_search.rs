@@ -0,0 +1,210 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, search::SearchQuery},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    ft::{parse_field_ref, parse_params, parse_query},
+    CommandError,
+};
+
+/// Represents the FT.SEARCH command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct FtSearch {
+    index: String,
+    query: SearchQuery,
+    /// Name of the field with the distance of each document, in the results of KNN queries.
+    score_field: Option<String>,
+    no_content: bool,
+    with_scores: bool,
+    return_fields: Option<Vec<String>>,
+}
+
+impl FtSearch {
+    /// Creates a new `FtSearch` instance from the given arguments.
+    ///
+    /// Syntax: `FT.SEARCH index query [NOCONTENT] [WITHSCORES] [RETURN count field [field ...]]
+    /// [SORTBY field [ASC | DESC]] [LIMIT offset num] [PARAMS nargs name value [name value ...]]
+    /// [DIALECT dialect]`
+    ///
+    /// The query syntax is described in `ft::parse_query`. KNN queries return the K nearest
+    /// documents among the documents matching the rest of the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the FT.SEARCH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(FtSearch)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<FtSearch, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("FT.SEARCH"));
+        }
+
+        let index = parse_string(&args[0], "Index")?;
+        let query = parse_string(&args[1], "Query")?;
+
+        let mut no_content = false;
+        let mut with_scores = false;
+        let mut return_fields = None;
+        let mut sort_by = None;
+        let mut offset = 0;
+        let mut limit = 10;
+        let mut params: Vec<(String, Bytes)> = vec![];
+
+        let mut idx = 2;
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "nocontent" => {
+                    no_content = true;
+                    idx += 1;
+                }
+                "withscores" => {
+                    with_scores = true;
+                    idx += 1;
+                }
+                "return" => {
+                    let count = args
+                        .get(idx + 1)
+                        .ok_or_else(|| wrong_num_args("FT.SEARCH"))?;
+                    let count = parse_int::<usize>(count, "Return count")?;
+                    let end = idx + 2 + count;
+                    if end > args.len() {
+                        return Err(wrong_num_args("FT.SEARCH"));
+                    }
+                    return_fields = Some(
+                        args[idx + 2..end]
+                            .iter()
+                            .map(|arg| parse_string(arg, "Field"))
+                            .collect::<Result<Vec<String>, CommandError>>()?,
+                    );
+                    idx = end;
+                }
+                "sortby" => {
+                    let field = args
+                        .get(idx + 1)
+                        .ok_or_else(|| wrong_num_args("FT.SEARCH"))?;
+                    let field = parse_field_ref(field)?;
+                    idx += 2;
+
+                    let mut asc = true;
+                    if let Some(order) = args.get(idx) {
+                        match parse_string(order, "Order")?.to_lowercase().as_str() {
+                            "asc" => idx += 1,
+                            "desc" => {
+                                asc = false;
+                                idx += 1;
+                            }
+                            _ => {}
+                        }
+                    }
+                    sort_by = Some((field, asc));
+                }
+                "limit" => {
+                    if args.len() < idx + 3 {
+                        return Err(wrong_num_args("FT.SEARCH"));
+                    }
+                    offset = parse_int::<usize>(&args[idx + 1], "Offset")?;
+                    limit = parse_int::<usize>(&args[idx + 2], "Limit")?;
+                    idx += 3;
+                }
+                "params" => {
+                    idx += 1;
+                    params = parse_params(&args, &mut idx, "FT.SEARCH")?;
+                }
+                "dialect" => {
+                    let dialect = args
+                        .get(idx + 1)
+                        .ok_or_else(|| wrong_num_args("FT.SEARCH"))?;
+                    parse_int::<u32>(dialect, "Dialect")?;
+                    idx += 2;
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        let (filter, knn) = parse_query(&query, &params)?;
+        let (knn, score_field) = match knn {
+            Some((knn, score_field)) => (Some(knn), Some(score_field)),
+            None => (None, None),
+        };
+
+        Ok(FtSearch {
+            index,
+            query: SearchQuery {
+                filter,
+                knn,
+                sort_by,
+                offset,
+                limit,
+            },
+            score_field,
+            no_content,
+            with_scores,
+            return_fields,
+        })
+    }
+
+    /// Executes the FT.SEARCH command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the number of matched documents as an `Integer`, followed by
+    /// the key of each document in the page as a `BulkString`, its score as a `BulkString` if
+    /// WITHSCORES is specified, and its fields (unless NOCONTENT is specified) as an `Array` of
+    /// field-value pairs. For KNN queries, the fields include the distance from the query
+    /// vector.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let (total, hits) = match db.ft_search(&self.index, &self.query) {
+            Ok(result) => result,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut reply = vec![RespType::Integer(total as i64)];
+        for hit in hits.into_iter() {
+            reply.push(RespType::BulkString(Bytes::from(hit.key)));
+            if self.with_scores {
+                reply.push(RespType::BulkString(Bytes::from(hit.score.to_string())));
+            }
+            if self.no_content {
+                continue;
+            }
+
+            let mut fields = vec![];
+            if let Some(score_field) = &self.score_field {
+                fields.push((score_field.clone(), hit.score.to_string().into_bytes()));
+            }
+            fields.extend(hit.fields);
+            if let Some(return_fields) = &self.return_fields {
+                fields.retain(|(f, _)| return_fields.contains(f));
+            }
+
+            reply.push(RespType::Array(
+                fields
+                    .into_iter()
+                    .flat_map(|(f, v)| {
+                        [
+                            RespType::BulkString(Bytes::from(f)),
+                            RespType::BulkString(Bytes::from(v)),
+                        ]
+                    })
+                    .collect(),
+            ));
+        }
+
+        RespType::Array(reply)
+    }
+}
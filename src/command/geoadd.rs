@@ -0,0 +1,140 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, geo::is_valid_location},
+};
+
+use super::{
+    args::{parse_float, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the GEOADD command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct GeoAdd {
+    key: String,
+    locations: Vec<(f64, f64, String)>,
+    nx: bool,
+    xx: bool,
+    ch: bool,
+}
+
+impl GeoAdd {
+    /// Creates a new `GeoAdd` instance from the given arguments.
+    ///
+    /// Syntax: `GEOADD key [NX | XX] [CH] longitude latitude member [longitude latitude member ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEOADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoAdd, CommandError> {
+        if args.len() < 4 {
+            return Err(wrong_num_args("GEOADD"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut nx = false;
+        let mut xx = false;
+        let mut ch = false;
+        let mut idx = 1;
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "nx" => nx = true,
+                "xx" => xx = true,
+                "ch" => ch = true,
+                _ => break,
+            }
+            idx += 1;
+        }
+
+        if nx && xx {
+            return Err(CommandError::Other(String::from(
+                "XX and NX options at the same time are not compatible",
+            )));
+        }
+
+        let items = args[idx..].chunks_exact(3);
+        if items.len() == 0 || !items.remainder().is_empty() {
+            return Err(wrong_num_args("GEOADD"));
+        }
+
+        let mut locations = vec![];
+        for item in items {
+            let (lon, lat) = parse_location(&item[0], &item[1])?;
+            let member = parse_string(&item[2], "Member")?;
+            locations.push((lon, lat, member));
+        }
+
+        Ok(GeoAdd {
+            key,
+            locations,
+            nx,
+            xx,
+            ch,
+        })
+    }
+
+    /// Executes the GEOADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members added as an `Integer`. With CH option, the members
+    /// whose location was updated are also counted.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.geoadd(&self.key, &self.locations, self.nx, self.xx, self.ch) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("GEOADD")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        if self.nx {
+            cmd.push(RespType::BulkString(Bytes::from("NX")));
+        }
+        if self.xx {
+            cmd.push(RespType::BulkString(Bytes::from("XX")));
+        }
+        if self.ch {
+            cmd.push(RespType::BulkString(Bytes::from("CH")));
+        }
+
+        for (lon, lat, member) in self.locations.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(lon.to_string())));
+            cmd.push(RespType::BulkString(Bytes::from(lat.to_string())));
+            cmd.push(RespType::BulkString(Bytes::from(member.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
+
+/// Parse a longitude, latitude pair and validate that the location can be indexed.
+pub fn parse_location(lon: &RespType, lat: &RespType) -> Result<(f64, f64), CommandError> {
+    let lon = parse_float(lon, "Longitude")?;
+    let lat = parse_float(lat, "Latitude")?;
+
+    if !is_valid_location(lon, lat) {
+        return Err(CommandError::Other(format!(
+            "Invalid longitude,latitude pair {:.6},{:.6}",
+            lon, lat
+        )));
+    }
+
+    Ok((lon, lat))
+}
@@ -0,0 +1,92 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, geo::GeoUnit},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the GEODIST command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct GeoDist {
+    key: String,
+    member1: String,
+    member2: String,
+    unit: GeoUnit,
+}
+
+impl GeoDist {
+    /// Creates a new `GeoDist` instance from the given arguments.
+    ///
+    /// Syntax: `GEODIST key member1 member2 [M | KM | FT | MI]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEODIST command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoDist)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoDist, CommandError> {
+        if args.len() != 3 && args.len() != 4 {
+            return Err(wrong_num_args("GEODIST"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let member1 = parse_string(&args[1], "Member")?;
+        let member2 = parse_string(&args[2], "Member")?;
+        let unit = match args.get(3) {
+            Some(arg) => parse_unit(arg)?,
+            None => GeoUnit::Meters,
+        };
+
+        Ok(GeoDist {
+            key,
+            member1,
+            member2,
+            unit,
+        })
+    }
+
+    /// Executes the GEODIST command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the distance in the specified unit as a `BulkString`, or a `NullBulkString`
+    /// if either of the members doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.geodist(&self.key, &self.member1, &self.member2) {
+            Ok(Some(dist)) => distance(dist, self.unit),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
+
+/// Parse the unit of a distance.
+pub fn parse_unit(arg: &RespType) -> Result<GeoUnit, CommandError> {
+    match parse_string(arg, "Unit")?.to_lowercase().as_str() {
+        "m" => Ok(GeoUnit::Meters),
+        "km" => Ok(GeoUnit::Kilometers),
+        "mi" => Ok(GeoUnit::Miles),
+        "ft" => Ok(GeoUnit::Feet),
+        _ => Err(CommandError::Other(String::from(
+            "Unsupported unit provided. please use M, KM, FT, MI",
+        ))),
+    }
+}
+
+/// Builds the reply for a distance in meters, converted to the given unit.
+pub fn distance(dist: f64, unit: GeoUnit) -> RespType {
+    let dist = dist / unit.to_meters();
+    RespType::BulkString(Bytes::from(format!("{:.4}", dist)))
+}
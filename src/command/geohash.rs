@@ -0,0 +1,69 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the GEOHASH command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct GeoHash {
+    key: String,
+    members: Vec<String>,
+}
+
+impl GeoHash {
+    /// Creates a new `GeoHash` instance from the given arguments.
+    ///
+    /// Syntax: `GEOHASH key [member [member ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEOHASH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoHash)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoHash, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("GEOHASH"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut members = vec![];
+        for arg in args[1..].iter() {
+            members.push(parse_string(arg, "Member")?);
+        }
+
+        Ok(GeoHash { key, members })
+    }
+
+    /// Executes the GEOHASH command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the 11 character geohash string of each member. A
+    /// `NullBulkString` is returned in place of the members which don't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.geohash(&self.key, &self.members) {
+            Ok(hashes) => RespType::Array(
+                hashes
+                    .into_iter()
+                    .map(|hash| match hash {
+                        Some(h) => RespType::BulkString(Bytes::from(h)),
+                        None => RespType::NullBulkString,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the GEOPOS command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct GeoPos {
+    key: String,
+    members: Vec<String>,
+}
+
+impl GeoPos {
+    /// Creates a new `GeoPos` instance from the given arguments.
+    ///
+    /// Syntax: `GEOPOS key [member [member ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEOPOS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoPos)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoPos, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("GEOPOS"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut members = vec![];
+        for arg in args[1..].iter() {
+            members.push(parse_string(arg, "Member")?);
+        }
+
+        Ok(GeoPos { key, members })
+    }
+
+    /// Executes the GEOPOS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the longitude and latitude of each member. A `NullArray`
+    /// is returned in place of the members which don't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.geopos(&self.key, &self.members) {
+            Ok(positions) => RespType::Array(
+                positions
+                    .into_iter()
+                    .map(|pos| match pos {
+                        Some((lon, lat)) => coordinates(lon, lat),
+                        None => RespType::NullArray,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
+
+/// Builds the reply for a longitude, latitude pair.
+pub fn coordinates(lon: f64, lat: f64) -> RespType {
+    RespType::Array(vec![
+        RespType::BulkString(Bytes::from(lon.to_string())),
+        RespType::BulkString(Bytes::from(lat.to_string())),
+    ])
+}
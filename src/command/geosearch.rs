@@ -0,0 +1,245 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        geo::{GeoMatch, GeoOrigin, GeoSearch as Search, GeoShape, GeoSort, GeoUnit},
+    },
+};
+
+use super::{
+    args::{parse_float, parse_int, parse_string, wrong_num_args},
+    geoadd::parse_location,
+    geodist::{distance, parse_unit},
+    geopos::coordinates,
+    CommandError,
+};
+
+/// Represents the GEOSEARCH command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct GeoSearch {
+    key: String,
+    args: SearchArgs,
+}
+
+/// Options of the GEOSEARCH and GEOSEARCHSTORE commands.
+#[derive(Debug, Clone)]
+pub struct SearchArgs {
+    pub search: Search,
+    pub with_coord: bool,
+    pub with_dist: bool,
+    pub with_hash: bool,
+    pub store_dist: bool,
+}
+
+impl GeoSearch {
+    /// Creates a new `GeoSearch` instance from the given arguments.
+    ///
+    /// Syntax: `GEOSEARCH key <FROMMEMBER member | FROMLONLAT longitude latitude>
+    /// <BYRADIUS radius <M | KM | FT | MI> | BYBOX width height <M | KM | FT | MI>>
+    /// [ASC | DESC] [COUNT count [ANY]] [WITHCOORD] [WITHDIST] [WITHHASH]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEOSEARCH command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoSearch)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoSearch, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("GEOSEARCH"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let args = parse_search_args(&args[1..], false)?;
+
+        Ok(GeoSearch { key, args })
+    }
+
+    /// Executes the GEOSEARCH command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` of the matching members. If any of WITHDIST, WITHHASH or WITHCOORD
+    /// options are specified, each item of the array is an `Array` with the member followed by
+    /// its distance, geohash and coordinates (in that order).
+    pub fn apply(&self, db: &DB) -> RespType {
+        let matches = match db.geosearch(&self.key, &self.args.search) {
+            Ok(matches) => matches,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        RespType::Array(matches.into_iter().map(|m| self.match_reply(m)).collect())
+    }
+
+    /// Builds the reply for a single matching member.
+    fn match_reply(&self, m: GeoMatch) -> RespType {
+        let member = RespType::BulkString(Bytes::from(m.member));
+        if !self.args.with_dist && !self.args.with_hash && !self.args.with_coord {
+            return member;
+        }
+
+        let mut reply = vec![member];
+        if self.args.with_dist {
+            reply.push(distance(m.dist, self.args.search.unit));
+        }
+        if self.args.with_hash {
+            reply.push(RespType::Integer(m.hash as i64));
+        }
+        if self.args.with_coord {
+            reply.push(coordinates(m.lon, m.lat));
+        }
+
+        RespType::Array(reply)
+    }
+}
+
+/// Parse the options of GEOSEARCH and GEOSEARCHSTORE commands, which follow the key(s).
+///
+/// # Arguments
+///
+/// * `args` - The arguments which follow the key(s).
+///
+/// * `store` - Set to `true` for GEOSEARCHSTORE command, which accepts STOREDIST option
+///   instead of the WITHCOORD, WITHDIST and WITHHASH options.
+pub fn parse_search_args(args: &[RespType], store: bool) -> Result<SearchArgs, CommandError> {
+    let cmd_name = if store { "GEOSEARCHSTORE" } else { "GEOSEARCH" };
+
+    let mut origin = None;
+    let mut shape = None;
+    let mut search_args = SearchArgs {
+        search: Search {
+            origin: GeoOrigin::LonLat(0.0, 0.0),
+            shape: GeoShape::Radius(0.0),
+            unit: GeoUnit::Meters,
+            sort: GeoSort::None,
+            count: None,
+            any: false,
+        },
+        with_coord: false,
+        with_dist: false,
+        with_hash: false,
+        store_dist: false,
+    };
+
+    let mut idx = 0;
+    while idx < args.len() {
+        let option = parse_string(&args[idx], "Option")?.to_lowercase();
+        let num_args = match option.as_str() {
+            "frommember" | "count" => 1,
+            "fromlonlat" | "byradius" => 2,
+            "bybox" => 3,
+            _ => 0,
+        };
+
+        if idx + num_args >= args.len() {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        match option.as_str() {
+            "frommember" | "fromlonlat" => {
+                if origin.is_some() {
+                    return Err(origin_err(cmd_name));
+                }
+
+                origin = Some(if option == "frommember" {
+                    GeoOrigin::Member(parse_string(&args[idx + 1], "Member")?)
+                } else {
+                    let (lon, lat) = parse_location(&args[idx + 1], &args[idx + 2])?;
+                    GeoOrigin::LonLat(lon, lat)
+                });
+            }
+            "byradius" => {
+                if shape.is_some() {
+                    return Err(shape_err(cmd_name));
+                }
+
+                let radius = parse_float(&args[idx + 1], "Radius")?;
+                if radius < 0.0 {
+                    return Err(CommandError::Other(String::from(
+                        "Radius cannot be negative",
+                    )));
+                }
+
+                shape = Some(GeoShape::Radius(radius));
+                search_args.search.unit = parse_unit(&args[idx + 2])?;
+            }
+            "bybox" => {
+                if shape.is_some() {
+                    return Err(shape_err(cmd_name));
+                }
+
+                let width = parse_float(&args[idx + 1], "Width")?;
+                let height = parse_float(&args[idx + 2], "Height")?;
+                if width < 0.0 || height < 0.0 {
+                    return Err(CommandError::Other(String::from(
+                        "Height or width cannot be negative",
+                    )));
+                }
+
+                shape = Some(GeoShape::Box { width, height });
+                search_args.search.unit = parse_unit(&args[idx + 3])?;
+            }
+            "asc" => search_args.search.sort = GeoSort::Asc,
+            "desc" => search_args.search.sort = GeoSort::Desc,
+            "count" => {
+                let count = parse_int::<i64>(&args[idx + 1], "Count")?;
+                if count <= 0 {
+                    return Err(CommandError::Other(String::from("COUNT must be > 0")));
+                }
+                search_args.search.count = Some(count as usize);
+
+                // ANY is only accepted immediately after COUNT
+                if let Some(arg) = args.get(idx + 2) {
+                    if parse_string(arg, "Option")?.eq_ignore_ascii_case("any") {
+                        search_args.search.any = true;
+                        idx += 1;
+                    }
+                }
+            }
+            "withcoord" if !store => search_args.with_coord = true,
+            "withdist" if !store => search_args.with_dist = true,
+            "withhash" if !store => search_args.with_hash = true,
+            "storedist" if store => search_args.store_dist = true,
+            "any" => {
+                return Err(CommandError::Other(String::from(
+                    "The ANY argument requires COUNT argument",
+                )))
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Invalid option '{}' for '{}' command",
+                    option, cmd_name
+                )))
+            }
+        }
+
+        idx += num_args + 1;
+    }
+
+    search_args.search.origin = origin.ok_or_else(|| origin_err(cmd_name))?;
+    search_args.search.shape = shape.ok_or_else(|| shape_err(cmd_name))?;
+
+    Ok(search_args)
+}
+
+fn origin_err(cmd_name: &str) -> CommandError {
+    CommandError::Other(format!(
+        "Exactly one of FROMMEMBER or FROMLONLAT can be specified for {}",
+        cmd_name
+    ))
+}
+
+fn shape_err(cmd_name: &str) -> CommandError {
+    CommandError::Other(format!(
+        "Exactly one of BYRADIUS and BYBOX can be specified for {}",
+        cmd_name
+    ))
+}
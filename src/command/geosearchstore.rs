@@ -0,0 +1,130 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        geo::{GeoOrigin, GeoShape, GeoSort, GeoUnit},
+    },
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    geosearch::{parse_search_args, SearchArgs},
+    CommandError,
+};
+
+/// Represents the GEOSEARCHSTORE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct GeoSearchStore {
+    dest_key: String,
+    src_key: String,
+    args: SearchArgs,
+}
+
+impl GeoSearchStore {
+    /// Creates a new `GeoSearchStore` instance from the given arguments.
+    ///
+    /// Syntax: `GEOSEARCHSTORE destination source <FROMMEMBER member | FROMLONLAT longitude latitude>
+    /// <BYRADIUS radius <M | KM | FT | MI> | BYBOX width height <M | KM | FT | MI>>
+    /// [ASC | DESC] [COUNT count [ANY]] [STOREDIST]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GEOSEARCHSTORE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GeoSearchStore)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GeoSearchStore, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("GEOSEARCHSTORE"));
+        }
+
+        let dest_key = parse_string(&args[0], "Destination key")?;
+        let src_key = parse_string(&args[1], "Source key")?;
+        let args = parse_search_args(&args[2..], true)?;
+
+        Ok(GeoSearchStore {
+            dest_key,
+            src_key,
+            args,
+        })
+    }
+
+    /// Executes the GEOSEARCHSTORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of members in the resulting sorted set as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.geosearchstore(
+            &self.dest_key,
+            &self.src_key,
+            &self.args.search,
+            self.args.store_dist,
+        ) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let search = &self.args.search;
+        let mut args = vec![
+            String::from("GEOSEARCHSTORE"),
+            self.dest_key.clone(),
+            self.src_key.clone(),
+        ];
+
+        match &search.origin {
+            GeoOrigin::Member(m) => args.extend([String::from("FROMMEMBER"), m.clone()]),
+            GeoOrigin::LonLat(lon, lat) => {
+                args.extend([String::from("FROMLONLAT"), lon.to_string(), lat.to_string()])
+            }
+        }
+
+        match search.shape {
+            GeoShape::Radius(r) => args.extend([String::from("BYRADIUS"), r.to_string()]),
+            GeoShape::Box { width, height } => {
+                args.extend([String::from("BYBOX"), width.to_string(), height.to_string()])
+            }
+        }
+
+        let unit = match search.unit {
+            GeoUnit::Meters => "M",
+            GeoUnit::Kilometers => "KM",
+            GeoUnit::Miles => "MI",
+            GeoUnit::Feet => "FT",
+        };
+        args.push(String::from(unit));
+
+        match search.sort {
+            GeoSort::Asc => args.push(String::from("ASC")),
+            GeoSort::Desc => args.push(String::from("DESC")),
+            GeoSort::None => {}
+        }
+
+        if let Some(count) = search.count {
+            args.extend([String::from("COUNT"), count.to_string()]);
+            if search.any {
+                args.push(String::from("ANY"));
+            }
+        }
+
+        if self.args.store_dist {
+            args.push(String::from("STOREDIST"));
+        }
+
+        RespType::Array(
+            args.into_iter()
+                .map(|arg| RespType::BulkString(Bytes::from(arg)))
+                .collect(),
+        )
+    }
+}
@@ -1,6 +1,8 @@
+use bytes::Bytes;
+
 use crate::{resp::types::RespType, storage::db::DB};
 
-use super::CommandError;
+use super::{args::parse_string, CommandError};
 
 /// Represents the GET command in Nimblecache.
 #[derive(Debug, Clone)]
@@ -28,15 +30,7 @@ impl Get {
         }
 
         // parse key
-        let key = &args[0];
-        let key = match key {
-            RespType::BulkString(k) => k.to_string(),
-            _ => {
-                return Err(CommandError::Other(String::from(
-                    "Invalid argument. Key must be a bulk string",
-                )));
-            }
-        };
+        let key = parse_string(&args[0], "Key")?;
 
         Ok(Get { key })
     }
@@ -55,7 +49,7 @@ impl Get {
     pub fn apply(&self, db: &DB) -> RespType {
         match db.get(self.key.clone()) {
             Ok(val) => match val {
-                Some(s) => RespType::BulkString(s),
+                Some(s) => RespType::BulkString(Bytes::from(s)),
                 None => RespType::NullBulkString,
             },
             Err(e) => RespType::SimpleError(format!("{}", e)),
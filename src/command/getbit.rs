@@ -0,0 +1,53 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    setbit::parse_bit_offset,
+    CommandError,
+};
+
+/// Represents the GETBIT command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct GetBit {
+    key: String,
+    offset: u64,
+}
+
+impl GetBit {
+    /// Creates a new `GetBit` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the GETBIT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(GetBit)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<GetBit, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("GETBIT"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let offset = parse_bit_offset(&args[1])?;
+
+        Ok(GetBit { key, offset })
+    }
+
+    /// Executes the GETBIT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the bit value stored at offset as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.getbit(&self.key, self.offset) {
+            Ok(bit) => RespType::Integer(bit as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the HDEL command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct HDel {
+    key: String,
+    fields: Vec<String>,
+}
+
+impl HDel {
+    /// Creates a new `HDel` instance from the given arguments.
+    ///
+    /// Syntax: `HDEL key field [field ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HDEL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HDel)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HDel, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("HDEL"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let fields = args[1..]
+            .iter()
+            .map(|arg| parse_string(arg, "Field"))
+            .collect::<Result<Vec<String>, CommandError>>()?;
+
+        Ok(HDel { key, fields })
+    }
+
+    /// Executes the HDEL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of fields which were removed as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hdel(&self.key, &self.fields) {
+            Ok(removed) => RespType::Integer(removed as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("HDEL")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+        cmd.extend(
+            self.fields
+                .iter()
+                .map(|f| RespType::BulkString(Bytes::from(f.clone()))),
+        );
+
+        RespType::Array(cmd)
+    }
+}
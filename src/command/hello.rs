@@ -0,0 +1,171 @@
+use bytes::Bytes;
+
+use crate::{
+    replication::Replication,
+    resp::types::{ProtocolVersion, RespType},
+};
+
+use super::{
+    args::{parse_int, parse_string},
+    auth::check_credentials,
+    client::{parse_name, ClientState},
+    CommandError,
+};
+
+/// Represents the HELLO command in Nimblecache, which switches the version of RESP used by
+/// the connection.
+#[derive(Debug, Clone)]
+pub struct Hello {
+    version: Option<ProtocolVersion>,
+    /// The username and the password.
+    auth: Option<(String, String)>,
+    name: Option<String>,
+}
+
+impl Hello {
+    /// Creates a new `Hello` instance from the given arguments.
+    ///
+    /// Syntax: `HELLO [protover [AUTH username password] [SETNAME clientname]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HELLO command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Hello)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails, or if the version of RESP is not supported.
+    pub fn with_args(args: Vec<RespType>) -> Result<Hello, CommandError> {
+        let mut hello = Hello {
+            version: None,
+            auth: None,
+            name: None,
+        };
+        if args.is_empty() {
+            return Ok(hello);
+        }
+
+        let protover = parse_int::<i64>(&args[0], "Protocol version").map_err(|_| {
+            CommandError::Other(String::from(
+                "Protocol version is not an integer or out of range",
+            ))
+        })?;
+        hello.version = match protover {
+            2 => Some(ProtocolVersion::Resp2),
+            3 => Some(ProtocolVersion::Resp3),
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "NOPROTO unsupported protocol version",
+                )))
+            }
+        };
+
+        let mut i = 1;
+        while i < args.len() {
+            let opt = parse_string(&args[i], "Option")?;
+            match opt.to_lowercase().as_str() {
+                "auth" if i + 2 < args.len() => {
+                    let username = parse_string(&args[i + 1], "Username")?;
+                    let password = parse_string(&args[i + 2], "Password")?;
+                    hello.auth = Some((username, password));
+                    i += 2;
+                }
+                "setname" if i + 1 < args.len() => {
+                    hello.name = Some(parse_name(&args[i + 1])?);
+                    i += 1;
+                }
+                _ => {
+                    return Err(CommandError::Other(format!(
+                        "Syntax error in HELLO option '{}'",
+                        opt
+                    )))
+                }
+            }
+            i += 1;
+        }
+
+        Ok(hello)
+    }
+
+    /// Executes the HELLO command. The connection is authenticated first, if the credentials
+    /// are given, and then its name and the version of RESP are set.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The state of the connection.
+    ///
+    /// * `requirepass` - The password of the default user, if any.
+    ///
+    /// * `replication` - Server replication, which decides the role of the server.
+    ///
+    /// # Returns
+    ///
+    /// A `Map` with the details of the server and the connection (`server`, `version`,
+    /// `proto`, `id`, `mode`, `role` and `modules`), or an error if the connection can't be
+    /// authenticated.
+    pub fn apply(
+        &self,
+        client: &mut ClientState,
+        requirepass: Option<&str>,
+        replication: &Replication,
+    ) -> RespType {
+        if let Some((username, password)) = &self.auth {
+            if let Err(e) = check_credentials(username, password, requirepass) {
+                return RespType::SimpleError(e);
+            }
+            client.authenticated = true;
+        }
+        if !client.authenticated {
+            return RespType::SimpleError(String::from(
+                "NOAUTH HELLO must be called with the client already authenticated, otherwise the HELLO <proto> AUTH <user> <pass> option can be used to authenticate the client and select the RESP protocol version at the same time",
+            ));
+        }
+
+        if let Some(name) = &self.name {
+            client.name = Some(name.clone()).filter(|name| !name.is_empty());
+        }
+        if let Some(version) = self.version {
+            client.version = version;
+        }
+
+        let proto = match client.version {
+            ProtocolVersion::Resp2 => 2,
+            ProtocolVersion::Resp3 => 3,
+        };
+        let role = match replication.is_slave() {
+            true => "replica",
+            false => "master",
+        };
+
+        RespType::Map(vec![
+            (
+                RespType::BulkString(Bytes::from("server")),
+                RespType::BulkString(Bytes::from("nimblecache")),
+            ),
+            (
+                RespType::BulkString(Bytes::from("version")),
+                RespType::BulkString(Bytes::from(env!("CARGO_PKG_VERSION"))),
+            ),
+            (
+                RespType::BulkString(Bytes::from("proto")),
+                RespType::Integer(proto),
+            ),
+            (
+                RespType::BulkString(Bytes::from("id")),
+                RespType::Integer(client.id as i64),
+            ),
+            (
+                RespType::BulkString(Bytes::from("mode")),
+                RespType::BulkString(Bytes::from("standalone")),
+            ),
+            (
+                RespType::BulkString(Bytes::from("role")),
+                RespType::BulkString(Bytes::from(role)),
+            ),
+            (
+                RespType::BulkString(Bytes::from("modules")),
+                RespType::Array(vec![]),
+            ),
+        ])
+    }
+}
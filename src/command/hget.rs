@@ -0,0 +1,58 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the HGET command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct HGet {
+    key: String,
+    field: String,
+}
+
+impl HGet {
+    /// Creates a new `HGet` instance from the given arguments.
+    ///
+    /// Syntax: `HGET key field`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HGET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HGet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HGet, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("HGET"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let field = parse_string(&args[1], "Field")?;
+
+        Ok(HGet { key, field })
+    }
+
+    /// Executes the HGET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the value of the field as a `BulkString`, or `NullBulkString` if the field
+    /// or the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hget(&self.key, &self.field) {
+            Ok(Some(value)) => RespType::BulkString(Bytes::from(value)),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
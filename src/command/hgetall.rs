@@ -0,0 +1,65 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the HGETALL command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct HGetAll {
+    key: String,
+}
+
+impl HGetAll {
+    /// Creates a new `HGetAll` instance from the given arguments.
+    ///
+    /// Syntax: `HGETALL key`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HGETALL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HGetAll)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HGetAll, CommandError> {
+        if args.len() != 1 {
+            return Err(wrong_num_args("HGETALL"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        Ok(HGetAll { key })
+    }
+
+    /// Executes the HGETALL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns a `Map` of each field to its value, as `BulkString`s (sent as a flat `Array`
+    /// in RESP2). The map is empty if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hgetall(&self.key) {
+            Ok(fields) => RespType::Map(
+                fields
+                    .into_iter()
+                    .map(|(field, value)| {
+                        (
+                            RespType::BulkString(Bytes::from(field)),
+                            RespType::BulkString(Bytes::from(value)),
+                        )
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, keys::ScanOptions},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    scan::{parse_scan_options, scan_reply},
+    CommandError,
+};
+
+/// Represents the HSCAN command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct HScan {
+    key: String,
+    cursor: u64,
+    opts: ScanOptions,
+    no_values: bool,
+}
+
+impl HScan {
+    /// Creates a new `HScan` instance from the given arguments.
+    ///
+    /// Syntax: `HSCAN key cursor [MATCH pattern] [COUNT count] [NOVALUES]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSCAN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HScan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HScan, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("HSCAN"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let cursor = parse_int::<u64>(&args[1], "Cursor")?;
+
+        let mut no_values = false;
+        let opts = parse_scan_options(&args[2..], |opt, _, idx| {
+            if opt != "novalues" {
+                return Ok(false);
+            }
+            no_values = true;
+            *idx += 1;
+            Ok(true)
+        })?;
+
+        Ok(HScan {
+            key,
+            cursor,
+            opts,
+            no_values,
+        })
+    }
+
+    /// Executes the HSCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the cursor for the next call as a `BulkString`, followed by an
+    /// `Array` with each field followed by its value, as `BulkString`s. Only the fields are
+    /// returned if NOVALUES is given.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hscan(&self.key, self.cursor, &self.opts) {
+            Ok((cursor, fields)) => scan_reply(
+                cursor,
+                fields
+                    .into_iter()
+                    .flat_map(|(field, value)| {
+                        let value =
+                            (!self.no_values).then(|| RespType::BulkString(Bytes::from(value)));
+                        std::iter::once(RespType::BulkString(Bytes::from(field))).chain(value)
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
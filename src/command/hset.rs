@@ -0,0 +1,78 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the HSET command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct HSet {
+    key: String,
+    fields: Vec<(String, Vec<u8>)>,
+}
+
+impl HSet {
+    /// Creates a new `HSet` instance from the given arguments.
+    ///
+    /// Syntax: `HSET key field value [field value ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the HSET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(HSet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<HSet, CommandError> {
+        let pairs = args.get(1..).unwrap_or_default().chunks_exact(2);
+        if pairs.len() == 0 || !pairs.remainder().is_empty() {
+            return Err(wrong_num_args("HSET"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut fields = vec![];
+        for pair in pairs {
+            let field = parse_string(&pair[0], "Field")?;
+            let value = parse_bytes(&pair[1], "Value")?.to_vec();
+            fields.push((field, value));
+        }
+
+        Ok(HSet { key, fields })
+    }
+
+    /// Executes the HSET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of fields which were added (excluding the updated fields) as an
+    /// `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.hset(&self.key, &self.fields) {
+            Ok(added) => RespType::Integer(added as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("HSET")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        for (field, value) in self.fields.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(field.clone())));
+            cmd.push(RespType::BulkString(Bytes::from(value.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -1,8 +1,14 @@
-use crate::{replication::Replication, resp::types::RespType};
+use bytes::Bytes;
 
-use super::CommandError;
+use crate::{
+    replication::Replication,
+    resp::types::RespType,
+    storage::{db::Storage, DBError},
+};
 
-const ALL_INFO_ARGS: [InfoArg; 1] = [InfoArg::Replication];
+use super::{args::parse_string, CommandError};
+
+const ALL_INFO_ARGS: [InfoArg; 2] = [InfoArg::Replication, InfoArg::Keyspace];
 
 /// Represents the INFO command in Nimblecache.
 #[derive(Debug, Clone)]
@@ -39,10 +45,17 @@ impl Info {
 
     /// Executes the INFO command.
     ///
+    /// # Arguments
+    ///
+    /// * `storage` - The databases.
+    ///
+    /// * `replication` - Server replication.
+    ///
     /// # Returns
     ///
-    /// Returns a `BulkString` with server info.
-    pub fn apply(&self, replication: &Replication) -> RespType {
+    /// Returns a `VerbatimString` of plain text with server info (sent as a `BulkString` in
+    /// RESP2).
+    pub fn apply(&self, storage: &Storage, replication: &Replication) -> RespType {
         // append section infos in a loop
         let mut info = String::new();
 
@@ -51,11 +64,15 @@ impl Info {
                 InfoArg::Replication => {
                     format!("# Replication\n{}\n", replication.info_str())
                 }
+                InfoArg::Keyspace => match keyspace_info_str(storage) {
+                    Ok(keyspace) => format!("# Keyspace\n{}\n", keyspace),
+                    Err(e) => return RespType::SimpleError(format!("{}", e)),
+                },
             };
 
             info.push_str(section.as_str())
         }
-        RespType::BulkString(info)
+        RespType::VerbatimString(String::from("txt"), Bytes::from(info))
     }
 }
 
@@ -64,6 +81,8 @@ impl Info {
 enum InfoArg {
     /// Info about replication.
     Replication,
+    /// Number of keys (and the keys with an expiry) in each database which is not empty.
+    Keyspace,
 }
 
 impl InfoArg {
@@ -71,22 +90,30 @@ impl InfoArg {
     ///
     /// # Validations
     /// - Optional params should be in BulkString format.
-    /// - Valid optional param values - `REPLICATION`.
+    /// - Valid optional param values - `REPLICATION`, `KEYSPACE`.
     fn parse(arg: &RespType) -> Result<InfoArg, CommandError> {
-        let s = match arg {
-            RespType::BulkString(s) => s,
-            _ => {
-                return Err(CommandError::Other(String::from(
-                    "Invalid argument. INFO parameters must be in bulk string format",
-                )))
-            }
-        };
+        let s = parse_string(arg, "INFO parameter")?;
 
         match s.to_lowercase().as_str() {
             "replication" => Ok(InfoArg::Replication),
+            "keyspace" => Ok(InfoArg::Keyspace),
             _ => Err(CommandError::Other(String::from(
                 "Invalid argument for INFO command",
             ))),
         }
     }
 }
+
+/// Returns the keyspace info in `db<index>:keys=<count>,expires=<count>` format, with a line
+/// for each database which is not empty.
+fn keyspace_info_str(storage: &Storage) -> Result<String, DBError> {
+    let mut s = String::new();
+    for (index, db) in storage.dbs()?.iter().enumerate() {
+        let (keys, expires) = db.keyspace_stats()?;
+        if keys > 0 {
+            s.push_str(format!("db{}:keys={},expires={}\n", index, keys, expires).as_str());
+        }
+    }
+
+    Ok(s)
+}
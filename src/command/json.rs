@@ -0,0 +1,68 @@
+use bytes::Bytes;
+use serde_json::Value as JsonValue;
+
+use crate::{resp::types::RespType, storage::json::JsonPath};
+
+use super::{args::parse_string, CommandError};
+
+/// Reads a command argument as a JSONPath or a legacy path.
+pub fn parse_path(arg: &RespType) -> Result<JsonPath, CommandError> {
+    let path = parse_string(arg, "Path")?;
+    JsonPath::parse(&path).map_err(|e| CommandError::Other(format!("{}", e)))
+}
+
+/// Reads a command argument as a JSON value.
+///
+/// # Arguments
+///
+/// * `arg` - The argument, which must be a `BulkString` with a serialized JSON value.
+///
+/// * `name` - Name of the argument. This is used in the error messages.
+pub fn parse_json(arg: &RespType, name: &str) -> Result<JsonValue, CommandError> {
+    let value = parse_string(arg, name)?;
+    serde_json::from_str(&value)
+        .map_err(|e| CommandError::Other(format!("{} is not a valid JSON value: {}", name, e)))
+}
+
+/// Serializes a JSON value into a `BulkString`.
+pub fn json_reply(value: &JsonValue) -> RespType {
+    RespType::BulkString(Bytes::from(value.to_string()))
+}
+
+/// Builds the reply of a command which is applied on each value matched by the path.
+///
+/// For JSONPath, an `Array` with the reply for each matched value is returned, where `None`
+/// results (values of the wrong type) are replied as `NullBulkString`. For legacy paths, the
+/// reply for the only matched value is returned, and an error is returned if the path doesn't
+/// exist or if the value is of the wrong type.
+pub fn matches_reply<T>(
+    path: &JsonPath,
+    results: Vec<Option<T>>,
+    reply: impl Fn(T) -> RespType,
+) -> RespType {
+    if !path.is_legacy() {
+        return RespType::Array(
+            results
+                .into_iter()
+                .map(|r| match r {
+                    Some(r) => reply(r),
+                    None => RespType::NullBulkString,
+                })
+                .collect(),
+        );
+    }
+
+    match results.into_iter().next() {
+        Some(Some(r)) => reply(r),
+        Some(None) => RespType::SimpleError(format!(
+            "WRONGTYPE Path '{}' holds a value of the wrong type",
+            path
+        )),
+        None => path_not_found(path),
+    }
+}
+
+/// Builds the error returned when a legacy path doesn't exist.
+pub fn path_not_found(path: &JsonPath) -> RespType {
+    RespType::SimpleError(format!("Path '{}' does not exist", path))
+}
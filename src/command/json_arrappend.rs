@@ -0,0 +1,82 @@
+use bytes::Bytes;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{matches_reply, parse_json, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.ARRAPPEND command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonArrAppend {
+    key: String,
+    path: JsonPath,
+    values: Vec<JsonValue>,
+}
+
+impl JsonArrAppend {
+    /// Creates a new `JsonArrAppend` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.ARRAPPEND key path value [value ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.ARRAPPEND command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonArrAppend)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonArrAppend, CommandError> {
+        if args.len() < 3 {
+            return Err(wrong_num_args("JSON.ARRAPPEND"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = parse_path(&args[1])?;
+
+        let mut values = vec![];
+        for arg in args[2..].iter() {
+            values.push(parse_json(arg, "Value")?);
+        }
+
+        Ok(JsonArrAppend { key, path, values })
+    }
+
+    /// Executes the JSON.ARRAPPEND command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns an `Array` with the new length of each matched array, or a
+    /// `NullBulkString` for the values which are not arrays. For legacy paths, the new length
+    /// is returned as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_arrappend(&self.key, &self.path, &self.values) {
+            Ok(results) => matches_reply(&self.path, results, |len| RespType::Integer(len as i64)),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("JSON.ARRAPPEND")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+        ];
+        for value in self.values.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(value.to_string())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,90 @@
+use bytes::Bytes;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    json::{matches_reply, parse_json, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.ARRINSERT command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonArrInsert {
+    key: String,
+    path: JsonPath,
+    index: i64,
+    values: Vec<JsonValue>,
+}
+
+impl JsonArrInsert {
+    /// Creates a new `JsonArrInsert` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.ARRINSERT key path index value [value ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.ARRINSERT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonArrInsert)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonArrInsert, CommandError> {
+        if args.len() < 4 {
+            return Err(wrong_num_args("JSON.ARRINSERT"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = parse_path(&args[1])?;
+        let index = parse_int(&args[2], "Index")?;
+
+        let mut values = vec![];
+        for arg in args[3..].iter() {
+            values.push(parse_json(arg, "Value")?);
+        }
+
+        Ok(JsonArrInsert {
+            key,
+            path,
+            index,
+            values,
+        })
+    }
+
+    /// Executes the JSON.ARRINSERT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns an `Array` with the new length of each matched array, or a
+    /// `NullBulkString` for the values which are not arrays. For legacy paths, the new length
+    /// is returned as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_arrinsert(&self.key, &self.path, self.index, &self.values) {
+            Ok(results) => matches_reply(&self.path, results, |len| RespType::Integer(len as i64)),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("JSON.ARRINSERT")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+            RespType::BulkString(Bytes::from(self.index.to_string())),
+        ];
+        for value in self.values.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(value.to_string())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{matches_reply, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.ARRLEN command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonArrLen {
+    key: String,
+    path: JsonPath,
+}
+
+impl JsonArrLen {
+    /// Creates a new `JsonArrLen` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.ARRLEN key [path]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.ARRLEN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonArrLen)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonArrLen, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(wrong_num_args("JSON.ARRLEN"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = match args.get(1) {
+            Some(arg) => parse_path(arg)?,
+            None => JsonPath::parse(".").map_err(|e| CommandError::Other(format!("{}", e)))?,
+        };
+
+        Ok(JsonArrLen { key, path })
+    }
+
+    /// Executes the JSON.ARRLEN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns an `Array` with the length of each matched array, or a
+    /// `NullBulkString` for the values which are not arrays. For legacy paths, the length is
+    /// returned as an `Integer`. `NullBulkString` is returned if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_arrlen(&self.key, &self.path) {
+            Ok(Some(results)) => {
+                matches_reply(&self.path, results, |len| RespType::Integer(len as i64))
+            }
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
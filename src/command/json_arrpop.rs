@@ -0,0 +1,83 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    json::{json_reply, matches_reply, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.ARRPOP command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonArrPop {
+    key: String,
+    path: JsonPath,
+    index: i64,
+}
+
+impl JsonArrPop {
+    /// Creates a new `JsonArrPop` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.ARRPOP key [path [index]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.ARRPOP command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonArrPop)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonArrPop, CommandError> {
+        if args.is_empty() || args.len() > 3 {
+            return Err(wrong_num_args("JSON.ARRPOP"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = match args.get(1) {
+            Some(arg) => parse_path(arg)?,
+            None => JsonPath::parse(".").map_err(|e| CommandError::Other(format!("{}", e)))?,
+        };
+        // the last element is removed by default
+        let index = match args.get(2) {
+            Some(arg) => parse_int(arg, "Index")?,
+            None => -1,
+        };
+
+        Ok(JsonArrPop { key, path, index })
+    }
+
+    /// Executes the JSON.ARRPOP command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns an `Array` with the serialized element removed from each matched
+    /// array. `NullBulkString` is returned for the values which are not arrays, and for empty
+    /// arrays. For legacy paths, the removed element is returned as a `BulkString`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_arrpop(&self.key, &self.path, self.index) {
+            Ok(results) => matches_reply(&self.path, results, |popped| match popped {
+                Some(v) => json_reply(&v),
+                None => RespType::NullBulkString,
+            }),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("JSON.ARRPOP")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+            RespType::BulkString(Bytes::from(self.index.to_string())),
+        ])
+    }
+}
@@ -0,0 +1,71 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::parse_path,
+    CommandError,
+};
+
+/// Represents the JSON.DEL command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonDel {
+    key: String,
+    path: JsonPath,
+}
+
+impl JsonDel {
+    /// Creates a new `JsonDel` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.DEL key [path]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.DEL command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonDel)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonDel, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(wrong_num_args("JSON.DEL"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = match args.get(1) {
+            Some(arg) => parse_path(arg)?,
+            None => JsonPath::parse("$").map_err(|e| CommandError::Other(format!("{}", e)))?,
+        };
+
+        Ok(JsonDel { key, path })
+    }
+
+    /// Executes the JSON.DEL command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of values deleted as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_del(&self.key, &self.path) {
+            Ok(count) => RespType::Integer(count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("JSON.DEL")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+        ])
+    }
+}
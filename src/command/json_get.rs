@@ -0,0 +1,98 @@
+use bytes::Bytes;
+use serde_json::{Map, Value as JsonValue};
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{json_reply, parse_path, path_not_found},
+    CommandError,
+};
+
+/// Represents the JSON.GET command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonGet {
+    key: String,
+    paths: Vec<JsonPath>,
+}
+
+impl JsonGet {
+    /// Creates a new `JsonGet` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.GET key [path [path ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.GET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonGet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonGet, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("JSON.GET"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut paths = vec![];
+        for arg in args[1..].iter() {
+            paths.push(parse_path(arg)?);
+        }
+
+        // the root of the document is returned by default
+        if paths.is_empty() {
+            paths.push(JsonPath::parse(".").map_err(|e| CommandError::Other(format!("{}", e)))?);
+        }
+
+        Ok(JsonGet { key, paths })
+    }
+
+    /// Executes the JSON.GET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the serialized JSON as a `BulkString`, or a `NullBulkString` if the key
+    /// doesn't exist.
+    ///
+    /// - For a single JSONPath, an array of the matched values is returned.
+    /// - For a single legacy path, the matched value is returned.
+    /// - For multiple paths, an object with the paths as keys is returned. Each key holds an array
+    ///   of the matched values, or the matched value if all the paths are legacy paths.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let matches = match db.json_get(&self.key, &self.paths) {
+            Ok(Some(matches)) => matches,
+            Ok(None) => return RespType::NullBulkString,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let legacy = self.paths.iter().all(|p| p.is_legacy());
+
+        let mut results = Map::new();
+        for (path, values) in self.paths.iter().zip(matches) {
+            let result = if legacy {
+                match values.into_iter().next() {
+                    Some(v) => v,
+                    None => return path_not_found(path),
+                }
+            } else {
+                JsonValue::Array(values)
+            };
+
+            if self.paths.len() == 1 {
+                return json_reply(&result);
+            }
+            results.insert(path.to_string(), result);
+        }
+
+        RespType::BulkString(Bytes::from(JsonValue::Object(results).to_string()))
+    }
+}
@@ -0,0 +1,74 @@
+use bytes::Bytes;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{parse_json, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.MERGE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonMerge {
+    key: String,
+    path: JsonPath,
+    value: JsonValue,
+}
+
+impl JsonMerge {
+    /// Creates a new `JsonMerge` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.MERGE key path value`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.MERGE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonMerge)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonMerge, CommandError> {
+        if args.len() != 3 {
+            return Err(wrong_num_args("JSON.MERGE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = parse_path(&args[1])?;
+        let value = parse_json(&args[2], "Value")?;
+
+        Ok(JsonMerge { key, path, value })
+    }
+
+    /// Executes the JSON.MERGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 'OK' as a `SimpleString` if the value is merged, or a `NullBulkString` if
+    /// the path can't be created.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_merge(&self.key, &self.path, self.value.clone()) {
+            Ok(true) => RespType::SimpleString(String::from("OK")),
+            Ok(false) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("JSON.MERGE")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+            RespType::BulkString(Bytes::from(self.value.to_string())),
+        ])
+    }
+}
@@ -0,0 +1,86 @@
+use serde_json::Value as JsonValue;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{json_reply, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.MGET command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonMGet {
+    keys: Vec<String>,
+    path: JsonPath,
+}
+
+impl JsonMGet {
+    /// Creates a new `JsonMGet` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.MGET key [key ...] path`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.MGET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonMGet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonMGet, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("JSON.MGET"));
+        }
+
+        let (path, keys) = args
+            .split_last()
+            .ok_or_else(|| wrong_num_args("JSON.MGET"))?;
+        let path = parse_path(path)?;
+
+        let mut parsed_keys = vec![];
+        for key in keys.iter() {
+            parsed_keys.push(parse_string(key, "Key")?);
+        }
+
+        Ok(JsonMGet {
+            keys: parsed_keys,
+            path,
+        })
+    }
+
+    /// Executes the JSON.MGET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the serialized JSON for each key, in the same format as JSON.GET
+    /// with a single path. A `NullBulkString` is returned in place of the keys which don't
+    /// exist or don't hold JSON, and for legacy paths which don't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_mget(&self.keys, &self.path) {
+            Ok(results) => RespType::Array(
+                results
+                    .into_iter()
+                    .map(|values| match values {
+                        Some(values) if !self.path.is_legacy() => {
+                            json_reply(&JsonValue::Array(values))
+                        }
+                        Some(values) => match values.first() {
+                            Some(v) => json_reply(v),
+                            None => RespType::NullBulkString,
+                        },
+                        None => RespType::NullBulkString,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
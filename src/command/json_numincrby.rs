@@ -0,0 +1,88 @@
+use bytes::Bytes;
+use serde_json::{Number, Value as JsonValue};
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{json_reply, matches_reply, parse_json, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.NUMINCRBY command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonNumIncrBy {
+    key: String,
+    path: JsonPath,
+    incr: Number,
+}
+
+impl JsonNumIncrBy {
+    /// Creates a new `JsonNumIncrBy` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.NUMINCRBY key path value`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.NUMINCRBY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonNumIncrBy)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonNumIncrBy, CommandError> {
+        if args.len() != 3 {
+            return Err(wrong_num_args("JSON.NUMINCRBY"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = parse_path(&args[1])?;
+        let incr = match parse_json(&args[2], "Value")? {
+            JsonValue::Number(n) => n,
+            _ => return Err(CommandError::Other(String::from("Value must be a number"))),
+        };
+
+        Ok(JsonNumIncrBy { key, path, incr })
+    }
+
+    /// Executes the JSON.NUMINCRBY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns a serialized JSON array with the new value of each matched
+    /// value (or `null` for the values which are not numbers) as a `BulkString`. For legacy
+    /// paths, the new value is returned as a `BulkString`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let results = match db.json_numincrby(&self.key, &self.path, &self.incr) {
+            Ok(results) => results,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        if self.path.is_legacy() {
+            return matches_reply(&self.path, results, |n| json_reply(&JsonValue::Number(n)));
+        }
+
+        json_reply(&JsonValue::Array(
+            results
+                .into_iter()
+                .map(|n| n.map_or(JsonValue::Null, JsonValue::Number))
+                .collect(),
+        ))
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("JSON.NUMINCRBY")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+            RespType::BulkString(Bytes::from(self.incr.to_string())),
+        ])
+    }
+}
@@ -0,0 +1,72 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{matches_reply, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.OBJKEYS command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonObjKeys {
+    key: String,
+    path: JsonPath,
+}
+
+impl JsonObjKeys {
+    /// Creates a new `JsonObjKeys` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.OBJKEYS key [path]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.OBJKEYS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonObjKeys)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonObjKeys, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(wrong_num_args("JSON.OBJKEYS"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = match args.get(1) {
+            Some(arg) => parse_path(arg)?,
+            None => JsonPath::parse(".").map_err(|e| CommandError::Other(format!("{}", e)))?,
+        };
+
+        Ok(JsonObjKeys { key, path })
+    }
+
+    /// Executes the JSON.OBJKEYS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns an `Array` with the keys of each matched object, or a
+    /// `NullBulkString` for the values which are not objects. For legacy paths, the keys are
+    /// returned as an `Array`. `NullBulkString` is returned if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_objkeys(&self.key, &self.path) {
+            Ok(Some(results)) => matches_reply(&self.path, results, |keys| {
+                RespType::Array(
+                    keys.into_iter()
+                        .map(|k| RespType::BulkString(Bytes::from(k)))
+                        .collect(),
+                )
+            }),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
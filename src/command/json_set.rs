@@ -0,0 +1,104 @@
+use bytes::Bytes;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{parse_json, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.SET command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonSet {
+    key: String,
+    path: JsonPath,
+    value: JsonValue,
+    nx: bool,
+    xx: bool,
+}
+
+impl JsonSet {
+    /// Creates a new `JsonSet` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.SET key path value [NX | XX]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.SET command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonSet)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonSet, CommandError> {
+        if args.len() != 3 && args.len() != 4 {
+            return Err(wrong_num_args("JSON.SET"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = parse_path(&args[1])?;
+        let value = parse_json(&args[2], "Value")?;
+
+        let (nx, xx) = match args.get(3) {
+            Some(arg) => match parse_string(arg, "Option")?.to_lowercase().as_str() {
+                "nx" => (true, false),
+                "xx" => (false, true),
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            },
+            None => (false, false),
+        };
+
+        Ok(JsonSet {
+            key,
+            path,
+            value,
+            nx,
+            xx,
+        })
+    }
+
+    /// Executes the JSON.SET command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 'OK' as a `SimpleString` if the value is set, or a `NullBulkString` if the
+    /// value is not set due to the NX or XX condition, or since the path can't be created.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_set(&self.key, &self.path, self.value.clone(), self.nx, self.xx) {
+            Ok(true) => RespType::SimpleString(String::from("OK")),
+            Ok(false) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("JSON.SET")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+            RespType::BulkString(Bytes::from(self.value.to_string())),
+        ];
+
+        if self.nx {
+            cmd.push(RespType::BulkString(Bytes::from("NX")));
+        }
+        if self.xx {
+            cmd.push(RespType::BulkString(Bytes::from("XX")));
+        }
+
+        RespType::Array(cmd)
+    }
+}
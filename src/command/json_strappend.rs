@@ -0,0 +1,90 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{matches_reply, parse_json, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.STRAPPEND command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonStrAppend {
+    key: String,
+    path: JsonPath,
+    value: String,
+}
+
+impl JsonStrAppend {
+    /// Creates a new `JsonStrAppend` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.STRAPPEND key [path] value`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.STRAPPEND command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonStrAppend)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonStrAppend, CommandError> {
+        if args.len() != 2 && args.len() != 3 {
+            return Err(wrong_num_args("JSON.STRAPPEND"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let (path, value) = if args.len() == 3 {
+            (parse_path(&args[1])?, &args[2])
+        } else {
+            (
+                JsonPath::parse(".").map_err(|e| CommandError::Other(format!("{}", e)))?,
+                &args[1],
+            )
+        };
+
+        let value = match parse_json(value, "Value")? {
+            serde_json::Value::String(s) => s,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Value must be a JSON string",
+                )))
+            }
+        };
+
+        Ok(JsonStrAppend { key, path, value })
+    }
+
+    /// Executes the JSON.STRAPPEND command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns an `Array` with the new length of each matched string, or a
+    /// `NullBulkString` for the values which are not strings. For legacy paths, the new length
+    /// is returned as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.json_strappend(&self.key, &self.path, &self.value) {
+            Ok(results) => matches_reply(&self.path, results, |len| RespType::Integer(len as i64)),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("JSON.STRAPPEND")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.path.to_string())),
+            RespType::BulkString(Bytes::from(
+                serde_json::Value::String(self.value.clone()).to_string(),
+            )),
+        ])
+    }
+}
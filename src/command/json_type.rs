@@ -0,0 +1,73 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, json::JsonPath},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    json::{matches_reply, parse_path},
+    CommandError,
+};
+
+/// Represents the JSON.TYPE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct JsonType {
+    key: String,
+    path: JsonPath,
+}
+
+impl JsonType {
+    /// Creates a new `JsonType` instance from the given arguments.
+    ///
+    /// Syntax: `JSON.TYPE key [path]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the JSON.TYPE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(JsonType)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<JsonType, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(wrong_num_args("JSON.TYPE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let path = match args.get(1) {
+            Some(arg) => parse_path(arg)?,
+            None => JsonPath::parse(".").map_err(|e| CommandError::Other(format!("{}", e)))?,
+        };
+
+        Ok(JsonType { key, path })
+    }
+
+    /// Executes the JSON.TYPE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For JSONPath, it returns an `Array` with the type of each matched value as a `BulkString`.
+    /// For legacy paths, the type is returned as a `SimpleString`. `NullBulkString` is returned
+    /// if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let legacy = self.path.is_legacy();
+        match db.json_type(&self.key, &self.path) {
+            Ok(Some(results)) => matches_reply(&self.path, results, |t| {
+                if legacy {
+                    RespType::SimpleString(String::from(t))
+                } else {
+                    RespType::BulkString(Bytes::from(t))
+                }
+            }),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the KEYS command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Keys {
+    pattern: String,
+}
+
+impl Keys {
+    /// Creates a new `Keys` instance from the given arguments.
+    ///
+    /// Syntax: `KEYS pattern`
+    ///
+    /// The pattern is glob-style: `*` matches any characters, `?` matches a single character,
+    /// `[...]` matches a character in the set (`[^...]` one which is not in the set), and `\`
+    /// escapes the next character.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the KEYS command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Keys)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Keys, CommandError> {
+        if args.len() != 1 {
+            return Err(wrong_num_args("KEYS"));
+        }
+
+        let pattern = parse_string(&args[0], "Pattern")?;
+
+        Ok(Keys { pattern })
+    }
+
+    /// Executes the KEYS command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the keys matching the pattern as `BulkString`s.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.keys(&self.pattern) {
+            Ok(keys) => RespType::Array(
+                keys.into_iter()
+                    .map(|k| RespType::BulkString(Bytes::from(k)))
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
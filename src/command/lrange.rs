@@ -1,6 +1,8 @@
+use bytes::Bytes;
+
 use crate::{resp::types::RespType, storage::db::DB};
 
-use super::CommandError;
+use super::{args::parse_string, CommandError};
 
 /// Represents the LRANGE command in Nimblecache.
 #[derive(Debug, Clone)]
@@ -29,21 +31,13 @@ impl LRange {
         }
 
         // parse key
-        let key = &args[0];
-        let key = match key {
-            RespType::BulkString(k) => k,
-            _ => {
-                return Err(CommandError::Other(String::from(
-                    "Invalid argument. Key must be a bulk string",
-                )));
-            }
-        };
+        let key = parse_string(&args[0], "Key")?;
 
         // parse start index
         let value = &args[1];
         let start_idx = match value {
             RespType::BulkString(v) => {
-                let start_idx = v.parse::<i64>();
+                let start_idx = String::from_utf8_lossy(v).parse::<i64>();
                 match start_idx {
                     Ok(i) => i,
                     Err(_) => {
@@ -64,7 +58,7 @@ impl LRange {
         let value = &args[2];
         let end_idx = match value {
             RespType::BulkString(v) => {
-                let end_idx = v.parse::<i64>();
+                let end_idx = String::from_utf8_lossy(v).parse::<i64>();
                 match end_idx {
                     Ok(i) => i,
                     Err(_) => {
@@ -82,7 +76,7 @@ impl LRange {
         };
 
         Ok(LRange {
-            key: key.to_string(),
+            key,
             start_idx,
             end_idx,
         })
@@ -100,7 +94,10 @@ impl LRange {
     pub fn apply(&self, db: &DB) -> RespType {
         match db.lrange(self.key.clone(), self.start_idx, self.end_idx) {
             Ok(elems) => {
-                let sub_list = elems.iter().cloned().map(RespType::BulkString).collect();
+                let sub_list = elems
+                    .into_iter()
+                    .map(|e| RespType::BulkString(Bytes::from(e)))
+                    .collect();
                 RespType::Array(sub_list)
             }
             Err(e) => RespType::SimpleError(format!("{}", e)),
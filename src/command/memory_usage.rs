@@ -0,0 +1,78 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the MEMORY USAGE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct MemoryUsage {
+    key: String,
+}
+
+impl MemoryUsage {
+    /// Creates a new `MemoryUsage` instance from the given arguments.
+    ///
+    /// Syntax: `MEMORY USAGE key [SAMPLES count]`
+    ///
+    /// The SAMPLES option is accepted for compatibility, but all the elements of the value are
+    /// always accounted for.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the MEMORY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MemoryUsage)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<MemoryUsage, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("MEMORY"));
+        }
+
+        let sub_cmd = parse_string(&args[0], "Subcommand")?;
+        if sub_cmd.to_lowercase() != "usage" {
+            return Err(CommandError::Other(format!(
+                "Invalid subcommand '{}' for 'MEMORY' command",
+                sub_cmd
+            )));
+        }
+
+        if args.len() != 2 && args.len() != 4 {
+            return Err(wrong_num_args("MEMORY USAGE"));
+        }
+
+        let key = parse_string(&args[1], "Key")?;
+
+        if args.len() == 4 {
+            if parse_string(&args[2], "Option")?.to_lowercase() != "samples" {
+                return Err(CommandError::Other(String::from(
+                    "Invalid option specified",
+                )));
+            }
+            parse_int::<u64>(&args[3], "Samples")?;
+        }
+
+        Ok(MemoryUsage { key })
+    }
+
+    /// Executes the MEMORY USAGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the approximate number of bytes used by the key and its value as an `Integer`,
+    /// or `NullBulkString` if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.memory_usage(&self.key) {
+            Ok(Some(bytes)) => RespType::Integer(bytes as i64),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,504 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use time::OffsetDateTime;
+
+use crate::{
+    migration::{CallError, MigrationConn, MigrationPool},
+    replication::Replication,
+    resp::types::RespType,
+    storage::db::Storage,
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    select::Select,
+    CommandError,
+};
+
+/// Timeout used when the timeout given to MIGRATE is 0.
+const DEFAULT_MIGRATE_TIMEOUT_MS: u64 = 1000;
+
+/// Represents the MIGRATE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Migrate {
+    host: String,
+    port: u16,
+    keys: Vec<String>,
+    dest_db: usize,
+    /// Timeout of each network operation, in milliseconds.
+    timeout: u64,
+    copy: bool,
+    replace: bool,
+    /// Username (for AUTH2) and password used to authenticate with the target instance.
+    auth: Option<(Option<String>, String)>,
+}
+
+/// A key which is serialized to be transferred.
+struct DumpedKey {
+    key: String,
+    payload: Vec<u8>,
+    expiry: Option<OffsetDateTime>,
+}
+
+/// Represents the errors which can occur while keys are transferred.
+enum TransferError {
+    /// The connection failed or timed out.
+    Io(CallError),
+    /// The target instance replied with an error.
+    Target(String),
+}
+
+impl Migrate {
+    /// Creates a new `Migrate` instance from the given arguments.
+    ///
+    /// Syntax: `MIGRATE host port key|"" destination-db timeout [COPY] [REPLACE]
+    /// [AUTH password | AUTH2 username password] [KEYS key [key ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the MIGRATE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Migrate)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Migrate, CommandError> {
+        if args.len() < 5 {
+            return Err(wrong_num_args("MIGRATE"));
+        }
+
+        let host = parse_string(&args[0], "Host")?;
+        let port = parse_int::<u16>(&args[1], "Port")?;
+        let key = parse_string(&args[2], "Key")?;
+        let dest_db = parse_int::<usize>(&args[3], "Destination DB")?;
+        let timeout = parse_int::<u64>(&args[4], "Timeout")?;
+
+        let mut keys = vec![key.clone()];
+        let mut copy = false;
+        let mut replace = false;
+        let mut auth = None;
+        let mut idx = 5;
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "copy" => {
+                    copy = true;
+                    idx += 1;
+                }
+                "replace" => {
+                    replace = true;
+                    idx += 1;
+                }
+                "auth" => {
+                    let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args("MIGRATE"))?;
+                    auth = Some((None, parse_string(arg, "Password")?));
+                    idx += 2;
+                }
+                "auth2" => {
+                    if idx + 2 >= args.len() {
+                        return Err(wrong_num_args("MIGRATE"));
+                    }
+                    let username = parse_string(&args[idx + 1], "Username")?;
+                    let password = parse_string(&args[idx + 2], "Password")?;
+                    auth = Some((Some(username), password));
+                    idx += 3;
+                }
+                "keys" => {
+                    if !key.is_empty() {
+                        return Err(CommandError::Other(String::from(
+                            "When using MIGRATE KEYS option, the key argument must be set to the empty string",
+                        )));
+                    }
+                    keys = args[idx + 1..]
+                        .iter()
+                        .map(|arg| parse_string(arg, "Key"))
+                        .collect::<Result<Vec<String>, CommandError>>()?;
+                    if keys.is_empty() {
+                        return Err(wrong_num_args("MIGRATE"));
+                    }
+                    idx = args.len();
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        Ok(Migrate {
+            host,
+            port,
+            keys,
+            dest_db,
+            timeout,
+            copy,
+            replace,
+            auth,
+        })
+    }
+
+    /// Executes the MIGRATE command. Each key is restored on the target instance (along
+    /// with its expiry), and is deleted locally only after the target acknowledges it,
+    /// unless COPY is given. A key which is modified while it's being transferred is not
+    /// deleted, so that the modification is not lost.
+    ///
+    /// The connection to the target instance is cached in the pool, and is reused by the
+    /// next migration to the same instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The databases.
+    ///
+    /// * `db_index` - The number of the database selected by the connection.
+    ///
+    /// * `replication` - Server replication. The deletion of the migrated keys is sent to
+    ///   the replicas.
+    ///
+    /// * `pool` - The cached connections to the target instances.
+    ///
+    /// # Returns
+    ///
+    /// `OK` as a `SimpleString` if the keys are migrated, or `NOKEY` if none of the keys
+    /// exist. An error is returned if the target instance can't be reached in time, or if
+    /// it rejects any of the keys. The keys transferred before the error remain migrated.
+    pub async fn apply(
+        &self,
+        storage: &Storage,
+        db_index: usize,
+        replication: &Replication,
+        pool: &MigrationPool,
+    ) -> RespType {
+        let db = match storage.db(db_index) {
+            Ok(db) => db,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut dumped = vec![];
+        for key in self.keys.iter() {
+            match db.dump_with_expiry(key) {
+                Ok(Some((payload, expiry))) => dumped.push(DumpedKey {
+                    key: key.clone(),
+                    payload,
+                    expiry,
+                }),
+                Ok(None) => {}
+                Err(e) => return RespType::SimpleError(format!("{}", e)),
+            }
+        }
+        if dumped.is_empty() {
+            return RespType::SimpleString(String::from("NOKEY"));
+        }
+
+        let io_timeout = match self.timeout {
+            0 => Duration::from_millis(DEFAULT_MIGRATE_TIMEOUT_MS),
+            t => Duration::from_millis(t),
+        };
+        let addr = format!("{}:{}", self.host, self.port);
+
+        let mut migrated = vec![];
+        let mut result = Ok(());
+        // a cached connection may have been closed by the target instance, in which case
+        // the migration is retried once with a new connection. It's not retried once a
+        // command may have reached the target instance, since its reply was lost (e.g. timed
+        // out) and restoring the key again would fail.
+        for _ in 0..2 {
+            let mut conn = match pool.acquire(&addr, io_timeout).await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    result = Err(TransferError::Io(CallError::NotSent(e)));
+                    break;
+                }
+            };
+
+            let reused = conn.is_reused();
+            result = self
+                .transfer(&mut conn, &dumped, io_timeout, &mut migrated)
+                .await;
+
+            match &result {
+                Err(TransferError::Io(CallError::NotSent(_))) if reused && migrated.is_empty() => {
+                    continue
+                }
+                Err(TransferError::Io(_)) => {}
+                _ => pool.release(conn).await,
+            }
+            break;
+        }
+
+        // delete the keys which were acknowledged by the target instance
+        let mut deleted = vec![];
+        if !self.copy {
+            for dumped_key in migrated {
+                match db.del_if_dumped(&dumped_key.key, &dumped_key.payload) {
+                    Ok(true) => deleted.push(dumped_key.key.clone()),
+                    Ok(false) => {}
+                    Err(e) => return RespType::SimpleError(format!("{}", e)),
+                }
+            }
+        }
+        if !deleted.is_empty() {
+            let bytes_replicated = replication
+                .write_to_replicas(Self::build_del_command(&deleted), db_index)
+                .await;
+            replication.incr_offset(bytes_replicated as u64);
+        }
+
+        match result {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(TransferError::Io(e)) => RespType::SimpleError(format!(
+                "IOERR error or timeout communicating with target instance: {}",
+                e
+            )),
+            Err(TransferError::Target(e)) => {
+                RespType::SimpleError(format!("Target instance replied with error: {}", e))
+            }
+        }
+    }
+
+    /// Authenticates with the target instance, selects the destination database and restores
+    /// the keys one at a time. The keys acknowledged by the target are added to `migrated`.
+    async fn transfer<'a>(
+        &self,
+        conn: &mut MigrationConn,
+        dumped: &'a [DumpedKey],
+        io_timeout: Duration,
+        migrated: &mut Vec<&'a DumpedKey>,
+    ) -> Result<(), TransferError> {
+        if let Some((username, password)) = &self.auth {
+            let mut cmd = vec![RespType::BulkString(Bytes::from("AUTH"))];
+            if let Some(username) = username {
+                cmd.push(RespType::BulkString(Bytes::from(username.clone())));
+            }
+            cmd.push(RespType::BulkString(Bytes::from(password.clone())));
+            Self::call(conn, RespType::Array(cmd), io_timeout).await?;
+        }
+
+        if conn.selected_db() != Some(self.dest_db) {
+            let select_cmd = Select::new(self.dest_db).build_command();
+            Self::call(conn, select_cmd, io_timeout).await?;
+            conn.set_selected_db(self.dest_db);
+        }
+
+        for dumped_key in dumped {
+            let ttl = match dumped_key.expiry {
+                Some(expiry) => {
+                    let ttl = (expiry - OffsetDateTime::now_utc()).whole_milliseconds();
+                    // the key may expire while it's being transferred
+                    if ttl <= 0 {
+                        continue;
+                    }
+                    ttl as u64
+                }
+                None => 0,
+            };
+
+            let restore_cmd = self.build_restore_command(dumped_key, ttl);
+            Self::call(conn, restore_cmd, io_timeout).await?;
+            migrated.push(dumped_key);
+        }
+
+        Ok(())
+    }
+
+    /// Sends a command to the target instance, and converts an error reply into an error.
+    async fn call(
+        conn: &mut MigrationConn,
+        cmd: RespType,
+        io_timeout: Duration,
+    ) -> Result<RespType, TransferError> {
+        match conn.call(cmd, io_timeout).await {
+            Ok(RespType::SimpleError(e)) => Err(TransferError::Target(e)),
+            Ok(reply) => Ok(reply),
+            Err(e) => Err(TransferError::Io(e)),
+        }
+    }
+
+    /// Builds the RESTORE command for a key, with a TTL relative to the current time (in
+    /// milliseconds), or `0` if the key doesn't expire. An absolute expiry isn't sent, since
+    /// the clocks of the two instances may not be in sync.
+    fn build_restore_command(&self, dumped_key: &DumpedKey, ttl: u64) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("RESTORE")),
+            RespType::BulkString(Bytes::from(dumped_key.key.clone())),
+            RespType::BulkString(Bytes::from(ttl.to_string())),
+            RespType::BulkString(Bytes::from(dumped_key.payload.clone())),
+        ];
+        if self.replace {
+            cmd.push(RespType::BulkString(Bytes::from("REPLACE")));
+        }
+
+        RespType::Array(cmd)
+    }
+
+    fn build_del_command(keys: &[String]) -> RespType {
+        let mut cmd = vec![RespType::BulkString(Bytes::from("DEL"))];
+        for key in keys {
+            cmd.push(RespType::BulkString(Bytes::from(key.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use futures::{SinkExt, StreamExt};
+    use tokio::{net::TcpListener, time::sleep};
+    use tokio_util::codec::Framed;
+
+    use super::*;
+    use crate::{
+        pubsub::PubSub,
+        resp::frame::RespCommandFrame,
+        storage::{
+            db::Value,
+            lazyfree::{LazyFree, LazyFreeConfig},
+            notify::NotifyFlags,
+        },
+        tracking::Tracking,
+    };
+
+    /// The key whose RESTORE is applied by the fake target instance, without a reply.
+    const LOST_REPLY_KEY: &str = "lost";
+
+    /// The commands received by the fake target instance, and the number of connections it
+    /// accepted.
+    #[derive(Default)]
+    struct Received {
+        commands: Vec<String>,
+        conns: usize,
+    }
+
+    /// Starts a fake target instance, which replies `+OK` to every command except the
+    /// RESTORE of `LOST_REPLY_KEY`. That one is applied, but its reply is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `close_after_restore` - Closes each connection after replying to a RESTORE, as a
+    ///   target instance which times out idle clients does.
+    async fn fake_target(close_after_restore: bool) -> (u16, Arc<Mutex<Received>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let received = Arc::new(Mutex::new(Received::default()));
+
+        let target_received = Arc::clone(&received);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                target_received.lock().unwrap().conns += 1;
+
+                let received = Arc::clone(&target_received);
+                tokio::spawn(async move {
+                    let mut conn = Framed::new(stream, RespCommandFrame::new());
+                    while let Some(Ok(cmd)) = conn.next().await {
+                        let args: Vec<String> = cmd
+                            .iter()
+                            .map(|arg| match arg {
+                                RespType::BulkString(b) => String::from_utf8_lossy(b).to_string(),
+                                _ => String::new(),
+                            })
+                            .collect();
+                        let is_restore = args[0] == "RESTORE";
+                        received.lock().unwrap().commands.push(args[..2].join(" "));
+
+                        if is_restore && args[1] == LOST_REPLY_KEY {
+                            continue;
+                        }
+                        let reply = RespType::SimpleString(String::from("OK"));
+                        conn.send(reply).await.unwrap();
+                        if is_restore && close_after_restore {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        (port, received)
+    }
+
+    fn new_storage() -> Storage {
+        let (lazy_free, _) = LazyFree::new(LazyFreeConfig::default());
+        let tracking = Tracking::new(PubSub::new(), 0);
+        Storage::new(1, lazy_free, NotifyFlags::default(), tracking)
+    }
+
+    fn set(storage: &Storage, key: &str) {
+        let db = storage.db(0).unwrap();
+        db.set(key.to_string(), Value::String(b"value".to_vec()), None)
+            .unwrap();
+    }
+
+    fn exists(storage: &Storage, key: &str) -> bool {
+        storage
+            .db(0)
+            .unwrap()
+            .get(key.to_string())
+            .unwrap()
+            .is_some()
+    }
+
+    async fn migrate(storage: &Storage, pool: &MigrationPool, port: u16, key: &str) -> RespType {
+        let args = ["127.0.0.1", &port.to_string(), key, "0", "200"]
+            .iter()
+            .map(|arg| RespType::BulkString(Bytes::from(arg.to_string())))
+            .collect();
+        let replication = Replication::new(String::from("test"), None);
+
+        Migrate::with_args(args)
+            .unwrap()
+            .apply(storage, 0, &replication, pool)
+            .await
+    }
+
+    #[tokio::test]
+    async fn not_retried_after_reply_timeout() {
+        let (port, received) = fake_target(false).await;
+        let storage = new_storage();
+        let pool = MigrationPool::new();
+        set(&storage, "a");
+        set(&storage, LOST_REPLY_KEY);
+
+        // the connection is cached for the next migration
+        let reply = migrate(&storage, &pool, port, "a").await;
+        assert!(matches!(reply, RespType::SimpleString(s) if s == "OK"));
+        assert!(!exists(&storage, "a"));
+
+        let reply = migrate(&storage, &pool, port, LOST_REPLY_KEY).await;
+        assert!(matches!(reply, RespType::SimpleError(e) if e.starts_with("IOERR")));
+        // the key isn't deleted, since it's not known if the target restored it
+        assert!(exists(&storage, LOST_REPLY_KEY));
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.conns, 1);
+        assert_eq!(received.commands, ["SELECT 0", "RESTORE a", "RESTORE lost"]);
+    }
+
+    #[tokio::test]
+    async fn retried_on_closed_connection() {
+        let (port, received) = fake_target(true).await;
+        let storage = new_storage();
+        let pool = MigrationPool::new();
+        set(&storage, "a");
+        set(&storage, "b");
+
+        let reply = migrate(&storage, &pool, port, "a").await;
+        assert!(matches!(reply, RespType::SimpleString(s) if s == "OK"));
+
+        // wait for the cached connection to be closed by the target
+        sleep(Duration::from_millis(50)).await;
+        let reply = migrate(&storage, &pool, port, "b").await;
+        assert!(matches!(reply, RespType::SimpleString(s) if s == "OK"));
+        assert!(!exists(&storage, "b"));
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.conns, 2);
+        assert_eq!(
+            received.commands,
+            ["SELECT 0", "RESTORE a", "SELECT 0", "RESTORE b"]
+        );
+    }
+}
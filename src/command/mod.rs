@@ -1,27 +1,216 @@
 use core::fmt;
 
+use auth::Auth;
+use bf_add::BfAdd;
+use bf_exists::BfExists;
+use bf_info::BfInfo;
+use bf_reserve::BfReserve;
+use bitcount::BitCount;
+use bitfield::Bitfield;
+use bitop::BitOp;
+use bitpos::BitPos;
+use cf_add::CfAdd;
+use cf_count::CfCount;
+use cf_del::CfDel;
+use cf_exists::CfExists;
+use cf_reserve::CfReserve;
+use client::Client;
+use cms_incrby::CmsIncrBy;
+use cms_init::CmsInit;
+use cms_merge::CmsMerge;
+use cms_query::CmsQuery;
+use copy::Copy;
+use dbsize::DbSize;
 use del::Del;
+use dump::Dump;
+use exists::Exists;
+use flush::Flush;
+use ft_aggregate::FtAggregate;
+use ft_create::FtCreate;
+use ft_dropindex::FtDropIndex;
+use ft_info::FtInfo;
+use ft_search::FtSearch;
+use geoadd::GeoAdd;
+use geodist::GeoDist;
+use geohash::GeoHash;
+use geopos::GeoPos;
+use geosearch::GeoSearch;
+use geosearchstore::GeoSearchStore;
 use get::Get;
+use getbit::GetBit;
+use hdel::HDel;
+use hello::Hello;
+use hget::HGet;
+use hgetall::HGetAll;
+use hscan::HScan;
+use hset::HSet;
 use info::Info;
+use json_arrappend::JsonArrAppend;
+use json_arrinsert::JsonArrInsert;
+use json_arrlen::JsonArrLen;
+use json_arrpop::JsonArrPop;
+use json_del::JsonDel;
+use json_get::JsonGet;
+use json_merge::JsonMerge;
+use json_mget::JsonMGet;
+use json_numincrby::JsonNumIncrBy;
+use json_objkeys::JsonObjKeys;
+use json_set::JsonSet;
+use json_strappend::JsonStrAppend;
+use json_type::JsonType;
+use keys::Keys;
 use lpush::LPush;
 use lrange::LRange;
+use memory_usage::MemoryUsage;
+use migrate::Migrate;
+use pfadd::PfAdd;
+use pfcount::PfCount;
+use pfmerge::PfMerge;
 use ping::Ping;
 use psync::Psync;
+use publish::Publish;
+use pubsub::PubSubQuery;
+use r#move::Move;
+use r#type::Type;
+use randomkey::RandomKey;
+use rename::Rename;
+use restore::Restore;
 use rpush::RPush;
+use scan::Scan;
+use select::Select;
 use set::Set;
+use setbit::SetBit;
+use sort::Sort;
+use subscribe::Subscribe;
+use swapdb::SwapDb;
+use topk_add::TopKAdd;
+use topk_list::TopKList;
+use topk_query::TopKQuery;
+use topk_reserve::TopKReserve;
+use ts_add::TsAdd;
+use ts_create::TsCreate;
+use ts_createrule::TsCreateRule;
+use ts_deleterule::TsDeleteRule;
+use ts_incrby::TsIncrBy;
+use ts_madd::TsMAdd;
+use ts_mrange::TsMRange;
+use ts_range::TsRange;
+use unlink::Unlink;
+use unsubscribe::Unsubscribe;
+use zscan::ZScan;
+use zscore::ZScore;
 
-use crate::{replication::Replication, resp::types::RespType, storage::db::DB};
+use crate::{
+    pubsub::{PubSub, SubscriptionKind},
+    replication::Replication,
+    resp::types::RespType,
+    storage::db::Storage,
+};
 
+mod args;
+mod auth;
+mod bf_add;
+mod bf_exists;
+mod bf_info;
+mod bf_reserve;
+mod bitcount;
+mod bitfield;
+mod bitop;
+mod bitpos;
+mod cf_add;
+mod cf_count;
+mod cf_del;
+mod cf_exists;
+mod cf_reserve;
+pub mod client;
+mod cms_incrby;
+mod cms_init;
+mod cms_merge;
+mod cms_query;
+mod copy;
+mod dbsize;
 mod del;
+mod dump;
+mod exists;
+mod flush;
+mod ft;
+mod ft_aggregate;
+mod ft_create;
+mod ft_dropindex;
+mod ft_info;
+mod ft_search;
+mod geoadd;
+mod geodist;
+mod geohash;
+mod geopos;
+mod geosearch;
+mod geosearchstore;
 mod get;
+mod getbit;
+mod hdel;
+mod hello;
+mod hget;
+mod hgetall;
+mod hscan;
+mod hset;
 mod info;
+mod json;
+mod json_arrappend;
+mod json_arrinsert;
+mod json_arrlen;
+mod json_arrpop;
+mod json_del;
+mod json_get;
+mod json_merge;
+mod json_mget;
+mod json_numincrby;
+mod json_objkeys;
+mod json_set;
+mod json_strappend;
+mod json_type;
+mod keys;
 mod lpush;
 mod lrange;
+mod memory_usage;
+mod migrate;
+mod r#move;
+mod pfadd;
+mod pfcount;
+mod pfmerge;
 pub mod ping;
 pub mod pipelining;
 pub mod psync;
+mod publish;
+mod pubsub;
+mod randomkey;
+mod rename;
+mod restore;
 mod rpush;
+mod scan;
+pub mod select;
 mod set;
+mod setbit;
+mod sort;
+mod subscribe;
+mod swapdb;
+mod topk_add;
+mod topk_list;
+mod topk_query;
+mod topk_reserve;
+mod ts;
+mod ts_add;
+mod ts_create;
+mod ts_createrule;
+mod ts_deleterule;
+mod ts_incrby;
+mod ts_madd;
+mod ts_mrange;
+mod ts_range;
+mod r#type;
+mod unlink;
+mod unsubscribe;
+mod zscan;
+mod zscore;
 
 /// Represents the supported Nimblecache commands.
 #[derive(Debug, Clone)]
@@ -50,6 +239,190 @@ pub enum Command {
     LRange(LRange),
     /// The PSYNC command.
     Psync(Psync),
+    /// The SETBIT command.
+    SetBit(SetBit),
+    /// The GETBIT command.
+    GetBit(GetBit),
+    /// The BITCOUNT command.
+    BitCount(BitCount),
+    /// The BITPOS command.
+    BitPos(BitPos),
+    /// The BITOP command.
+    BitOp(BitOp),
+    /// The BITFIELD and BITFIELD_RO commands.
+    Bitfield(Bitfield),
+    /// The PFADD command.
+    PfAdd(PfAdd),
+    /// The PFCOUNT command.
+    PfCount(PfCount),
+    /// The PFMERGE command.
+    PfMerge(PfMerge),
+    /// The GEOADD command.
+    GeoAdd(GeoAdd),
+    /// The GEOPOS command.
+    GeoPos(GeoPos),
+    /// The GEODIST command.
+    GeoDist(GeoDist),
+    /// The GEOHASH command.
+    GeoHash(GeoHash),
+    /// The GEOSEARCH command.
+    GeoSearch(GeoSearch),
+    /// The GEOSEARCHSTORE command.
+    GeoSearchStore(GeoSearchStore),
+    /// The JSON.SET command.
+    JsonSet(JsonSet),
+    /// The JSON.GET command.
+    JsonGet(JsonGet),
+    /// The JSON.MGET command.
+    JsonMGet(JsonMGet),
+    /// The JSON.DEL command.
+    JsonDel(JsonDel),
+    /// The JSON.MERGE command.
+    JsonMerge(JsonMerge),
+    /// The JSON.NUMINCRBY command.
+    JsonNumIncrBy(JsonNumIncrBy),
+    /// The JSON.STRAPPEND command.
+    JsonStrAppend(JsonStrAppend),
+    /// The JSON.ARRAPPEND command.
+    JsonArrAppend(JsonArrAppend),
+    /// The JSON.ARRINSERT command.
+    JsonArrInsert(JsonArrInsert),
+    /// The JSON.ARRPOP command.
+    JsonArrPop(JsonArrPop),
+    /// The JSON.ARRLEN command.
+    JsonArrLen(JsonArrLen),
+    /// The JSON.OBJKEYS command.
+    JsonObjKeys(JsonObjKeys),
+    /// The JSON.TYPE command.
+    JsonType(JsonType),
+    /// The BF.RESERVE command.
+    BfReserve(BfReserve),
+    /// The BF.ADD and BF.MADD commands.
+    BfAdd(BfAdd),
+    /// The BF.EXISTS and BF.MEXISTS commands.
+    BfExists(BfExists),
+    /// The BF.INFO command.
+    BfInfo(BfInfo),
+    /// The CF.RESERVE command.
+    CfReserve(CfReserve),
+    /// The CF.ADD and CF.ADDNX commands.
+    CfAdd(CfAdd),
+    /// The CF.EXISTS command.
+    CfExists(CfExists),
+    /// The CF.DEL command.
+    CfDel(CfDel),
+    /// The CF.COUNT command.
+    CfCount(CfCount),
+    /// The CMS.INITBYDIM and CMS.INITBYPROB commands.
+    CmsInit(CmsInit),
+    /// The CMS.INCRBY command.
+    CmsIncrBy(CmsIncrBy),
+    /// The CMS.QUERY command.
+    CmsQuery(CmsQuery),
+    /// The CMS.MERGE command.
+    CmsMerge(CmsMerge),
+    /// The TOPK.RESERVE command.
+    TopKReserve(TopKReserve),
+    /// The TOPK.ADD and TOPK.INCRBY commands.
+    TopKAdd(TopKAdd),
+    /// The TOPK.QUERY command.
+    TopKQuery(TopKQuery),
+    /// The TOPK.LIST command.
+    TopKList(TopKList),
+    /// The MEMORY USAGE command.
+    MemoryUsage(MemoryUsage),
+    /// Create a time series.
+    TsCreate(TsCreate),
+    /// Add a sample to a time series.
+    TsAdd(TsAdd),
+    /// Add samples to multiple time series.
+    TsMAdd(TsMAdd),
+    /// Increment the latest sample of a time series.
+    TsIncrBy(TsIncrBy),
+    /// Query a range of samples from a time series (TS.RANGE and TS.REVRANGE).
+    TsRange(TsRange),
+    /// Query a range of samples from the time series matching the label filters (TS.MRANGE and TS.MREVRANGE).
+    TsMRange(TsMRange),
+    /// Create a compaction rule between two time series.
+    TsCreateRule(TsCreateRule),
+    /// Delete a compaction rule.
+    TsDeleteRule(TsDeleteRule),
+    /// Set fields of a hash.
+    HSet(HSet),
+    /// Get the value of a hash field.
+    HGet(HGet),
+    /// Get all the fields and values of a hash.
+    HGetAll(HGetAll),
+    /// Delete fields of a hash.
+    HDel(HDel),
+    /// Create a search index over hashes.
+    FtCreate(FtCreate),
+    /// Drop a search index.
+    FtDropIndex(FtDropIndex),
+    /// Search the documents of an index.
+    FtSearch(FtSearch),
+    /// Run an aggregation pipeline over the documents of an index.
+    FtAggregate(FtAggregate),
+    /// Get the details of a search index.
+    FtInfo(FtInfo),
+    /// Count the keys which exist.
+    Exists(Exists),
+    /// Get the type of the value stored at a key.
+    Type(Type),
+    /// Get the number of keys.
+    DbSize(DbSize),
+    /// Get the keys matching a pattern.
+    Keys(Keys),
+    /// Get a random key.
+    RandomKey(RandomKey),
+    /// Incrementally iterate over the keys.
+    Scan(Scan),
+    /// Incrementally iterate over the fields of a hash.
+    HScan(HScan),
+    /// Incrementally iterate over the members of a sorted set.
+    ZScan(ZScan),
+    /// Rename a key.
+    Rename(Rename),
+    /// Copy the value of a key to another key.
+    Copy(Copy),
+    /// Move a key to another logical database.
+    Move(Move),
+    /// Select the database of the connection.
+    Select(Select),
+    /// Swap the contents of two databases.
+    SwapDb(SwapDb),
+    /// Delete the keys of the selected database, or of all the databases.
+    Flush(Flush),
+    /// Delete keys, freeing their memory in the background.
+    Unlink(Unlink),
+    /// Serialize the value stored at a key.
+    Dump(Dump),
+    /// Create a key from a value serialized by DUMP.
+    Restore(Restore),
+    /// Transfer keys to another instance.
+    Migrate(Migrate),
+    /// Sort the elements of a list or a sorted set.
+    Sort(Sort),
+    /// Subscribe to channels (SUBSCRIBE), to patterns of channels (PSUBSCRIBE) or to shard
+    /// channels (SSUBSCRIBE).
+    Subscribe(Subscribe),
+    /// Unsubscribe from channels (UNSUBSCRIBE), from patterns of channels (PUNSUBSCRIBE) or
+    /// from shard channels (SUNSUBSCRIBE).
+    Unsubscribe(Unsubscribe),
+    /// Publish a message to a channel (PUBLISH) or to a shard channel (SPUBLISH).
+    Publish(Publish),
+    /// Inspect the channels, patterns and shard channels subscribed to.
+    PubSub(PubSubQuery),
+    /// The QUIT command.
+    Quit,
+    /// Manage the state of the connection, like the tracking of the keys it caches.
+    Client(Client),
+    /// Switch the version of RESP used by the connection, optionally authenticating it.
+    Hello(Hello),
+    /// Authenticate the connection.
+    Auth(Auth),
+    /// The ZSCORE command.
+    ZScore(ZScore),
 }
 
 impl Command {
@@ -58,7 +431,7 @@ impl Command {
     /// # Arguments
     ///
     /// * `frame` - A vector of `RespType` representing the command and its arguments.
-    /// The first item is always the command name, and the rest are its arguments.
+    ///   The first item is always the command name, and the rest are its arguments.
     ///
     /// # Returns
     ///
@@ -67,7 +440,7 @@ impl Command {
     pub fn from_resp_command_frame(frame: Vec<RespType>) -> Result<Command, CommandError> {
         let (cmd_name, args) = frame.split_at(1);
         let cmd_name = match &cmd_name[0] {
-            RespType::BulkString(s) => s.clone(),
+            RespType::BulkString(s) => String::from_utf8_lossy(s).to_string(),
             _ => return Err(CommandError::InvalidFormat),
         };
 
@@ -126,6 +499,133 @@ impl Command {
                     Err(e) => return Err(e),
                 }
             }
+            "setbit" => Command::SetBit(SetBit::with_args(Vec::from(args))?),
+            "getbit" => Command::GetBit(GetBit::with_args(Vec::from(args))?),
+            "bitcount" => Command::BitCount(BitCount::with_args(Vec::from(args))?),
+            "bitpos" => Command::BitPos(BitPos::with_args(Vec::from(args))?),
+            "bitop" => Command::BitOp(BitOp::with_args(Vec::from(args))?),
+            "bitfield" => Command::Bitfield(Bitfield::with_args(Vec::from(args), false)?),
+            "bitfield_ro" => Command::Bitfield(Bitfield::with_args(Vec::from(args), true)?),
+            "pfadd" => Command::PfAdd(PfAdd::with_args(Vec::from(args))?),
+            "pfcount" => Command::PfCount(PfCount::with_args(Vec::from(args))?),
+            "pfmerge" => Command::PfMerge(PfMerge::with_args(Vec::from(args))?),
+            "geoadd" => Command::GeoAdd(GeoAdd::with_args(Vec::from(args))?),
+            "geopos" => Command::GeoPos(GeoPos::with_args(Vec::from(args))?),
+            "geodist" => Command::GeoDist(GeoDist::with_args(Vec::from(args))?),
+            "geohash" => Command::GeoHash(GeoHash::with_args(Vec::from(args))?),
+            "geosearch" => Command::GeoSearch(GeoSearch::with_args(Vec::from(args))?),
+            "geosearchstore" => {
+                Command::GeoSearchStore(GeoSearchStore::with_args(Vec::from(args))?)
+            }
+            "json.set" => Command::JsonSet(JsonSet::with_args(Vec::from(args))?),
+            "json.get" => Command::JsonGet(JsonGet::with_args(Vec::from(args))?),
+            "json.mget" => Command::JsonMGet(JsonMGet::with_args(Vec::from(args))?),
+            "json.del" => Command::JsonDel(JsonDel::with_args(Vec::from(args))?),
+            "json.merge" => Command::JsonMerge(JsonMerge::with_args(Vec::from(args))?),
+            "json.numincrby" => Command::JsonNumIncrBy(JsonNumIncrBy::with_args(Vec::from(args))?),
+            "json.strappend" => Command::JsonStrAppend(JsonStrAppend::with_args(Vec::from(args))?),
+            "json.arrappend" => Command::JsonArrAppend(JsonArrAppend::with_args(Vec::from(args))?),
+            "json.arrinsert" => Command::JsonArrInsert(JsonArrInsert::with_args(Vec::from(args))?),
+            "json.arrpop" => Command::JsonArrPop(JsonArrPop::with_args(Vec::from(args))?),
+            "json.arrlen" => Command::JsonArrLen(JsonArrLen::with_args(Vec::from(args))?),
+            "json.objkeys" => Command::JsonObjKeys(JsonObjKeys::with_args(Vec::from(args))?),
+            "json.type" => Command::JsonType(JsonType::with_args(Vec::from(args))?),
+            "bf.reserve" => Command::BfReserve(BfReserve::with_args(Vec::from(args))?),
+            "bf.add" => Command::BfAdd(BfAdd::with_args(Vec::from(args), false)?),
+            "bf.madd" => Command::BfAdd(BfAdd::with_args(Vec::from(args), true)?),
+            "bf.exists" => Command::BfExists(BfExists::with_args(Vec::from(args), false)?),
+            "bf.mexists" => Command::BfExists(BfExists::with_args(Vec::from(args), true)?),
+            "bf.info" => Command::BfInfo(BfInfo::with_args(Vec::from(args))?),
+            "cf.reserve" => Command::CfReserve(CfReserve::with_args(Vec::from(args))?),
+            "cf.add" => Command::CfAdd(CfAdd::with_args(Vec::from(args), false)?),
+            "cf.addnx" => Command::CfAdd(CfAdd::with_args(Vec::from(args), true)?),
+            "cf.exists" => Command::CfExists(CfExists::with_args(Vec::from(args))?),
+            "cf.del" => Command::CfDel(CfDel::with_args(Vec::from(args))?),
+            "cf.count" => Command::CfCount(CfCount::with_args(Vec::from(args))?),
+            "cms.initbydim" => Command::CmsInit(CmsInit::with_args(Vec::from(args), false)?),
+            "cms.initbyprob" => Command::CmsInit(CmsInit::with_args(Vec::from(args), true)?),
+            "cms.incrby" => Command::CmsIncrBy(CmsIncrBy::with_args(Vec::from(args))?),
+            "cms.query" => Command::CmsQuery(CmsQuery::with_args(Vec::from(args))?),
+            "cms.merge" => Command::CmsMerge(CmsMerge::with_args(Vec::from(args))?),
+            "topk.reserve" => Command::TopKReserve(TopKReserve::with_args(Vec::from(args))?),
+            "topk.add" => Command::TopKAdd(TopKAdd::with_args(Vec::from(args), false)?),
+            "topk.incrby" => Command::TopKAdd(TopKAdd::with_args(Vec::from(args), true)?),
+            "topk.query" => Command::TopKQuery(TopKQuery::with_args(Vec::from(args))?),
+            "topk.list" => Command::TopKList(TopKList::with_args(Vec::from(args))?),
+            "memory" => Command::MemoryUsage(MemoryUsage::with_args(Vec::from(args))?),
+            "ts.create" => Command::TsCreate(TsCreate::with_args(Vec::from(args))?),
+            "ts.add" => Command::TsAdd(TsAdd::with_args(Vec::from(args))?),
+            "ts.madd" => Command::TsMAdd(TsMAdd::with_args(Vec::from(args))?),
+            "ts.incrby" => Command::TsIncrBy(TsIncrBy::with_args(Vec::from(args))?),
+            "ts.range" => Command::TsRange(TsRange::with_args(Vec::from(args), false)?),
+            "ts.revrange" => Command::TsRange(TsRange::with_args(Vec::from(args), true)?),
+            "ts.mrange" => Command::TsMRange(TsMRange::with_args(Vec::from(args), false)?),
+            "ts.mrevrange" => Command::TsMRange(TsMRange::with_args(Vec::from(args), true)?),
+            "ts.createrule" => Command::TsCreateRule(TsCreateRule::with_args(Vec::from(args))?),
+            "ts.deleterule" => Command::TsDeleteRule(TsDeleteRule::with_args(Vec::from(args))?),
+            "hset" => Command::HSet(HSet::with_args(Vec::from(args))?),
+            "hget" => Command::HGet(HGet::with_args(Vec::from(args))?),
+            "hgetall" => Command::HGetAll(HGetAll::with_args(Vec::from(args))?),
+            "hdel" => Command::HDel(HDel::with_args(Vec::from(args))?),
+            "ft.create" => Command::FtCreate(FtCreate::with_args(Vec::from(args))?),
+            "ft.dropindex" => Command::FtDropIndex(FtDropIndex::with_args(Vec::from(args))?),
+            "ft.search" => Command::FtSearch(FtSearch::with_args(Vec::from(args))?),
+            "ft.aggregate" => Command::FtAggregate(FtAggregate::with_args(Vec::from(args))?),
+            "ft.info" => Command::FtInfo(FtInfo::with_args(Vec::from(args))?),
+            "exists" => Command::Exists(Exists::with_args(Vec::from(args))?),
+            "type" => Command::Type(Type::with_args(Vec::from(args))?),
+            "dbsize" => Command::DbSize(DbSize::with_args(Vec::from(args))?),
+            "keys" => Command::Keys(Keys::with_args(Vec::from(args))?),
+            "randomkey" => Command::RandomKey(RandomKey::with_args(Vec::from(args))?),
+            "scan" => Command::Scan(Scan::with_args(Vec::from(args))?),
+            "hscan" => Command::HScan(HScan::with_args(Vec::from(args))?),
+            "zscan" => Command::ZScan(ZScan::with_args(Vec::from(args))?),
+            "rename" => Command::Rename(Rename::with_args(Vec::from(args), false)?),
+            "renamenx" => Command::Rename(Rename::with_args(Vec::from(args), true)?),
+            "copy" => Command::Copy(Copy::with_args(Vec::from(args))?),
+            "move" => Command::Move(Move::with_args(Vec::from(args))?),
+            "select" => Command::Select(Select::with_args(Vec::from(args))?),
+            "swapdb" => Command::SwapDb(SwapDb::with_args(Vec::from(args))?),
+            "flushdb" => Command::Flush(Flush::with_args(Vec::from(args), false)?),
+            "flushall" => Command::Flush(Flush::with_args(Vec::from(args), true)?),
+            "unlink" => Command::Unlink(Unlink::with_args(Vec::from(args))?),
+            "dump" => Command::Dump(Dump::with_args(Vec::from(args))?),
+            "restore" => Command::Restore(Restore::with_args(Vec::from(args))?),
+            "migrate" => Command::Migrate(Migrate::with_args(Vec::from(args))?),
+            "sort" => Command::Sort(Sort::with_args(Vec::from(args), false)?),
+            "sort_ro" => Command::Sort(Sort::with_args(Vec::from(args), true)?),
+            "subscribe" => Command::Subscribe(Subscribe::with_args(
+                Vec::from(args),
+                SubscriptionKind::Channel,
+            )?),
+            "psubscribe" => Command::Subscribe(Subscribe::with_args(
+                Vec::from(args),
+                SubscriptionKind::Pattern,
+            )?),
+            "ssubscribe" => Command::Subscribe(Subscribe::with_args(
+                Vec::from(args),
+                SubscriptionKind::ShardChannel,
+            )?),
+            "unsubscribe" => Command::Unsubscribe(Unsubscribe::with_args(
+                Vec::from(args),
+                SubscriptionKind::Channel,
+            )?),
+            "punsubscribe" => Command::Unsubscribe(Unsubscribe::with_args(
+                Vec::from(args),
+                SubscriptionKind::Pattern,
+            )?),
+            "sunsubscribe" => Command::Unsubscribe(Unsubscribe::with_args(
+                Vec::from(args),
+                SubscriptionKind::ShardChannel,
+            )?),
+            "publish" => Command::Publish(Publish::with_args(Vec::from(args), false)?),
+            "spublish" => Command::Publish(Publish::with_args(Vec::from(args), true)?),
+            "pubsub" => Command::PubSub(PubSubQuery::with_args(Vec::from(args))?),
+            "quit" => Command::Quit,
+            "client" => Command::Client(Client::with_args(Vec::from(args))?),
+            "hello" => Command::Hello(Hello::with_args(Vec::from(args))?),
+            "auth" => Command::Auth(Auth::with_args(Vec::from(args))?),
+            "zscore" => Command::ZScore(ZScore::with_args(Vec::from(args))?),
             _ => {
                 return Err(CommandError::UnknownCommand(ErrUnknownCommand {
                     cmd: cmd_name,
@@ -140,17 +640,34 @@ impl Command {
     ///
     /// # Arguments
     ///
-    /// * `db` - The database where the key and values are stored.
+    /// * `storage` - The databases where the key and values are stored.
+    ///
+    /// * `db_index` - The number of the database selected by the connection. This is updated
+    ///   by the SELECT command.
     ///
     /// * `replication` - Server replication.
     ///
+    /// * `pubsub` - The subscriptions of all the clients, to which messages are published.
+    ///
     /// # Returns
     ///
     /// The result of the command execution as a `RespType`.
-    pub fn execute(&self, db: &DB, replication: &Replication) -> RespType {
+    pub fn execute(
+        &self,
+        storage: &Storage,
+        db_index: &mut usize,
+        replication: &Replication,
+        pubsub: &PubSub,
+    ) -> RespType {
+        let db = match storage.db(*db_index) {
+            Ok(db) => db,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+        let db = db.as_ref();
+
         match self {
             Command::Ping(ping) => ping.apply(),
-            Command::Info(info) => info.apply(replication),
+            Command::Info(info) => info.apply(storage, replication),
             // MULTI calls are handled inside FrameHandler.handle since it involves command queueing.
             Command::Multi => RespType::SimpleString(String::from("OK")),
             // EXEC calls are handled inside FrameHandler.handle too, since it involves executing queued commands.
@@ -164,9 +681,130 @@ impl Command {
             Command::RPush(rpush) => rpush.apply(db),
             Command::LRange(lrange) => lrange.apply(db),
             Command::Psync(psync) => psync.apply(replication),
+            Command::SetBit(setbit) => setbit.apply(db),
+            Command::GetBit(getbit) => getbit.apply(db),
+            Command::BitCount(bitcount) => bitcount.apply(db),
+            Command::BitPos(bitpos) => bitpos.apply(db),
+            Command::BitOp(bitop) => bitop.apply(db),
+            Command::Bitfield(bitfield) => bitfield.apply(db),
+            Command::PfAdd(pfadd) => pfadd.apply(db),
+            Command::PfCount(pfcount) => pfcount.apply(db),
+            Command::PfMerge(pfmerge) => pfmerge.apply(db),
+            Command::GeoAdd(geoadd) => geoadd.apply(db),
+            Command::GeoPos(geopos) => geopos.apply(db),
+            Command::GeoDist(geodist) => geodist.apply(db),
+            Command::GeoHash(geohash) => geohash.apply(db),
+            Command::GeoSearch(geosearch) => geosearch.apply(db),
+            Command::GeoSearchStore(geosearchstore) => geosearchstore.apply(db),
+            Command::JsonSet(jsonset) => jsonset.apply(db),
+            Command::JsonGet(jsonget) => jsonget.apply(db),
+            Command::JsonMGet(jsonmget) => jsonmget.apply(db),
+            Command::JsonDel(jsondel) => jsondel.apply(db),
+            Command::JsonMerge(jsonmerge) => jsonmerge.apply(db),
+            Command::JsonNumIncrBy(jsonnumincrby) => jsonnumincrby.apply(db),
+            Command::JsonStrAppend(jsonstrappend) => jsonstrappend.apply(db),
+            Command::JsonArrAppend(jsonarrappend) => jsonarrappend.apply(db),
+            Command::JsonArrInsert(jsonarrinsert) => jsonarrinsert.apply(db),
+            Command::JsonArrPop(jsonarrpop) => jsonarrpop.apply(db),
+            Command::JsonArrLen(jsonarrlen) => jsonarrlen.apply(db),
+            Command::JsonObjKeys(jsonobjkeys) => jsonobjkeys.apply(db),
+            Command::JsonType(jsontype) => jsontype.apply(db),
+            Command::BfReserve(bfreserve) => bfreserve.apply(db),
+            Command::BfAdd(bfadd) => bfadd.apply(db),
+            Command::BfExists(bfexists) => bfexists.apply(db),
+            Command::BfInfo(bfinfo) => bfinfo.apply(db),
+            Command::CfReserve(cfreserve) => cfreserve.apply(db),
+            Command::CfAdd(cfadd) => cfadd.apply(db),
+            Command::CfExists(cfexists) => cfexists.apply(db),
+            Command::CfDel(cfdel) => cfdel.apply(db),
+            Command::CfCount(cfcount) => cfcount.apply(db),
+            Command::CmsInit(cmsinit) => cmsinit.apply(db),
+            Command::CmsIncrBy(cmsincrby) => cmsincrby.apply(db),
+            Command::CmsQuery(cmsquery) => cmsquery.apply(db),
+            Command::CmsMerge(cmsmerge) => cmsmerge.apply(db),
+            Command::TopKReserve(topkreserve) => topkreserve.apply(db),
+            Command::TopKAdd(topkadd) => topkadd.apply(db),
+            Command::TopKQuery(topkquery) => topkquery.apply(db),
+            Command::TopKList(topklist) => topklist.apply(db),
+            Command::MemoryUsage(memoryusage) => memoryusage.apply(db),
+            Command::TsCreate(tscreate) => tscreate.apply(db),
+            Command::TsAdd(tsadd) => tsadd.apply(db),
+            Command::TsMAdd(tsmadd) => tsmadd.apply(db),
+            Command::TsIncrBy(tsincrby) => tsincrby.apply(db),
+            Command::TsRange(tsrange) => tsrange.apply(db),
+            Command::TsMRange(tsmrange) => tsmrange.apply(db),
+            Command::TsCreateRule(tscreaterule) => tscreaterule.apply(db),
+            Command::TsDeleteRule(tsdeleterule) => tsdeleterule.apply(db),
+            Command::HSet(hset) => hset.apply(db),
+            Command::HGet(hget) => hget.apply(db),
+            Command::HGetAll(hgetall) => hgetall.apply(db),
+            Command::HDel(hdel) => hdel.apply(db),
+            Command::FtCreate(ftcreate) => ftcreate.apply(db),
+            Command::FtDropIndex(ftdropindex) => ftdropindex.apply(db),
+            Command::FtSearch(ftsearch) => ftsearch.apply(db),
+            Command::FtAggregate(ftaggregate) => ftaggregate.apply(db),
+            Command::FtInfo(ftinfo) => ftinfo.apply(db),
+            Command::Exists(exists) => exists.apply(db),
+            Command::Type(r#type) => r#type.apply(db),
+            Command::DbSize(dbsize) => dbsize.apply(db),
+            Command::Keys(keys) => keys.apply(db),
+            Command::RandomKey(randomkey) => randomkey.apply(db),
+            Command::Scan(scan) => scan.apply(db),
+            Command::HScan(hscan) => hscan.apply(db),
+            Command::ZScan(zscan) => zscan.apply(db),
+            Command::Rename(rename) => rename.apply(db),
+            Command::Copy(copy) => copy.apply(storage, *db_index, db),
+            Command::Move(r#move) => r#move.apply(storage, *db_index),
+            Command::Select(select) => select.apply(storage, db_index),
+            Command::SwapDb(swapdb) => swapdb.apply(storage),
+            Command::Flush(flush) => flush.apply(storage, db),
+            Command::Unlink(unlink) => unlink.apply(db),
+            Command::Dump(dump) => dump.apply(db),
+            Command::Restore(restore) => restore.apply(db),
+            // MIGRATE calls are handled inside FrameHandler.handle and MultiCommand.exec, since
+            // they involve network I/O.
+            Command::Migrate(_) => {
+                RespType::SimpleError(String::from("MIGRATE is not allowed in this context"))
+            }
+            Command::Sort(sort) => sort.apply(db),
+            // SUBSCRIBE and UNSUBSCRIBE calls are handled inside FrameHandler.handle, since they
+            // change the state of the connection.
+            Command::Subscribe(_) | Command::Unsubscribe(_) => RespType::SimpleError(String::from(
+                "SUBSCRIBE and UNSUBSCRIBE are not allowed in this context",
+            )),
+            Command::Publish(publish) => publish.apply(pubsub),
+            Command::PubSub(query) => query.apply(pubsub),
+            // QUIT calls are handled inside FrameHandler.handle, since they close the connection.
+            Command::Quit => RespType::SimpleString(String::from("OK")),
+            // CLIENT calls are handled inside FrameHandler.handle, since they change the state of
+            // the connection.
+            Command::Client(_) => {
+                RespType::SimpleError(String::from("CLIENT is not allowed in this context"))
+            }
+            // HELLO and AUTH calls are handled inside FrameHandler.handle, since they change the
+            // state of the connection.
+            Command::Hello(_) | Command::Auth(_) => RespType::SimpleError(String::from(
+                "HELLO and AUTH are not allowed in this context",
+            )),
+            Command::ZScore(zscore) => zscore.apply(db),
         }
     }
 
+    /// Returns `true` if the command can be executed by a connection in the subscriber mode
+    /// of RESP2. Any command can be executed by a subscribed RESP3 connection.
+    pub fn is_allowed_when_subscribed(&self) -> bool {
+        matches!(
+            self,
+            Command::Subscribe(_) | Command::Unsubscribe(_) | Command::Ping(_) | Command::Quit
+        )
+    }
+
+    /// Returns `true` if the command can be executed by a connection which is not
+    /// authenticated yet.
+    pub fn is_allowed_unauthenticated(&self) -> bool {
+        matches!(self, Command::Auth(_) | Command::Hello(_) | Command::Quit)
+    }
+
     /// Builds the RESP command which is to be sent as part of replication stream.
     /// Returns None if the command is for READ operation.
     pub fn replication_cmd(&self) -> Option<RespType> {
@@ -175,6 +813,50 @@ impl Command {
             Command::Del(del) => Some(del.build_command()),
             Command::LPush(lpush) => Some(lpush.build_command()),
             Command::RPush(rpush) => Some(rpush.build_command()),
+            Command::SetBit(setbit) => Some(setbit.build_command()),
+            Command::BitOp(bitop) => Some(bitop.build_command()),
+            Command::Bitfield(bitfield) if bitfield.is_write() => Some(bitfield.build_command()),
+            Command::PfAdd(pfadd) => Some(pfadd.build_command()),
+            Command::PfMerge(pfmerge) => Some(pfmerge.build_command()),
+            Command::GeoAdd(geoadd) => Some(geoadd.build_command()),
+            Command::GeoSearchStore(geosearchstore) => Some(geosearchstore.build_command()),
+            Command::JsonSet(jsonset) => Some(jsonset.build_command()),
+            Command::JsonDel(jsondel) => Some(jsondel.build_command()),
+            Command::JsonMerge(jsonmerge) => Some(jsonmerge.build_command()),
+            Command::JsonNumIncrBy(jsonnumincrby) => Some(jsonnumincrby.build_command()),
+            Command::JsonStrAppend(jsonstrappend) => Some(jsonstrappend.build_command()),
+            Command::JsonArrAppend(jsonarrappend) => Some(jsonarrappend.build_command()),
+            Command::JsonArrInsert(jsonarrinsert) => Some(jsonarrinsert.build_command()),
+            Command::JsonArrPop(jsonarrpop) => Some(jsonarrpop.build_command()),
+            Command::BfReserve(bfreserve) => Some(bfreserve.build_command()),
+            Command::BfAdd(bfadd) => Some(bfadd.build_command()),
+            Command::CfReserve(cfreserve) => Some(cfreserve.build_command()),
+            Command::CfAdd(cfadd) => Some(cfadd.build_command()),
+            Command::CfDel(cfdel) => Some(cfdel.build_command()),
+            Command::CmsInit(cmsinit) => Some(cmsinit.build_command()),
+            Command::CmsIncrBy(cmsincrby) => Some(cmsincrby.build_command()),
+            Command::CmsMerge(cmsmerge) => Some(cmsmerge.build_command()),
+            Command::TopKReserve(topkreserve) => Some(topkreserve.build_command()),
+            Command::TopKAdd(topkadd) => Some(topkadd.build_command()),
+            Command::TsCreate(tscreate) => Some(tscreate.build_command()),
+            Command::TsAdd(tsadd) => Some(tsadd.build_command()),
+            Command::TsMAdd(tsmadd) => Some(tsmadd.build_command()),
+            Command::TsIncrBy(tsincrby) => Some(tsincrby.build_command()),
+            Command::TsCreateRule(tscreaterule) => Some(tscreaterule.build_command()),
+            Command::TsDeleteRule(tsdeleterule) => Some(tsdeleterule.build_command()),
+            Command::HSet(hset) => Some(hset.build_command()),
+            Command::HDel(hdel) => Some(hdel.build_command()),
+            Command::FtCreate(ftcreate) => Some(ftcreate.build_command()),
+            Command::FtDropIndex(ftdropindex) => Some(ftdropindex.build_command()),
+            Command::Rename(rename) => Some(rename.build_command()),
+            Command::Copy(copy) => Some(copy.build_command()),
+            Command::Move(r#move) => Some(r#move.build_command()),
+            Command::SwapDb(swapdb) => Some(swapdb.build_command()),
+            Command::Flush(flush) => Some(flush.build_command()),
+            Command::Unlink(unlink) => Some(unlink.build_command()),
+            Command::Restore(restore) => Some(restore.build_command()),
+            Command::Sort(sort) if sort.is_write() => Some(sort.build_command()),
+            Command::Publish(publish) => Some(publish.build_command()),
             _ => None,
         }
     }
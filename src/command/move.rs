@@ -0,0 +1,67 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the MOVE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Move {
+    key: String,
+    db_index: usize,
+}
+
+impl Move {
+    /// Creates a new `Move` instance from the given arguments.
+    ///
+    /// Syntax: `MOVE key db`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the MOVE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Move)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Move, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("MOVE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let db_index = parse_int::<usize>(&args[1], "DB index")?;
+
+        Ok(Move { key, db_index })
+    }
+
+    /// Executes the MOVE command. The expiry of the key is retained.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The databases.
+    ///
+    /// * `db_index` - The number of the database selected by the connection.
+    ///
+    /// # Returns
+    ///
+    /// It returns `1` as an `Integer` if the key is moved, or `0` if the key doesn't exist or
+    /// the destination database already has the key.
+    pub fn apply(&self, storage: &Storage, db_index: usize) -> RespType {
+        match storage.move_key(db_index, &self.key, self.db_index) {
+            Ok(moved) => RespType::Integer(moved as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("MOVE")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.db_index.to_string())),
+        ])
+    }
+}
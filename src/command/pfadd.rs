@@ -0,0 +1,71 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the PFADD command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct PfAdd {
+    key: String,
+    elements: Vec<Vec<u8>>,
+}
+
+impl PfAdd {
+    /// Creates a new `PfAdd` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PFADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PfAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PfAdd, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("PFADD"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut elements = vec![];
+        for arg in args[1..].iter() {
+            elements.push(parse_bytes(arg, "Element")?.to_vec());
+        }
+
+        Ok(PfAdd { key, elements })
+    }
+
+    /// Executes the PFADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 1 as an `Integer` if the HyperLogLog was created or altered, else 0.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.pfadd(&self.key, &self.elements) {
+            Ok(updated) => RespType::Integer(updated as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("PFADD")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        for element in self.elements.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(element.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the PFCOUNT command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct PfCount {
+    keys: Vec<String>,
+}
+
+impl PfCount {
+    /// Creates a new `PfCount` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PFCOUNT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PfCount)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PfCount, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("PFCOUNT"));
+        }
+
+        let mut keys = vec![];
+        for arg in args.iter() {
+            keys.push(parse_string(arg, "Key")?);
+        }
+
+        Ok(PfCount { keys })
+    }
+
+    /// Executes the PFCOUNT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the approximated cardinality of the HyperLogLog (or the union of the
+    /// HyperLogLogs, if multiple keys are specified) as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.pfcount(&self.keys) {
+            Ok(card) => RespType::Integer(card as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the PFMERGE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct PfMerge {
+    dest_key: String,
+    keys: Vec<String>,
+}
+
+impl PfMerge {
+    /// Creates a new `PfMerge` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PFMERGE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PfMerge)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PfMerge, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("PFMERGE"));
+        }
+
+        let dest_key = parse_string(&args[0], "Destination key")?;
+
+        let mut keys = vec![];
+        for arg in args[1..].iter() {
+            keys.push(parse_string(arg, "Key")?);
+        }
+
+        Ok(PfMerge { dest_key, keys })
+    }
+
+    /// Executes the PFMERGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an 'OK` as a `SimpleString` if the HyperLogLogs are merged successfully.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.pfmerge(&self.dest_key, &self.keys) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("PFMERGE")),
+            RespType::BulkString(Bytes::from(self.dest_key.clone())),
+        ];
+
+        for key in self.keys.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(key.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -1,3 +1,5 @@
+use bytes::Bytes;
+
 use crate::resp::types::RespType;
 
 use super::CommandError;
@@ -6,7 +8,7 @@ use super::CommandError;
 #[derive(Debug, Clone)]
 pub struct Ping {
     /// Custom message
-    msg: Option<String>,
+    msg: Option<Bytes>,
 }
 
 impl Ping {
@@ -42,13 +44,26 @@ impl Ping {
     /// - If a message was provided, it returns that message as a `BulkString`.
     pub fn apply(&self) -> RespType {
         if let Some(msg) = &self.msg {
-            RespType::BulkString(msg.to_string())
+            RespType::BulkString(msg.clone())
         } else {
             RespType::SimpleString(String::from("PONG"))
         }
     }
 
+    /// Executes the PING command for a connection in the subscriber mode.
+    ///
+    /// # Returns
+    ///
+    /// An `Array` of "pong" and the message, which is an empty string if no message was
+    /// provided.
+    pub fn apply_subscribed(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("pong")),
+            RespType::BulkString(self.msg.clone().unwrap_or_default()),
+        ])
+    }
+
     pub fn build_command() -> RespType {
-        RespType::Array(vec![RespType::BulkString(String::from("PING"))])
+        RespType::Array(vec![RespType::BulkString(Bytes::from("PING"))])
     }
 }
@@ -1,4 +1,7 @@
-use crate::{replication::Replication, resp::types::RespType, storage::db::DB};
+use crate::{
+    migration::MigrationPool, pubsub::PubSub, replication::Replication, resp::types::RespType,
+    storage::db::Storage, tracking,
+};
 
 use super::Command;
 
@@ -56,23 +59,48 @@ impl MultiCommand {
     ///
     /// # Arguments
     ///
-    /// * `db` - The database where the key and values are stored.
+    /// * `storage` - The databases where the key and values are stored.
+    ///
+    /// * `db_index` - The number of the database selected by the connection. This is updated
+    ///   if a SELECT command is in the pipeline.
     ///
     /// * `replication` - Server replication.
     ///
+    /// * `migration` - The cached connections used by MIGRATE.
+    ///
+    /// * `pubsub` - The subscriptions of all the clients, to which messages are published.
+    ///
     /// # Returns
     ///
     /// A `RespType::Array` containing the responses for each command in the pipeline.
-    pub async fn exec(&mut self, db: &DB, replication: &Replication) -> RespType {
+    pub async fn exec(
+        &mut self,
+        storage: &Storage,
+        db_index: &mut usize,
+        replication: &Replication,
+        migration: &MigrationPool,
+        pubsub: &PubSub,
+    ) -> RespType {
         let mut responses: Vec<RespType> = vec![];
 
         for cmd in self.commands.iter() {
+            // only the keys read by the read-only commands are tracked
+            let replica_cmd = cmd.replication_cmd();
+            tracking::set_read_only(replica_cmd.is_none());
+
             // execute the command
-            let res = cmd.execute(db, replication);
+            let res = match cmd {
+                Command::Migrate(migrate) => {
+                    migrate
+                        .apply(storage, *db_index, replication, migration)
+                        .await
+                }
+                _ => cmd.execute(storage, db_index, replication, pubsub),
+            };
 
             // send commands to replica if required
-            if let Some(replica_cmd) = cmd.replication_cmd() {
-                replication.write_to_replicas(replica_cmd).await;
+            if let Some(replica_cmd) = replica_cmd {
+                replication.write_to_replicas(replica_cmd, *db_index).await;
             };
 
             responses.push(res);
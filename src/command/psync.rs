@@ -1,3 +1,5 @@
+use bytes::Bytes;
+
 use crate::{replication::Replication, resp::types::RespType};
 
 use super::CommandError;
@@ -28,7 +30,7 @@ impl Psync {
         // parse replication id
         let replication_id = &args[0];
         let replication_id = match replication_id {
-            RespType::BulkString(id) => id.to_string(),
+            RespType::BulkString(id) => String::from_utf8_lossy(id).to_string(),
             _ => {
                 return Err(CommandError::Other(String::from(
                     "Invalid argument. replication id must be a bulk string",
@@ -43,7 +45,7 @@ impl Psync {
                 if v == "-1" {
                     None
                 } else {
-                    let offset = v.parse::<u64>();
+                    let offset = String::from_utf8_lossy(v).parse::<u64>();
                     match offset {
                         Ok(i) => Some(i),
                         Err(_) => {
@@ -87,9 +89,9 @@ impl Psync {
     pub fn build_command(&self) -> RespType {
         let offset = self.offset.map_or("-1".to_string(), |v| v.to_string());
         RespType::Array(vec![
-            RespType::BulkString(String::from("PSYNC")),
-            RespType::BulkString(self.replication_id.clone()),
-            RespType::BulkString(offset),
+            RespType::BulkString(Bytes::from("PSYNC")),
+            RespType::BulkString(Bytes::from(self.replication_id.clone())),
+            RespType::BulkString(Bytes::from(offset)),
         ])
     }
 }
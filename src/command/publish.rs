@@ -0,0 +1,76 @@
+use bytes::Bytes;
+
+use crate::{pubsub::PubSub, resp::types::RespType};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the PUBLISH and SPUBLISH commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Publish {
+    channel: String,
+    message: Bytes,
+    /// Set to `true` if the message is published to a shard channel.
+    shard: bool,
+}
+
+impl Publish {
+    /// Creates a new `Publish` instance from the given arguments.
+    ///
+    /// Syntax: `PUBLISH channel message`, or `SPUBLISH shardchannel message`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `shard` - Set to `true` for SPUBLISH command, which publishes to a shard channel.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Publish)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, shard: bool) -> Result<Publish, CommandError> {
+        let cmd_name = if shard { "SPUBLISH" } else { "PUBLISH" };
+        if args.len() != 2 {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let channel = parse_string(&args[0], "Channel")?;
+        let message = parse_bytes(&args[1], "Message")?;
+
+        Ok(Publish {
+            channel,
+            message,
+            shard,
+        })
+    }
+
+    /// Executes the PUBLISH or SPUBLISH command. Messages published to a shard channel are
+    /// received only by the subscribers of the shard channel.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubsub` - The subscriptions of all the clients.
+    ///
+    /// # Returns
+    ///
+    /// The number of clients which received the message as an `Integer`.
+    pub fn apply(&self, pubsub: &PubSub) -> RespType {
+        let receivers = match self.shard {
+            true => pubsub.spublish(&self.channel, self.message.clone()),
+            false => pubsub.publish(&self.channel, self.message.clone()),
+        };
+
+        RespType::Integer(receivers as i64)
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from(if self.shard { "SPUBLISH" } else { "PUBLISH" })),
+            RespType::BulkString(Bytes::from(self.channel.clone())),
+            RespType::BulkString(self.message.clone()),
+        ])
+    }
+}
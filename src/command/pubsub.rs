@@ -0,0 +1,133 @@
+use bytes::Bytes;
+
+use crate::{
+    pubsub::{PubSub, SubscriptionKind},
+    resp::types::RespType,
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the PUBSUB command in Nimblecache, which inspects the state of the pub/sub.
+#[derive(Debug, Clone)]
+pub struct PubSubQuery {
+    query: Query,
+}
+
+/// The subcommands of the PUBSUB command.
+#[derive(Debug, Clone)]
+enum Query {
+    /// The active channels (or shard channels), optionally matching a glob-style pattern.
+    Channels(SubscriptionKind, Option<String>),
+    /// The number of subscribers of the channels (or shard channels).
+    NumSub(SubscriptionKind, Vec<String>),
+    /// The number of patterns subscribed to.
+    NumPat,
+}
+
+impl PubSubQuery {
+    /// Creates a new `PubSubQuery` instance from the given arguments.
+    ///
+    /// Syntax: `PUBSUB CHANNELS [pattern]`, `PUBSUB NUMSUB [channel [channel ...]]`,
+    /// `PUBSUB NUMPAT`, `PUBSUB SHARDCHANNELS [pattern]` or
+    /// `PUBSUB SHARDNUMSUB [shardchannel [shardchannel ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the PUBSUB command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PubSubQuery)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<PubSubQuery, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("PUBSUB"));
+        }
+
+        let sub_cmd = parse_string(&args[0], "Subcommand")?;
+        let query = match sub_cmd.to_lowercase().as_str() {
+            "channels" => Query::Channels(SubscriptionKind::Channel, parse_pattern(&args)?),
+            "shardchannels" => {
+                Query::Channels(SubscriptionKind::ShardChannel, parse_pattern(&args)?)
+            }
+            "numsub" => Query::NumSub(SubscriptionKind::Channel, parse_channels(&args)?),
+            "shardnumsub" => Query::NumSub(SubscriptionKind::ShardChannel, parse_channels(&args)?),
+            "numpat" => {
+                if args.len() != 1 {
+                    return Err(wrong_num_args("PUBSUB NUMPAT"));
+                }
+                Query::NumPat
+            }
+            _ => {
+                return Err(CommandError::Other(format!(
+                    "Invalid subcommand '{}' for 'PUBSUB' command",
+                    sub_cmd
+                )))
+            }
+        };
+
+        Ok(PubSubQuery { query })
+    }
+
+    /// Executes the PUBSUB command.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubsub` - The subscriptions of all the clients.
+    ///
+    /// # Returns
+    ///
+    /// * CHANNELS - The channels with at least one subscriber as an `Array`. The channels
+    ///   subscribed to only through patterns are not returned.
+    /// * NUMSUB - A `Map` of each channel to its number of subscribers (sent as a flat `Array`
+    ///   in RESP2).
+    /// * NUMPAT - The number of patterns subscribed to as an `Integer`.
+    /// * SHARDCHANNELS and SHARDNUMSUB - Same as CHANNELS and NUMSUB, for the shard
+    ///   channels.
+    pub fn apply(&self, pubsub: &PubSub) -> RespType {
+        match &self.query {
+            Query::Channels(kind, pattern) => {
+                let mut channels = pubsub.channels(*kind, pattern.as_deref());
+                channels.sort();
+                RespType::Array(
+                    channels
+                        .into_iter()
+                        .map(|c| RespType::BulkString(Bytes::from(c)))
+                        .collect(),
+                )
+            }
+            Query::NumSub(kind, channels) => RespType::Map(
+                channels
+                    .iter()
+                    .map(|c| {
+                        (
+                            RespType::BulkString(Bytes::from(c.clone())),
+                            RespType::Integer(pubsub.num_subscribers(*kind, c) as i64),
+                        )
+                    })
+                    .collect(),
+            ),
+            Query::NumPat => RespType::Integer(pubsub.num_patterns() as i64),
+        }
+    }
+}
+
+/// Reads the optional pattern of the CHANNELS and SHARDCHANNELS subcommands.
+fn parse_pattern(args: &[RespType]) -> Result<Option<String>, CommandError> {
+    match args.len() {
+        1 => Ok(None),
+        2 => Ok(Some(parse_string(&args[1], "Pattern")?)),
+        _ => Err(wrong_num_args("PUBSUB")),
+    }
+}
+
+/// Reads the channels of the NUMSUB and SHARDNUMSUB subcommands.
+fn parse_channels(args: &[RespType]) -> Result<Vec<String>, CommandError> {
+    args[1..]
+        .iter()
+        .map(|arg| parse_string(arg, "Channel"))
+        .collect()
+}
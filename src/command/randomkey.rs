@@ -0,0 +1,48 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{args::wrong_num_args, CommandError};
+
+/// Represents the RANDOMKEY command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct RandomKey;
+
+impl RandomKey {
+    /// Creates a new `RandomKey` instance from the given arguments.
+    ///
+    /// Syntax: `RANDOMKEY`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the RANDOMKEY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RandomKey)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<RandomKey, CommandError> {
+        if !args.is_empty() {
+            return Err(wrong_num_args("RANDOMKEY"));
+        }
+
+        Ok(RandomKey)
+    }
+
+    /// Executes the RANDOMKEY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns a random key as a `BulkString`, or a `NullBulkString` if the DB is empty.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.random_key() {
+            Ok(Some(k)) => RespType::BulkString(Bytes::from(k)),
+            Ok(None) => RespType::NullBulkString,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the RENAME and RENAMENX commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Rename {
+    src: String,
+    dest: String,
+    nx: bool,
+}
+
+impl Rename {
+    /// Creates a new `Rename` instance from the given arguments.
+    ///
+    /// Syntax: `RENAME key newkey` or `RENAMENX key newkey`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `nx` - Set to `true` for RENAMENX command, which renames the key only if the new key
+    ///   doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Rename)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, nx: bool) -> Result<Rename, CommandError> {
+        if args.len() != 2 {
+            let cmd_name = if nx { "RENAMENX" } else { "RENAME" };
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let src = parse_string(&args[0], "Key")?;
+        let dest = parse_string(&args[1], "New key")?;
+
+        Ok(Rename { src, dest, nx })
+    }
+
+    /// Executes the RENAME or RENAMENX command. The expiry of the key is retained.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// For RENAME, it returns `OK` as a `SimpleString` once the key is renamed. For RENAMENX,
+    /// it returns `1` as an `Integer` if the key is renamed, or `0` if the new key already
+    /// exists. An error is returned if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.rename(&self.src, &self.dest, self.nx) {
+            Ok(_) if !self.nx => RespType::SimpleString(String::from("OK")),
+            Ok(renamed) => RespType::Integer(renamed as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let cmd_name = if self.nx { "RENAMENX" } else { "RENAME" };
+
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from(cmd_name)),
+            RespType::BulkString(Bytes::from(self.src.clone())),
+            RespType::BulkString(Bytes::from(self.dest.clone())),
+        ])
+    }
+}
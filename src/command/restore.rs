@@ -0,0 +1,170 @@
+use bytes::Bytes;
+use time::OffsetDateTime;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, dump::restore_expiry},
+};
+
+use super::{
+    args::{parse_bytes, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the RESTORE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Restore {
+    key: String,
+    payload: Bytes,
+    /// The expiry time of the key, computed from the TTL when the command is parsed.
+    expiry: Option<OffsetDateTime>,
+    replace: bool,
+    idle_time: Option<u64>,
+    freq: Option<u8>,
+}
+
+impl Restore {
+    /// Creates a new `Restore` instance from the given arguments.
+    ///
+    /// Syntax: `RESTORE key ttl serialized-value [REPLACE] [ABSTTL] [IDLETIME seconds]
+    /// [FREQ frequency]`
+    ///
+    /// IDLETIME and FREQ are validated but have no effect, since keys are not evicted based
+    /// on their access time or frequency.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the RESTORE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Restore)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Restore, CommandError> {
+        if args.len() < 3 {
+            return Err(wrong_num_args("RESTORE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let ttl = match parse_string(&args[1], "TTL")?.parse::<i64>() {
+            Ok(ttl) if ttl >= 0 => ttl as u64,
+            Ok(_) => {
+                return Err(CommandError::Other(String::from(
+                    "Invalid TTL value, must be >= 0",
+                )))
+            }
+            Err(_) => return Err(CommandError::Other(String::from("Invalid TTL value"))),
+        };
+        let payload = parse_bytes(&args[2], "Serialized value")?;
+
+        let mut replace = false;
+        let mut abs_ttl = false;
+        let mut idle_time = None;
+        let mut freq = None;
+        let mut idx = 3;
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "replace" => {
+                    replace = true;
+                    idx += 1;
+                }
+                "absttl" => {
+                    abs_ttl = true;
+                    idx += 1;
+                }
+                "idletime" => {
+                    let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args("RESTORE"))?;
+                    idle_time = Some(parse_int::<u64>(arg, "Idle time")?);
+                    idx += 2;
+                }
+                "freq" => {
+                    let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args("RESTORE"))?;
+                    let f = parse_int::<u8>(arg, "Frequency").map_err(|_| {
+                        CommandError::Other(String::from(
+                            "Invalid FREQ value, must be >= 0 and <= 255",
+                        ))
+                    })?;
+                    freq = Some(f);
+                    idx += 2;
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        if idle_time.is_some() && freq.is_some() {
+            return Err(CommandError::Other(String::from(
+                "IDLETIME and FREQ cannot be specified together",
+            )));
+        }
+
+        let expiry =
+            restore_expiry(ttl, abs_ttl).map_err(|e| CommandError::Other(format!("{}", e)))?;
+
+        Ok(Restore {
+            key,
+            payload,
+            expiry,
+            replace,
+            idle_time,
+            freq,
+        })
+    }
+
+    /// Executes the RESTORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// `OK` as a `SimpleString` if the key is restored. An error is returned if the payload
+    /// is corrupted or was created by an incompatible version, or if the key already exists
+    /// and REPLACE is not given.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.restore(&self.key, &self.payload, self.expiry, self.replace) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Builds the command which is sent to the replicas. The expiry is always sent as an
+    /// absolute Unix time (with ABSTTL), so that the key expires at the same time on the
+    /// replicas regardless of the replication lag.
+    ///
+    /// # Returns
+    ///
+    /// The RESTORE command, as an array of bulk strings.
+    pub fn build_command(&self) -> RespType {
+        let ms_from_epoch = self.expiry.map_or(0, |exp_ts| {
+            (exp_ts - OffsetDateTime::UNIX_EPOCH).whole_milliseconds() as u64
+        });
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("RESTORE")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(ms_from_epoch.to_string())),
+            RespType::BulkString(self.payload.clone()),
+        ];
+
+        if self.replace {
+            cmd.push(RespType::BulkString(Bytes::from("REPLACE")));
+        }
+        if self.expiry.is_some() {
+            cmd.push(RespType::BulkString(Bytes::from("ABSTTL")));
+        }
+        if let Some(idle_time) = self.idle_time {
+            cmd.push(RespType::BulkString(Bytes::from("IDLETIME")));
+            cmd.push(RespType::BulkString(Bytes::from(idle_time.to_string())));
+        }
+        if let Some(freq) = self.freq {
+            cmd.push(RespType::BulkString(Bytes::from("FREQ")));
+            cmd.push(RespType::BulkString(Bytes::from(freq.to_string())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -1,6 +1,8 @@
+use bytes::Bytes;
+
 use crate::{resp::types::RespType, storage::db::DB};
 
-use super::CommandError;
+use super::{args::parse_string, CommandError};
 
 /// Represents the RPUSH command in Nimblecache.
 #[derive(Debug, Clone)]
@@ -28,33 +30,15 @@ impl RPush {
         }
 
         // parse key
-        let key = &args[0];
-        let key = match key {
-            RespType::BulkString(k) => k,
-            _ => {
-                return Err(CommandError::Other(String::from(
-                    "Invalid argument. Key must be a bulk string",
-                )));
-            }
-        };
+        let key = parse_string(&args[0], "Key")?;
 
         // parse values
         let mut values: Vec<String> = vec![];
         for arg in args[1..].iter() {
-            match arg {
-                RespType::BulkString(v) => values.push(v.to_string()),
-                _ => {
-                    return Err(CommandError::Other(String::from(
-                        "Invalid argument. Value must be a bulk string",
-                    )));
-                }
-            }
+            values.push(parse_string(arg, "Value")?);
         }
 
-        Ok(RPush {
-            key: key.to_string(),
-            values,
-        })
+        Ok(RPush { key, values })
     }
 
     /// Executes the RPUSH command.
@@ -75,13 +59,13 @@ impl RPush {
 
     pub fn build_command(&self) -> RespType {
         let mut args: Vec<RespType> = vec![
-            RespType::BulkString(String::from("RPUSH")),
-            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(Bytes::from("RPUSH")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
         ];
 
         let arg_vals = self.values.clone();
         for arg in arg_vals.iter() {
-            args.push(RespType::BulkString(arg.to_string()));
+            args.push(RespType::BulkString(Bytes::from(arg.to_string())));
         }
 
         RespType::Array(args)
@@ -0,0 +1,146 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, keys::ScanOptions},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Number of elements walked by a call of the SCAN family of commands, unless COUNT is given.
+const DEFAULT_SCAN_COUNT: usize = 10;
+
+/// Represents the SCAN command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Scan {
+    cursor: u64,
+    opts: ScanOptions,
+    type_name: Option<String>,
+}
+
+impl Scan {
+    /// Creates a new `Scan` instance from the given arguments.
+    ///
+    /// Syntax: `SCAN cursor [MATCH pattern] [COUNT count] [TYPE type]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SCAN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Scan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Scan, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("SCAN"));
+        }
+
+        let cursor = parse_int::<u64>(&args[0], "Cursor")?;
+
+        let mut type_name = None;
+        let opts = parse_scan_options(&args[1..], |opt, args, idx| {
+            if opt != "type" {
+                return Ok(false);
+            }
+            let arg = args.get(*idx + 1).ok_or_else(|| wrong_num_args("SCAN"))?;
+            type_name = Some(parse_string(arg, "Type")?);
+            *idx += 2;
+            Ok(true)
+        })?;
+
+        Ok(Scan {
+            cursor,
+            opts,
+            type_name,
+        })
+    }
+
+    /// Executes the SCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the cursor for the next call as a `BulkString`, followed by an
+    /// `Array` of the keys as `BulkString`s. The cursor is `0` once the iteration is complete.
+    /// Every key which exists for the whole iteration is returned at least once, while a key
+    /// may be returned more than once.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.scan(self.cursor, &self.opts, self.type_name.as_deref()) {
+            Ok((cursor, keys)) => scan_reply(
+                cursor,
+                keys.into_iter()
+                    .map(|k| RespType::BulkString(Bytes::from(k)))
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
+
+/// Reads the options of the SCAN family of commands.
+///
+/// Syntax: `[MATCH pattern] [COUNT count]`
+///
+/// # Arguments
+///
+/// * `args` - The arguments, starting from the first option.
+///
+/// * `parse_option` - Reads the options which are specific to the command, if the lowercase
+///   option at the given index is one of them. It must move the index past the option, and
+///   return `Ok(true)` if the argument is such an option.
+pub fn parse_scan_options(
+    args: &[RespType],
+    mut parse_option: impl FnMut(&str, &[RespType], &mut usize) -> Result<bool, CommandError>,
+) -> Result<ScanOptions, CommandError> {
+    let mut opts = ScanOptions {
+        pattern: None,
+        count: DEFAULT_SCAN_COUNT,
+    };
+
+    let mut idx = 0;
+    while idx < args.len() {
+        let opt = parse_string(&args[idx], "Option")?.to_lowercase();
+        match opt.as_str() {
+            "match" => {
+                let arg = args.get(idx + 1).ok_or_else(syntax_err)?;
+                opts.pattern = Some(parse_string(arg, "Pattern")?);
+                idx += 2;
+            }
+            "count" => {
+                let arg = args.get(idx + 1).ok_or_else(syntax_err)?;
+                opts.count = parse_int::<usize>(arg, "Count")?;
+                if opts.count == 0 {
+                    return Err(syntax_err());
+                }
+                idx += 2;
+            }
+            _ => {
+                if !parse_option(&opt, args, &mut idx)? {
+                    return Err(syntax_err());
+                }
+            }
+        }
+    }
+
+    Ok(opts)
+}
+
+/// Builds the reply of the SCAN family of commands, with the cursor for the next call and the
+/// elements.
+pub fn scan_reply(cursor: u64, elements: Vec<RespType>) -> RespType {
+    RespType::Array(vec![
+        RespType::BulkString(Bytes::from(cursor.to_string())),
+        RespType::Array(elements),
+    ])
+}
+
+fn syntax_err() -> CommandError {
+    CommandError::Other(String::from("Syntax error"))
+}
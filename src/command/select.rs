@@ -0,0 +1,70 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::{
+    args::{parse_int, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the SELECT command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Select {
+    index: usize,
+}
+
+impl Select {
+    pub fn new(index: usize) -> Select {
+        Select { index }
+    }
+
+    /// Creates a new `Select` instance from the given arguments.
+    ///
+    /// Syntax: `SELECT index`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SELECT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Select)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Select, CommandError> {
+        if args.len() != 1 {
+            return Err(wrong_num_args("SELECT"));
+        }
+
+        let index = parse_int::<usize>(&args[0], "DB index")?;
+
+        Ok(Select { index })
+    }
+
+    /// Executes the SELECT command, which changes the database selected by the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The databases.
+    ///
+    /// * `db_index` - The number of the database selected by the connection, which is updated.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` if the database exists.
+    pub fn apply(&self, storage: &Storage, db_index: &mut usize) -> RespType {
+        match storage.db(self.index) {
+            Ok(_) => {
+                *db_index = self.index;
+                RespType::SimpleString(String::from("OK"))
+            }
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("SELECT")),
+            RespType::BulkString(Bytes::from(self.index.to_string())),
+        ])
+    }
+}
@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use time::{Duration, OffsetDateTime};
 
 use crate::{
@@ -5,13 +6,16 @@ use crate::{
     storage::db::{Value, DB},
 };
 
-use super::CommandError;
+use super::{
+    args::{parse_bytes, parse_string},
+    CommandError,
+};
 
 /// Represents the SET command in Nimblecache.
 #[derive(Debug, Clone)]
 pub struct Set {
     key: String,
-    value: String,
+    value: Bytes,
     expiry: Option<OffsetDateTime>,
 }
 
@@ -34,26 +38,10 @@ impl Set {
         }
 
         // parse key
-        let key = &args[0];
-        let key = match key {
-            RespType::BulkString(k) => k,
-            _ => {
-                return Err(CommandError::Other(String::from(
-                    "Invalid argument. Key must be a bulk string",
-                )));
-            }
-        };
+        let key = parse_string(&args[0], "Key")?;
 
         // parse value
-        let value = &args[1];
-        let value = match value {
-            RespType::BulkString(v) => v.to_string(),
-            _ => {
-                return Err(CommandError::Other(String::from(
-                    "Invalid argument. Value must be a bulk string",
-                )));
-            }
-        };
+        let value = parse_bytes(&args[1], "Value")?;
 
         let mut expiry: Option<OffsetDateTime> = None;
 
@@ -87,11 +75,7 @@ impl Set {
             }
         }
 
-        Ok(Set {
-            key: key.to_string(),
-            value,
-            expiry,
-        })
+        Ok(Set { key, value, expiry })
     }
 
     /// Executes the SET command.
@@ -106,25 +90,25 @@ impl Set {
     pub fn apply(&self, db: &DB) -> RespType {
         match db.set(
             self.key.clone(),
-            Value::String(self.value.clone()),
+            Value::String(self.value.to_vec()),
             self.expiry,
         ) {
-            Ok(_) => RespType::BulkString("OK".to_string()),
+            Ok(_) => RespType::BulkString(Bytes::from("OK")),
             Err(e) => RespType::SimpleError(format!("{}", e)),
         }
     }
 
     pub fn build_command(&self) -> RespType {
         let mut cmd = vec![
-            RespType::BulkString(String::from("SET")),
-            RespType::BulkString(self.key.clone()),
+            RespType::BulkString(Bytes::from("SET")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
             RespType::BulkString(self.value.clone()),
         ];
 
         if let Some(exp_ts) = self.expiry {
             let ms_from_epoch = (exp_ts - OffsetDateTime::UNIX_EPOCH).whole_milliseconds() as u64;
-            cmd.push(RespType::BulkString(String::from("PXAT")));
-            cmd.push(RespType::BulkString(ms_from_epoch.to_string()));
+            cmd.push(RespType::BulkString(Bytes::from("PXAT")));
+            cmd.push(RespType::BulkString(Bytes::from(ms_from_epoch.to_string())));
         }
 
         RespType::Array(cmd)
@@ -132,6 +116,7 @@ impl Set {
 }
 
 /// Options supported by the SET command.
+#[allow(clippy::upper_case_acronyms)]
 enum SetOption {
     /// TTL for the key specified in milliseconds.
     PX(u64),
@@ -155,7 +140,7 @@ impl SetOption {
 
         let opt_name = opts[start_idx];
         let opt_name = match opt_name {
-            RespType::BulkString(o) => o,
+            RespType::BulkString(o) => String::from_utf8_lossy(o),
             _ => {
                 return Err(CommandError::Other(String::from(
                     "Invalid argument. All arguments should be in bulk string format",
@@ -183,7 +168,7 @@ impl SetOption {
 
         let px_val = opts[px_val_idx];
         let px_val = match px_val {
-            RespType::BulkString(p) => p,
+            RespType::BulkString(p) => String::from_utf8_lossy(p),
             _ => {
                 return Err(CommandError::Other(String::from(
                     "Value for PX should be in bulk string format",
@@ -214,7 +199,7 @@ impl SetOption {
 
         let pxat_val = opts[pxat_val_idx];
         let pxat_val = match pxat_val {
-            RespType::BulkString(p) => p,
+            RespType::BulkString(p) => String::from_utf8_lossy(p),
             _ => {
                 return Err(CommandError::Other(String::from(
                     "Value for PXAT should be in bulk string format",
@@ -0,0 +1,86 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{bitmap::MAX_BIT_OFFSET, db::DB},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the SETBIT command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct SetBit {
+    key: String,
+    offset: u64,
+    bit: u8,
+}
+
+impl SetBit {
+    /// Creates a new `SetBit` instance from the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SETBIT command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SetBit)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SetBit, CommandError> {
+        if args.len() != 3 {
+            return Err(wrong_num_args("SETBIT"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let offset = parse_bit_offset(&args[1])?;
+
+        let bit = match parse_int::<u8>(&args[2], "Bit") {
+            Ok(b) if b <= 1 => b,
+            _ => {
+                return Err(CommandError::Other(String::from(
+                    "Bit is not an integer or out of range",
+                )))
+            }
+        };
+
+        Ok(SetBit { key, offset, bit })
+    }
+
+    /// Executes the SETBIT command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the original bit value stored at offset as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.setbit(&self.key, self.offset, self.bit) {
+            Ok(old_bit) => RespType::Integer(old_bit as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("SETBIT")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.offset.to_string())),
+            RespType::BulkString(Bytes::from(self.bit.to_string())),
+        ])
+    }
+}
+
+/// Parse a bit offset, which should be a positive integer less than 2^32 (512 MB).
+pub fn parse_bit_offset(arg: &RespType) -> Result<u64, CommandError> {
+    match parse_int::<u64>(arg, "Bit offset") {
+        Ok(offset) if offset < MAX_BIT_OFFSET => Ok(offset),
+        _ => Err(CommandError::Other(String::from(
+            "Bit offset is not an integer or out of range",
+        ))),
+    }
+}
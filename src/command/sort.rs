@@ -0,0 +1,178 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, sort::SortOptions},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the SORT and SORT_RO commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Sort {
+    key: String,
+    opts: SortOptions,
+    /// The key where the result is stored, instead of being returned.
+    store: Option<String>,
+    read_only: bool,
+}
+
+impl Sort {
+    /// Creates a new `Sort` instance from the given arguments.
+    ///
+    /// Syntax: `SORT key [BY pattern] [LIMIT offset count] [GET pattern [GET pattern ...]]
+    /// [ASC | DESC] [ALPHA] [STORE destination]`, or `SORT_RO` with the same options except
+    /// STORE.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `read_only` - Set to `true` for SORT_RO command, which doesn't accept STORE.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Sort)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, read_only: bool) -> Result<Sort, CommandError> {
+        let cmd_name = if read_only { "SORT_RO" } else { "SORT" };
+        if args.is_empty() {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut opts = SortOptions::default();
+        let mut store = None;
+        let mut idx = 1;
+        while idx < args.len() {
+            match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+                "by" => {
+                    let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args(cmd_name))?;
+                    opts.by = Some(parse_string(arg, "Pattern")?);
+                    idx += 2;
+                }
+                "limit" => {
+                    if idx + 2 >= args.len() {
+                        return Err(wrong_num_args(cmd_name));
+                    }
+                    let offset = parse_int::<i64>(&args[idx + 1], "Offset")?;
+                    let count = parse_int::<i64>(&args[idx + 2], "Count")?;
+                    opts.limit = Some((offset, count));
+                    idx += 3;
+                }
+                "get" => {
+                    let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args(cmd_name))?;
+                    opts.get.push(parse_string(arg, "Pattern")?);
+                    idx += 2;
+                }
+                "asc" => {
+                    opts.desc = false;
+                    idx += 1;
+                }
+                "desc" => {
+                    opts.desc = true;
+                    idx += 1;
+                }
+                "alpha" => {
+                    opts.alpha = true;
+                    idx += 1;
+                }
+                "store" if !read_only => {
+                    let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args(cmd_name))?;
+                    store = Some(parse_string(arg, "Destination")?);
+                    idx += 2;
+                }
+                _ => {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )))
+                }
+            }
+        }
+
+        Ok(Sort {
+            key,
+            opts,
+            store,
+            read_only,
+        })
+    }
+
+    /// Executes the SORT or SORT_RO command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// The sorted elements (or the values of the GET patterns, where missing values are
+    /// `NullBulkString`) as an `Array`. With STORE, the number of elements in the stored list
+    /// is returned as an `Integer`. An error is returned if the key is not a list or a
+    /// sorted set, or if a weight is not a number without ALPHA.
+    pub fn apply(&self, db: &DB) -> RespType {
+        if let Some(dest) = &self.store {
+            return match db.sort_store(&self.key, &self.opts, dest) {
+                Ok(len) => RespType::Integer(len as i64),
+                Err(e) => RespType::SimpleError(format!("{}", e)),
+            };
+        }
+
+        match db.sort(&self.key, &self.opts) {
+            Ok(values) => RespType::Array(
+                values
+                    .into_iter()
+                    .map(|v| match v {
+                        Some(v) => RespType::BulkString(Bytes::from(v)),
+                        None => RespType::NullBulkString,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    /// Returns `true` if the result is stored (SORT with STORE), which is the only case in
+    /// which the command is replicated.
+    pub fn is_write(&self) -> bool {
+        self.store.is_some()
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let cmd_name = if self.read_only { "SORT_RO" } else { "SORT" };
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from(cmd_name)),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        if let Some(by) = &self.opts.by {
+            cmd.push(RespType::BulkString(Bytes::from("BY")));
+            cmd.push(RespType::BulkString(Bytes::from(by.clone())));
+        }
+        if let Some((offset, count)) = self.opts.limit {
+            cmd.push(RespType::BulkString(Bytes::from("LIMIT")));
+            cmd.push(RespType::BulkString(Bytes::from(offset.to_string())));
+            cmd.push(RespType::BulkString(Bytes::from(count.to_string())));
+        }
+        for pattern in self.opts.get.iter() {
+            cmd.push(RespType::BulkString(Bytes::from("GET")));
+            cmd.push(RespType::BulkString(Bytes::from(pattern.clone())));
+        }
+        if self.opts.desc {
+            cmd.push(RespType::BulkString(Bytes::from("DESC")));
+        }
+        if self.opts.alpha {
+            cmd.push(RespType::BulkString(Bytes::from("ALPHA")));
+        }
+        if let Some(dest) = &self.store {
+            cmd.push(RespType::BulkString(Bytes::from("STORE")));
+            cmd.push(RespType::BulkString(Bytes::from(dest.clone())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,102 @@
+use bytes::Bytes;
+
+use crate::{
+    pubsub::{Subscriber, SubscriptionKind},
+    resp::types::RespType,
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the SUBSCRIBE, PSUBSCRIBE and SSUBSCRIBE commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Subscribe {
+    /// The channels, the glob-style patterns of the channels, or the shard channels.
+    channels: Vec<String>,
+    kind: SubscriptionKind,
+}
+
+impl Subscribe {
+    /// Creates a new `Subscribe` instance from the given arguments.
+    ///
+    /// Syntax: `SUBSCRIBE channel [channel ...]`, `PSUBSCRIBE pattern [pattern ...]`, or
+    /// `SSUBSCRIBE shardchannel [shardchannel ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `kind` - The kind of subscriptions, which decides the command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Subscribe)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(
+        args: Vec<RespType>,
+        kind: SubscriptionKind,
+    ) -> Result<Subscribe, CommandError> {
+        let cmd_name = match kind {
+            SubscriptionKind::Channel => "SUBSCRIBE",
+            SubscriptionKind::Pattern => "PSUBSCRIBE",
+            SubscriptionKind::ShardChannel => "SSUBSCRIBE",
+        };
+        if args.is_empty() {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let channels = parse_channels(&args, kind)?;
+
+        Ok(Subscribe { channels, kind })
+    }
+
+    /// Executes the SUBSCRIBE, PSUBSCRIBE or SSUBSCRIBE command. The connection enters the
+    /// subscriber mode, where the messages published to the channels are pushed to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The subscriptions of the connection.
+    ///
+    /// # Returns
+    ///
+    /// A reply for each channel, which is a `Push` (an `Array` in RESP2) of the kind of the
+    /// reply (`subscribe`, `psubscribe` or `ssubscribe`), the channel, and the number of
+    /// subscriptions of the connection. Shard channels are counted separately from the other subscriptions.
+    pub fn apply(&self, subscriber: &mut Subscriber) -> Vec<RespType> {
+        let reply_kind = match self.kind {
+            SubscriptionKind::Channel => "subscribe",
+            SubscriptionKind::Pattern => "psubscribe",
+            SubscriptionKind::ShardChannel => "ssubscribe",
+        };
+
+        self.channels
+            .iter()
+            .map(|channel| {
+                let count = subscriber.subscribe(self.kind, channel);
+
+                RespType::Push(vec![
+                    RespType::BulkString(Bytes::from(reply_kind)),
+                    RespType::BulkString(Bytes::from(channel.clone())),
+                    RespType::Integer(count as i64),
+                ])
+            })
+            .collect()
+    }
+}
+
+/// Reads the arguments as the channels of the subscriptions. Shard channels may hash to
+/// different slots, since there's no cluster mode and this node owns every slot.
+pub fn parse_channels(
+    args: &[RespType],
+    kind: SubscriptionKind,
+) -> Result<Vec<String>, CommandError> {
+    let arg_name = match kind {
+        SubscriptionKind::Pattern => "Pattern",
+        _ => "Channel",
+    };
+    args.iter()
+        .map(|arg| parse_string(arg, arg_name))
+        .collect::<Result<Vec<String>, CommandError>>()
+}
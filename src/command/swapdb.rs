@@ -0,0 +1,64 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::Storage};
+
+use super::{
+    args::{parse_int, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the SWAPDB command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct SwapDb {
+    index1: usize,
+    index2: usize,
+}
+
+impl SwapDb {
+    /// Creates a new `SwapDb` instance from the given arguments.
+    ///
+    /// Syntax: `SWAPDB index1 index2`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the SWAPDB command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(SwapDb)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<SwapDb, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("SWAPDB"));
+        }
+
+        let index1 = parse_int::<usize>(&args[0], "DB index")?;
+        let index2 = parse_int::<usize>(&args[1], "DB index")?;
+
+        Ok(SwapDb { index1, index2 })
+    }
+
+    /// Executes the SWAPDB command.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - The databases.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` once the contents of the databases are swapped.
+    pub fn apply(&self, storage: &Storage) -> RespType {
+        match storage.swap(self.index1, self.index2) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("SWAPDB")),
+            RespType::BulkString(Bytes::from(self.index1.to_string())),
+            RespType::BulkString(Bytes::from(self.index2.to_string())),
+        ])
+    }
+}
@@ -0,0 +1,126 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Max increment of an item in a single TOPK.INCRBY call.
+const TOPK_MAX_INCREMENT: u64 = 100000;
+
+/// Represents the TOPK.ADD and TOPK.INCRBY commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TopKAdd {
+    key: String,
+    items: Vec<(Vec<u8>, u64)>,
+    with_increments: bool,
+}
+
+impl TopKAdd {
+    /// Creates a new `TopKAdd` instance from the given arguments.
+    ///
+    /// Syntax: `TOPK.ADD key item [item ...]` or
+    /// `TOPK.INCRBY key item increment [item increment ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `with_increments` - Set to `true` for TOPK.INCRBY command, where each item is followed
+    ///   by its increment.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TopKAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, with_increments: bool) -> Result<TopKAdd, CommandError> {
+        let cmd_name = if with_increments {
+            "TOPK.INCRBY"
+        } else {
+            "TOPK.ADD"
+        };
+        if args.len() < 2 {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut items = vec![];
+        if with_increments {
+            let pairs = args[1..].chunks_exact(2);
+            if !pairs.remainder().is_empty() {
+                return Err(wrong_num_args(cmd_name));
+            }
+
+            for pair in pairs {
+                let item = parse_bytes(&pair[0], "Item")?.to_vec();
+                let incr = parse_int::<u64>(&pair[1], "Increment")?;
+                if incr == 0 || incr > TOPK_MAX_INCREMENT {
+                    return Err(CommandError::Other(format!(
+                        "Increment must be between 1 and {}",
+                        TOPK_MAX_INCREMENT
+                    )));
+                }
+                items.push((item, incr));
+            }
+        } else {
+            for arg in args[1..].iter() {
+                items.push((parse_bytes(arg, "Item")?.to_vec(), 1));
+            }
+        }
+
+        Ok(TopKAdd {
+            key,
+            items,
+            with_increments,
+        })
+    }
+
+    /// Executes the TOPK.ADD or TOPK.INCRBY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the item expelled from the top K list as a `BulkString`, or
+    /// `NullBulkString` if no item was expelled, for each of the items.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.topk_incrby(&self.key, &self.items) {
+            Ok(expelled) => RespType::Array(
+                expelled
+                    .into_iter()
+                    .map(|item| match item {
+                        Some(item) => RespType::BulkString(Bytes::from(item)),
+                        None => RespType::NullBulkString,
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let cmd_name = if self.with_increments {
+            "TOPK.INCRBY"
+        } else {
+            "TOPK.ADD"
+        };
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from(cmd_name)),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+
+        for (item, incr) in self.items.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(item.clone())));
+            if self.with_increments {
+                cmd.push(RespType::BulkString(Bytes::from(incr.to_string())));
+            }
+        }
+
+        RespType::Array(cmd)
+    }
+}
@@ -0,0 +1,78 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the TOPK.LIST command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TopKList {
+    key: String,
+    with_count: bool,
+}
+
+impl TopKList {
+    /// Creates a new `TopKList` instance from the given arguments.
+    ///
+    /// Syntax: `TOPK.LIST key [WITHCOUNT]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TOPK.LIST command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TopKList)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TopKList, CommandError> {
+        if args.is_empty() || args.len() > 2 {
+            return Err(wrong_num_args("TOPK.LIST"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let with_count = match args.get(1) {
+            Some(arg) => {
+                if parse_string(arg, "Option")?.to_lowercase() != "withcount" {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )));
+                }
+                true
+            }
+            None => false,
+        };
+
+        Ok(TopKList { key, with_count })
+    }
+
+    /// Executes the TOPK.LIST command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the items in the top K list, in the descending order of their
+    /// counts. With WITHCOUNT, each item is followed by its count as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        let items = match db.topk_list(&self.key) {
+            Ok(items) => items,
+            Err(e) => return RespType::SimpleError(format!("{}", e)),
+        };
+
+        let mut reply = vec![];
+        for (item, count) in items {
+            reply.push(RespType::BulkString(Bytes::from(item)));
+            if self.with_count {
+                reply.push(RespType::Integer(count as i64));
+            }
+        }
+
+        RespType::Array(reply)
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_bytes, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the TOPK.QUERY command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TopKQuery {
+    key: String,
+    items: Vec<Vec<u8>>,
+}
+
+impl TopKQuery {
+    /// Creates a new `TopKQuery` instance from the given arguments.
+    ///
+    /// Syntax: `TOPK.QUERY key item [item ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TOPK.QUERY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TopKQuery)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TopKQuery, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("TOPK.QUERY"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut items = vec![];
+        for arg in args[1..].iter() {
+            items.push(parse_bytes(arg, "Item")?.to_vec());
+        }
+
+        Ok(TopKQuery { key, items })
+    }
+
+    /// Executes the TOPK.QUERY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with 1 as an `Integer` for the items in the top K list, and 0 for
+    /// the rest.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.topk_query(&self.key, &self.items) {
+            Ok(results) => RespType::Array(
+                results
+                    .into_iter()
+                    .map(|found| RespType::Integer(found as i64))
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        top_k::{TOPK_DEFAULT_DECAY, TOPK_DEFAULT_DEPTH, TOPK_DEFAULT_WIDTH},
+    },
+};
+
+use super::{
+    args::{parse_float, parse_int, parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the TOPK.RESERVE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TopKReserve {
+    key: String,
+    topk: usize,
+    width: usize,
+    depth: usize,
+    decay: f64,
+}
+
+impl TopKReserve {
+    /// Creates a new `TopKReserve` instance from the given arguments.
+    ///
+    /// Syntax: `TOPK.RESERVE key topk [width depth decay]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TOPK.RESERVE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TopKReserve)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TopKReserve, CommandError> {
+        if args.len() != 2 && args.len() != 5 {
+            return Err(wrong_num_args("TOPK.RESERVE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let topk = parse_int::<usize>(&args[1], "TopK")?;
+
+        let (width, depth, decay) = if args.len() == 5 {
+            (
+                parse_int::<usize>(&args[2], "Width")?,
+                parse_int::<usize>(&args[3], "Depth")?,
+                parse_float(&args[4], "Decay")?,
+            )
+        } else {
+            (TOPK_DEFAULT_WIDTH, TOPK_DEFAULT_DEPTH, TOPK_DEFAULT_DECAY)
+        };
+
+        if topk == 0 || width == 0 || depth == 0 {
+            return Err(CommandError::Other(String::from(
+                "TopK, width and depth must be positive integers",
+            )));
+        }
+        if width.checked_mul(depth).is_none() {
+            return Err(CommandError::Other(String::from(
+                "Width and depth are too large",
+            )));
+        }
+        if decay <= 0.0 || decay > 1.0 {
+            return Err(CommandError::Other(String::from(
+                "Decay must be between 0 and 1",
+            )));
+        }
+
+        Ok(TopKReserve {
+            key,
+            topk,
+            width,
+            depth,
+            decay,
+        })
+    }
+
+    /// Executes the TOPK.RESERVE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns 'OK' as a `SimpleString` if the structure is created, or an error if the key
+    /// already exists.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.topk_reserve(&self.key, self.topk, self.width, self.depth, self.decay) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("TOPK.RESERVE")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.topk.to_string())),
+            RespType::BulkString(Bytes::from(self.width.to_string())),
+            RespType::BulkString(Bytes::from(self.depth.to_string())),
+            RespType::BulkString(Bytes::from(self.decay.to_string())),
+        ])
+    }
+}
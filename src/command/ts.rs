@@ -0,0 +1,313 @@
+use bytes::Bytes;
+use time::OffsetDateTime;
+
+use crate::{
+    resp::types::RespType,
+    storage::time_series::{
+        Aggregation, DuplicatePolicy, RangeAggregation, RangeQuery, SeriesOptions,
+    },
+};
+
+use super::{
+    args::{parse_float, parse_int, parse_string},
+    CommandError,
+};
+
+/// Reads a command argument as the timestamp of a sample, in milliseconds. `*` is read as the
+/// current time.
+pub fn parse_timestamp(arg: &RespType) -> Result<u64, CommandError> {
+    if parse_string(arg, "Timestamp")? == "*" {
+        return Ok(now_ms());
+    }
+
+    parse_int::<u64>(arg, "Timestamp")
+}
+
+/// Returns the current time in milliseconds since the UNIX epoch.
+pub fn now_ms() -> u64 {
+    (OffsetDateTime::now_utc() - OffsetDateTime::UNIX_EPOCH).whole_milliseconds() as u64
+}
+
+/// Reads the options for creating a series, if the argument at `idx` is one of them. The
+/// index is moved past the option and its values.
+///
+/// Options: `RETENTION retentionPeriod`, `DUPLICATE_POLICY policy` and
+/// `LABELS label value [label value ...]`, where the labels span till the end of the arguments.
+///
+/// # Returns
+///
+/// * `Ok(true)` if the argument is a series option.
+/// * `Ok(false)` if the argument is not a series option.
+/// * `Err(CommandError)` if the option is invalid.
+pub fn parse_series_option(
+    args: &[RespType],
+    idx: &mut usize,
+    opts: &mut SeriesOptions,
+) -> Result<bool, CommandError> {
+    match parse_string(&args[*idx], "Option")?.to_lowercase().as_str() {
+        "retention" => {
+            let arg = args.get(*idx + 1).ok_or_else(missing_value_err)?;
+            opts.retention = Some(parse_int::<u64>(arg, "Retention")?);
+            *idx += 2;
+        }
+        "duplicate_policy" => {
+            let arg = args.get(*idx + 1).ok_or_else(missing_value_err)?;
+            opts.duplicate_policy = Some(parse_duplicate_policy(arg)?);
+            *idx += 2;
+        }
+        "labels" => {
+            let pairs = args[*idx + 1..].chunks_exact(2);
+            if !pairs.remainder().is_empty() {
+                return Err(CommandError::Other(String::from(
+                    "Each label must have a value",
+                )));
+            }
+
+            let mut labels = vec![];
+            for pair in pairs {
+                labels.push((
+                    parse_string(&pair[0], "Label")?,
+                    parse_string(&pair[1], "Label value")?,
+                ));
+            }
+            opts.labels = Some(labels);
+            *idx = args.len();
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Builds the arguments of the series options, to be used when a command is replicated.
+pub fn series_options_args(opts: &SeriesOptions) -> Vec<RespType> {
+    let mut args = vec![];
+
+    if let Some(retention) = opts.retention {
+        args.push(RespType::BulkString(Bytes::from("RETENTION")));
+        args.push(RespType::BulkString(Bytes::from(retention.to_string())));
+    }
+    if let Some(policy) = opts.duplicate_policy {
+        args.push(RespType::BulkString(Bytes::from("DUPLICATE_POLICY")));
+        args.push(RespType::BulkString(Bytes::from(duplicate_policy_name(
+            policy,
+        ))));
+    }
+    if let Some(labels) = &opts.labels {
+        args.push(RespType::BulkString(Bytes::from("LABELS")));
+        for (label, value) in labels.iter() {
+            args.push(RespType::BulkString(Bytes::from(label.clone())));
+            args.push(RespType::BulkString(Bytes::from(value.clone())));
+        }
+    }
+
+    args
+}
+
+/// Reads a command argument as a duplicate policy.
+pub fn parse_duplicate_policy(arg: &RespType) -> Result<DuplicatePolicy, CommandError> {
+    match parse_string(arg, "Duplicate policy")?
+        .to_lowercase()
+        .as_str()
+    {
+        "block" => Ok(DuplicatePolicy::Block),
+        "first" => Ok(DuplicatePolicy::First),
+        "last" => Ok(DuplicatePolicy::Last),
+        "min" => Ok(DuplicatePolicy::Min),
+        "max" => Ok(DuplicatePolicy::Max),
+        "sum" => Ok(DuplicatePolicy::Sum),
+        _ => Err(CommandError::Other(String::from(
+            "Unknown duplicate policy",
+        ))),
+    }
+}
+
+pub fn duplicate_policy_name(policy: DuplicatePolicy) -> &'static str {
+    match policy {
+        DuplicatePolicy::Block => "BLOCK",
+        DuplicatePolicy::First => "FIRST",
+        DuplicatePolicy::Last => "LAST",
+        DuplicatePolicy::Min => "MIN",
+        DuplicatePolicy::Max => "MAX",
+        DuplicatePolicy::Sum => "SUM",
+    }
+}
+
+/// Reads the arguments `aggregator bucketDuration` of the AGGREGATION option.
+pub fn parse_aggregation(args: &[RespType]) -> Result<(Aggregation, u64), CommandError> {
+    if args.len() < 2 {
+        return Err(missing_value_err());
+    }
+
+    let aggregation = match parse_string(&args[0], "Aggregator")?
+        .to_lowercase()
+        .as_str()
+    {
+        "avg" => Aggregation::Avg,
+        "sum" => Aggregation::Sum,
+        "min" => Aggregation::Min,
+        "max" => Aggregation::Max,
+        "count" => Aggregation::Count,
+        "first" => Aggregation::First,
+        "last" => Aggregation::Last,
+        _ => {
+            return Err(CommandError::Other(String::from(
+                "Unknown aggregation type",
+            )))
+        }
+    };
+
+    let bucket_duration = parse_int::<u64>(&args[1], "Bucket duration")?;
+    if bucket_duration == 0 {
+        return Err(CommandError::Other(String::from(
+            "Bucket duration must be greater than zero",
+        )));
+    }
+
+    Ok((aggregation, bucket_duration))
+}
+
+pub fn aggregation_name(aggregation: Aggregation) -> &'static str {
+    match aggregation {
+        Aggregation::Avg => "AVG",
+        Aggregation::Sum => "SUM",
+        Aggregation::Min => "MIN",
+        Aggregation::Max => "MAX",
+        Aggregation::Count => "COUNT",
+        Aggregation::First => "FIRST",
+        Aggregation::Last => "LAST",
+    }
+}
+
+/// Reads the arguments of a range query.
+///
+/// Syntax: `fromTimestamp toTimestamp [FILTER_BY_TS ts...] [FILTER_BY_VALUE min max]
+/// [COUNT count] [ALIGN align] [AGGREGATION aggregator bucketDuration]`
+///
+/// # Arguments
+///
+/// * `args` - The arguments, starting from `fromTimestamp`.
+///
+/// * `rev` - Set to `true` if the samples are queried in the reverse order.
+///
+/// * `parse_option` - Reads the options which are specific to the command, if the argument at
+///   the given index is one of them. It must move the index past the option, and return
+///   `Ok(true)` if the argument is such an option.
+pub fn parse_range_query(
+    args: &[RespType],
+    rev: bool,
+    mut parse_option: impl FnMut(&[RespType], &mut usize) -> Result<bool, CommandError>,
+) -> Result<RangeQuery, CommandError> {
+    if args.len() < 2 {
+        return Err(missing_value_err());
+    }
+
+    let from = match parse_string(&args[0], "From timestamp")?.as_str() {
+        "-" => 0,
+        _ => parse_int::<u64>(&args[0], "From timestamp")?,
+    };
+    let to = match parse_string(&args[1], "To timestamp")?.as_str() {
+        "+" => u64::MAX,
+        _ => parse_int::<u64>(&args[1], "To timestamp")?,
+    };
+
+    let mut query = RangeQuery {
+        from,
+        to,
+        rev,
+        filter_by_ts: None,
+        filter_by_value: None,
+        count: None,
+        aggregation: None,
+    };
+    let mut align = None;
+
+    let mut idx = 2;
+    while idx < args.len() {
+        match parse_string(&args[idx], "Option")?.to_lowercase().as_str() {
+            "filter_by_ts" => {
+                let mut timestamps = vec![];
+                idx += 1;
+                while let Some(Ok(ts)) = args.get(idx).map(|arg| parse_int::<u64>(arg, "")) {
+                    timestamps.push(ts);
+                    idx += 1;
+                }
+                if timestamps.is_empty() {
+                    return Err(missing_value_err());
+                }
+                query.filter_by_ts = Some(timestamps);
+            }
+            "filter_by_value" => {
+                if args.len() < idx + 3 {
+                    return Err(missing_value_err());
+                }
+                let min = parse_float(&args[idx + 1], "Min value")?;
+                let max = parse_float(&args[idx + 2], "Max value")?;
+                query.filter_by_value = Some((min, max));
+                idx += 3;
+            }
+            "count" => {
+                let arg = args.get(idx + 1).ok_or_else(missing_value_err)?;
+                query.count = Some(parse_int::<usize>(arg, "Count")?);
+                idx += 2;
+            }
+            "align" => {
+                let arg = args.get(idx + 1).ok_or_else(missing_value_err)?;
+                align = Some(match parse_string(arg, "Align")?.to_lowercase().as_str() {
+                    "start" | "-" => from,
+                    "end" | "+" => to,
+                    _ => parse_int::<u64>(arg, "Align")?,
+                });
+                idx += 2;
+            }
+            "aggregation" => {
+                let (aggregation, bucket_duration) = parse_aggregation(&args[idx + 1..])?;
+                query.aggregation = Some(RangeAggregation {
+                    aggregation,
+                    bucket_duration,
+                    align: 0,
+                });
+                idx += 3;
+            }
+            _ => {
+                if !parse_option(args, &mut idx)? {
+                    return Err(CommandError::Other(String::from(
+                        "Invalid option specified",
+                    )));
+                }
+            }
+        }
+    }
+
+    match (query.aggregation.as_mut(), align) {
+        (Some(agg), Some(align)) => agg.align = align,
+        (None, Some(_)) => {
+            return Err(CommandError::Other(String::from(
+                "ALIGN can be used only with AGGREGATION",
+            )))
+        }
+        _ => {}
+    }
+
+    Ok(query)
+}
+
+/// Builds the reply for a list of samples. Each sample is replied as an `Array` with the
+/// timestamp as an `Integer` and the value as a `SimpleString`.
+pub fn samples_reply(samples: Vec<(u64, f64)>) -> RespType {
+    RespType::Array(
+        samples
+            .into_iter()
+            .map(|(ts, value)| {
+                RespType::Array(vec![
+                    RespType::Integer(ts as i64),
+                    RespType::SimpleString(value.to_string()),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn missing_value_err() -> CommandError {
+    CommandError::Other(String::from("Missing value for the option"))
+}
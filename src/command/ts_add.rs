@@ -0,0 +1,122 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        time_series::{DuplicatePolicy, SeriesOptions},
+    },
+};
+
+use super::{
+    args::{parse_float, parse_string, wrong_num_args},
+    ts::{
+        duplicate_policy_name, parse_duplicate_policy, parse_series_option, parse_timestamp,
+        series_options_args,
+    },
+    CommandError,
+};
+
+/// Represents the TS.ADD command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsAdd {
+    key: String,
+    timestamp: u64,
+    value: f64,
+    opts: SeriesOptions,
+    on_duplicate: Option<DuplicatePolicy>,
+}
+
+impl TsAdd {
+    /// Creates a new `TsAdd` instance from the given arguments.
+    ///
+    /// Syntax: `TS.ADD key timestamp value [RETENTION retentionPeriod]
+    /// [DUPLICATE_POLICY policy] [ON_DUPLICATE policy] [LABELS label value [label value ...]]`
+    ///
+    /// The timestamp `*` is resolved to the current time here, so that the replicas add the
+    /// sample with the same timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TS.ADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TsAdd, CommandError> {
+        if args.len() < 3 {
+            return Err(wrong_num_args("TS.ADD"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let timestamp = parse_timestamp(&args[1])?;
+        let value = parse_float(&args[2], "Value")?;
+
+        let mut opts = SeriesOptions::default();
+        let mut on_duplicate = None;
+        let mut idx = 3;
+        while idx < args.len() {
+            if parse_string(&args[idx], "Option")?.to_lowercase() == "on_duplicate" {
+                let arg = args.get(idx + 1).ok_or_else(|| wrong_num_args("TS.ADD"))?;
+                on_duplicate = Some(parse_duplicate_policy(arg)?);
+                idx += 2;
+            } else if !parse_series_option(&args, &mut idx, &mut opts)? {
+                return Err(CommandError::Other(String::from(
+                    "Invalid option specified",
+                )));
+            }
+        }
+
+        Ok(TsAdd {
+            key,
+            timestamp,
+            value,
+            opts,
+            on_duplicate,
+        })
+    }
+
+    /// Executes the TS.ADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the timestamp of the sample as an `Integer`, or an error if the sample is
+    /// rejected.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_add(
+            &self.key,
+            self.timestamp,
+            self.value,
+            &self.opts,
+            self.on_duplicate,
+        ) {
+            Ok(ts) => RespType::Integer(ts as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("TS.ADD")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.timestamp.to_string())),
+            RespType::BulkString(Bytes::from(self.value.to_string())),
+        ];
+
+        if let Some(policy) = self.on_duplicate {
+            cmd.push(RespType::BulkString(Bytes::from("ON_DUPLICATE")));
+            cmd.push(RespType::BulkString(Bytes::from(duplicate_policy_name(
+                policy,
+            ))));
+        }
+        // labels span till the end of the arguments, so the series options go last
+        cmd.extend(series_options_args(&self.opts));
+
+        RespType::Array(cmd)
+    }
+}
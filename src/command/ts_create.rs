@@ -0,0 +1,81 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, time_series::SeriesOptions},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    ts::{parse_series_option, series_options_args},
+    CommandError,
+};
+
+/// Represents the TS.CREATE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsCreate {
+    key: String,
+    opts: SeriesOptions,
+}
+
+impl TsCreate {
+    /// Creates a new `TsCreate` instance from the given arguments.
+    ///
+    /// Syntax: `TS.CREATE key [RETENTION retentionPeriod] [DUPLICATE_POLICY policy]
+    /// [LABELS label value [label value ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TS.CREATE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsCreate)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TsCreate, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("TS.CREATE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        let mut opts = SeriesOptions::default();
+        let mut idx = 1;
+        while idx < args.len() {
+            if !parse_series_option(&args, &mut idx, &mut opts)? {
+                return Err(CommandError::Other(String::from(
+                    "Invalid option specified",
+                )));
+            }
+        }
+
+        Ok(TsCreate { key, opts })
+    }
+
+    /// Executes the TS.CREATE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` if the series was created, or an error if the key
+    /// already exists.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_create(&self.key, &self.opts) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("TS.CREATE")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+        ];
+        cmd.extend(series_options_args(&self.opts));
+
+        RespType::Array(cmd)
+    }
+}
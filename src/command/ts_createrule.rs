@@ -0,0 +1,95 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, time_series::RangeAggregation},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    ts::{aggregation_name, parse_aggregation},
+    CommandError,
+};
+
+/// Represents the TS.CREATERULE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsCreateRule {
+    src: String,
+    dest: String,
+    aggregation: RangeAggregation,
+}
+
+impl TsCreateRule {
+    /// Creates a new `TsCreateRule` instance from the given arguments.
+    ///
+    /// Syntax: `TS.CREATERULE sourceKey destKey AGGREGATION aggregator bucketDuration
+    /// [alignTimestamp]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TS.CREATERULE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsCreateRule)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TsCreateRule, CommandError> {
+        if args.len() != 5 && args.len() != 6 {
+            return Err(wrong_num_args("TS.CREATERULE"));
+        }
+
+        let src = parse_string(&args[0], "Source key")?;
+        let dest = parse_string(&args[1], "Destination key")?;
+
+        if parse_string(&args[2], "Option")?.to_lowercase() != "aggregation" {
+            return Err(CommandError::Other(String::from(
+                "AGGREGATION must be specified",
+            )));
+        }
+        let (aggregation, bucket_duration) = parse_aggregation(&args[3..5])?;
+
+        let align = match args.get(5) {
+            Some(arg) => parse_int::<u64>(arg, "Align timestamp")?,
+            None => 0,
+        };
+
+        Ok(TsCreateRule {
+            src,
+            dest,
+            aggregation: RangeAggregation {
+                aggregation,
+                bucket_duration,
+                align,
+            },
+        })
+    }
+
+    /// Executes the TS.CREATERULE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` if the rule was created, or an error if either of
+    /// the series doesn't exist or can't be part of the rule.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_createrule(&self.src, &self.dest, self.aggregation) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("TS.CREATERULE")),
+            RespType::BulkString(Bytes::from(self.src.clone())),
+            RespType::BulkString(Bytes::from(self.dest.clone())),
+            RespType::BulkString(Bytes::from("AGGREGATION")),
+            RespType::BulkString(Bytes::from(aggregation_name(self.aggregation.aggregation))),
+            RespType::BulkString(Bytes::from(self.aggregation.bucket_duration.to_string())),
+            RespType::BulkString(Bytes::from(self.aggregation.align.to_string())),
+        ])
+    }
+}
@@ -0,0 +1,65 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the TS.DELETERULE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsDeleteRule {
+    src: String,
+    dest: String,
+}
+
+impl TsDeleteRule {
+    /// Creates a new `TsDeleteRule` instance from the given arguments.
+    ///
+    /// Syntax: `TS.DELETERULE sourceKey destKey`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TS.DELETERULE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsDeleteRule)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TsDeleteRule, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("TS.DELETERULE"));
+        }
+
+        let src = parse_string(&args[0], "Source key")?;
+        let dest = parse_string(&args[1], "Destination key")?;
+
+        Ok(TsDeleteRule { src, dest })
+    }
+
+    /// Executes the TS.DELETERULE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns `OK` as a `SimpleString` if the rule was deleted, or an error if the rule
+    /// doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_deleterule(&self.src, &self.dest) {
+            Ok(_) => RespType::SimpleString(String::from("OK")),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        RespType::Array(vec![
+            RespType::BulkString(Bytes::from("TS.DELETERULE")),
+            RespType::BulkString(Bytes::from(self.src.clone())),
+            RespType::BulkString(Bytes::from(self.dest.clone())),
+        ])
+    }
+}
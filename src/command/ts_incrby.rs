@@ -0,0 +1,101 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, time_series::SeriesOptions},
+};
+
+use super::{
+    args::{parse_float, parse_string, wrong_num_args},
+    ts::{now_ms, parse_series_option, parse_timestamp, series_options_args},
+    CommandError,
+};
+
+/// Represents the TS.INCRBY command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsIncrBy {
+    key: String,
+    incr: f64,
+    timestamp: u64,
+    opts: SeriesOptions,
+}
+
+impl TsIncrBy {
+    /// Creates a new `TsIncrBy` instance from the given arguments.
+    ///
+    /// Syntax: `TS.INCRBY key addend [TIMESTAMP timestamp] [RETENTION retentionPeriod]
+    /// [DUPLICATE_POLICY policy] [LABELS label value [label value ...]]`
+    ///
+    /// If the timestamp isn't specified, the current time is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TS.INCRBY command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsIncrBy)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TsIncrBy, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("TS.INCRBY"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let incr = parse_float(&args[1], "Addend")?;
+
+        let mut timestamp = None;
+        let mut opts = SeriesOptions::default();
+        let mut idx = 2;
+        while idx < args.len() {
+            if parse_string(&args[idx], "Option")?.to_lowercase() == "timestamp" {
+                let arg = args
+                    .get(idx + 1)
+                    .ok_or_else(|| wrong_num_args("TS.INCRBY"))?;
+                timestamp = Some(parse_timestamp(arg)?);
+                idx += 2;
+            } else if !parse_series_option(&args, &mut idx, &mut opts)? {
+                return Err(CommandError::Other(String::from(
+                    "Invalid option specified",
+                )));
+            }
+        }
+
+        Ok(TsIncrBy {
+            key,
+            incr,
+            timestamp: timestamp.unwrap_or_else(now_ms),
+            opts,
+        })
+    }
+
+    /// Executes the TS.INCRBY command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the timestamp of the sample as an `Integer`, or an error if the timestamp
+    /// is earlier than the latest sample.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_incrby(&self.key, self.timestamp, self.incr, &self.opts) {
+            Ok(ts) => RespType::Integer(ts as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![
+            RespType::BulkString(Bytes::from("TS.INCRBY")),
+            RespType::BulkString(Bytes::from(self.key.clone())),
+            RespType::BulkString(Bytes::from(self.incr.to_string())),
+            RespType::BulkString(Bytes::from("TIMESTAMP")),
+            RespType::BulkString(Bytes::from(self.timestamp.to_string())),
+        ];
+        cmd.extend(series_options_args(&self.opts));
+
+        RespType::Array(cmd)
+    }
+}
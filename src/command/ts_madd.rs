@@ -0,0 +1,84 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_float, parse_string, wrong_num_args},
+    ts::parse_timestamp,
+    CommandError,
+};
+
+/// Represents the TS.MADD command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsMAdd {
+    samples: Vec<(String, u64, f64)>,
+}
+
+impl TsMAdd {
+    /// Creates a new `TsMAdd` instance from the given arguments.
+    ///
+    /// Syntax: `TS.MADD key timestamp value [key timestamp value ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TS.MADD command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsMAdd)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<TsMAdd, CommandError> {
+        let triples = args.chunks_exact(3);
+        if triples.len() == 0 || !triples.remainder().is_empty() {
+            return Err(wrong_num_args("TS.MADD"));
+        }
+
+        let mut samples = vec![];
+        for triple in triples {
+            samples.push((
+                parse_string(&triple[0], "Key")?,
+                parse_timestamp(&triple[1])?,
+                parse_float(&triple[2], "Value")?,
+            ));
+        }
+
+        Ok(TsMAdd { samples })
+    }
+
+    /// Executes the TS.MADD command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the timestamp of each sample as an `Integer`, or an error
+    /// if the sample is rejected.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_madd(&self.samples) {
+            Ok(results) => RespType::Array(
+                results
+                    .into_iter()
+                    .map(|r| match r {
+                        Ok(ts) => RespType::Integer(ts as i64),
+                        Err(e) => RespType::SimpleError(format!("{}", e)),
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![RespType::BulkString(Bytes::from("TS.MADD"))];
+
+        for (key, ts, value) in self.samples.iter() {
+            cmd.push(RespType::BulkString(Bytes::from(key.clone())));
+            cmd.push(RespType::BulkString(Bytes::from(ts.to_string())));
+            cmd.push(RespType::BulkString(Bytes::from(value.to_string())));
+        }
+
+        RespType::Array(cmd)
+    }
+}
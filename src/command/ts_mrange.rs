@@ -0,0 +1,162 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{
+        db::DB,
+        time_series::{LabelFilter, RangeQuery},
+    },
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    ts::{parse_range_query, samples_reply},
+    CommandError,
+};
+
+/// Represents the TS.MRANGE and TS.MREVRANGE commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsMRange {
+    query: RangeQuery,
+    with_labels: bool,
+    filters: Vec<LabelFilter>,
+}
+
+impl TsMRange {
+    /// Creates a new `TsMRange` instance from the given arguments.
+    ///
+    /// Syntax: `TS.MRANGE fromTimestamp toTimestamp [FILTER_BY_TS ts...]
+    /// [FILTER_BY_VALUE min max] [WITHLABELS] [COUNT count] [ALIGN align]
+    /// [AGGREGATION aggregator bucketDuration] FILTER filterExpr...` or the same for
+    /// `TS.MREVRANGE`
+    ///
+    /// Each filter expression is one of `label=value`, `label!=value`, `label=` (the label
+    /// is absent), `label!=` (the label is present), `label=(value1,value2,...)` or
+    /// `label!=(value1,value2,...)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `rev` - Set to `true` for TS.MREVRANGE command, which returns the samples in the
+    ///   descending order of timestamps.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsMRange)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, rev: bool) -> Result<TsMRange, CommandError> {
+        let cmd_name = if rev { "TS.MREVRANGE" } else { "TS.MRANGE" };
+        if args.len() < 4 {
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let mut with_labels = false;
+        let mut filters = vec![];
+        let query = parse_range_query(&args, rev, |args, idx| {
+            match parse_string(&args[*idx], "Option")?.to_lowercase().as_str() {
+                "withlabels" => {
+                    with_labels = true;
+                    *idx += 1;
+                }
+                "filter" => {
+                    for arg in args[*idx + 1..].iter() {
+                        filters.push(parse_filter(&parse_string(arg, "Filter")?)?);
+                    }
+                    *idx = args.len();
+                }
+                _ => return Ok(false),
+            }
+            Ok(true)
+        })?;
+
+        if filters.is_empty() {
+            return Err(CommandError::Other(String::from(
+                "FILTER must be specified with at least one filter expression",
+            )));
+        }
+
+        Ok(TsMRange {
+            query,
+            with_labels,
+            filters,
+        })
+    }
+
+    /// Executes the TS.MRANGE or TS.MREVRANGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with an entry for each matched series. Each entry is an `Array`
+    /// with the key as a `BulkString`, the labels as an `Array` of label-value pairs (empty
+    /// unless WITHLABELS is specified), and the samples as in TS.RANGE.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_mrange(&self.filters, &self.query) {
+            Ok(series) => RespType::Array(
+                series
+                    .into_iter()
+                    .map(|(key, labels, samples)| {
+                        let labels = if self.with_labels {
+                            labels
+                                .into_iter()
+                                .map(|(label, value)| {
+                                    RespType::Array(vec![
+                                        RespType::BulkString(Bytes::from(label)),
+                                        RespType::BulkString(Bytes::from(value)),
+                                    ])
+                                })
+                                .collect()
+                        } else {
+                            vec![]
+                        };
+
+                        RespType::Array(vec![
+                            RespType::BulkString(Bytes::from(key)),
+                            RespType::Array(labels),
+                            samples_reply(samples),
+                        ])
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
+
+/// Parses a label filter expression.
+fn parse_filter(expr: &str) -> Result<LabelFilter, CommandError> {
+    let (label, values, negated) = match expr.split_once("!=") {
+        Some((label, values)) => (label, values, true),
+        None => match expr.split_once('=') {
+            Some((label, values)) => (label, values, false),
+            None => {
+                return Err(CommandError::Other(format!(
+                    "Invalid filter expression '{}'",
+                    expr
+                )))
+            }
+        },
+    };
+
+    if label.is_empty() {
+        return Err(CommandError::Other(format!(
+            "Invalid filter expression '{}'",
+            expr
+        )));
+    }
+
+    let values = match values.strip_prefix('(').and_then(|v| v.strip_suffix(')')) {
+        Some(list) => list.split(',').map(|v| v.trim().to_string()).collect(),
+        None => vec![values.to_string()],
+    };
+
+    if negated {
+        Ok(LabelFilter::NotEq(label.to_string(), values))
+    } else {
+        Ok(LabelFilter::Eq(label.to_string(), values))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, time_series::RangeQuery},
+};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    ts::{parse_range_query, samples_reply},
+    CommandError,
+};
+
+/// Represents the TS.RANGE and TS.REVRANGE commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct TsRange {
+    key: String,
+    query: RangeQuery,
+}
+
+impl TsRange {
+    /// Creates a new `TsRange` instance from the given arguments.
+    ///
+    /// Syntax: `TS.RANGE key fromTimestamp toTimestamp [FILTER_BY_TS ts...]
+    /// [FILTER_BY_VALUE min max] [COUNT count] [ALIGN align]
+    /// [AGGREGATION aggregator bucketDuration]` or the same for `TS.REVRANGE`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `rev` - Set to `true` for TS.REVRANGE command, which returns the samples in the
+    ///   descending order of timestamps.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TsRange)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>, rev: bool) -> Result<TsRange, CommandError> {
+        if args.len() < 3 {
+            let cmd_name = if rev { "TS.REVRANGE" } else { "TS.RANGE" };
+            return Err(wrong_num_args(cmd_name));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let query = parse_range_query(&args[1..], rev, |_, _| Ok(false))?;
+
+        Ok(TsRange { key, query })
+    }
+
+    /// Executes the TS.RANGE or TS.REVRANGE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` of the samples, where each sample is an `Array` with the timestamp
+    /// as an `Integer` and the value as a `SimpleString`. When aggregated, the timestamp of
+    /// each bucket is its start.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.ts_range(&self.key, &self.query) {
+            Ok(samples) => samples_reply(samples),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
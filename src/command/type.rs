@@ -0,0 +1,53 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the TYPE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Type {
+    key: String,
+}
+
+impl Type {
+    /// Creates a new `Type` instance from the given arguments.
+    ///
+    /// Syntax: `TYPE key`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the TYPE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Type)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Type, CommandError> {
+        if args.len() != 1 {
+            return Err(wrong_num_args("TYPE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+
+        Ok(Type { key })
+    }
+
+    /// Executes the TYPE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the name of the type of the value stored at key as a `SimpleString`, or
+    /// `none` if the key doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.key_type(&self.key) {
+            Ok(name) => RespType::SimpleString(String::from(name.unwrap_or("none"))),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use bytes::Bytes;
+
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the UNLINK command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Unlink {
+    keys: Vec<String>,
+}
+
+impl Unlink {
+    /// Creates a new `Unlink` instance from the given arguments.
+    ///
+    /// Syntax: `UNLINK key [key ...]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the UNLINK command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Unlink)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<Unlink, CommandError> {
+        if args.is_empty() {
+            return Err(wrong_num_args("UNLINK"));
+        }
+
+        let keys = args
+            .iter()
+            .map(|arg| parse_string(arg, "Key"))
+            .collect::<Result<Vec<String>, CommandError>>()?;
+
+        Ok(Unlink { keys })
+    }
+
+    /// Executes the UNLINK command. The keys are removed right away, like DEL, but the memory
+    /// of large values is freed in the background.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the number of deleted keys as an `Integer`.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.unlink(&self.keys.iter().map(AsRef::as_ref).collect::<Vec<&str>>()) {
+            Ok(del_count) => RespType::Integer(del_count as i64),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+
+    pub fn build_command(&self) -> RespType {
+        let mut cmd = vec![RespType::BulkString(Bytes::from("UNLINK"))];
+        cmd.extend(
+            self.keys
+                .iter()
+                .map(|key| RespType::BulkString(Bytes::from(key.clone()))),
+        );
+
+        RespType::Array(cmd)
+    }
+}
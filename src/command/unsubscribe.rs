@@ -0,0 +1,89 @@
+use bytes::Bytes;
+
+use crate::{
+    pubsub::{Subscriber, SubscriptionKind},
+    resp::types::RespType,
+};
+
+use super::{subscribe::parse_channels, CommandError};
+
+/// Represents the UNSUBSCRIBE, PUNSUBSCRIBE and SUNSUBSCRIBE commands in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct Unsubscribe {
+    /// The channels, the glob-style patterns of the channels, or the shard channels. All the
+    /// subscriptions of the kind are removed if empty.
+    channels: Vec<String>,
+    kind: SubscriptionKind,
+}
+
+impl Unsubscribe {
+    /// Creates a new `Unsubscribe` instance from the given arguments.
+    ///
+    /// Syntax: `UNSUBSCRIBE [channel [channel ...]]`, `PUNSUBSCRIBE [pattern [pattern ...]]`,
+    /// or `SUNSUBSCRIBE [shardchannel [shardchannel ...]]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the command.
+    ///
+    /// * `kind` - The kind of subscriptions, which decides the command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Unsubscribe)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(
+        args: Vec<RespType>,
+        kind: SubscriptionKind,
+    ) -> Result<Unsubscribe, CommandError> {
+        let channels = parse_channels(&args, kind)?;
+
+        Ok(Unsubscribe { channels, kind })
+    }
+
+    /// Executes the UNSUBSCRIBE, PUNSUBSCRIBE or SUNSUBSCRIBE command. The connection leaves
+    /// the subscriber mode once it has no subscriptions.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscriber` - The subscriptions of the connection.
+    ///
+    /// # Returns
+    ///
+    /// A reply for each channel, which is a `Push` (an `Array` in RESP2) of the kind of the
+    /// reply (`unsubscribe`, `punsubscribe` or `sunsubscribe`), the channel, and the number
+    /// of subscriptions of the connection. If there are no channels to unsubscribe from, a
+    /// single reply is returned with a `Null` as the channel.
+    pub fn apply(&self, subscriber: &mut Subscriber) -> Vec<RespType> {
+        let reply_kind = match self.kind {
+            SubscriptionKind::Channel => "unsubscribe",
+            SubscriptionKind::Pattern => "punsubscribe",
+            SubscriptionKind::ShardChannel => "sunsubscribe",
+        };
+
+        let channels = match self.channels.is_empty() {
+            true => subscriber.subscriptions(self.kind),
+            false => self.channels.clone(),
+        };
+        if channels.is_empty() {
+            return vec![RespType::Push(vec![
+                RespType::BulkString(Bytes::from(reply_kind)),
+                RespType::Null,
+                RespType::Integer(subscriber.count_of(self.kind) as i64),
+            ])];
+        }
+
+        channels
+            .into_iter()
+            .map(|channel| {
+                let count = subscriber.unsubscribe(self.kind, &channel);
+
+                RespType::Push(vec![
+                    RespType::BulkString(Bytes::from(reply_kind)),
+                    RespType::BulkString(Bytes::from(channel)),
+                    RespType::Integer(count as i64),
+                ])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,74 @@
+use bytes::Bytes;
+
+use crate::{
+    resp::types::RespType,
+    storage::{db::DB, keys::ScanOptions},
+};
+
+use super::{
+    args::{parse_int, parse_string, wrong_num_args},
+    scan::{parse_scan_options, scan_reply},
+    CommandError,
+};
+
+/// Represents the ZSCAN command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct ZScan {
+    key: String,
+    cursor: u64,
+    opts: ScanOptions,
+}
+
+impl ZScan {
+    /// Creates a new `ZScan` instance from the given arguments.
+    ///
+    /// Syntax: `ZSCAN key cursor [MATCH pattern] [COUNT count]`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZSCAN command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZScan)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZScan, CommandError> {
+        if args.len() < 2 {
+            return Err(wrong_num_args("ZSCAN"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let cursor = parse_int::<u64>(&args[1], "Cursor")?;
+        let opts = parse_scan_options(&args[2..], |_, _, _| Ok(false))?;
+
+        Ok(ZScan { key, cursor, opts })
+    }
+
+    /// Executes the ZSCAN command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns an `Array` with the cursor for the next call as a `BulkString`, followed by an
+    /// `Array` with each member followed by its score, as `BulkString`s.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zscan(&self.key, self.cursor, &self.opts) {
+            Ok((cursor, members)) => scan_reply(
+                cursor,
+                members
+                    .into_iter()
+                    .flat_map(|(member, score)| {
+                        [
+                            RespType::BulkString(Bytes::from(member)),
+                            RespType::BulkString(Bytes::from(score.to_string())),
+                        ]
+                    })
+                    .collect(),
+            ),
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
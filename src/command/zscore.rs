@@ -0,0 +1,56 @@
+use crate::{resp::types::RespType, storage::db::DB};
+
+use super::{
+    args::{parse_string, wrong_num_args},
+    CommandError,
+};
+
+/// Represents the ZSCORE command in Nimblecache.
+#[derive(Debug, Clone)]
+pub struct ZScore {
+    key: String,
+    member: String,
+}
+
+impl ZScore {
+    /// Creates a new `ZScore` instance from the given arguments.
+    ///
+    /// Syntax: `ZSCORE key member`
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A vector of `RespType` representing the arguments to the ZSCORE command.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ZScore)` if parsing succeeds.
+    /// * `Err(CommandError)` if parsing fails.
+    pub fn with_args(args: Vec<RespType>) -> Result<ZScore, CommandError> {
+        if args.len() != 2 {
+            return Err(wrong_num_args("ZSCORE"));
+        }
+
+        let key = parse_string(&args[0], "Key")?;
+        let member = parse_string(&args[1], "Member")?;
+
+        Ok(ZScore { key, member })
+    }
+
+    /// Executes the ZSCORE command.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The database where the key and values are stored.
+    ///
+    /// # Returns
+    ///
+    /// It returns the score of the member as a `Double` (sent as a `BulkString` in RESP2), or
+    /// `Null` if either the key or the member doesn't exist.
+    pub fn apply(&self, db: &DB) -> RespType {
+        match db.zscore(&self.key, &self.member) {
+            Ok(Some(score)) => RespType::Double(score),
+            Ok(None) => RespType::Null,
+            Err(e) => RespType::SimpleError(format!("{}", e)),
+        }
+    }
+}
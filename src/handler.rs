@@ -1,24 +1,53 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
 use anyhow::Result;
 use futures::{SinkExt, StreamExt};
 use log::{error, warn};
 use tokio::net::TcpStream;
 use tokio_util::codec::Framed;
 
+use crate::command::client::ClientState;
 use crate::command::pipelining::MultiCommand;
+use crate::migration::MigrationPool;
+use crate::pubsub::{PubSub, Subscriber};
 use crate::replication::Replication;
-use crate::resp::types::RespType;
-use crate::storage::db::DB;
-use crate::{command::Command, resp::frame::RespCommandFrame};
+use crate::resp::types::{ProtocolVersion, RespType};
+use crate::storage::db::Storage;
+use crate::tracking::{self, Tracking};
+use crate::{
+    command::Command,
+    resp::frame::{FrameError, RespCommandFrame},
+};
+
+/// The id assigned to the next client connection.
+static NEXT_CLIENT_ID: AtomicU64 = AtomicU64::new(1);
 
 /// Handles RESP command frames over a single TCP connection.
 pub struct FrameHandler {
     /// The framed connection using `RespCommandFrame` as the codec.
     conn: Framed<TcpStream, RespCommandFrame>,
+    /// The number of the database selected by the connection (using the SELECT command).
+    db_index: usize,
+    /// The state of the connection, like its id and the version of RESP it uses.
+    client: ClientState,
 }
 impl FrameHandler {
-    /// Creates a new `FrameHandler` instance.
+    /// Creates a new `FrameHandler` instance, which selects the first database.
     pub fn new(conn: Framed<TcpStream, RespCommandFrame>) -> FrameHandler {
-        FrameHandler { conn }
+        FrameHandler {
+            conn,
+            db_index: 0,
+            client: ClientState {
+                id: NEXT_CLIENT_ID.fetch_add(1, Ordering::Relaxed),
+                name: None,
+                authenticated: true,
+                version: ProtocolVersion::Resp2,
+                caching: None,
+            },
+        }
     }
 
     /// Handles incoming RESP command frames.
@@ -41,12 +70,39 @@ impl FrameHandler {
     /// `EXEC` command is received. When `EXEC` is called, all the queued
     /// commands are executed, and the array of responses is sent back.
     ///
+    /// ## Subscriber Mode
+    ///
+    /// Once the connection subscribes to a channel or a pattern, the messages published to
+    /// them are sent while it waits for the next command. In RESP2, only the (P|S)SUBSCRIBE,
+    /// (P|S)UNSUBSCRIBE, PING and QUIT commands are accepted until all the subscriptions are
+    /// removed. The connection is closed if it doesn't keep up with the published messages.
+    ///
+    /// ## Client Side Caching
+    ///
+    /// Once the connection enables tracking (using the CLIENT TRACKING command), the keys
+    /// read by its commands are remembered, and invalidation messages are sent when they're
+    /// modified. Tracking is disabled when the connection is closed.
+    ///
+    /// ## Protocol Version
+    ///
+    /// The responses are sent in RESP2, until the connection switches to RESP3 using the
+    /// HELLO command. If a password is required, the connection must be authenticated (using
+    /// the AUTH or HELLO command) before any other command is accepted.
+    ///
     /// # Arguments
     ///
-    /// * `db` - The database where the key and values are stored.
+    /// * `storage` - The databases where the key and values are stored.
     ///
     /// * `replication` - Server replication.
     ///
+    /// * `migration` - The cached connections used by MIGRATE.
+    ///
+    /// * `pubsub` - The subscriptions of all the clients.
+    ///
+    /// * `tracking` - The keys cached by the clients.
+    ///
+    /// * `requirepass` - The password of the default user, if any.
+    ///
     /// # Returns
     ///
     /// A `Result` indicating whether the operation succeeded or failed.
@@ -55,32 +111,130 @@ impl FrameHandler {
     ///
     /// This method will return an error if there's an issue with reading
     /// from or writing to the connection.
-    pub async fn handle(mut self, db: &DB, replication: &Replication) -> Result<()> {
+    pub async fn handle(
+        mut self,
+        storage: &Storage,
+        replication: &Replication,
+        migration: &MigrationPool,
+        pubsub: &Arc<PubSub>,
+        tracking: &Tracking,
+        requirepass: Option<&str>,
+    ) -> Result<()> {
+        let client_id = self.client.id;
+        self.client.authenticated = requirepass.is_none();
+        tracking.connect(client_id, self.client.version);
+        let res = tracking::scope(
+            client_id,
+            self.handle_frames(
+                storage,
+                replication,
+                migration,
+                pubsub,
+                tracking,
+                requirepass,
+            ),
+        )
+        .await;
+        tracking.disconnect(client_id);
+
+        res
+    }
+
+    async fn handle_frames(
+        mut self,
+        storage: &Storage,
+        replication: &Replication,
+        migration: &MigrationPool,
+        pubsub: &Arc<PubSub>,
+        tracking: &Tracking,
+        requirepass: Option<&str>,
+    ) -> Result<()> {
         // commands are queued here if MULTI command was issued
         let mut multicommand = MultiCommand::new();
+        // the subscriptions of the connection, and the messages pushed to it
+        let mut subscriber = Subscriber::new(Arc::clone(pubsub), self.client.id);
+
+        loop {
+            // The published (and pushed) messages are sent while waiting for the next command.
+            let resp_cmd = tokio::select! {
+                resp_cmd = self.conn.next() => resp_cmd,
+                msg = subscriber.recv() => {
+                    let msg = match msg {
+                        Some(msg) => msg,
+                        None => {
+                            warn!("Closing the connection of a slow subscriber");
+                            break;
+                        }
+                    };
+                    if let Err(e) = self.conn.send(msg).await {
+                        error!("Error sending message: {}", e);
+                        break;
+                    }
+                    continue;
+                }
+            };
+            let resp_cmd = match resp_cmd {
+                Some(resp_cmd) => resp_cmd,
+                None => break,
+            };
 
-        while let Some(resp_cmd) = self.conn.next().await {
             match resp_cmd {
                 Ok(cmd_frame) => {
                     // Read the command from the frame.
                     let resp_cmd = Command::from_resp_command_frame(cmd_frame);
 
+                    // The flag set by CLIENT CACHING applies to the next command only.
+                    if let Ok(cmd) = &resp_cmd {
+                        if !matches!(cmd, Command::Client(_)) {
+                            let caching = self.client.caching.take();
+                            tracking::track_reads(tracking.tracks_reads(self.client.id, caching));
+                        }
+                    }
+
+                    // The subscriber mode restricts the commands in RESP2 only, since the
+                    // published messages can't be told apart from the responses otherwise.
+                    let subscribed =
+                        subscriber.count() > 0 && self.client.version == ProtocolVersion::Resp2;
+
                     // If command is parsed successfully, execute it and get the RESP response,
                     // otherwise set a SimpleError RESP value as the response.
                     let response = match resp_cmd {
+                        Ok(cmd)
+                            if !self.client.authenticated && !cmd.is_allowed_unauthenticated() =>
+                        {
+                            RespType::SimpleError(String::from("NOAUTH Authentication required."))
+                        }
+                        Ok(cmd) if subscribed && !cmd.is_allowed_when_subscribed() => {
+                            RespType::SimpleError(String::from(
+                                "only (P|S)SUBSCRIBE / (P|S)UNSUBSCRIBE / PING / QUIT are allowed in this context",
+                            ))
+                        }
                         Ok(cmd) => match cmd {
                             // Initialize pipeline if MULTI command is issued
                             Command::Multi => {
                                 let init_multicommand = &mut multicommand.init();
                                 match init_multicommand {
-                                    Ok(_) => cmd.execute(db, replication),
+                                    Ok(_) => cmd.execute(
+                                        storage,
+                                        &mut self.db_index,
+                                        replication,
+                                        pubsub,
+                                    ),
                                     Err(e) => RespType::SimpleError(format!("{}", e)),
                                 }
                             }
                             // Execute all commands in pipeline if EXEC command is issued
                             Command::Exec => {
                                 if multicommand.is_active() {
-                                    multicommand.exec(db, replication).await
+                                    multicommand
+                                        .exec(
+                                            storage,
+                                            &mut self.db_index,
+                                            replication,
+                                            migration,
+                                            pubsub,
+                                        )
+                                        .await
                                 } else {
                                     RespType::SimpleError(String::from("EXEC without MULTI"))
                                 }
@@ -88,7 +242,7 @@ impl FrameHandler {
                             Command::Discard => {
                                 if multicommand.is_active() {
                                     multicommand.discard();
-                                    cmd.execute(db, replication)
+                                    cmd.execute(storage, &mut self.db_index, replication, pubsub)
                                 } else {
                                     RespType::SimpleError(String::from("DISCARD without MULTI"))
                                 }
@@ -107,16 +261,63 @@ impl FrameHandler {
 
                                 break;
                             }
+                            // MIGRATE is executed asynchronously, since it involves network I/O
+                            Command::Migrate(migrate) if !multicommand.is_active() => {
+                                migrate
+                                    .apply(storage, self.db_index, replication, migration)
+                                    .await
+                            }
+                            Command::Quit => {
+                                self.conn
+                                    .send(RespType::SimpleString(String::from("OK")))
+                                    .await?;
+                                break;
+                            }
+                            Command::Ping(ping) if subscribed => ping.apply_subscribed(),
+                            Command::Subscribe(subscribe) if !multicommand.is_active() => {
+                                let replies = subscribe.apply(&mut subscriber);
+                                self.feed_replies(replies).await?
+                            }
+                            Command::Unsubscribe(unsubscribe) if !multicommand.is_active() => {
+                                // the messages published before the subscriptions are removed
+                                // are sent first
+                                let mut replies = subscriber.pending();
+                                replies.extend(unsubscribe.apply(&mut subscriber));
+                                self.feed_replies(replies).await?
+                            }
+                            Command::Client(client) if !multicommand.is_active() => {
+                                client.apply(&mut self.client, tracking)
+                            }
+                            Command::Auth(auth) if !multicommand.is_active() => {
+                                auth.apply(&mut self.client, requirepass)
+                            }
+                            Command::Hello(hello) if !multicommand.is_active() => {
+                                let res = hello.apply(&mut self.client, requirepass, replication);
+                                // the response is sent in the version of RESP switched to
+                                if self.conn.codec().version() != self.client.version {
+                                    self.conn.codec_mut().set_version(self.client.version);
+                                    tracking.connect(self.client.id, self.client.version);
+                                }
+                                res
+                            }
                             _ => {
                                 // Queue commands if pipeline is active, else execute the command
                                 if multicommand.is_active() {
                                     multicommand.add_command(cmd);
                                     RespType::SimpleString(String::from("QUEUED"))
                                 } else {
-                                    let res = cmd.execute(db, replication);
-                                    if let Some(replica_cmd) = cmd.replication_cmd() {
-                                        let bytes_replicated =
-                                            replication.write_to_replicas(replica_cmd).await;
+                                    let replica_cmd = cmd.replication_cmd();
+                                    tracking::set_read_only(replica_cmd.is_none());
+                                    let res = cmd.execute(
+                                        storage,
+                                        &mut self.db_index,
+                                        replication,
+                                        pubsub,
+                                    );
+                                    if let Some(replica_cmd) = replica_cmd {
+                                        let bytes_replicated = replication
+                                            .write_to_replicas(replica_cmd, self.db_index)
+                                            .await;
                                         replication.incr_offset(bytes_replicated as u64);
                                     };
 
@@ -139,7 +340,19 @@ impl FrameHandler {
                     }
                 }
                 Err(e) => {
-                    error!("Error reading the request: {}", e);
+                    match e.get_ref().and_then(|e| e.downcast_ref::<FrameError>()) {
+                        // The protocol errors are reported to the client before closing the
+                        // connection, since the rest of the input can't be read reliably.
+                        Some(FrameError::Protocol(err)) => {
+                            warn!("Protocol error from client {}: {}", self.client.id, err);
+                            let res = RespType::SimpleError(format!("ERR Protocol error: {}", err));
+                            self.conn.send(res).await?;
+                        }
+                        Some(FrameError::QueryBufferLimit(_)) => {
+                            warn!("Closing the connection of client {}: {}", self.client.id, e);
+                        }
+                        None => error!("Error reading the request: {}", e),
+                    }
                     break;
                 }
             };
@@ -158,17 +371,21 @@ impl FrameHandler {
     ///
     /// # Arguments
     ///
-    /// * `db` - The database where the key and values are stored.
+    /// * `storage` - The databases where the key and values are stored.
     ///
     /// * `replication` - Server replication.
     ///
+    /// * `pubsub` - The subscriptions of all the clients, to which the replicated messages
+    ///   are published.
+    ///
     /// # Returns
     ///
     /// A `Result` indicating whether the operation succeeded or failed.
     pub async fn handle_replication_stream(
         mut self,
-        db: &DB,
+        storage: &Storage,
         replication: &Replication,
+        pubsub: &PubSub,
     ) -> Result<()> {
         while let Some(resp_cmd) = self.conn.next().await {
             match resp_cmd {
@@ -179,10 +396,11 @@ impl FrameHandler {
                     // If command is parsed successfully, execute it.
                     match resp_cmd {
                         Ok(cmd) => {
-                            cmd.execute(db, replication);
+                            cmd.execute(storage, &mut self.db_index, replication, pubsub);
                             if let Some(replica_cmd) = cmd.replication_cmd() {
-                                let bytes_replicated =
-                                    replication.write_to_replicas(replica_cmd).await;
+                                let bytes_replicated = replication
+                                    .write_to_replicas(replica_cmd, self.db_index)
+                                    .await;
                                 replication.incr_offset(bytes_replicated as u64);
                             };
                         }
@@ -199,4 +417,15 @@ impl FrameHandler {
 
         Ok(())
     }
+
+    /// Writes all the replies except the last one into the connection, without flushing
+    /// them. The last reply is returned, to be sent as the response of the command.
+    async fn feed_replies(&mut self, mut replies: Vec<RespType>) -> Result<RespType> {
+        let last = replies.pop().unwrap_or(RespType::NullBulkString);
+        for reply in replies {
+            self.conn.feed(reply).await?;
+        }
+
+        Ok(last)
+    }
 }
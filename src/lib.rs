@@ -0,0 +1,4 @@
+//! The RESP (REdis Serialization Protocol) implementation of Nimblecache, which is shared by
+//! the server and the benchmarks.
+
+pub mod resp;
@@ -1,28 +1,44 @@
 mod command;
 mod handler;
+mod migration;
+mod pubsub;
 mod replication;
-mod resp;
 mod server;
 mod storage;
+mod tracking;
 
 use std::{error::Error, sync::Arc, time::Duration};
 
 use crate::server::Server;
 use clap::Parser;
 use log::{error, info};
+use nimblecache::resp;
+use pubsub::{keyspace::KeyspaceNotifier, PubSub};
 use rand::distributions::{Alphanumeric, DistString};
 use replication::{master::MasterServer, Replication};
-use resp::types::RespType;
-use storage::ttl::KeyEvictor;
+use resp::{
+    frame::{
+        FrameLimits, DEFAULT_CLIENT_QUERY_BUFFER_LIMIT, DEFAULT_MAX_MULTIBULK_LEN,
+        DEFAULT_PROTO_MAX_BULK_LEN,
+    },
+    types::RespType,
+};
+use storage::{
+    lazyfree::{LazyFree, LazyFreeConfig},
+    notify::NotifyFlags,
+    ttl::KeyEvictor,
+};
 use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpStream},
     sync::{mpsc, Notify, OwnedSemaphorePermit, Semaphore},
     time::timeout,
 };
+use tracking::{Tracking, DEFAULT_TRACKING_TABLE_MAX_KEYS};
 
 const DEFAULT_PORT: u16 = 6379;
 const DEFAULT_MAX_CONNECTIONS: usize = 128;
+const DEFAULT_DATABASES: usize = 16;
 
 #[derive(Debug, Parser)]
 #[command(
@@ -41,6 +57,36 @@ struct Cli {
     /// Maximum number of client connections supported
     #[arg(long)]
     maxclients: Option<usize>,
+    /// Number of logical databases, which are selected using the SELECT command
+    #[arg(long)]
+    databases: Option<usize>,
+    /// Free the memory of expired keys in the background
+    #[arg(long)]
+    lazyfree_lazy_expire: bool,
+    /// Free the memory of overwritten values (by SET, RENAME etc.) in the background
+    #[arg(long)]
+    lazyfree_lazy_server_del: bool,
+    /// Classes of keyspace events which are published over pub/sub (e.g. `KEA`)
+    #[arg(long, default_value = "")]
+    notify_keyspace_events: String,
+    /// Max number of keys remembered for the clients which track the keys they cache (`0`
+    /// for no limit). Keys are invalidated once there are more
+    #[arg(long)]
+    tracking_table_max_keys: Option<usize>,
+    /// Password of the default user, which the clients must authenticate with (using the
+    /// AUTH or HELLO command)
+    #[arg(long)]
+    requirepass: Option<String>,
+    /// Max length of a bulk string in a command, in bytes (512 MB by default)
+    #[arg(long)]
+    proto_max_bulk_len: Option<usize>,
+    /// Max number of arguments of a command (1048576 by default)
+    #[arg(long)]
+    max_multibulk_len: Option<usize>,
+    /// Max number of bytes buffered for a command of a client, which is disconnected once the
+    /// limit is exceeded (1 GB by default)
+    #[arg(long)]
+    client_query_buffer_limit: Option<usize>,
 }
 
 /// Accepts a new TCP connection with connection limit enforcement.
@@ -113,17 +159,17 @@ async fn accept_conn(
 /// What's happening in `main`?
 ///
 /// * Start 2 tokio runtimes - one for accepting connections,
-/// another for handling the commands from these TCP connections. The TCP streams
-/// from acceptor runtime is passed to command handler runtime using a channel.
-/// Note that values global to the application are passed to the tokio runtimes via separate Arcs.
+///   another for handling the commands from these TCP connections. The TCP streams
+///   from acceptor runtime is passed to command handler runtime using a channel.
+///   Note that values global to the application are passed to the tokio runtimes via separate Arcs.
 ///
 /// * Initialize storage.
 ///
 /// * Start both acceptor and command handler runtimes.
 ///
 /// * If server is started in slave mode, establish connection with master server, perform
-/// a handshake and start listening to the replication stream from the master server. This happens inside
-/// the acceptor tokio runtime.
+///   a handshake and start listening to the replication stream from the master server. This happens inside
+///   the acceptor tokio runtime.
 fn main() -> Result<(), Box<dyn Error>> {
     env_logger::init();
 
@@ -185,8 +231,39 @@ fn main() -> Result<(), Box<dyn Error>> {
     let replication_acceptor_arc = Arc::new(replication);
     let replication_cmd_handler_arc = Arc::clone(&replication_acceptor_arc);
 
+    // Initialize the pub/sub and wrap it into 2 separate Arcs (1 for each tokio runtimes)
+    let pubsub_acceptor_arc = PubSub::new();
+    let pubsub_cmd_handler_arc = Arc::clone(&pubsub_acceptor_arc);
+
+    // Initialize the tracking of the keys cached by the clients, whose invalidation messages
+    // are sent through the pub/sub
+    let tracking_table_max_keys = cli
+        .tracking_table_max_keys
+        .unwrap_or(DEFAULT_TRACKING_TABLE_MAX_KEYS);
+    let tracking = Tracking::new(Arc::clone(&pubsub_acceptor_arc), tracking_table_max_keys);
+
+    // The limits on the commands received, so that the clients can't make the server buffer
+    // huge amounts of data
+    let frame_limits = FrameLimits {
+        max_bulk_len: cli.proto_max_bulk_len.unwrap_or(DEFAULT_PROTO_MAX_BULK_LEN),
+        max_multibulk_len: cli.max_multibulk_len.unwrap_or(DEFAULT_MAX_MULTIBULK_LEN),
+        query_buffer_limit: cli
+            .client_query_buffer_limit
+            .unwrap_or(DEFAULT_CLIENT_QUERY_BUFFER_LIMIT),
+    };
+
     // Initialize storage and wrap them into 2 separate Arcs (1 for each tokio runtimes)
-    let shared_storage = storage::db::Storage::new(storage::db::DB::new());
+    let num_dbs = cli.databases.unwrap_or(DEFAULT_DATABASES).max(1);
+    let (lazy_free, mut lazy_freer) = LazyFree::new(LazyFreeConfig {
+        lazy_expire: cli.lazyfree_lazy_expire,
+        lazy_server_del: cli.lazyfree_lazy_server_del,
+    });
+    let notify_flags = match NotifyFlags::parse(&cli.notify_keyspace_events) {
+        Ok(flags) => flags,
+        Err(e) => panic!("{}", e),
+    };
+    let shared_storage =
+        storage::db::Storage::new(num_dbs, lazy_free, notify_flags, Arc::clone(&tracking));
     let storage_acceptor_arc = Arc::new(shared_storage);
     let storage_cmd_handler_arc = Arc::clone(&storage_acceptor_arc);
 
@@ -195,20 +272,49 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     // Spawn task for handling commands (command handler runtime)
     cmd_runtime.spawn(async move {
-        let mut server = Server::new(storage_cmd_handler_arc, replication_cmd_handler_arc);
+        let mut server = Server::new(
+            storage_cmd_handler_arc,
+            replication_cmd_handler_arc,
+            pubsub_cmd_handler_arc,
+            tracking,
+            cli.requirepass,
+            frame_limits,
+        );
 
         while let Some((stream, permit)) = rx.recv().await {
             server.handle_commands(stream, permit).await
         }
     });
 
-    // Spawn task for handling TTL of keys (background tasks runtime)
-    let mut key_evictor =
-        KeyEvictor::new(storage_acceptor_arc.clone().db(), Arc::new(Notify::new()));
+    // Spawn task for freeing the memory of deleted values (background tasks runtime)
     bg_tasks_runtime.spawn(async move {
-        key_evictor.run().await;
+        lazy_freer.run().await;
     });
 
+    // Spawn tasks for handling TTL of keys, one for each database (background tasks runtime)
+    for db in storage_acceptor_arc.dbs()? {
+        let mut key_evictor = KeyEvictor::new(db, Arc::new(Notify::new()));
+        bg_tasks_runtime.spawn(async move {
+            key_evictor.run().await;
+        });
+    }
+
+    // Spawn tasks for publishing keyspace notifications, one for each database, if they're
+    // enabled (background tasks runtime)
+    if notify_flags != NotifyFlags::default() {
+        for db in storage_acceptor_arc.dbs()? {
+            let notifier = KeyspaceNotifier::new(
+                db,
+                Arc::clone(&storage_acceptor_arc),
+                Arc::clone(&pubsub_acceptor_arc),
+                notify_flags,
+            );
+            bg_tasks_runtime.spawn(async move {
+                notifier.run().await;
+            });
+        }
+    }
+
     // Run the acceptor runtime
     acceptor_runtime.block_on(async move {
         let port = cli.port.unwrap_or(DEFAULT_PORT);
@@ -220,7 +326,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 Err(e) => {
                     error!("Error using master TCP stream inside async runtime: {}", e);
                     panic!("Failed to connect with master TCP stream: {}", e);
-                },
+                }
             };
 
             let stream = match MasterServer::perform_handshake(master_stream).await {
@@ -230,7 +336,12 @@ fn main() -> Result<(), Box<dyn Error>> {
 
             tokio::spawn(async move {
                 tokio::select! {
-                    res = MasterServer::listen(stream, storage_acceptor_arc, replication_acceptor_arc) => {
+                    res = MasterServer::listen(
+                        stream,
+                        storage_acceptor_arc,
+                        replication_acceptor_arc,
+                        pubsub_acceptor_arc,
+                    ) => {
                         if let Err(err) = res {
                             error!("failed to process the request from master: {}", err);
                         }
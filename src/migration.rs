@@ -0,0 +1,207 @@
+use std::{collections::HashMap, fmt, time::Duration};
+
+use anyhow::{anyhow, Result};
+use bytes::BytesMut;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::Mutex,
+    time::{timeout, Instant},
+};
+
+use crate::resp::types::RespType;
+
+/// Cached connections which are idle for longer than this are closed.
+const CONN_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Max number of connections which are cached.
+const MAX_CACHED_CONNS: usize = 64;
+
+/// Caches the connections to the target instances of MIGRATE, so that repeated migrations to
+/// the same instance don't have to connect (and authenticate) again.
+#[derive(Debug)]
+pub struct MigrationPool {
+    /// Idle connections, by the address of the target instance.
+    conns: Mutex<HashMap<String, MigrationConn>>,
+}
+
+/// A client connection to the target instance of MIGRATE.
+#[derive(Debug)]
+pub struct MigrationConn {
+    addr: String,
+    stream: TcpStream,
+    /// Bytes read from the stream, which are not parsed yet.
+    buf: BytesMut,
+    /// The database selected on the target instance, if any.
+    selected_db: Option<usize>,
+    /// Set to `true` if the connection was taken from the pool, instead of being created for
+    /// the migration. A cached connection may have been closed by the target instance.
+    reused: bool,
+    last_used: Instant,
+}
+
+/// Represents the errors which can occur when a command is sent to the target instance.
+#[derive(Debug)]
+pub enum CallError {
+    /// The command wasn't sent completely, hence the target instance didn't execute it. The
+    /// connection was closed by the target instance, or the write failed or timed out.
+    NotSent(anyhow::Error),
+    /// The command was sent, but its reply wasn't received in time or the connection failed
+    /// while reading it. The target instance may have executed the command.
+    NoReply(anyhow::Error),
+}
+
+impl MigrationPool {
+    /// Creates an empty `MigrationPool`.
+    pub fn new() -> MigrationPool {
+        MigrationPool {
+            conns: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes the cached connection to the target instance out of the pool, or connects to
+    /// the instance if there's none. Connections which have been idle for too long are
+    /// closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the target instance, in `host:port` format.
+    ///
+    /// * `io_timeout` - Max time to wait for the connection to be established.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the connection, or an error if the instance can't be reached.
+    pub async fn acquire(&self, addr: &str, io_timeout: Duration) -> Result<MigrationConn> {
+        {
+            let mut conns = self.conns.lock().await;
+            conns.retain(|_, conn| conn.last_used.elapsed() < CONN_IDLE_TIMEOUT);
+            if let Some(mut conn) = conns.remove(addr) {
+                conn.reused = true;
+                return Ok(conn);
+            }
+        }
+
+        let stream = timeout(io_timeout, TcpStream::connect(addr)).await??;
+
+        Ok(MigrationConn {
+            addr: addr.to_string(),
+            stream,
+            buf: BytesMut::new(),
+            selected_db: None,
+            reused: false,
+            last_used: Instant::now(),
+        })
+    }
+
+    /// Returns a connection to the pool, so that it can be used by the next migration to the
+    /// same instance. The connection is closed if the pool is full.
+    pub async fn release(&self, conn: MigrationConn) {
+        let mut conns = self.conns.lock().await;
+        if conns.len() < MAX_CACHED_CONNS {
+            conns.insert(conn.addr.clone(), conn);
+        }
+    }
+}
+
+impl MigrationConn {
+    /// Sends a command to the target instance and reads its reply.
+    ///
+    /// # Arguments
+    ///
+    /// * `cmd` - The command, as an array of bulk strings.
+    ///
+    /// * `io_timeout` - Max time to wait for the command to be sent, and for the reply to
+    ///   be received.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the reply, which is a simple string, an error or an integer.
+    /// An error is returned if the connection fails, or if the reply is of any other type.
+    /// The error tells whether the command may have reached the target instance.
+    pub async fn call(
+        &mut self,
+        cmd: RespType,
+        io_timeout: Duration,
+    ) -> Result<RespType, CallError> {
+        if self.is_closed() {
+            return Err(CallError::NotSent(anyhow!(
+                "Connection closed by target instance"
+            )));
+        }
+
+        match timeout(io_timeout, self.stream.write_all(&cmd.to_bytes())).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(CallError::NotSent(e.into())),
+            Err(e) => return Err(CallError::NotSent(e.into())),
+        }
+        let reply = match timeout(io_timeout, self.read_reply()).await {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(e)) => return Err(CallError::NoReply(e)),
+            Err(e) => return Err(CallError::NoReply(e.into())),
+        };
+        self.last_used = Instant::now();
+
+        Ok(reply)
+    }
+
+    /// Checks if the connection was closed by the target instance, without waiting for it.
+    /// A cached connection is closed when the target instance times out the idle clients.
+    /// Since nothing is expected from the target instance between the commands, any data
+    /// received makes the connection unusable as well.
+    fn is_closed(&mut self) -> bool {
+        !matches!(
+            self.stream.try_read_buf(&mut self.buf),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock
+        )
+    }
+
+    /// Returns the database selected on the target instance, if any.
+    pub fn selected_db(&self) -> Option<usize> {
+        self.selected_db
+    }
+
+    /// Records the database selected on the target instance.
+    pub fn set_selected_db(&mut self, db_index: usize) {
+        self.selected_db = Some(db_index);
+    }
+
+    /// Returns `true` if the connection was taken from the pool.
+    pub fn is_reused(&self) -> bool {
+        self.reused
+    }
+
+    /// Reads a single line reply from the stream.
+    async fn read_reply(&mut self) -> Result<RespType> {
+        loop {
+            if let Some(pos) = self.buf.windows(2).position(|w| w == b"\r\n") {
+                let line = self.buf.split_to(pos + 2);
+                let text = String::from_utf8_lossy(&line[1..pos]).to_string();
+
+                return match line[0] {
+                    b'+' => Ok(RespType::SimpleString(text)),
+                    b'-' => Ok(RespType::SimpleError(text)),
+                    b':' => match text.parse::<i64>() {
+                        Ok(n) => Ok(RespType::Integer(n)),
+                        Err(_) => Err(anyhow!("Invalid integer reply from target instance")),
+                    },
+                    _ => Err(anyhow!("Unexpected reply from target instance")),
+                };
+            }
+
+            if self.stream.read_buf(&mut self.buf).await? == 0 {
+                return Err(anyhow!("Connection closed by target instance"));
+            }
+        }
+    }
+}
+
+impl std::error::Error for CallError {}
+
+impl fmt::Display for CallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CallError::NotSent(e) => e.fmt(f),
+            CallError::NoReply(e) => e.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use log::error;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::storage::{
+    db::{Storage, DB},
+    notify::NotifyFlags,
+};
+
+use super::PubSub;
+
+/// KeyspaceNotifier publishes the keyspace events sent by a database as notifications.
+pub struct KeyspaceNotifier {
+    /// The database whose notifications are published.
+    db: Arc<DB>,
+    /// The databases, which are used to find the current number of the database, since it
+    /// changes when the database is swapped.
+    storage: Arc<Storage>,
+    pubsub: Arc<PubSub>,
+    flags: NotifyFlags,
+}
+
+impl KeyspaceNotifier {
+    /// Creates a new KeyspaceNotifier instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Arc reference to the database.
+    /// * `storage` - Arc reference to the databases.
+    /// * `pubsub` - Arc reference to the pub/sub, where the notifications are published.
+    /// * `flags` - The `notify-keyspace-events` flags, which decide the channels to which the
+    ///   notifications are published.
+    pub fn new(
+        db: Arc<DB>,
+        storage: Arc<Storage>,
+        pubsub: Arc<PubSub>,
+        flags: NotifyFlags,
+    ) -> KeyspaceNotifier {
+        KeyspaceNotifier {
+            db,
+            storage,
+            pubsub,
+            flags,
+        }
+    }
+
+    /// Runs the job which publishes the keyspace notifications, as they're received.
+    pub async fn run(&self) {
+        let mut notifications_rx = self.db.subscribe_notifications();
+
+        loop {
+            match notifications_rx.recv().await {
+                Ok((event, key)) => self.publish(event, &key),
+                Err(RecvError::Lagged(skipped)) => {
+                    error!("Dropped {} keyspace notifications", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Publishes an event on a key to the keyspace and keyevent channels, as enabled.
+    fn publish(&self, event: &str, key: &str) {
+        let db_index = match self.storage.index_of(&self.db) {
+            Some(index) => index,
+            None => return,
+        };
+
+        if self.flags.keyspace() {
+            let channel = format!("__keyspace@{}__:{}", db_index, key);
+            self.pubsub
+                .publish(&channel, Bytes::from(event.to_string()));
+        }
+        if self.flags.keyevent() {
+            let channel = format!("__keyevent@{}__:{}", db_index, event);
+            self.pubsub.publish(&channel, Bytes::from(key.to_string()));
+        }
+    }
+}
@@ -0,0 +1,613 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+use bytes::Bytes;
+use log::{error, warn};
+use tokio::sync::{
+    mpsc::{self, error::TrySendError},
+    Notify,
+};
+
+use crate::{resp::types::RespType, storage::keys::glob_match};
+
+use slot::hash_slot;
+
+pub mod keyspace;
+pub mod slot;
+
+/// Max number of messages queued for a subscriber. A subscriber which doesn't read the
+/// messages as fast as they're published is disconnected once its queue is full, so that a
+/// stalled client can't grow the memory unbounded.
+const SUBSCRIBER_QUEUE_CAPACITY: usize = 4096;
+
+/// The items queued for a subscriber.
+#[derive(Debug, Clone)]
+enum Queued {
+    /// A message published to the subscriptions, or pushed to the client.
+    Message(RespType),
+    /// A shard channel which was unsubscribed by the server, since its slot moved away.
+    ShardChannelDropped(String),
+}
+
+/// The sending half of the message queue of a subscriber.
+#[derive(Debug, Clone)]
+struct SubscriberTx {
+    tx: mpsc::Sender<Queued>,
+    /// Notified when the subscriber is disconnected for being too slow.
+    evicted: Arc<Notify>,
+}
+
+/// The subscribers of a channel (or a pattern), by their ids.
+type Subscribers = HashMap<u64, SubscriberTx>;
+
+/// The kinds of subscriptions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubscriptionKind {
+    /// A channel, to which messages are published by PUBLISH.
+    Channel,
+    /// A glob-style pattern, matching the channels to which messages are published by
+    /// PUBLISH.
+    Pattern,
+    /// A shard channel, to which messages are published by SPUBLISH. Shard channels are
+    /// separate from the other channels, and hash to a slot just like keys.
+    ShardChannel,
+}
+
+/// Routes the published messages to the clients subscribed to the channels, or to the
+/// glob-style patterns matching the channels. Messages published to the shard channels are
+/// routed to their subscribers only.
+///
+/// Out-of-band messages which are not published to any channel (like the invalidation
+/// messages of RESP3 clients) are pushed to the clients directly.
+#[derive(Debug, Default)]
+pub struct PubSub {
+    channels: RwLock<HashMap<String, Subscribers>>,
+    patterns: RwLock<HashMap<String, Subscribers>>,
+    shard_channels: RwLock<HashMap<String, Subscribers>>,
+    /// The message queues of all the connected clients, by their ids.
+    clients: RwLock<Subscribers>,
+}
+
+/// The subscriptions of a client connection, along with the queue of the messages published
+/// to them (or pushed to the client). The subscriptions are removed when the subscriber is
+/// dropped.
+#[derive(Debug)]
+pub struct Subscriber {
+    /// The id of the client connection.
+    id: u64,
+    pubsub: Arc<PubSub>,
+    tx: SubscriberTx,
+    rx: mpsc::Receiver<Queued>,
+    channels: HashSet<String>,
+    patterns: HashSet<String>,
+    shard_channels: HashSet<String>,
+}
+
+impl PubSub {
+    /// Creates a new `PubSub` instance, without any subscribers.
+    pub fn new() -> Arc<PubSub> {
+        Arc::new(PubSub::default())
+    }
+
+    /// Publishes a message to a channel. Subscribers whose queues are full are disconnected.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - The channel to which the message is published.
+    ///
+    /// * `message` - The message.
+    ///
+    /// # Returns
+    ///
+    /// The number of clients which received the message. A client subscribed to the channel
+    /// and to matching patterns receives the message once for each subscription.
+    pub fn publish(&self, channel: &str, message: Bytes) -> usize {
+        let mut receivers = 0;
+        let mut slow = vec![];
+
+        match self.channels.read() {
+            Ok(channels) => {
+                if let Some(subscribers) = channels.get(channel) {
+                    let msg = Queued::Message(RespType::Push(vec![
+                        RespType::BulkString(Bytes::from("message")),
+                        RespType::BulkString(Bytes::from(channel.to_string())),
+                        RespType::BulkString(message.clone()),
+                    ]));
+                    receivers += deliver(subscribers, &msg, &mut slow);
+                }
+            }
+            Err(e) => error!("Failed to publish to channel subscribers: {}", e),
+        }
+
+        match self.patterns.read() {
+            Ok(patterns) => {
+                for (pattern, subscribers) in patterns.iter() {
+                    if !glob_match(pattern.as_bytes(), channel.as_bytes()) {
+                        continue;
+                    }
+
+                    let msg = Queued::Message(RespType::Push(vec![
+                        RespType::BulkString(Bytes::from("pmessage")),
+                        RespType::BulkString(Bytes::from(pattern.clone())),
+                        RespType::BulkString(Bytes::from(channel.to_string())),
+                        RespType::BulkString(message.clone()),
+                    ]));
+                    receivers += deliver(subscribers, &msg, &mut slow);
+                }
+            }
+            Err(e) => error!("Failed to publish to pattern subscribers: {}", e),
+        }
+
+        self.remove_slow(slow);
+        receivers
+    }
+
+    /// Publishes a message to a shard channel. Subscribers whose queues are full are
+    /// disconnected.
+    ///
+    /// # Returns
+    ///
+    /// The number of clients which received the message.
+    pub fn spublish(&self, shard_channel: &str, message: Bytes) -> usize {
+        let mut receivers = 0;
+        let mut slow = vec![];
+
+        match self.shard_channels.read() {
+            Ok(shard_channels) => {
+                if let Some(subscribers) = shard_channels.get(shard_channel) {
+                    let msg = Queued::Message(RespType::Push(vec![
+                        RespType::BulkString(Bytes::from("smessage")),
+                        RespType::BulkString(Bytes::from(shard_channel.to_string())),
+                        RespType::BulkString(message),
+                    ]));
+                    receivers += deliver(subscribers, &msg, &mut slow);
+                }
+            }
+            Err(e) => error!("Failed to publish to shard channel subscribers: {}", e),
+        }
+
+        self.remove_slow(slow);
+        receivers
+    }
+
+    /// Sends a message published to a channel to one of its subscribers only, if the client
+    /// is subscribed to the channel. The subscriber is disconnected if its queue is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The id of the subscribed client.
+    ///
+    /// * `channel` - The channel to which the message is published.
+    ///
+    /// * `message` - The message, which can be any RESP value.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the client received the message.
+    pub fn send_to(&self, id: u64, channel: &str, message: RespType) -> bool {
+        let mut slow = vec![];
+        let received = match self.channels.read() {
+            Ok(channels) => match channels.get(channel).and_then(|s| s.get_key_value(&id)) {
+                Some(subscriber) => {
+                    let msg = Queued::Message(RespType::Push(vec![
+                        RespType::BulkString(Bytes::from("message")),
+                        RespType::BulkString(Bytes::from(channel.to_string())),
+                        message,
+                    ]));
+                    deliver([subscriber], &msg, &mut slow) > 0
+                }
+                None => false,
+            },
+            Err(e) => {
+                error!("Failed to send to the channel subscriber: {}", e);
+                false
+            }
+        };
+
+        self.remove_slow(slow);
+        received
+    }
+
+    /// Pushes an out-of-band message to a client, whether or not it's subscribed to any
+    /// channel. The client is disconnected if its queue is full.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the client received the message.
+    pub fn push_to(&self, id: u64, msg: RespType) -> bool {
+        let mut slow = vec![];
+        let received = match self.clients.read() {
+            Ok(clients) => match clients.get_key_value(&id) {
+                Some(client) => deliver([client], &Queued::Message(msg), &mut slow) > 0,
+                None => false,
+            },
+            Err(e) => {
+                error!("Failed to push to the client: {}", e);
+                false
+            }
+        };
+
+        self.remove_slow(slow);
+        received
+    }
+
+    /// Unsubscribes all the subscribers of the shard channels which hash to the slot. This
+    /// must be called when the slot is no longer owned by this node, since the messages of
+    /// its shard channels are published on the new owner. Each subscriber is sent a
+    /// `sunsubscribe` message for each of its shard channels in the slot, so that it can
+    /// subscribe again on the new owner.
+    ///
+    /// # Returns
+    ///
+    /// The number of shard channels which were dropped.
+    #[allow(dead_code)] // there's no cluster mode yet, hence slots never move
+    pub fn drop_slot(&self, slot: u16) -> usize {
+        let dropped: Vec<(String, Subscribers)> = match self.shard_channels.write() {
+            Ok(mut shard_channels) => {
+                let in_slot: Vec<String> = shard_channels
+                    .keys()
+                    .filter(|c| hash_slot(c.as_bytes()) == slot)
+                    .cloned()
+                    .collect();
+                in_slot
+                    .iter()
+                    .filter_map(|c| shard_channels.remove_entry(c))
+                    .collect()
+            }
+            Err(e) => {
+                error!("Failed to drop the shard channels of the slot: {}", e);
+                return 0;
+            }
+        };
+
+        let mut slow = vec![];
+        for (shard_channel, subscribers) in dropped.iter() {
+            let msg = Queued::ShardChannelDropped(shard_channel.clone());
+            deliver(subscribers, &msg, &mut slow);
+        }
+
+        self.remove_slow(slow);
+        dropped.len()
+    }
+
+    /// Returns the channels (or the shard channels) which have at least one subscriber. Only
+    /// the channels matching the glob-style pattern are returned, if given.
+    pub fn channels(&self, kind: SubscriptionKind, pattern: Option<&str>) -> Vec<String> {
+        match self.subscriptions(kind).read() {
+            Ok(channels) => channels
+                .keys()
+                .filter(|c| pattern.is_none_or(|p| glob_match(p.as_bytes(), c.as_bytes())))
+                .cloned()
+                .collect(),
+            Err(e) => {
+                error!("Failed to read the channels: {}", e);
+                vec![]
+            }
+        }
+    }
+
+    /// Returns the number of subscribers of a channel (or a shard channel). Subscribers of
+    /// the patterns are not counted.
+    pub fn num_subscribers(&self, kind: SubscriptionKind, channel: &str) -> usize {
+        match self.subscriptions(kind).read() {
+            Ok(channels) => channels.get(channel).map_or(0, |s| s.len()),
+            Err(e) => {
+                error!("Failed to read the channels: {}", e);
+                0
+            }
+        }
+    }
+
+    /// Returns the number of patterns which have at least one subscriber.
+    pub fn num_patterns(&self) -> usize {
+        match self.patterns.read() {
+            Ok(patterns) => patterns.len(),
+            Err(e) => {
+                error!("Failed to read the patterns: {}", e);
+                0
+            }
+        }
+    }
+
+    /// Returns the subscriptions of the kind, by the channels (or the patterns).
+    fn subscriptions(&self, kind: SubscriptionKind) -> &RwLock<HashMap<String, Subscribers>> {
+        match kind {
+            SubscriptionKind::Channel => &self.channels,
+            SubscriptionKind::Pattern => &self.patterns,
+            SubscriptionKind::ShardChannel => &self.shard_channels,
+        }
+    }
+
+    /// Disconnects the subscribers which didn't keep up with the published messages.
+    fn remove_slow(&self, slow: Vec<u64>) {
+        for id in slow {
+            warn!("Disconnecting subscriber {}, since its queue is full", id);
+            self.remove_subscriber(id);
+        }
+    }
+
+    /// Removes all the subscriptions of a subscriber.
+    fn remove_subscriber(&self, id: u64) {
+        for subscriptions in [&self.channels, &self.patterns, &self.shard_channels] {
+            match subscriptions.write() {
+                Ok(mut subscriptions) => subscriptions.retain(|_, subscribers| {
+                    subscribers.remove(&id);
+                    !subscribers.is_empty()
+                }),
+                Err(e) => error!("Failed to remove the subscriber: {}", e),
+            }
+        }
+    }
+}
+
+impl Subscriber {
+    /// Creates a new `Subscriber` instance for a client connection, without any
+    /// subscriptions. The messages can be pushed to the client right away.
+    pub fn new(pubsub: Arc<PubSub>, id: u64) -> Subscriber {
+        let (tx, rx) = mpsc::channel(SUBSCRIBER_QUEUE_CAPACITY);
+
+        let subscriber = Subscriber {
+            id,
+            pubsub,
+            tx: SubscriberTx {
+                tx,
+                evicted: Arc::new(Notify::new()),
+            },
+            rx,
+            channels: HashSet::new(),
+            patterns: HashSet::new(),
+            shard_channels: HashSet::new(),
+        };
+        match subscriber.pubsub.clients.write() {
+            Ok(mut clients) => {
+                clients.insert(id, subscriber.tx.clone());
+            }
+            Err(e) => error!("Failed to add the client: {}", e),
+        }
+
+        subscriber
+    }
+
+    /// Subscribes to a channel, a pattern or a shard channel.
+    ///
+    /// # Returns
+    ///
+    /// The number of shard channels subscribed to for a shard channel, or the number of
+    /// channels and patterns subscribed to otherwise.
+    pub fn subscribe(&mut self, kind: SubscriptionKind, name: &str) -> usize {
+        if self.names_mut(kind).insert(name.to_string()) {
+            self.add_to(self.pubsub.subscriptions(kind), name);
+        }
+        self.count_of(kind)
+    }
+
+    /// Unsubscribes from a channel, a pattern or a shard channel.
+    ///
+    /// # Returns
+    ///
+    /// The number of subscriptions remaining, counted like `subscribe`.
+    pub fn unsubscribe(&mut self, kind: SubscriptionKind, name: &str) -> usize {
+        if self.names_mut(kind).remove(name) {
+            self.remove_from(self.pubsub.subscriptions(kind), name);
+        }
+        self.count_of(kind)
+    }
+
+    /// Returns the channels, patterns or shard channels subscribed to, in sorted order.
+    pub fn subscriptions(&self, kind: SubscriptionKind) -> Vec<String> {
+        let names = match kind {
+            SubscriptionKind::Channel => &self.channels,
+            SubscriptionKind::Pattern => &self.patterns,
+            SubscriptionKind::ShardChannel => &self.shard_channels,
+        };
+
+        let mut names: Vec<String> = names.iter().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Returns the total number of channels, patterns and shard channels subscribed to.
+    pub fn count(&self) -> usize {
+        self.channels.len() + self.patterns.len() + self.shard_channels.len()
+    }
+
+    /// Returns the number of subscriptions which is reported for the kind. Shard channels
+    /// are counted separately from the channels and patterns.
+    pub fn count_of(&self, kind: SubscriptionKind) -> usize {
+        match kind {
+            SubscriptionKind::ShardChannel => self.shard_channels.len(),
+            _ => self.channels.len() + self.patterns.len(),
+        }
+    }
+
+    fn names_mut(&mut self, kind: SubscriptionKind) -> &mut HashSet<String> {
+        match kind {
+            SubscriptionKind::Channel => &mut self.channels,
+            SubscriptionKind::Pattern => &mut self.patterns,
+            SubscriptionKind::ShardChannel => &mut self.shard_channels,
+        }
+    }
+
+    /// Waits for the next message published to the subscriptions, or pushed to the client.
+    ///
+    /// # Returns
+    ///
+    /// The message, or `None` if the subscriber was disconnected for being too slow.
+    pub async fn recv(&mut self) -> Option<RespType> {
+        let queued = tokio::select! {
+            biased;
+            _ = self.tx.evicted.notified() => None,
+            queued = self.rx.recv() => queued,
+        };
+
+        queued.map(|queued| self.dequeue(queued))
+    }
+
+    /// Returns the messages which are already queued, without waiting for more.
+    pub fn pending(&mut self) -> Vec<RespType> {
+        let mut msgs = vec![];
+        while let Ok(queued) = self.rx.try_recv() {
+            msgs.push(self.dequeue(queued));
+        }
+        msgs
+    }
+
+    /// Converts a queued item into the message sent to the client. A dropped shard channel
+    /// is removed from the subscriptions, and is reported like SUNSUBSCRIBE does.
+    fn dequeue(&mut self, queued: Queued) -> RespType {
+        match queued {
+            Queued::Message(msg) => msg,
+            Queued::ShardChannelDropped(shard_channel) => {
+                // the subscribers of the shard channel are already removed from `PubSub`
+                self.shard_channels.remove(&shard_channel);
+
+                RespType::Push(vec![
+                    RespType::BulkString(Bytes::from("sunsubscribe")),
+                    RespType::BulkString(Bytes::from(shard_channel)),
+                    RespType::Integer(self.shard_channels.len() as i64),
+                ])
+            }
+        }
+    }
+
+    fn add_to(&self, subscriptions: &RwLock<HashMap<String, Subscribers>>, name: &str) {
+        match subscriptions.write() {
+            Ok(mut subscriptions) => {
+                subscriptions
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(self.id, self.tx.clone());
+            }
+            Err(e) => error!("Failed to add the subscription: {}", e),
+        }
+    }
+
+    fn remove_from(&self, subscriptions: &RwLock<HashMap<String, Subscribers>>, name: &str) {
+        match subscriptions.write() {
+            Ok(mut subscriptions) => {
+                if let Some(subscribers) = subscriptions.get_mut(name) {
+                    subscribers.remove(&self.id);
+                    if subscribers.is_empty() {
+                        subscriptions.remove(name);
+                    }
+                }
+            }
+            Err(e) => error!("Failed to remove the subscription: {}", e),
+        }
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        match self.pubsub.clients.write() {
+            Ok(mut clients) => {
+                clients.remove(&self.id);
+            }
+            Err(e) => error!("Failed to remove the client: {}", e),
+        }
+        if self.count() > 0 {
+            self.pubsub.remove_subscriber(self.id);
+        }
+    }
+}
+
+/// Sends a message to the subscribers, and returns the number of subscribers which received
+/// it. The ids of the subscribers whose queues are full are added to `slow`, and they're
+/// notified of the eviction.
+fn deliver<'a>(
+    subscribers: impl IntoIterator<Item = (&'a u64, &'a SubscriberTx)>,
+    msg: &Queued,
+    slow: &mut Vec<u64>,
+) -> usize {
+    let mut receivers = 0;
+    for (id, subscriber) in subscribers {
+        match subscriber.tx.try_send(msg.clone()) {
+            Ok(_) => receivers += 1,
+            Err(TrySendError::Full(_)) => {
+                subscriber.evicted.notify_one();
+                slow.push(*id);
+            }
+            // the connection is closed, and its subscriptions are about to be removed
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+
+    receivers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the kind and the channel of a subscription reply, and the subscription count.
+    fn reply_parts(reply: &RespType) -> (String, String, i64) {
+        match reply {
+            RespType::Push(parts) => match parts.as_slice() {
+                [RespType::BulkString(kind), RespType::BulkString(channel), RespType::Integer(count)] => {
+                    (
+                        String::from_utf8_lossy(kind).to_string(),
+                        String::from_utf8_lossy(channel).to_string(),
+                        *count,
+                    )
+                }
+                _ => panic!("unexpected reply {:?}", reply),
+            },
+            _ => panic!("unexpected reply {:?}", reply),
+        }
+    }
+
+    #[test]
+    fn drop_slot() {
+        let pubsub = PubSub::new();
+        let slot = hash_slot(b"a");
+        assert_ne!(hash_slot(b"b"), slot);
+
+        let mut first = Subscriber::new(Arc::clone(&pubsub), 1);
+        first.subscribe(SubscriptionKind::ShardChannel, "{a}1");
+        first.subscribe(SubscriptionKind::ShardChannel, "{a}2");
+        first.subscribe(SubscriptionKind::ShardChannel, "b");
+        first.subscribe(SubscriptionKind::Channel, "{a}1");
+        let mut second = Subscriber::new(Arc::clone(&pubsub), 2);
+        second.subscribe(SubscriptionKind::ShardChannel, "{a}1");
+
+        assert_eq!(pubsub.drop_slot(slot), 2);
+        assert_eq!(pubsub.drop_slot(slot), 0);
+
+        // the subscribers are notified once for each of their shard channels in the slot
+        let mut replies: Vec<(String, String, i64)> =
+            first.pending().iter().map(reply_parts).collect();
+        replies.sort();
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0].0, "sunsubscribe");
+        assert_eq!(replies[1].0, "sunsubscribe");
+        assert_eq!(
+            (replies[0].1.as_str(), replies[1].1.as_str()),
+            ("{a}1", "{a}2")
+        );
+        let mut counts = [replies[0].2, replies[1].2];
+        counts.sort();
+        assert_eq!(counts, [1, 2]);
+        assert_eq!(first.subscriptions(SubscriptionKind::ShardChannel), ["b"]);
+        assert_eq!(first.subscriptions(SubscriptionKind::Channel), ["{a}1"]);
+
+        let replies: Vec<(String, String, i64)> =
+            second.pending().iter().map(reply_parts).collect();
+        assert_eq!(
+            replies,
+            [(String::from("sunsubscribe"), String::from("{a}1"), 0)]
+        );
+        assert_eq!(second.count(), 0);
+
+        // the shard channels of the other slots and the channels aren't affected
+        assert_eq!(pubsub.channels(SubscriptionKind::ShardChannel, None), ["b"]);
+        assert_eq!(pubsub.spublish("{a}1", Bytes::from("msg")), 0);
+        assert_eq!(pubsub.spublish("b", Bytes::from("msg")), 1);
+        assert_eq!(pubsub.publish("{a}1", Bytes::from("msg")), 1);
+
+        // the shard channels can be subscribed again
+        second.subscribe(SubscriptionKind::ShardChannel, "{a}1");
+        assert_eq!(pubsub.spublish("{a}1", Bytes::from("msg")), 1);
+    }
+}
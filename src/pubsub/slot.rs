@@ -0,0 +1,44 @@
+// Shard channels hash to one of the 16384 slots, same as the keys in Redis Cluster, so that
+// they're owned by the same shard as the keys of the slot.
+//
+// The slot is the CRC16 (XMODEM) of the name, modulo 16384. If the name contains a hash tag
+// (a non-empty substring between the first `{` and the next `}`), only the hash tag is hashed,
+// so that related names can be forced into the same slot.
+//
+// There's no cluster mode yet, so this node owns every slot, and shard channels hashing to
+// different slots can be subscribed together. When a slot moves away, `PubSub::drop_slot`
+// unsubscribes the subscribers of its shard channels, since their messages are published on
+// the new owner.
+
+/// Number of hash slots.
+const NUM_SLOTS: u16 = 16384;
+
+/// Returns the hash slot of a key or a shard channel.
+pub fn hash_slot(name: &[u8]) -> u16 {
+    let hashed = match name.iter().position(|&b| b == b'{') {
+        Some(start) => match name[start + 1..].iter().position(|&b| b == b'}') {
+            Some(len) if len > 0 => &name[start + 1..start + 1 + len],
+            _ => name,
+        },
+        None => name,
+    };
+
+    crc16(hashed) % NUM_SLOTS
+}
+
+/// Computes the CRC16 (XMODEM) checksum of the bytes.
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &b in bytes {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+
+    crc
+}
@@ -9,7 +9,9 @@ use tokio_util::codec::{BytesCodec, Framed};
 use crate::command::ping::Ping;
 use crate::command::psync::Psync;
 use crate::resp::types::RespType;
-use crate::{handler::FrameHandler, resp::frame::RespCommandFrame, storage::db::Storage};
+use crate::{
+    handler::FrameHandler, pubsub::PubSub, resp::frame::RespCommandFrame, storage::db::Storage,
+};
 
 use super::Replication;
 
@@ -22,9 +24,9 @@ impl MasterServer {
     ///
     /// - Send a PING request and validate for PONG response
     /// - Send 2 REPLCONF commands to master: `REPLCONF listening-port <PORT>` and `REPLCONF capa psync2`,
-    ///         where `<PORT>` is the port where the replica is listening. This is not implemented as of now.
+    ///   where `<PORT>` is the port where the replica is listening. This is not implemented as of now.
     /// - Send PSYNC <REPLICATION_ID> <OFFSET> command to master, and perform a full-resync if required. Resync
-    ///         is not implemented as of now.
+    ///   is not implemented as of now.
     pub async fn perform_handshake(stream: TcpStream) -> Result<TcpStream> {
         let mut handshake_frame = Framed::with_capacity(stream, BytesCodec::new(), 8 * 1024);
 
@@ -95,7 +97,7 @@ impl MasterServer {
                     match resp_val {
                         Ok((ss, _)) => {
                             if let RespType::BulkString(s) = ss {
-                                if !s.starts_with("FULLRESYNC") {
+                                if !s.starts_with(b"FULLRESYNC") {
                                     return Err(anyhow!(
                                         "Invalid response for PSYNC request to master during handshake",
                                     ));
@@ -136,8 +138,8 @@ impl MasterServer {
         stream: TcpStream,
         storage: Arc<Storage>,
         replication: Arc<Replication>,
+        pubsub: Arc<PubSub>,
     ) -> Result<()> {
-        let db = storage.as_ref().db().clone();
         let replication = replication.clone();
 
         // listen to the master server replication stream
@@ -146,7 +148,7 @@ impl MasterServer {
         let handler = FrameHandler::new(resp_command_frame);
         info!("Initialize master server listener");
         if let Err(e) = handler
-            .handle_replication_stream(db.as_ref(), replication.as_ref())
+            .handle_replication_stream(storage.as_ref(), replication.as_ref(), pubsub.as_ref())
             .await
         {
             error!("Failed to handle command: {}", e);
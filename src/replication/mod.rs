@@ -52,7 +52,11 @@ impl Replication {
         s.push_str("role:");
 
         if self.is_slave() {
-            s.push_str("slave");
+            s.push_str("slave\n");
+            if let (Some(host), Some(port)) = (&self.master_host, self.master_port) {
+                s.push_str(format!("master_host:{}\n", host).as_str());
+                s.push_str(format!("master_port:{}\n", port).as_str());
+            }
         } else {
             let offset = self.offset.load(Ordering::SeqCst);
             s.push_str("master\n");
@@ -73,8 +77,9 @@ impl Replication {
         self.replica_peers.add_peer(stream).await;
     }
 
-    /// Send RESP data which is to be broadcast to all replicas.
-    pub async fn write_to_replicas(&self, resp_data: RespType) -> usize {
-        self.replica_peers.replicate(resp_data).await
+    /// Send RESP data which is to be broadcast to all replicas. `db_index` is the number of
+    /// the database on which the command was executed.
+    pub async fn write_to_replicas(&self, resp_data: RespType, db_index: usize) -> usize {
+        self.replica_peers.replicate(resp_data, db_index).await
     }
 }
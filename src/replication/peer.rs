@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use log::{error, info};
 use rand::distributions::{Alphanumeric, DistString};
 use tokio::io::AsyncWriteExt;
@@ -8,6 +8,7 @@ use tokio::net::TcpStream;
 use tokio::sync::broadcast::{self, Receiver, Sender};
 use tokio::sync::Mutex;
 
+use crate::command::select::Select;
 use crate::resp::types::RespType;
 
 /// Stores a list of slave replicas and provides a mechanism to broadcast the replication stream to the peers.
@@ -18,6 +19,9 @@ pub struct ReplicaPeers {
     sender: Sender<Bytes>,
     /// The list of connected peers.
     peers: Arc<Mutex<Vec<Peer>>>,
+    /// The database selected by the replication stream, if any. SELECT is sent to the stream
+    /// whenever a command is replicated from another database.
+    selected_db: Arc<Mutex<Option<usize>>>,
 }
 
 impl ReplicaPeers {
@@ -27,6 +31,7 @@ impl ReplicaPeers {
         ReplicaPeers {
             sender: tx,
             peers: Arc::new(Mutex::new(vec![])),
+            selected_db: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -42,23 +47,36 @@ impl ReplicaPeers {
         new_peer.init_replication(self.peers.clone()).await;
         peers.push(new_peer);
 
+        // the new peer starts from its default database, so the database must be selected
+        // again before the next command
+        *self.selected_db.lock().await = None;
+
         info!("Number of peers connected: {}", peers.len());
     }
 
-    /// Replicates the given `RespType` data to all connected peers.
+    /// Replicates the given `RespType` data to all connected peers. The data is preceded by
+    /// a SELECT command, if the database differs from the one selected by the stream.
     ///
     /// # Arguments
     /// * `resp_data` - The `RespType` data to be sent to the replication stream.
-    pub async fn replicate(&self, resp_data: RespType) -> usize {
+    /// * `db_index` - The number of the database on which the command was executed.
+    pub async fn replicate(&self, resp_data: RespType, db_index: usize) -> usize {
         let peers = self.peers.lock().await;
-        let resp_bytes = resp_data.to_bytes();
+        let mut selected_db = self.selected_db.lock().await;
+
+        let mut resp_bytes = BytesMut::new();
+        if *selected_db != Some(db_index) {
+            resp_bytes.extend_from_slice(&Select::new(db_index).build_command().to_bytes());
+            *selected_db = Some(db_index);
+        }
+        resp_bytes.extend_from_slice(&resp_data.to_bytes());
         let cmd_bytes_len = resp_bytes.len();
 
-        if peers.len() == 0 {
+        if peers.is_empty() {
             return cmd_bytes_len;
         }
 
-        if let Err(e) = self.sender.send(resp_bytes) {
+        if let Err(e) = self.sender.send(resp_bytes.freeze()) {
             error!("{}", e);
         }
 
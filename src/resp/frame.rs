@@ -1,16 +1,29 @@
-use bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut, BytesMut};
 use core::fmt;
 use std::io::Error;
 use tokio_util::codec::{Decoder, Encoder};
 
-use crate::resp::types::RespType;
+use crate::resp::types::{ProtocolVersion, RespType};
 
-use super::RespError;
+use super::{inline::parse_inline_command, RespError};
+
+/// Max length of an inline command. The connection is closed if a longer line is received,
+/// so that a client can't make the server buffer an unbounded line. The same limit applies
+/// to the lengths of the arrays and bulk strings.
+const INLINE_MAX_SIZE: usize = 64 * 1024;
+/// Default max length of a bulk string in a command (512 MB).
+pub const DEFAULT_PROTO_MAX_BULK_LEN: usize = 512 * 1024 * 1024;
+/// Default max number of arguments of a command.
+pub const DEFAULT_MAX_MULTIBULK_LEN: usize = 1024 * 1024;
+/// Default max number of bytes buffered for a command (1 GB).
+pub const DEFAULT_CLIENT_QUERY_BUFFER_LIMIT: usize = 1024 * 1024 * 1024;
 
 /// A tokio_utils Frame codec for working with TCP streams as a `Sink` and `Stream` of `RespType`.
 ///
-/// This codec specifically handles Nimblecache commands, which are always represented
-/// as array of bulk strings in the RESP (REdis Serialization Protocol) protocol.
+/// This codec specifically handles Nimblecache commands, which are represented as array of
+/// bulk strings in the RESP (REdis Serialization Protocol) protocol. Inline commands (a line
+/// of space separated arguments, like `PING\r\n`) are accepted as well, so that the
+/// commands can be typed by hand over `telnet` or `nc`.
 ///
 /// The codec uses a `CommandBuilder` internally to construct the array of bulk strings
 /// that make up a Nimblecache command.
@@ -20,7 +33,7 @@ use super::RespError;
 /// ```
 /// use tokio::net::TcpStream;
 /// use tokio_util::codec::Framed;
-/// use crate::resp::frame::RespCommandFrame;
+/// use nimblecache::resp::frame::RespCommandFrame;
 ///
 /// async fn handle_connection(stream: TcpStream) {
 ///     let mut framed = Framed::new(stream, RespCommandFrame::new());
@@ -30,16 +43,200 @@ use super::RespError;
 pub struct RespCommandFrame {
     /// Builder for appending the bulk strings in the command array.
     cmd_builder: Option<CommandBuilder>,
+    /// The version of RESP in which the responses are encoded.
+    version: ProtocolVersion,
+    /// The limits on the commands received.
+    limits: FrameLimits,
 }
 
 impl RespCommandFrame {
-    /// Creates a new `RespCommandFrame`.
+    /// Creates a new `RespCommandFrame`, with the default limits.
     ///
     /// # Returns
     ///
-    /// A new instance of `RespCommandFrame` with no command builder initialized.
+    /// A new instance of `RespCommandFrame` with no command builder initialized, which
+    /// encodes the responses in RESP2.
     pub fn new() -> RespCommandFrame {
-        RespCommandFrame { cmd_builder: None }
+        RespCommandFrame::with_limits(FrameLimits::default())
+    }
+
+    /// Creates a new `RespCommandFrame`, which rejects the commands exceeding the given limits.
+    pub fn with_limits(limits: FrameLimits) -> RespCommandFrame {
+        RespCommandFrame {
+            cmd_builder: None,
+            version: ProtocolVersion::Resp2,
+            limits,
+        }
+    }
+
+    /// Returns the version of RESP in which the responses are encoded.
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// Switches the version of RESP in which the responses are encoded.
+    pub fn set_version(&mut self, version: ProtocolVersion) {
+        self.version = version;
+    }
+
+    /// Decodes the next command from the buffer, if it has been received completely.
+    ///
+    /// The parts of the command are split off the buffer as soon as they're received, so the
+    /// decoding resumes from where it stopped on the next call, rather than parsing the
+    /// buffered bytes again. The bulk strings share the memory of the buffer, instead of
+    /// being copied.
+    fn decode_command(&mut self, src: &mut BytesMut) -> Result<Option<Vec<RespType>>, Error> {
+        loop {
+            // A command in RESP protocol should be an array of Bulk Strings, or an inline
+            // command. Check the first 2 bytes to validate if its a RESP array.
+            let cmd_builder = match self.cmd_builder.as_mut() {
+                Some(cmd_builder) => cmd_builder,
+                None => {
+                    match src.first() {
+                        None => return Ok(None),
+                        Some(b'*') => {}
+                        Some(_) => match decode_inline(src)? {
+                            // skip the blank lines
+                            Some(cmd) if cmd.is_empty() => continue,
+                            cmd => return Ok(cmd),
+                        },
+                    }
+
+                    let (cmd_len, bytes_read) = match RespType::parse_array_len(src) {
+                        Ok(Some((len, bytes_read))) => (len, bytes_read),
+                        Ok(None) if src.len() > INLINE_MAX_SIZE => {
+                            return Err(protocol_error(RespError::InvalidArray(String::from(
+                                "too big mbulk count string",
+                            ))));
+                        }
+                        Ok(None) => return Ok(None),
+                        Err(_) => return Err(protocol_error(invalid_multibulk_len())),
+                    };
+                    if cmd_len > self.limits.max_multibulk_len {
+                        return Err(protocol_error(invalid_multibulk_len()));
+                    }
+
+                    // advance buffer
+                    src.advance(bytes_read);
+
+                    // empty arrays are skipped
+                    if cmd_len == 0 {
+                        continue;
+                    }
+
+                    // initilize command builder, if its a valid RESP array.
+                    self.cmd_builder.insert(CommandBuilder::new(cmd_len))
+                }
+            };
+
+            // A bulk string has the below format
+            //
+            // `${string length in bytes }\r\n{string value}\r\n`
+            //
+            // Once the length of the bulk string is read, wait till the buffer contains the
+            // string value (including the CRLF at the end).
+            let part = match cmd_builder.bulkstr_len {
+                Some(bulkstr_len) => {
+                    if src.len() < bulkstr_len + 2 {
+                        return Ok(None);
+                    }
+
+                    if &src[bulkstr_len..bulkstr_len + 2] != b"\r\n" {
+                        return Err(protocol_error(RespError::InvalidBulkString(String::from(
+                            "expected CRLF",
+                        ))));
+                    }
+
+                    let bulkstr = src.split_to(bulkstr_len).freeze();
+                    src.advance(2);
+                    cmd_builder.bulkstr_len = None;
+                    cmd_builder.size += bulkstr_len + 2;
+
+                    RespType::BulkString(bulkstr)
+                }
+                None => match src.first() {
+                    None => return Ok(None),
+                    Some(b'$') => {
+                        // Validate and check the length of next bulk string
+                        let (bulkstr_len, bytes_read) = match RespType::parse_bulk_string_len(src) {
+                            Ok(Some((len, bytes_read))) => (len, bytes_read),
+                            Ok(None) if src.len() > INLINE_MAX_SIZE => {
+                                return Err(protocol_error(RespError::InvalidBulkString(
+                                    String::from("too big bulk count string"),
+                                )));
+                            }
+                            Ok(None) => return Ok(None),
+                            Err(_) => return Err(protocol_error(invalid_bulk_len())),
+                        };
+                        // the length must leave room for the CRLF after the string, since the
+                        // limit can be configured as high as `usize::MAX`
+                        if bulkstr_len > self.limits.max_bulk_len
+                            || bulkstr_len.checked_add(2).is_none()
+                        {
+                            return Err(protocol_error(invalid_bulk_len()));
+                        }
+
+                        src.advance(bytes_read);
+                        cmd_builder.bulkstr_len = Some(bulkstr_len);
+                        cmd_builder.size += bytes_read;
+                        continue;
+                    }
+                    // Simple strings and integers are read as bulk strings
+                    Some(b'+' | b':') => match decode_line(src)? {
+                        Some(mut line) => {
+                            cmd_builder.size += line.len() + 2;
+                            line.advance(1);
+                            RespType::BulkString(line.freeze())
+                        }
+                        None => return Ok(None),
+                    },
+                    Some(c) => {
+                        return Err(protocol_error(RespError::InvalidBulkString(format!(
+                            "expected '$', got '{}'",
+                            *c as char
+                        ))));
+                    }
+                },
+            };
+
+            // append the bulk string to the command builder
+            cmd_builder.add_part(part);
+
+            // if the command builder has all the parts, return it, else check buffer again
+            if cmd_builder.all_parts_received() {
+                let cmd = self.cmd_builder.take().unwrap().build();
+                return Ok(Some(cmd));
+            }
+        }
+    }
+}
+
+impl Default for RespCommandFrame {
+    fn default() -> Self {
+        RespCommandFrame::new()
+    }
+}
+
+/// The limits on the commands received by the server, which protect it from clients that
+/// make it buffer huge amounts of data.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameLimits {
+    /// Max length of a bulk string in a command.
+    pub max_bulk_len: usize,
+    /// Max number of arguments of a command (including the name of the command).
+    pub max_multibulk_len: usize,
+    /// Max number of bytes buffered for a command which hasn't been received completely.
+    pub query_buffer_limit: usize,
+}
+
+impl Default for FrameLimits {
+    /// The defaults are the same as Redis.
+    fn default() -> Self {
+        FrameLimits {
+            max_bulk_len: DEFAULT_PROTO_MAX_BULK_LEN,
+            max_multibulk_len: DEFAULT_MAX_MULTIBULK_LEN,
+            query_buffer_limit: DEFAULT_CLIENT_QUERY_BUFFER_LIMIT,
+        }
     }
 }
 
@@ -52,7 +249,11 @@ impl Decoder for RespCommandFrame {
     ///
     /// This method implements the RESP protocol decoding logic, specifically handling
     /// arrays of bulk strings which represent Nimblecache commands. It uses a `CommandBuilder`
-    /// to accumulate the parts of the command as they are received.
+    /// to accumulate the parts of the command as they are received. Simple strings and
+    /// integers are accepted in the array as well, and are read as bulk strings.
+    ///
+    /// If the input doesn't start with an array, it's read as an inline command. Blank lines
+    /// and empty arrays are skipped.
     ///
     /// # Arguments
     ///
@@ -62,95 +263,107 @@ impl Decoder for RespCommandFrame {
     ///
     /// * `Ok(Some(Vec<RespType>))` if a complete command (array of bulk strings) was successfully decoded.
     /// * `Ok(None)` if more data is needed to complete the command.
-    /// * `Err(std::io::Error)` if an error occurred during decoding.
+    /// * `Err(std::io::Error)` if the input is not valid RESP or exceeds the limits, in which
+    ///   case the error wraps a `FrameError`.
     fn decode(
         &mut self,
         src: &mut bytes::BytesMut,
     ) -> std::result::Result<Option<Self::Item>, Self::Error> {
-        // A command in RESP protocol should always be an array of Bulk Strings.
-        // Check the first 2 bytes to validate if its a RESP array.
-        if self.cmd_builder.is_none() {
-            let (cmd_len, bytes_read) = match RespType::parse_array_len(src.clone()) {
-                Ok(arr_len) => match arr_len {
-                    Some((len, bytes_read)) => (len, bytes_read),
-                    None => return Ok(None),
-                },
-                Err(e) => {
-                    return Err(Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        FrameError::from(e),
-                    ));
-                }
-            };
+        let cmd = self.decode_command(src)?;
+
+        // The bytes of the command received so far are either in the buffer, or have been
+        // moved to the command builder.
+        if cmd.is_none() {
+            let buffered = src.len() + self.cmd_builder.as_ref().map_or(0, |b| b.size);
+            if buffered > self.limits.query_buffer_limit {
+                return Err(Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    FrameError::QueryBufferLimit(buffered),
+                ));
+            }
+        }
 
-            // initilize command builder, if its a valid RESP array.
-            self.cmd_builder = Some(CommandBuilder::new(cmd_len));
+        Ok(cmd)
+    }
+}
 
-            // advance buffer
-            src.advance(bytes_read);
+/// Reads an inline command from the buffer.
+///
+/// # Returns
+///
+/// * `Ok(Some(Vec<RespType>))` if a complete line was read. The arguments are empty if the
+///   line is blank.
+/// * `Ok(None)` if more data is needed to complete the line.
+/// * `Err(std::io::Error)` if the line is longer than `INLINE_MAX_SIZE`, or if its quotes are
+///   unbalanced.
+fn decode_inline(src: &mut BytesMut) -> Result<Option<Vec<RespType>>, Error> {
+    let newline = match src.iter().position(|b| *b == b'\n') {
+        Some(newline) => newline,
+        None if src.len() > INLINE_MAX_SIZE => {
+            return Err(protocol_error(RespError::InvalidInlineCommand(
+                String::from("too big inline request"),
+            )));
         }
+        None => return Ok(None),
+    };
 
-        // Read all bytes in buffer
-        while !src.is_empty() {
-            // Validate and check the length of next bulk string
-            let (bulkstr_len, bytes_read) = match RespType::parse_bulk_string_len(src.clone()) {
-                Ok(bulkstr_len) => match bulkstr_len {
-                    Some((len, bytes_read)) => (len, bytes_read),
-                    None => return Ok(None),
-                },
-                Err(e) => {
-                    return Err(Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        FrameError::from(e),
-                    ));
-                }
-            };
+    let line = src.split_to(newline + 1);
+    // the line may be terminated by either LF or CRLF
+    let line = line.strip_suffix(b"\r\n").unwrap_or(&line[..newline]);
 
-            // A bulk string has the below format
-            //
-            // `${string length in bytes }\r\n{string value}\r\n`
-            //
-            // Check if the buffer contains the required number of bytes to parse
-            // the bulk string (including the CRLF at the end)
-            let bulkstr_bytes = bulkstr_len + bytes_read + 2;
-            if src.len() < bulkstr_bytes {
-                return Ok(None);
-            }
+    match parse_inline_command(line) {
+        Ok(args) => Ok(Some(args)),
+        Err(e) => Err(protocol_error(e)),
+    }
+}
 
-            // now that its sure the buffer has all the bytes required to parse the bulk string, parse it.
-            let (bulkstr, bytes_read) = match RespType::new_bulk_string(src.clone()) {
-                Ok((resp_type, bytes_read)) => (resp_type, bytes_read),
-                Err(e) => {
-                    return Err(Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        FrameError::from(e),
-                    ));
-                }
-            };
+/// Reads a line terminated by CRLF from the buffer.
+///
+/// # Returns
+///
+/// * `Ok(Some(BytesMut))` - The line, without the CRLF.
+/// * `Ok(None)` - If more data is needed to complete the line.
+/// * `Err(std::io::Error)` - If the line isn't terminated by CRLF.
+fn decode_line(src: &mut BytesMut) -> Result<Option<BytesMut>, Error> {
+    let newline = match src.iter().position(|b| *b == b'\n') {
+        Some(newline) => newline,
+        None if src.len() > INLINE_MAX_SIZE => {
+            return Err(protocol_error(RespError::Other(String::from(
+                "too big line",
+            ))));
+        }
+        None => return Ok(None),
+    };
+    if newline == 0 || src[newline - 1] != b'\r' {
+        return Err(protocol_error(RespError::Other(String::from(
+            "line is not terminated by CRLF",
+        ))));
+    }
 
-            // append the bulk string to the command builder
-            self.cmd_builder.as_mut().unwrap().add_part(bulkstr);
+    let mut line = src.split_to(newline + 1);
+    line.truncate(newline - 1);
 
-            // advance buffer
-            src.advance(bytes_read);
+    Ok(Some(line))
+}
 
-            // if the command builder has all the parts, return it, else check buffer again
-            let cmd_builder = self.cmd_builder.as_ref().unwrap();
-            if cmd_builder.all_parts_received() {
-                let cmd = cmd_builder.build();
-                self.cmd_builder = None;
-                return Ok(Some(cmd));
-            }
-        }
+fn invalid_multibulk_len() -> RespError {
+    RespError::InvalidArray(String::from("invalid multibulk length"))
+}
 
-        Ok(None)
-    }
+fn invalid_bulk_len() -> RespError {
+    RespError::InvalidBulkString(String::from("invalid bulk length"))
+}
+
+/// Wraps an error in parsing the input into an `std::io::Error`, as expected by the codec.
+fn protocol_error(err: RespError) -> Error {
+    Error::new(std::io::ErrorKind::InvalidData, FrameError::Protocol(err))
 }
 
 impl Encoder<RespType> for RespCommandFrame {
     type Error = std::io::Error;
 
-    /// Encodes a `RespType` into bytes and writes them to the output buffer.
+    /// Encodes a `RespType` into bytes and writes them to the output buffer, as per the
+    /// version of RESP used by the connection.
     ///
     /// It's primarily used for sending responses to Nimblecache commands.
     ///
@@ -164,7 +377,7 @@ impl Encoder<RespType> for RespCommandFrame {
     /// * `Ok(())` if the encoding was successful.
     /// * `Err(std::io::Error)` if an error occurred during encoding.
     fn encode(&mut self, item: RespType, dst: &mut bytes::BytesMut) -> Result<(), Self::Error> {
-        dst.put_slice(&item.to_bytes());
+        dst.put_slice(&item.to_bytes_with(self.version));
 
         Ok(())
     }
@@ -175,7 +388,10 @@ impl Encoder<RespType> for RespCommandFrame {
 struct CommandBuilder {
     parts: Vec<RespType>,
     num_parts: usize,
-    parts_parsed: usize,
+    /// The length of the bulk string being received, once its length has been read.
+    bulkstr_len: Option<usize>,
+    /// The number of bytes read for the parts received so far.
+    size: usize,
 }
 
 impl CommandBuilder {
@@ -184,18 +400,18 @@ impl CommandBuilder {
         CommandBuilder {
             parts: vec![],
             num_parts,
-            parts_parsed: 0,
+            bulkstr_len: None,
+            size: 0,
         }
     }
 
-    /// Adds a part to the command being built and increments the count of parsed parts.
+    /// Adds a part to the command being built.
     ///
     /// # Arguments
     ///
     /// * `part` - A `RespType` representing a part of the command.
     pub fn add_part(&mut self, part: RespType) {
         self.parts.push(part);
-        self.parts_parsed += 1;
     }
 
     /// Checks if all expected parts of the command have been received.
@@ -205,7 +421,7 @@ impl CommandBuilder {
     /// `true` if the number of parsed parts equals the expected number of parts,
     /// `false` otherwise.
     pub fn all_parts_received(&self) -> bool {
-        self.num_parts == self.parts_parsed
+        self.num_parts == self.parts.len()
     }
 
     /// Builds and returns the complete command as a vector of RESP values.
@@ -213,27 +429,376 @@ impl CommandBuilder {
     /// # Returns
     ///
     /// A vector of `RespType` containing all the parts of the command.
-    pub fn build(&self) -> Vec<RespType> {
-        self.parts.clone()
+    pub fn build(self) -> Vec<RespType> {
+        self.parts
     }
 }
 
 /// Represents error that can occur during RESP command frame parsing.
 #[derive(Debug)]
-pub struct FrameError {
-    err: RespError,
-}
-
-impl FrameError {
-    pub fn from(err: RespError) -> FrameError {
-        FrameError { err }
-    }
+pub enum FrameError {
+    /// The input is not a valid command. It's reported to the client as a protocol error.
+    Protocol(RespError),
+    /// The client sent more bytes than the query buffer limit, without completing a command.
+    QueryBufferLimit(usize),
 }
 
 impl std::error::Error for FrameError {}
 
 impl fmt::Display for FrameError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.err.fmt(f)
+        match self {
+            FrameError::Protocol(err) => err.fmt(f),
+            FrameError::QueryBufferLimit(size) => {
+                write!(f, "query buffer of {} bytes exceeds the limit", size)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::*;
+
+    /// Decodes the input, returning the arguments of the decoded command.
+    fn decode(
+        frame: &mut RespCommandFrame,
+        src: &mut BytesMut,
+    ) -> Result<Option<Vec<Vec<u8>>>, Error> {
+        let cmd = frame.decode(src)?;
+        Ok(cmd.map(|args| args.into_iter().map(bulk_bytes).collect()))
+    }
+
+    fn bulk_bytes(arg: RespType) -> Vec<u8> {
+        match arg {
+            RespType::BulkString(b) => b.to_vec(),
+            arg => panic!("expected a bulk string, got {:?}", arg),
+        }
+    }
+
+    /// Decodes the input, which is expected to be rejected, returning the error.
+    fn decode_err(frame: &mut RespCommandFrame, input: &[u8]) -> FrameError {
+        let err = decode(frame, &mut BytesMut::from(input)).expect_err("expected an error");
+        *err.into_inner()
+            .expect("expected a frame error")
+            .downcast::<FrameError>()
+            .expect("expected a frame error")
+    }
+
+    fn assert_protocol_error(err: FrameError, msg: &str) {
+        match err {
+            FrameError::Protocol(err) => assert_eq!(err.to_string(), msg),
+            err => panic!("expected a protocol error, got {:?}", err),
+        }
+    }
+
+    fn with_limits(
+        max_bulk_len: usize,
+        max_multibulk_len: usize,
+        query_buffer_limit: usize,
+    ) -> RespCommandFrame {
+        RespCommandFrame::with_limits(FrameLimits {
+            max_bulk_len,
+            max_multibulk_len,
+            query_buffer_limit,
+        })
+    }
+
+    #[test]
+    fn decodes_commands() {
+        let mut frame = RespCommandFrame::new();
+        let mut src = BytesMut::from(&b"*2\r\n$4\r\nECHO\r\n$5\r\nhello\r\nPING\r\n"[..]);
+
+        let cmd = decode(&mut frame, &mut src).unwrap();
+        assert_eq!(cmd, Some(vec![b"ECHO".to_vec(), b"hello".to_vec()]));
+        let cmd = decode(&mut frame, &mut src).unwrap();
+        assert_eq!(cmd, Some(vec![b"PING".to_vec()]));
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn bulk_len_limit() {
+        let mut frame = with_limits(5, 8, usize::MAX);
+        let mut src = BytesMut::from(&b"*1\r\n$5\r\nhello\r\n"[..]);
+        let cmd = decode(&mut frame, &mut src).unwrap();
+        assert_eq!(cmd, Some(vec![b"hello".to_vec()]));
+
+        let mut frame = with_limits(5, 8, usize::MAX);
+        let err = decode_err(&mut frame, b"*1\r\n$6\r\n");
+        assert_protocol_error(err, "invalid bulk length");
+    }
+
+    #[test]
+    fn bulk_len_overflow() {
+        let mut frame = with_limits(usize::MAX, 8, usize::MAX);
+        let input = format!("*1\r\n${}\r\n", usize::MAX - 1);
+        let err = decode_err(&mut frame, input.as_bytes());
+        assert_protocol_error(err, "invalid bulk length");
+    }
+
+    #[test]
+    fn multibulk_len_limit() {
+        let mut frame = with_limits(16, 3, usize::MAX);
+        let mut src = BytesMut::from(&b"*3\r\n$1\r\na\r\n$1\r\nb\r\n$1\r\nc\r\n"[..]);
+        let cmd = decode(&mut frame, &mut src).unwrap();
+        assert_eq!(cmd, Some(vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]));
+
+        let mut frame = with_limits(16, 3, usize::MAX);
+        let err = decode_err(&mut frame, b"*4\r\n");
+        assert_protocol_error(err, "invalid multibulk length");
+    }
+
+    #[test]
+    fn query_buffer_limit() {
+        // the array header isn't counted once it's parsed, which leaves the 6 bytes of the
+        // bulk string header and the bytes of the value received so far
+        let input = b"*1\r\n$100\r\naaaaaaaaaaaaaaaaaaaa";
+
+        let mut frame = with_limits(1024, 8, 26);
+        let mut src = BytesMut::from(&input[..]);
+        assert_eq!(decode(&mut frame, &mut src).unwrap(), None);
+
+        let mut frame = with_limits(1024, 8, 25);
+        match decode_err(&mut frame, input) {
+            FrameError::QueryBufferLimit(size) => assert_eq!(size, 26),
+            err => panic!("expected the query buffer limit error, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn malformed_lengths() {
+        let cases: [(&[u8], &str); 6] = [
+            (b"*abc\r\n", "invalid multibulk length"),
+            (b"*-1\r\n", "invalid multibulk length"),
+            (b"*\r\n", "invalid multibulk length"),
+            (b"*1\r\n$abc\r\n", "invalid bulk length"),
+            (b"*1\r\n$-1\r\n", "invalid bulk length"),
+            (b"*1\r\n:1\n", "line is not terminated by CRLF"),
+        ];
+
+        for (input, msg) in cases {
+            let err = decode_err(&mut RespCommandFrame::new(), input);
+            assert_protocol_error(err, msg);
+        }
+
+        let err = decode_err(&mut RespCommandFrame::new(), b"*1\r\n#3\r\n");
+        assert_protocol_error(err, "expected '$', got '#'");
+    }
+
+    #[test]
+    fn too_big_length_prefixes() {
+        let mut input = b"*".to_vec();
+        input.resize(INLINE_MAX_SIZE + 2, b'1');
+        let err = decode_err(&mut RespCommandFrame::new(), &input);
+        assert_protocol_error(err, "too big mbulk count string");
+
+        let mut input = b"*1\r\n$".to_vec();
+        input.resize(INLINE_MAX_SIZE + 8, b'1');
+        let err = decode_err(&mut RespCommandFrame::new(), &input);
+        assert_protocol_error(err, "too big bulk count string");
+    }
+
+    #[test]
+    fn bulk_string_without_crlf() {
+        let err = decode_err(&mut RespCommandFrame::new(), b"*1\r\n$4\r\nPINGXY");
+        assert_protocol_error(err, "expected CRLF");
+
+        let err = decode_err(&mut RespCommandFrame::new(), b"*1\r\n$4\r\nPING\n\r");
+        assert_protocol_error(err, "expected CRLF");
+    }
+
+    #[test]
+    fn resumes_decoding_across_calls() {
+        let input = b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$11\r\nsome\r\nvalue\r\n";
+        let mut frame = RespCommandFrame::new();
+        let mut src = BytesMut::new();
+
+        for (i, b) in input.iter().enumerate() {
+            src.put_u8(*b);
+            let cmd = decode(&mut frame, &mut src).unwrap();
+
+            if i < input.len() - 1 {
+                assert_eq!(cmd, None, "decoded a command after {} bytes", i + 1);
+            } else {
+                let args = [b"SET".to_vec(), b"key".to_vec(), b"some\r\nvalue".to_vec()];
+                assert_eq!(cmd, Some(args.to_vec()));
+            }
+        }
+        assert!(src.is_empty());
+    }
+
+    /// Bytes which the random arguments are made of. The RESP markers are overrepresented,
+    /// so that the mutated inputs are likely to hit the edge cases of the decoder.
+    const FUZZ_ALPHABET: &[u8] = b"*$+:-0123456789\r\n \"'\\xabcXYZ\0\xff";
+
+    /// Builds a random pipeline of valid commands, along with the arguments of each command
+    /// as the decoder should return them.
+    fn random_pipeline(rng: &mut StdRng) -> (Vec<u8>, Vec<Vec<Vec<u8>>>) {
+        let mut input = vec![];
+        let mut cmds = vec![];
+
+        for _ in 0..rng.gen_range(1..6) {
+            match rng.gen_range(0..10) {
+                // an inline command
+                0 | 1 => {
+                    let args: Vec<Vec<u8>> = (0..rng.gen_range(1..4))
+                        .map(|_| random_bytes(rng, b"abcXYZ0123456789", 1..8))
+                        .collect();
+                    input.extend_from_slice(&args.join(&b' '));
+                    input.extend_from_slice(if rng.gen_bool(0.5) { b"\r\n" } else { b"\n" });
+                    cmds.push(args);
+                }
+                // the empty arrays and blank lines are skipped
+                2 => input.extend_from_slice(if rng.gen_bool(0.5) {
+                    b"*0\r\n"
+                } else {
+                    b"\r\n"
+                }),
+                _ => {
+                    let args: Vec<Vec<u8>> = (0..rng.gen_range(1..5))
+                        .map(|_| random_bytes(rng, FUZZ_ALPHABET, 0..16))
+                        .collect();
+                    input.extend_from_slice(format!("*{}\r\n", args.len()).as_bytes());
+                    for arg in args.iter() {
+                        // simple strings and integers can be sent instead of bulk strings
+                        if !arg.is_empty()
+                            && !arg.contains(&b'\r')
+                            && !arg.contains(&b'\n')
+                            && rng.gen_bool(0.2)
+                        {
+                            input.push(if rng.gen_bool(0.5) { b'+' } else { b':' });
+                            input.extend_from_slice(arg);
+                        } else {
+                            input.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+                            input.extend_from_slice(arg);
+                        }
+                        input.extend_from_slice(b"\r\n");
+                    }
+                    cmds.push(args);
+                }
+            }
+        }
+
+        (input, cmds)
+    }
+
+    fn random_bytes(rng: &mut StdRng, alphabet: &[u8], len: std::ops::Range<usize>) -> Vec<u8> {
+        (0..rng.gen_range(len))
+            .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+            .collect()
+    }
+
+    /// Splits the input into chunks of random lengths, as they may be read from the socket.
+    fn random_chunks<'a>(rng: &mut StdRng, input: &'a [u8]) -> Vec<&'a [u8]> {
+        let mut chunks = vec![];
+        let mut rest = input;
+        while !rest.is_empty() {
+            let (chunk, remaining) = rest.split_at(rng.gen_range(1..=rest.len().min(32)));
+            chunks.push(chunk);
+            rest = remaining;
+        }
+
+        chunks
+    }
+
+    /// Decodes the chunks one by one, as they're received, and returns the decoded commands.
+    fn decode_chunks(frame: &mut RespCommandFrame, chunks: &[&[u8]]) -> Vec<Vec<Vec<u8>>> {
+        let mut src = BytesMut::new();
+        let mut cmds = vec![];
+
+        for chunk in chunks {
+            src.extend_from_slice(chunk);
+            while let Some(cmd) = decode(frame, &mut src).unwrap() {
+                cmds.push(cmd);
+            }
+        }
+        assert!(src.is_empty());
+        assert!(frame.cmd_builder.is_none());
+
+        cmds
+    }
+
+    #[test]
+    fn fuzz_split_points_of_pipelines() {
+        let mut rng = StdRng::seed_from_u64(49);
+
+        for _ in 0..200 {
+            let (input, expected) = random_pipeline(&mut rng);
+
+            // every prefix of the pipeline is decoded, with the rest received later
+            for split in 0..=input.len() {
+                let chunks = [&input[..split], &input[split..]];
+                let cmds = decode_chunks(&mut RespCommandFrame::new(), &chunks);
+                assert_eq!(cmds, expected, "split at {} of {:?}", split, input);
+            }
+
+            for _ in 0..10 {
+                let chunks = random_chunks(&mut rng, &input);
+                let cmds = decode_chunks(&mut RespCommandFrame::new(), &chunks);
+                assert_eq!(cmds, expected, "chunks {:?}", chunks);
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_random_and_mutated_input() {
+        let mut rng = StdRng::seed_from_u64(49);
+        let limits = FrameLimits {
+            max_bulk_len: 8,
+            max_multibulk_len: 4,
+            query_buffer_limit: 64,
+        };
+
+        for _ in 0..20_000 {
+            let input = match rng.gen_bool(0.5) {
+                true => random_bytes(&mut rng, FUZZ_ALPHABET, 0..128),
+                false => {
+                    let (mut input, _) = random_pipeline(&mut rng);
+                    for _ in 0..rng.gen_range(1..4) {
+                        let pos = rng.gen_range(0..=input.len());
+                        let byte = FUZZ_ALPHABET[rng.gen_range(0..FUZZ_ALPHABET.len())];
+                        match rng.gen_range(0..3) {
+                            0 => input.insert(pos, byte),
+                            _ if pos == input.len() => {}
+                            1 => input[pos] = byte,
+                            _ => {
+                                input.remove(pos);
+                            }
+                        }
+                    }
+                    input
+                }
+            };
+
+            let mut frame = RespCommandFrame::with_limits(limits);
+            let mut src = BytesMut::new();
+            'chunks: for chunk in random_chunks(&mut rng, &input) {
+                src.extend_from_slice(chunk);
+                loop {
+                    match frame.decode(&mut src) {
+                        Ok(Some(_)) => {}
+                        Ok(None) => {
+                            let buffered =
+                                src.len() + frame.cmd_builder.as_ref().map_or(0, |b| b.size);
+                            assert!(
+                                buffered <= limits.query_buffer_limit,
+                                "buffered {} bytes of {:?}",
+                                buffered,
+                                input
+                            );
+                            break;
+                        }
+                        // the connection is closed on errors
+                        Err(err) => {
+                            let err = err.into_inner().expect("expected a frame error");
+                            assert!(err.is::<FrameError>(), "unexpected error {:?}", err);
+                            break 'chunks;
+                        }
+                    }
+                }
+            }
+        }
     }
 }
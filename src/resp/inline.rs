@@ -0,0 +1,138 @@
+use bytes::Bytes;
+
+use super::{types::RespType, RespError};
+
+/// Splits an inline command into its arguments, which are returned as bulk strings.
+///
+/// Inline commands are sent as a single line of space separated arguments (e.g. `SET key
+/// value`), which is convenient when typing the commands by hand, over `telnet` or `nc`. The
+/// arguments are split the same way as Redis does:
+///
+/// - Arguments are separated by one or more whitespace characters.
+/// - An argument in double quotes can contain whitespace, and the escape sequences `\n`, `\r`,
+///   `\t`, `\b`, `\a`, `\\`, `\"` and `\xHH` (a hex encoded byte).
+/// - An argument in single quotes can contain whitespace, and `\'` as the only escape sequence.
+/// - A closing quote must be followed by whitespace or the end of the line.
+///
+/// # Arguments
+///
+/// * `line` - The inline command, without the line terminator.
+///
+/// # Returns
+///
+/// * `Ok(Vec<RespType>)` - The arguments of the command as `BulkString`s. It's empty if the
+///   line is blank.
+/// * `Err(RespError)` - If the quotes in the line are unbalanced.
+pub fn parse_inline_command(line: &[u8]) -> Result<Vec<RespType>, RespError> {
+    let mut args = vec![];
+    let mut i = 0;
+
+    loop {
+        // skip the whitespace between the arguments
+        while i < line.len() && line[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i == line.len() {
+            return Ok(args);
+        }
+
+        let mut arg = vec![];
+        match line[i] {
+            b'"' => i = read_double_quoted(line, i + 1, &mut arg)?,
+            b'\'' => i = read_single_quoted(line, i + 1, &mut arg)?,
+            _ => {
+                while i < line.len() && !line[i].is_ascii_whitespace() {
+                    arg.push(line[i]);
+                    i += 1;
+                }
+            }
+        }
+
+        args.push(RespType::BulkString(Bytes::from(arg)));
+    }
+}
+
+/// Reads an argument in double quotes, starting after the opening quote.
+///
+/// # Returns
+///
+/// The position after the closing quote.
+fn read_double_quoted(line: &[u8], mut i: usize, arg: &mut Vec<u8>) -> Result<usize, RespError> {
+    loop {
+        match line.get(i) {
+            None => return Err(unbalanced_quotes()),
+            Some(b'"') => return closing_quote(line, i),
+            Some(b'\\') if i + 3 < line.len() && line[i + 1] == b'x' => {
+                match (hex_digit(line[i + 2]), hex_digit(line[i + 3])) {
+                    (Some(high), Some(low)) => {
+                        arg.push(high << 4 | low);
+                        i += 4;
+                    }
+                    _ => {
+                        arg.push(b'x');
+                        i += 2;
+                    }
+                }
+            }
+            Some(b'\\') if i + 1 < line.len() => {
+                arg.push(match line[i + 1] {
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    b'b' => 0x08,
+                    b'a' => 0x07,
+                    c => c,
+                });
+                i += 2;
+            }
+            Some(&c) => {
+                arg.push(c);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Reads an argument in single quotes, starting after the opening quote.
+///
+/// # Returns
+///
+/// The position after the closing quote.
+fn read_single_quoted(line: &[u8], mut i: usize, arg: &mut Vec<u8>) -> Result<usize, RespError> {
+    loop {
+        match line.get(i) {
+            None => return Err(unbalanced_quotes()),
+            Some(b'\'') => return closing_quote(line, i),
+            Some(b'\\') if line.get(i + 1) == Some(&b'\'') => {
+                arg.push(b'\'');
+                i += 2;
+            }
+            Some(&c) => {
+                arg.push(c);
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Checks that the closing quote at the given position is followed by whitespace or the end
+/// of the line.
+///
+/// # Returns
+///
+/// The position after the closing quote.
+fn closing_quote(line: &[u8], i: usize) -> Result<usize, RespError> {
+    match line.get(i + 1) {
+        Some(c) if !c.is_ascii_whitespace() => Err(unbalanced_quotes()),
+        _ => Ok(i + 1),
+    }
+}
+
+/// Returns the value of a hex digit.
+fn hex_digit(c: u8) -> Option<u8> {
+    (c as char).to_digit(16).map(|d| d as u8)
+}
+
+fn unbalanced_quotes() -> RespError {
+    RespError::InvalidInlineCommand(String::from("unbalanced quotes in request"))
+}
@@ -1,4 +1,5 @@
 pub mod frame;
+mod inline;
 pub mod types;
 
 /// Represents errors that can occur during RESP parsing.
@@ -10,6 +11,8 @@ pub enum RespError {
     InvalidSimpleString(String),
     /// Represents an error in parsing an array, with an error message.
     InvalidArray(String),
+    /// Represents an error in parsing an inline command, with an error message.
+    InvalidInlineCommand(String),
     /// Represents any other error with a descriptive message.
     Other(String),
 }
@@ -21,6 +24,7 @@ impl std::fmt::Display for RespError {
             RespError::InvalidBulkString(msg) => msg.as_str().fmt(f),
             RespError::InvalidSimpleString(msg) => msg.as_str().fmt(f),
             RespError::InvalidArray(msg) => msg.as_str().fmt(f),
+            RespError::InvalidInlineCommand(msg) => msg.as_str().fmt(f),
         }
     }
 }
@@ -11,84 +11,146 @@ pub enum RespType {
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#simple-strings>
     SimpleString(String),
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#bulk-strings>
-    BulkString(String),
+    ///
+    /// Bulk strings are binary safe, hence the raw bytes are stored instead of an UTF-8 string.
+    BulkString(Bytes),
     /// Null representation in RESP2. It's simply a BulkString with length of negative one (-1).
     NullBulkString,
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#arrays>
     Array(Vec<RespType>),
+    /// Null array representation in RESP2. It's an Array with length of negative one (-1).
+    NullArray,
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#simple-errors>
     SimpleError(String),
     /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#integers>
     Integer(i64),
-}
-
-impl RespType {
-    /// Parse the given bytes into a BulkString RESP value. This will return the parsed RESP
-    /// value and the number of bytes read from the buffer.
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#nulls>
     ///
-    /// Example BulkString: `$5\r\nhello\r\n`
+    /// Null in RESP3. It's sent as a `NullBulkString` in RESP2.
+    Null,
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#booleans>
     ///
-    /// # BulkString Parts:
-    /// ```
-    ///     $      |            5           | \r\n |    hello     | \r\n
-    /// identifier | string length in bytes | CRLF | string value | CRLF
-    /// ```
+    /// Sent as an `Integer` (`1` or `0`) in RESP2.
+    Boolean(bool),
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#doubles>
     ///
-    /// # Parsing Logic:
-    /// - The buffer is read until CRLF characters ("\r\n") are encountered.
-    /// - That slice of bytes are then parsed into an int. That will be the string length in bytes (let's say `bulkstr_len`)
-    /// - `bulkstr_len` number of bytes are read from the buffer again from where it was stopped previously.
-    /// - This 2nd slice of bytes is then parsed into an UTF-8 string.
+    /// Sent as a `BulkString` in RESP2.
+    Double(f64),
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#big-numbers>
     ///
-    /// Note: The first byte in the buffer is skipped since it's just an identifier for the
-    /// RESP type and is not the part of the actual value itself.
-    pub fn new_bulk_string(buffer: BytesMut) -> Result<(RespType, usize), RespError> {
-        let (bulkstr_len, bytes_consumed) =
-            if let Some((buf_data, len)) = Self::read_till_crlf(&buffer[1..]) {
-                let bulkstr_len = Self::parse_usize_from_buf(buf_data)?;
-                (bulkstr_len, len + 1)
-            } else {
-                return Err(RespError::InvalidBulkString(String::from(
-                    "Invalid value for bulk string",
-                )));
-            };
+    /// The digits of the number (with an optional sign). Sent as a `BulkString` in RESP2.
+    BigNumber(String),
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#verbatim-strings>
+    ///
+    /// The 3 character format of the text (like `txt` or `mkd`), and the text. Only the text
+    /// is sent as a `BulkString` in RESP2.
+    VerbatimString(String, Bytes),
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#maps>
+    ///
+    /// Sent as an `Array` of each key followed by its value in RESP2.
+    Map(Vec<(RespType, RespType)>),
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#sets>
+    ///
+    /// Sent as an `Array` in RESP2.
+    Set(Vec<RespType>),
+    /// Refer <https://redis.io/docs/latest/develop/reference/protocol-spec/#pushes>
+    ///
+    /// Out-of-band data, like the messages published to the subscribed channels. Sent as an
+    /// `Array` in RESP2.
+    Push(Vec<RespType>),
+}
 
-        let bulkstr_end_idx = bytes_consumed + bulkstr_len;
-        if bulkstr_end_idx >= buffer.len() {
-            return Err(RespError::InvalidBulkString(String::from(
-                "Invalid value for bulk string length",
-            )));
-        }
-        let bulkstr = String::from_utf8(buffer[bytes_consumed..bulkstr_end_idx].to_vec());
+/// The versions of RESP. The version of a connection is switched by the HELLO command.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ProtocolVersion {
+    /// The default version, which supports simple/bulk strings, arrays, integers and errors.
+    #[default]
+    Resp2,
+    /// Adds maps, sets, doubles, booleans, nulls, big numbers, verbatim strings and pushes.
+    Resp3,
+}
 
-        match bulkstr {
-            Ok(bs) => Ok((RespType::BulkString(bs), bulkstr_end_idx + 2)),
-            Err(_) => Err(RespError::InvalidBulkString(String::from(
-                "Bulk string value is not a valid UTF-8 string",
-            ))),
-        }
+impl RespType {
+    /// Convert the RESP value into its byte values, as per RESP2.
+    pub fn to_bytes(&self) -> Bytes {
+        self.to_bytes_with(ProtocolVersion::Resp2)
     }
 
-    /// Convert the RESP value into its byte values.
-    pub fn to_bytes(&self) -> Bytes {
-        return match self {
+    /// Convert the RESP value into its byte values, as per the given version of RESP. The
+    /// RESP3 types are sent as their closest RESP2 types if the version is RESP2, and the
+    /// RESP2 nulls are sent as the RESP3 null if the version is RESP3.
+    pub fn to_bytes_with(&self, version: ProtocolVersion) -> Bytes {
+        let resp3 = version == ProtocolVersion::Resp3;
+        match self {
             RespType::SimpleString(ss) => Bytes::from_iter(format!("+{}\r\n", ss).into_bytes()),
             RespType::BulkString(bs) => {
-                let bulkstr_bytes = format!("${}\r\n{}\r\n", bs.chars().count(), bs).into_bytes();
-                Bytes::from_iter(bulkstr_bytes)
+                let mut bulkstr_bytes = format!("${}\r\n", bs.len()).into_bytes();
+                bulkstr_bytes.extend_from_slice(bs);
+                bulkstr_bytes.extend_from_slice(b"\r\n");
+                Bytes::from(bulkstr_bytes)
             }
-            RespType::NullBulkString => Bytes::from("$-1\r\n"),
-            RespType::Array(arr) => {
-                let mut arr_bytes = format!("*{}\r\n", arr.len()).into_bytes();
-                arr.iter()
-                    .map(|v| v.to_bytes())
-                    .for_each(|b| arr_bytes.extend(b));
-
-                Bytes::from_iter(arr_bytes)
+            RespType::NullBulkString | RespType::NullArray | RespType::Null if resp3 => {
+                Bytes::from("_\r\n")
             }
+            RespType::NullBulkString | RespType::Null => Bytes::from("$-1\r\n"),
+            RespType::Array(arr) => Self::aggregate_to_bytes('*', arr.iter(), arr.len(), version),
+            RespType::NullArray => Bytes::from("*-1\r\n"),
             RespType::SimpleError(es) => Bytes::from_iter(format!("-{}\r\n", es).into_bytes()),
             RespType::Integer(i) => Bytes::from_iter(format!(":{}\r\n", i).into_bytes()),
-        };
+            RespType::Boolean(b) if resp3 => Bytes::from(if *b { "#t\r\n" } else { "#f\r\n" }),
+            RespType::Boolean(b) => RespType::Integer(*b as i64).to_bytes(),
+            RespType::Double(d) if resp3 => {
+                Bytes::from_iter(format!(",{}\r\n", format_double(*d)).into_bytes())
+            }
+            RespType::Double(d) => RespType::BulkString(Bytes::from(format_double(*d))).to_bytes(),
+            RespType::BigNumber(n) if resp3 => Bytes::from_iter(format!("({}\r\n", n).into_bytes()),
+            RespType::BigNumber(n) => RespType::BulkString(Bytes::from(n.clone())).to_bytes(),
+            RespType::VerbatimString(format, text) if resp3 => {
+                let mut verbatim_bytes =
+                    format!("={}\r\n{}:", format.len() + 1 + text.len(), format).into_bytes();
+                verbatim_bytes.extend_from_slice(text);
+                verbatim_bytes.extend_from_slice(b"\r\n");
+                Bytes::from(verbatim_bytes)
+            }
+            RespType::VerbatimString(_, text) => RespType::BulkString(text.clone()).to_bytes(),
+            RespType::Map(entries) if resp3 => Self::aggregate_to_bytes(
+                '%',
+                entries.iter().flat_map(|(k, v)| [k, v]),
+                entries.len(),
+                version,
+            ),
+            RespType::Map(entries) => Self::aggregate_to_bytes(
+                '*',
+                entries.iter().flat_map(|(k, v)| [k, v]),
+                entries.len() * 2,
+                version,
+            ),
+            RespType::Set(elems) if resp3 => {
+                Self::aggregate_to_bytes('~', elems.iter(), elems.len(), version)
+            }
+            RespType::Push(elems) if resp3 => {
+                Self::aggregate_to_bytes('>', elems.iter(), elems.len(), version)
+            }
+            RespType::Set(elems) | RespType::Push(elems) => {
+                Self::aggregate_to_bytes('*', elems.iter(), elems.len(), version)
+            }
+        }
+    }
+
+    // Convert the elements of an aggregate type (like an array or a map) into bytes, prefixed
+    // with the identifier of the type and the number of elements.
+    fn aggregate_to_bytes<'a>(
+        identifier: char,
+        elems: impl Iterator<Item = &'a RespType>,
+        len: usize,
+        version: ProtocolVersion,
+    ) -> Bytes {
+        let mut aggregate_bytes = format!("{}{}\r\n", identifier, len).into_bytes();
+        elems
+            .map(|v| v.to_bytes_with(version))
+            .for_each(|b| aggregate_bytes.extend(b));
+
+        Bytes::from(aggregate_bytes)
     }
 
     /// Parses the length of a RESP array from the given byte buffer.
@@ -98,7 +160,7 @@ impl RespType {
     ///
     /// # Arguments
     ///
-    /// * `src` - A `BytesMut` containing the bytes to parse.
+    /// * `src` - The bytes to parse.
     ///
     /// # Returns
     ///
@@ -107,8 +169,8 @@ impl RespType {
     ///   - The number of bytes read from the input
     /// * `Ok(None)` - If there's not enough data in the buffer to parse the length
     /// * `Err(RespError)` - If the input is not a valid RESP array prefix or if parsing fails
-    pub fn parse_array_len(src: BytesMut) -> Result<Option<(usize, usize)>, RespError> {
-        let (array_prefix_bytes, bytes_read) = match Self::read_till_crlf(&src[..]) {
+    pub fn parse_array_len(src: &[u8]) -> Result<Option<(usize, usize)>, RespError> {
+        let (array_prefix_bytes, bytes_read) = match Self::read_till_crlf(src) {
             Some((b, size)) => (b, size),
             None => return Ok(None),
         };
@@ -132,7 +194,7 @@ impl RespType {
     ///
     /// # Arguments
     ///
-    /// * `src` - A `BytesMut` containing the bytes to parse.
+    /// * `src` - The bytes to parse.
     ///
     /// # Returns
     ///
@@ -142,8 +204,8 @@ impl RespType {
     /// * `Ok(None)` - If there's not enough data in the buffer to parse the length
     /// * `Err(RespError)` - If the input is not a valid RESP bulk string prefix or if parsing fails
     ///
-    pub fn parse_bulk_string_len(src: BytesMut) -> Result<Option<(usize, usize)>, RespError> {
-        let (bulkstr_prefix_bytes, bytes_read) = match Self::read_till_crlf(&src[..]) {
+    pub fn parse_bulk_string_len(src: &[u8]) -> Result<Option<(usize, usize)>, RespError> {
+        let (bulkstr_prefix_bytes, bytes_read) = match Self::read_till_crlf(src) {
             Some((b, size)) => (b, size),
             None => return Ok(None),
         };
@@ -177,7 +239,7 @@ impl RespType {
     /// Example SimpleString: `+OK\r\n`
     ///
     /// # SimpleString Parts:
-    /// ```
+    /// ```text
     ///      +      |      OK      | \r\n
     ///  identifier | string value | CRLF
     /// ```
@@ -209,7 +271,7 @@ impl RespType {
 
     // Parse an integer from bytes
     fn parse_usize_from_buf(buf: &[u8]) -> Result<usize, RespError> {
-        let utf8_str = String::from_utf8(buf.to_vec());
+        let utf8_str = std::str::from_utf8(buf);
         match utf8_str {
             Ok(s) => {
                 let int = s.parse::<usize>();
@@ -224,3 +286,13 @@ impl RespType {
         }
     }
 }
+
+/// Formats a double the way RESP3 expects it, which is also used for the bulk strings in
+/// RESP2. Infinities are sent as `inf` and `-inf`.
+fn format_double(d: f64) -> String {
+    if d.is_nan() {
+        String::from("nan")
+    } else {
+        d.to_string()
+    }
+}
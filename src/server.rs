@@ -5,8 +5,13 @@ use tokio::{net::TcpStream, sync::OwnedSemaphorePermit};
 use tokio_util::codec::Framed;
 
 use crate::{
-    handler::FrameHandler, replication::Replication, resp::frame::RespCommandFrame,
+    handler::FrameHandler,
+    migration::MigrationPool,
+    pubsub::PubSub,
+    replication::Replication,
+    resp::frame::{FrameLimits, RespCommandFrame},
     storage::db::Storage,
+    tracking::Tracking,
 };
 
 /// Represents a TCP server that listens for and handles RESP commands.
@@ -16,27 +21,67 @@ pub struct Server {
     storage: Arc<Storage>,
     /// Contains the replication info.
     replication: Arc<Replication>,
+    /// Contains the cached connections used by MIGRATE.
+    migration: Arc<MigrationPool>,
+    /// Contains the subscriptions of all the clients.
+    pubsub: Arc<PubSub>,
+    /// Contains the keys cached by the clients.
+    tracking: Arc<Tracking>,
+    /// The password of the default user, if any.
+    requirepass: Option<Arc<str>>,
+    /// The limits on the commands received from the clients.
+    frame_limits: FrameLimits,
 }
 
 impl Server {
     /// Creates a new `Server` instance.
-    pub fn new(storage: Arc<Storage>, replication: Arc<Replication>) -> Server {
+    pub fn new(
+        storage: Arc<Storage>,
+        replication: Arc<Replication>,
+        pubsub: Arc<PubSub>,
+        tracking: Arc<Tracking>,
+        requirepass: Option<String>,
+        frame_limits: FrameLimits,
+    ) -> Server {
         Server {
             storage,
             replication,
+            migration: Arc::new(MigrationPool::new()),
+            pubsub,
+            tracking,
+            requirepass: requirepass.map(Arc::from),
+            frame_limits,
         }
     }
 
     /// Reads the Nimblecache commands as tokio-util frames from the incoming TCP stream,
     /// and handle them in a separate Tokio async task.
     pub async fn handle_commands(&mut self, sock: TcpStream, permit: OwnedSemaphorePermit) {
-        let db = self.storage.as_ref().db().clone();
+        let storage = Arc::clone(&self.storage);
         let replication = Arc::clone(&self.replication);
-        let resp_command_frame = Framed::with_capacity(sock, RespCommandFrame::new(), 8 * 1024);
+        let migration = Arc::clone(&self.migration);
+        let pubsub = Arc::clone(&self.pubsub);
+        let tracking = Arc::clone(&self.tracking);
+        let requirepass = self.requirepass.clone();
+        let resp_command_frame = Framed::with_capacity(
+            sock,
+            RespCommandFrame::with_limits(self.frame_limits),
+            8 * 1024,
+        );
 
         tokio::spawn(async move {
             let handler = FrameHandler::new(resp_command_frame);
-            if let Err(e) = handler.handle(db.as_ref(), replication.as_ref()).await {
+            if let Err(e) = handler
+                .handle(
+                    storage.as_ref(),
+                    replication.as_ref(),
+                    migration.as_ref(),
+                    &pubsub,
+                    tracking.as_ref(),
+                    requirepass.as_deref(),
+                )
+                .await
+            {
                 error!("Failed to handle command: {}", e);
             }
 
@@ -0,0 +1,484 @@
+use super::{
+    db::{Keyspace, Value, DB},
+    notify::NotifyClass,
+    DBError,
+};
+
+/// Maximum number of bits which can be addressed in a string value (512 MB).
+pub const MAX_BIT_OFFSET: u64 = 4 * 1024 * 1024 * 1024;
+
+/// The unit in which the start and end indices of a bit range are specified.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BitRangeUnit {
+    /// Indices are byte offsets.
+    Byte,
+    /// Indices are bit offsets.
+    Bit,
+}
+
+/// Represents an inclusive range within a string value. Negative indices are counted from the
+/// end of the string, where -1 is the last byte (or bit).
+#[derive(Debug, Clone, Copy)]
+pub struct BitRange {
+    pub start: i64,
+    /// End index. `None` means the range extends till the end of the string.
+    pub end: Option<i64>,
+    pub unit: BitRangeUnit,
+}
+
+/// Bitwise operations supported by the BITOP command.
+#[derive(Debug, Clone, Copy)]
+pub enum BitOp {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+/// Integer type of a BITFIELD field. `bits` can range from 1 to 64 for signed integers and
+/// from 1 to 63 for unsigned integers.
+#[derive(Debug, Clone, Copy)]
+pub struct BitfieldType {
+    pub signed: bool,
+    pub bits: u8,
+}
+
+/// Behaviour of BITFIELD SET and INCRBY operations when the result doesn't fit in the field.
+#[derive(Debug, Clone, Copy)]
+pub enum BitfieldOverflow {
+    /// Wrap around, both for signed and unsigned integers.
+    Wrap,
+    /// Saturate to the minimum or maximum value of the field.
+    Sat,
+    /// Do not perform the operation, and return nil.
+    Fail,
+}
+
+/// Operations supported by the BITFIELD command. `offset` is the bit offset of the field.
+#[derive(Debug, Clone, Copy)]
+pub enum BitfieldOp {
+    Get {
+        ty: BitfieldType,
+        offset: u64,
+    },
+    Set {
+        ty: BitfieldType,
+        offset: u64,
+        value: i64,
+        overflow: BitfieldOverflow,
+    },
+    IncrBy {
+        ty: BitfieldType,
+        offset: u64,
+        incr: i64,
+        overflow: BitfieldOverflow,
+    },
+}
+
+impl DB {
+    /// Sets or clears the bit at offset in the string value stored at key. The string is grown
+    /// (and padded with zeros) if the offset is beyond the current length of the string.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the string is stored.
+    ///
+    /// * `offset` - Offset of the bit.
+    ///
+    /// * `bit` - The bit value to be set (0 or 1).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u8)` - The original bit value stored at offset.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn setbit(&self, k: &str, offset: u64, bit: u8) -> Result<u8, DBError> {
+        self.write(|keyspace| {
+            let bytes = string_value_mut(keyspace, k)?;
+            grow_to_fit(bytes, offset, 1);
+            let old_bit = set_bit(bytes, offset, bit);
+            keyspace.notify(NotifyClass::String, "setbit", k);
+
+            Ok(old_bit)
+        })
+    }
+
+    /// Returns the bit value at offset in the string value stored at key.
+    /// Offsets beyond the string length (or a missing key) are considered as zero bits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u8)` - The bit value stored at offset.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn getbit(&self, k: &str, offset: u64) -> Result<u8, DBError> {
+        self.read(|keyspace| Ok(get_bit(string_value(keyspace, k)?, offset)))
+    }
+
+    /// Counts the number of set bits in the string value stored at key. The count can be limited
+    /// to a range of bytes or bits.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Number of bits set to 1.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn bitcount(&self, k: &str, range: Option<BitRange>) -> Result<u64, DBError> {
+        self.read(|keyspace| {
+            let bytes = string_value(keyspace, k)?;
+            let range = match range {
+                Some(r) => r,
+                None => BitRange {
+                    start: 0,
+                    end: None,
+                    unit: BitRangeUnit::Byte,
+                },
+            };
+
+            match bit_range(bytes.len(), &range) {
+                Some((start, end)) => Ok(count_bits(bytes, start, end)),
+                None => Ok(0),
+            }
+        })
+    }
+
+    /// Returns the position of the first bit set to 1 or 0 in the string value stored at key.
+    /// The search can be limited to a range of bytes or bits.
+    ///
+    /// When looking for clear bits without specifying an end index, the string is considered
+    /// to be padded with zeros on the right. Hence the position of the bit right after the
+    /// string is returned if all the bits in the string are set.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(i64)` - Position of the first matching bit, or -1 if it's not found.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn bitpos(&self, k: &str, bit: u8, range: Option<BitRange>) -> Result<i64, DBError> {
+        self.read(|keyspace| {
+            let bytes = match keyspace.get(k) {
+                Some(Value::String(s)) => s,
+                Some(_) => return Err(DBError::WrongType),
+                None => return Ok(if bit == 1 { -1 } else { 0 }),
+            };
+
+            let end_given = range.is_some_and(|r| r.end.is_some());
+            let range = match range {
+                Some(r) => r,
+                None => BitRange {
+                    start: 0,
+                    end: None,
+                    unit: BitRangeUnit::Byte,
+                },
+            };
+
+            let (start, end) = match bit_range(bytes.len(), &range) {
+                Some(r) => r,
+                None => return Ok(-1),
+            };
+
+            match find_bit(bytes, bit, start, end) {
+                Some(pos) => Ok(pos as i64),
+                None if bit == 0 && !end_given => Ok(end as i64 + 1),
+                None => Ok(-1),
+            }
+        })
+    }
+
+    /// Performs a bitwise operation between the string values stored at the given keys and
+    /// stores the result in the destination key. Missing keys are considered as a stream of
+    /// zero bytes, and shorter strings are zero padded to the length of the longest string.
+    /// If the result is an empty string, the destination key is deleted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Length of the string stored in the destination key.
+    /// * `Err(DBError)` - if any of the source keys has non-string data.
+    pub fn bitop(&self, op: BitOp, dest: &str, keys: &[String]) -> Result<usize, DBError> {
+        self.write(|keyspace| {
+            let mut sources: Vec<&[u8]> = vec![];
+            for k in keys {
+                sources.push(string_value(keyspace, k)?);
+            }
+
+            let result_len = sources.iter().map(|s| s.len()).max().unwrap_or(0);
+            let mut result: Vec<u8> = vec![0; result_len];
+
+            for (i, byte) in result.iter_mut().enumerate() {
+                let mut src_bytes = sources.iter().map(|s| *s.get(i).unwrap_or(&0));
+                let first = src_bytes.next().unwrap_or(0);
+                *byte = match op {
+                    BitOp::And => src_bytes.fold(first, |acc, b| acc & b),
+                    BitOp::Or => src_bytes.fold(first, |acc, b| acc | b),
+                    BitOp::Xor => src_bytes.fold(first, |acc, b| acc ^ b),
+                    BitOp::Not => !first,
+                };
+            }
+
+            if result.is_empty() {
+                if keyspace.remove(dest).is_some() {
+                    keyspace.notify(NotifyClass::Generic, "del", dest);
+                }
+            } else {
+                keyspace.insert(dest, Value::String(result));
+                keyspace.notify(NotifyClass::String, "set", dest);
+            }
+
+            Ok(result_len)
+        })
+    }
+
+    /// Performs the list of BITFIELD operations on the string value stored at key, in the
+    /// given order. The key is not created if only GET operations are specified.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<i64>>)` - Result of each operation. The result is `None` for the SET
+    ///   and INCRBY operations which failed due to an overflow, when FAIL overflow behaviour is used.
+    /// * `Err(DBError)` - if key already exists and has non-string data.
+    pub fn bitfield(&self, k: &str, ops: &[BitfieldOp]) -> Result<Vec<Option<i64>>, DBError> {
+        let has_writes = ops.iter().any(|op| !matches!(op, BitfieldOp::Get { .. }));
+        if !has_writes {
+            return self.read(|keyspace| {
+                let bytes = string_value(keyspace, k)?;
+                Ok(ops
+                    .iter()
+                    .map(|op| match op {
+                        BitfieldOp::Get { ty, offset } => Some(read_field(bytes, *ty, *offset)),
+                        _ => None,
+                    })
+                    .collect())
+            });
+        }
+
+        self.write(|keyspace| {
+            let bytes = string_value_mut(keyspace, k)?;
+            let mut results = vec![];
+
+            for op in ops {
+                let res = match *op {
+                    BitfieldOp::Get { ty, offset } => Some(read_field(bytes, ty, offset)),
+                    BitfieldOp::Set {
+                        ty,
+                        offset,
+                        value,
+                        overflow,
+                    } => {
+                        let old_val = read_field(bytes, ty, offset);
+                        // unsigned values are treated as 64 bit unsigned integers, hence
+                        // negative values overflow to the upper limit of the field.
+                        let new_val = if ty.signed {
+                            value as i128
+                        } else {
+                            value as u64 as i128
+                        };
+                        handle_overflow(ty, new_val, overflow).map(|v| {
+                            grow_to_fit(bytes, offset, ty.bits as u64);
+                            write_field(bytes, ty, offset, v);
+                            old_val
+                        })
+                    }
+                    BitfieldOp::IncrBy {
+                        ty,
+                        offset,
+                        incr,
+                        overflow,
+                    } => {
+                        let old_val = read_field(bytes, ty, offset);
+                        let new_val = old_val as i128 + incr as i128;
+                        handle_overflow(ty, new_val, overflow).inspect(|&v| {
+                            grow_to_fit(bytes, offset, ty.bits as u64);
+                            write_field(bytes, ty, offset, v);
+                        })
+                    }
+                };
+
+                results.push(res);
+            }
+
+            let modified = ops
+                .iter()
+                .zip(results.iter())
+                .any(|(op, res)| !matches!(op, BitfieldOp::Get { .. }) && res.is_some());
+            if modified {
+                keyspace.notify(NotifyClass::String, "setbit", k);
+            }
+
+            Ok(results)
+        })
+    }
+}
+
+impl BitfieldType {
+    /// Returns the minimum and maximum values which can be stored in a field of this type.
+    fn range(&self) -> (i128, i128) {
+        if self.signed {
+            let max = (1i128 << (self.bits - 1)) - 1;
+            (-max - 1, max)
+        } else {
+            (0, (1i128 << self.bits) - 1)
+        }
+    }
+}
+
+/// Returns the string value stored at key, or an empty slice if the key is missing.
+fn string_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<&'a [u8], DBError> {
+    match keyspace.get(k) {
+        Some(Value::String(s)) => Ok(s),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(&[]),
+    }
+}
+
+/// Returns a mutable reference to the string value stored at key. An empty string is
+/// initialized against the key if it's missing.
+fn string_value_mut<'a>(keyspace: &'a mut Keyspace, k: &str) -> Result<&'a mut Vec<u8>, DBError> {
+    match keyspace.get(k) {
+        Some(Value::String(_)) => {}
+        Some(_) => return Err(DBError::WrongType),
+        None => keyspace.insert(k, Value::String(vec![])),
+    }
+
+    match keyspace.get_mut(k) {
+        Some(Value::String(s)) => Ok(s),
+        _ => Err(DBError::Other(String::from(
+            "Failed to initialize string value",
+        ))),
+    }
+}
+
+/// Zero pad the bytes so that `num_bits` bits starting from `offset` can be addressed.
+fn grow_to_fit(bytes: &mut Vec<u8>, offset: u64, num_bits: u64) {
+    let required_len = ((offset + num_bits - 1) / 8 + 1) as usize;
+    if bytes.len() < required_len {
+        bytes.resize(required_len, 0);
+    }
+}
+
+/// Returns the bit at offset. Bit 0 is the most significant bit of the first byte.
+fn get_bit(bytes: &[u8], offset: u64) -> u8 {
+    match bytes.get((offset / 8) as usize) {
+        Some(byte) => (byte >> (7 - (offset % 8))) & 1,
+        None => 0,
+    }
+}
+
+/// Sets the bit at offset and returns the previous bit value. The bytes should already be
+/// long enough to address the offset.
+fn set_bit(bytes: &mut [u8], offset: u64, bit: u8) -> u8 {
+    let byte = &mut bytes[(offset / 8) as usize];
+    let mask = 1u8 << (7 - (offset % 8));
+    let old_bit = (*byte & mask != 0) as u8;
+
+    if bit == 1 {
+        *byte |= mask;
+    } else {
+        *byte &= !mask;
+    }
+
+    old_bit
+}
+
+/// Converts the range into inclusive start and end bit offsets, after resolving negative
+/// indices and clamping them to the length of the string. `None` is returned if the range is empty.
+fn bit_range(len: usize, range: &BitRange) -> Option<(u64, u64)> {
+    let len = match range.unit {
+        BitRangeUnit::Byte => len as i64,
+        BitRangeUnit::Bit => len as i64 * 8,
+    };
+
+    let mut start = range.start;
+    let mut end = range.end.unwrap_or(-1);
+    if start < 0 {
+        start += len;
+    }
+    if end < 0 {
+        end += len;
+    }
+    start = start.max(0);
+    end = end.max(0).min(len - 1);
+
+    if len == 0 || start > end {
+        return None;
+    }
+
+    match range.unit {
+        BitRangeUnit::Byte => Some((start as u64 * 8, end as u64 * 8 + 7)),
+        BitRangeUnit::Bit => Some((start as u64, end as u64)),
+    }
+}
+
+/// Masks out the bits of the byte at `idx` which are outside the start and end bit offsets.
+fn mask_byte(byte: u8, idx: u64, start: u64, end: u64) -> u8 {
+    let mut byte = byte;
+    if idx == start / 8 {
+        byte &= 0xff >> (start % 8);
+    }
+    if idx == end / 8 {
+        byte &= 0xff << (7 - end % 8);
+    }
+
+    byte
+}
+
+/// Counts the set bits between the start and end bit offsets (both inclusive).
+fn count_bits(bytes: &[u8], start: u64, end: u64) -> u64 {
+    (start / 8..=end / 8)
+        .map(|idx| mask_byte(bytes[idx as usize], idx, start, end).count_ones() as u64)
+        .sum()
+}
+
+/// Returns the offset of the first bit matching `bit`, between the start and end bit offsets (both inclusive).
+fn find_bit(bytes: &[u8], bit: u8, start: u64, end: u64) -> Option<u64> {
+    for idx in start / 8..=end / 8 {
+        // Flip the bits when looking for a clear bit, so that the search is always for a set bit.
+        let byte = if bit == 1 {
+            bytes[idx as usize]
+        } else {
+            !bytes[idx as usize]
+        };
+
+        let byte = mask_byte(byte, idx, start, end);
+        if byte != 0 {
+            return Some(idx * 8 + byte.leading_zeros() as u64);
+        }
+    }
+
+    None
+}
+
+/// Reads the integer field of the given type at the bit offset.
+fn read_field(bytes: &[u8], ty: BitfieldType, offset: u64) -> i64 {
+    let mut raw: u64 = 0;
+    for i in 0..ty.bits as u64 {
+        raw = (raw << 1) | get_bit(bytes, offset + i) as u64;
+    }
+
+    if ty.signed && ty.bits < 64 && raw & (1 << (ty.bits - 1)) != 0 {
+        // sign extend the value
+        (raw as i128 - (1i128 << ty.bits)) as i64
+    } else {
+        raw as i64
+    }
+}
+
+/// Writes the integer field of the given type at the bit offset. Only the lower bits of the
+/// value which fits in the field are written.
+fn write_field(bytes: &mut [u8], ty: BitfieldType, offset: u64, value: i64) {
+    let raw = value as u64;
+    for i in 0..ty.bits as u64 {
+        let bit = ((raw >> (ty.bits as u64 - 1 - i)) & 1) as u8;
+        set_bit(bytes, offset + i, bit);
+    }
+}
+
+/// Fits the value into the range of the field type as per the overflow behaviour.
+/// Returns `None` if the value overflows and the overflow behaviour is `FAIL`.
+fn handle_overflow(ty: BitfieldType, value: i128, overflow: BitfieldOverflow) -> Option<i64> {
+    let (min, max) = ty.range();
+    if value >= min && value <= max {
+        return Some(value as i64);
+    }
+
+    match overflow {
+        BitfieldOverflow::Wrap => Some(((value - min).rem_euclid(1i128 << ty.bits) + min) as i64),
+        BitfieldOverflow::Sat => Some(value.clamp(min, max) as i64),
+        BitfieldOverflow::Fail => None,
+    }
+}
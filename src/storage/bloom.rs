@@ -0,0 +1,313 @@
+use std::mem::size_of;
+
+use super::{
+    db::{Keyspace, Value, DB},
+    dump::{bad_format_err, Decoder, Dump, Encoder},
+    hyperloglog::murmurhash64a,
+    notify::NotifyClass,
+    DBError,
+};
+
+// Bloom filters are scalable: they start with a single sub-filter sized for the requested
+// capacity and error rate. Once a sub-filter is full, a new one is added with its capacity
+// multiplied by the expansion rate, and its error rate halved so that the compounded error
+// rate stays within twice the requested rate. Items are only added to the last sub-filter, while
+// lookups check all of them.
+
+/// Error rate used when the filter is created by BF.ADD or BF.MADD.
+const BF_DEFAULT_ERROR_RATE: f64 = 0.01;
+/// Capacity used when the filter is created by BF.ADD or BF.MADD.
+const BF_DEFAULT_CAPACITY: u64 = 100;
+/// Expansion rate used when the filter is created by BF.ADD or BF.MADD.
+const BF_DEFAULT_EXPANSION: u32 = 2;
+/// The error rate of each new sub-filter is multiplied by this ratio.
+const BF_ERROR_TIGHTENING_RATIO: f64 = 0.5;
+/// Seed used for hashing the items.
+const BF_HASH_SEED: u64 = 0xc6a4a7935bd1e995;
+
+/// A scalable Bloom filter.
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    error_rate: f64,
+    /// `None` for non-scaling filters.
+    expansion: Option<u32>,
+    filters: Vec<SubFilter>,
+}
+
+/// A fixed size Bloom filter, which is a layer of the scalable filter.
+#[derive(Debug, Clone)]
+struct SubFilter {
+    bits: Vec<u64>,
+    hashes: u32,
+    capacity: u64,
+    items: u64,
+}
+
+/// Details of a Bloom filter, as returned by BF.INFO.
+#[derive(Debug, Clone)]
+pub struct BloomInfo {
+    pub capacity: u64,
+    pub size: usize,
+    pub filters: usize,
+    pub items: u64,
+    pub expansion: Option<u32>,
+}
+
+impl BloomFilter {
+    /// Creates a Bloom filter with a single sub-filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `error_rate` - The desired probability of false positives, between 0 and 1.
+    ///
+    /// * `capacity` - The number of items expected to be added before a new sub-filter is created.
+    ///
+    /// * `expansion` - The capacity of each new sub-filter is multiplied by this value. `None`
+    ///   creates a non-scaling filter, which returns an error once it's full.
+    pub fn new(error_rate: f64, capacity: u64, expansion: Option<u32>) -> BloomFilter {
+        BloomFilter {
+            error_rate,
+            expansion,
+            filters: vec![SubFilter::new(capacity, error_rate)],
+        }
+    }
+
+    /// Adds the item to the filter.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(true)` if the item was added.
+    /// * `Some(false)` if the item may have been added already.
+    /// * `None` if the filter is full and non-scaling.
+    fn add(&mut self, item: &[u8]) -> Option<bool> {
+        let (h1, h2) = hash_item(item);
+        if self.filters.iter().any(|f| f.contains(h1, h2)) {
+            return Some(false);
+        }
+
+        let last = self.filters.last()?;
+        if last.items >= last.capacity {
+            let expansion = self.expansion?;
+            let capacity = last.capacity.saturating_mul(expansion as u64);
+            let error_rate =
+                self.error_rate * BF_ERROR_TIGHTENING_RATIO.powi(self.filters.len() as i32);
+            self.filters.push(SubFilter::new(capacity, error_rate));
+        }
+
+        let last = self.filters.last_mut()?;
+        last.add(h1, h2);
+        Some(true)
+    }
+
+    /// Checks if the item may have been added to the filter.
+    fn contains(&self, item: &[u8]) -> bool {
+        let (h1, h2) = hash_item(item);
+        self.filters.iter().any(|f| f.contains(h1, h2))
+    }
+
+    /// Returns the approximate number of bytes used by the filter.
+    pub fn memory_usage(&self) -> usize {
+        size_of::<BloomFilter>()
+            + self
+                .filters
+                .iter()
+                .map(|f| size_of::<SubFilter>() + f.bits.len() * size_of::<u64>())
+                .sum::<usize>()
+    }
+
+    fn info(&self) -> BloomInfo {
+        BloomInfo {
+            capacity: self.filters.iter().map(|f| f.capacity).sum(),
+            size: self.filters.iter().map(|f| f.bits.len() * 8).sum(),
+            filters: self.filters.len(),
+            items: self.filters.iter().map(|f| f.items).sum(),
+            expansion: self.expansion,
+        }
+    }
+}
+
+impl SubFilter {
+    fn new(capacity: u64, error_rate: f64) -> SubFilter {
+        let ln2 = std::f64::consts::LN_2;
+        let bits_per_item = -error_rate.ln() / (ln2 * ln2);
+        let num_bits = ((capacity as f64 * bits_per_item).ceil() as u64).max(64);
+
+        SubFilter {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            hashes: (ln2 * bits_per_item).ceil().max(1.0) as u32,
+            capacity,
+            items: 0,
+        }
+    }
+
+    fn add(&mut self, h1: u64, h2: u64) {
+        for bit in self.bit_indices(h1, h2) {
+            self.bits[bit / 64] |= 1 << (bit % 64);
+        }
+        self.items += 1;
+    }
+
+    fn contains(&self, h1: u64, h2: u64) -> bool {
+        self.bit_indices(h1, h2)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+
+    /// Returns the bits for the item, derived from the two hashes using double hashing.
+    fn bit_indices(&self, h1: u64, h2: u64) -> impl Iterator<Item = usize> {
+        let num_bits = self.bits.len() as u64 * 64;
+        (0..self.hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits) as usize)
+    }
+}
+
+impl DB {
+    /// Creates an empty Bloom filter at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the filter is to be created.
+    ///
+    /// * `error_rate` - The desired probability of false positives, between 0 and 1.
+    ///
+    /// * `capacity` - The number of items expected to be added.
+    ///
+    /// * `expansion` - The growth rate of the sub-filters. `None` for non-scaling filters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the filter was created.
+    /// * `Err(DBError)` - if key already exists.
+    pub fn bf_reserve(
+        &self,
+        k: &str,
+        error_rate: f64,
+        capacity: u64,
+        expansion: Option<u32>,
+    ) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_some() {
+                return Err(DBError::Other(String::from("Item exists")));
+            }
+
+            let bf = BloomFilter::new(error_rate, capacity, expansion);
+            keyspace.insert(k, Value::BloomFilter(bf));
+            keyspace.notify(NotifyClass::Module, "bf.reserve", k);
+            Ok(())
+        })
+    }
+
+    /// Adds the items to the Bloom filter stored at key. A filter with the default error rate
+    /// and capacity is created if the key doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<bool>>)` - For each item, `true` if it was added, `false` if it may have
+    ///   been added already, or `None` if the filter is full and non-scaling.
+    /// * `Err(DBError)` - if key already exists and is not a Bloom filter.
+    pub fn bf_add(&self, k: &str, items: &[Vec<u8>]) -> Result<Vec<Option<bool>>, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_none() {
+                let bf = BloomFilter::new(
+                    BF_DEFAULT_ERROR_RATE,
+                    BF_DEFAULT_CAPACITY,
+                    Some(BF_DEFAULT_EXPANSION),
+                );
+                keyspace.insert(k, Value::BloomFilter(bf));
+            }
+
+            let bf = match keyspace.get_mut(k) {
+                Some(Value::BloomFilter(bf)) => bf,
+                _ => return Err(DBError::WrongType),
+            };
+
+            let added = items.iter().map(|item| bf.add(item)).collect();
+            keyspace.notify(NotifyClass::Module, "bf.add", k);
+            Ok(added)
+        })
+    }
+
+    /// Checks if the items may have been added to the Bloom filter stored at key. `false` is
+    /// returned for all the items if the key doesn't exist.
+    pub fn bf_exists(&self, k: &str, items: &[Vec<u8>]) -> Result<Vec<bool>, DBError> {
+        self.read(|keyspace| match bf_value(keyspace, k)? {
+            Some(bf) => Ok(items.iter().map(|item| bf.contains(item)).collect()),
+            None => Ok(vec![false; items.len()]),
+        })
+    }
+
+    /// Returns the details of the Bloom filter stored at key, or `None` if the key doesn't exist.
+    pub fn bf_info(&self, k: &str) -> Result<Option<BloomInfo>, DBError> {
+        self.read(|keyspace| Ok(bf_value(keyspace, k)?.map(|bf| bf.info())))
+    }
+}
+
+/// Returns the Bloom filter stored at key, if any.
+fn bf_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<Option<&'a BloomFilter>, DBError> {
+    match keyspace.get(k) {
+        Some(Value::BloomFilter(bf)) => Ok(Some(bf)),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(None),
+    }
+}
+
+/// Returns the two hashes of the item, which are combined to derive the bits of the item.
+fn hash_item(item: &[u8]) -> (u64, u64) {
+    let h1 = murmurhash64a(item, BF_HASH_SEED);
+    let h2 = murmurhash64a(item, h1);
+    (h1, h2)
+}
+
+impl Dump for BloomFilter {
+    fn dump(&self, enc: &mut Encoder) {
+        enc.f64(self.error_rate);
+        enc.u64(self.expansion.map_or(0, |e| e as u64));
+        enc.size(self.filters.len());
+        for filter in self.filters.iter() {
+            enc.size(filter.bits.len());
+            filter.bits.iter().for_each(|word| enc.u64(*word));
+            enc.u64(filter.hashes as u64);
+            enc.u64(filter.capacity);
+            enc.u64(filter.items);
+        }
+    }
+
+    fn restore(dec: &mut Decoder) -> Result<BloomFilter, DBError> {
+        let error_rate = dec.f64()?;
+        // non-scaling filters have no expansion
+        let expansion = match dec.u64()? {
+            0 => None,
+            e => Some(u32::try_from(e).map_err(|_| bad_format_err())?),
+        };
+
+        let mut filters = vec![];
+        for _ in 0..dec.size()? {
+            let mut bits = vec![];
+            for _ in 0..dec.size()? {
+                bits.push(dec.u64()?);
+            }
+            let hashes = u32::try_from(dec.u64()?).map_err(|_| bad_format_err())?;
+            let capacity = dec.u64()?;
+            let items = dec.u64()?;
+
+            if bits.is_empty() || hashes == 0 {
+                return Err(bad_format_err());
+            }
+            filters.push(SubFilter {
+                bits,
+                hashes,
+                capacity,
+                items,
+            });
+        }
+
+        if filters.is_empty() || !(error_rate > 0.0 && error_rate < 1.0) {
+            return Err(bad_format_err());
+        }
+
+        Ok(BloomFilter {
+            error_rate,
+            expansion,
+            filters,
+        })
+    }
+}
@@ -0,0 +1,222 @@
+use std::mem::size_of;
+
+use super::{
+    db::{Keyspace, Value, DB},
+    dump::{bad_format_err, Decoder, Dump, Encoder},
+    hyperloglog::murmurhash64a,
+    notify::NotifyClass,
+    DBError,
+};
+
+// A Count-Min Sketch is a matrix of counters, with `depth` rows of `width` counters each.
+// Each row hashes the item with a different seed, and increments one counter in the row.
+// The count of an item is the minimum of its counters across the rows, which can
+// overestimate (but never underestimate) the actual count.
+
+/// A Count-Min Sketch.
+#[derive(Debug, Clone)]
+pub struct CountMinSketch {
+    width: usize,
+    depth: usize,
+    /// The counters of all the rows, `width` counters per row.
+    counters: Vec<u64>,
+    /// Sum of all the increments.
+    count: u64,
+}
+
+impl CountMinSketch {
+    /// Creates an empty sketch with the given dimensions.
+    pub fn new(width: usize, depth: usize) -> CountMinSketch {
+        CountMinSketch {
+            width,
+            depth,
+            counters: vec![0; width * depth],
+            count: 0,
+        }
+    }
+
+    /// Returns the dimensions of a sketch which overestimates the counts by at most
+    /// `error * total count`, with the probability `1 - probability`.
+    pub fn dimensions_for(error: f64, probability: f64) -> (usize, usize) {
+        let width = (2.0 / error).ceil() as usize;
+        let depth = (probability.ln() / 0.5f64.ln()).ceil() as usize;
+        (width.max(1), depth.max(1))
+    }
+
+    /// Increments the counters of the item and returns its new count.
+    fn incr(&mut self, item: &[u8], incr: u64) -> u64 {
+        let mut min = u64::MAX;
+        for row in 0..self.depth {
+            let idx = self.counter_idx(item, row);
+            self.counters[idx] = self.counters[idx].saturating_add(incr);
+            min = min.min(self.counters[idx]);
+        }
+        self.count = self.count.saturating_add(incr);
+
+        min
+    }
+
+    /// Returns the count of the item.
+    fn query(&self, item: &[u8]) -> u64 {
+        (0..self.depth)
+            .map(|row| self.counters[self.counter_idx(item, row)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn counter_idx(&self, item: &[u8], row: usize) -> usize {
+        let hash = murmurhash64a(item, row as u64);
+        row * self.width + (hash % self.width as u64) as usize
+    }
+
+    /// Returns the approximate number of bytes used by the sketch.
+    pub fn memory_usage(&self) -> usize {
+        size_of::<CountMinSketch>() + self.counters.len() * size_of::<u64>()
+    }
+}
+
+impl DB {
+    /// Creates an empty Count-Min Sketch at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the sketch is to be created.
+    ///
+    /// * `width` - Number of counters in each row.
+    ///
+    /// * `depth` - Number of rows.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the sketch was created.
+    /// * `Err(DBError)` - if key already exists.
+    pub fn cms_init(&self, k: &str, width: usize, depth: usize) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_some() {
+                return Err(DBError::Other(String::from("CMS: key already exists")));
+            }
+
+            keyspace.insert(k, Value::CountMinSketch(CountMinSketch::new(width, depth)));
+            keyspace.notify(NotifyClass::Module, "cms.init", k);
+            Ok(())
+        })
+    }
+
+    /// Increments the count of the items in the Count-Min Sketch stored at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u64>)` - The new count of each item.
+    /// * `Err(DBError)` - if key doesn't exist or is not a Count-Min Sketch.
+    pub fn cms_incrby(&self, k: &str, items: &[(Vec<u8>, u64)]) -> Result<Vec<u64>, DBError> {
+        self.write(|keyspace| {
+            let cms = match keyspace.get_mut(k) {
+                Some(Value::CountMinSketch(cms)) => cms,
+                Some(_) => return Err(DBError::WrongType),
+                None => return Err(key_not_found_err()),
+            };
+
+            let counts = items
+                .iter()
+                .map(|(item, incr)| cms.incr(item, *incr))
+                .collect();
+            keyspace.notify(NotifyClass::Module, "cms.incrby", k);
+            Ok(counts)
+        })
+    }
+
+    /// Returns the count of the items in the Count-Min Sketch stored at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<u64>)` - The count of each item.
+    /// * `Err(DBError)` - if key doesn't exist or is not a Count-Min Sketch.
+    pub fn cms_query(&self, k: &str, items: &[Vec<u8>]) -> Result<Vec<u64>, DBError> {
+        self.read(|keyspace| {
+            let cms = cms_value(keyspace, k)?;
+            Ok(items.iter().map(|item| cms.query(item)).collect())
+        })
+    }
+
+    /// Merges the Count-Min Sketches stored at the source keys into the sketch stored at the
+    /// destination key. The counters of each source are multiplied by its weight.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the sketches were merged.
+    /// * `Err(DBError)` - if any of the keys doesn't exist or is not a Count-Min Sketch, or if
+    ///   the dimensions of the sketches are not equal.
+    pub fn cms_merge(&self, dest: &str, sources: &[(String, u64)]) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            let dest_cms = cms_value(keyspace, dest)?;
+            let mut merged = CountMinSketch::new(dest_cms.width, dest_cms.depth);
+
+            for (src, weight) in sources.iter() {
+                let src_cms = cms_value(keyspace, src)?;
+                if src_cms.width != merged.width || src_cms.depth != merged.depth {
+                    return Err(DBError::Other(String::from(
+                        "CMS: width/depth is not equal",
+                    )));
+                }
+
+                for (counter, src_counter) in merged.counters.iter_mut().zip(&src_cms.counters) {
+                    *counter = counter.saturating_add(src_counter.saturating_mul(*weight));
+                }
+                merged.count = merged
+                    .count
+                    .saturating_add(src_cms.count.saturating_mul(*weight));
+            }
+
+            // the destination retains its expiry, as the sketch is updated in place
+            if let Some(Value::CountMinSketch(cms)) = keyspace.get_mut(dest) {
+                *cms = merged;
+            }
+            keyspace.notify(NotifyClass::Module, "cms.merge", dest);
+            Ok(())
+        })
+    }
+}
+
+/// Returns the Count-Min Sketch stored at key.
+fn cms_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<&'a CountMinSketch, DBError> {
+    match keyspace.get(k) {
+        Some(Value::CountMinSketch(cms)) => Ok(cms),
+        Some(_) => Err(DBError::WrongType),
+        None => Err(key_not_found_err()),
+    }
+}
+
+fn key_not_found_err() -> DBError {
+    DBError::Other(String::from("CMS: key does not exist"))
+}
+
+impl Dump for CountMinSketch {
+    fn dump(&self, enc: &mut Encoder) {
+        enc.size(self.width);
+        enc.size(self.depth);
+        enc.size(self.counters.len());
+        self.counters.iter().for_each(|c| enc.u64(*c));
+        enc.u64(self.count);
+    }
+
+    fn restore(dec: &mut Decoder) -> Result<CountMinSketch, DBError> {
+        let width = dec.size()?;
+        let depth = dec.size()?;
+        let mut counters = vec![];
+        for _ in 0..dec.size()? {
+            counters.push(dec.u64()?);
+        }
+        let count = dec.u64()?;
+
+        if width == 0 || depth == 0 || width.checked_mul(depth) != Some(counters.len()) {
+            return Err(bad_format_err());
+        }
+
+        Ok(CountMinSketch {
+            width,
+            depth,
+            counters,
+            count,
+        })
+    }
+}
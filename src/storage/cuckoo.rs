@@ -0,0 +1,440 @@
+use std::mem::size_of;
+
+use super::{
+    db::{Keyspace, Value, DB},
+    dump::{bad_format_err, Decoder, Dump, Encoder},
+    hyperloglog::murmurhash64a,
+    notify::NotifyClass,
+    DBError,
+};
+
+// Cuckoo filters store an 8 bit fingerprint of each item, in one of two candidate buckets.
+// The alternate bucket of a fingerprint is derived from its current bucket and the fingerprint
+// alone, so fingerprints can be relocated without knowing the original item. The number of
+// buckets is always a power of two, which makes the alternate bucket computation reversible.
+//
+// When both candidate buckets are full, a fingerprint is evicted to its alternate bucket, up
+// to the max iterations. If that fails too, the filter is expanded with a new sub-filter
+// (unless the expansion is 0). Victims are picked deterministically, so that replicas which
+// replay the same commands end up with identical filters.
+
+/// Capacity used when the filter is created by CF.ADD or CF.ADDNX.
+const CF_DEFAULT_CAPACITY: u64 = 1024;
+/// Number of items in each bucket, unless specified by CF.RESERVE.
+pub const CF_DEFAULT_BUCKET_SIZE: usize = 2;
+/// Number of attempts to relocate items before expanding, unless specified by CF.RESERVE.
+pub const CF_DEFAULT_MAX_ITERATIONS: u32 = 20;
+/// Growth rate of the sub-filters, unless specified by CF.RESERVE.
+pub const CF_DEFAULT_EXPANSION: u32 = 1;
+/// Fingerprint value which marks an empty slot.
+const CF_EMPTY: u8 = 0;
+
+/// A scalable Cuckoo filter.
+#[derive(Debug, Clone)]
+pub struct CuckooFilter {
+    bucket_size: usize,
+    max_iterations: u32,
+    expansion: u32,
+    filters: Vec<SubFilter>,
+}
+
+/// A fixed size Cuckoo filter, which is a layer of the scalable filter.
+#[derive(Debug, Clone)]
+struct SubFilter {
+    /// Fingerprints of all the buckets, `bucket_size` slots per bucket.
+    slots: Vec<u8>,
+    num_buckets: u64,
+}
+
+/// The fingerprint of an item and its candidate bucket hashes.
+#[derive(Debug, Clone, Copy)]
+struct Fingerprint {
+    fp: u8,
+    h1: u64,
+    h2: u64,
+}
+
+impl CuckooFilter {
+    /// Creates a Cuckoo filter with a single sub-filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of items expected to be added.
+    ///
+    /// * `bucket_size` - Number of items in each bucket.
+    ///
+    /// * `max_iterations` - Number of attempts to relocate items, before the filter is expanded.
+    ///
+    /// * `expansion` - The number of buckets of each new sub-filter is multiplied by this value.
+    ///   0 creates a non-scaling filter, which returns an error once it's full.
+    pub fn new(
+        capacity: u64,
+        bucket_size: usize,
+        max_iterations: u32,
+        expansion: u32,
+    ) -> CuckooFilter {
+        let num_buckets = (capacity / bucket_size as u64).max(1).next_power_of_two();
+        // the number of buckets must remain a power of two as the filter expands
+        let expansion = match expansion {
+            0 => 0,
+            _ => expansion.next_power_of_two(),
+        };
+
+        CuckooFilter {
+            bucket_size,
+            max_iterations,
+            expansion,
+            filters: vec![SubFilter::new(num_buckets, bucket_size)],
+        }
+    }
+
+    /// Adds the item to the filter.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the item was added.
+    /// * `false` if the filter is full.
+    fn add(&mut self, item: &[u8]) -> bool {
+        let fp = fingerprint(item);
+
+        // the item is stored in the first sub-filter with a free slot in either bucket
+        for filter in self.filters.iter_mut() {
+            if filter.try_insert(fp, self.bucket_size) {
+                return true;
+            }
+        }
+
+        if let Some(filter) = self.filters.last_mut() {
+            if filter.insert_with_kicks(fp, self.bucket_size, self.max_iterations) {
+                return true;
+            }
+        }
+
+        if self.expansion == 0 {
+            return false;
+        }
+
+        let num_buckets = match self.filters.last() {
+            Some(filter) => filter.num_buckets.saturating_mul(self.expansion as u64),
+            None => 1,
+        };
+        let mut filter = SubFilter::new(num_buckets, self.bucket_size);
+        let added = filter.try_insert(fp, self.bucket_size);
+        self.filters.push(filter);
+
+        added
+    }
+
+    /// Checks if the item may have been added to the filter.
+    fn contains(&self, item: &[u8]) -> bool {
+        self.count(item) > 0
+    }
+
+    /// Returns the number of times the item may have been added to the filter.
+    fn count(&self, item: &[u8]) -> u64 {
+        let fp = fingerprint(item);
+        self.filters
+            .iter()
+            .map(|f| f.count(fp, self.bucket_size))
+            .sum()
+    }
+
+    /// Returns the approximate number of bytes used by the filter.
+    pub fn memory_usage(&self) -> usize {
+        size_of::<CuckooFilter>()
+            + self
+                .filters
+                .iter()
+                .map(|f| size_of::<SubFilter>() + f.slots.len())
+                .sum::<usize>()
+    }
+
+    /// Deletes one occurrence of the item from the filter. Returns `false` if the item
+    /// is not found.
+    fn delete(&mut self, item: &[u8]) -> bool {
+        let fp = fingerprint(item);
+        self.filters
+            .iter_mut()
+            .rev()
+            .any(|f| f.delete(fp, self.bucket_size))
+    }
+}
+
+impl SubFilter {
+    fn new(num_buckets: u64, bucket_size: usize) -> SubFilter {
+        SubFilter {
+            slots: vec![CF_EMPTY; num_buckets as usize * bucket_size],
+            num_buckets,
+        }
+    }
+
+    fn bucket(&self, hash: u64, bucket_size: usize) -> std::ops::Range<usize> {
+        let start = (hash & (self.num_buckets - 1)) as usize * bucket_size;
+        start..start + bucket_size
+    }
+
+    /// Stores the fingerprint in a free slot of either candidate bucket.
+    fn try_insert(&mut self, fp: Fingerprint, bucket_size: usize) -> bool {
+        for hash in [fp.h1, fp.h2] {
+            let bucket = self.bucket(hash, bucket_size);
+            if let Some(slot) = self.slots[bucket].iter_mut().find(|s| **s == CF_EMPTY) {
+                *slot = fp.fp;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Stores the fingerprint by relocating the existing fingerprints to their alternate buckets.
+    /// The relocations are reverted if a free slot is not found within the max iterations.
+    fn insert_with_kicks(
+        &mut self,
+        fp: Fingerprint,
+        bucket_size: usize,
+        max_iterations: u32,
+    ) -> bool {
+        let mut relocated = vec![];
+        let mut current = fp.fp;
+        let mut hash = fp.h1;
+
+        for i in 0..max_iterations {
+            let bucket = self.bucket(hash, bucket_size);
+            let slot = bucket.start + i as usize % bucket_size;
+
+            let victim = self.slots[slot];
+            self.slots[slot] = current;
+            relocated.push((slot, victim));
+
+            current = victim;
+            hash = alt_hash(hash, current);
+
+            let bucket = self.bucket(hash, bucket_size);
+            if let Some(slot) = self.slots[bucket].iter_mut().find(|s| **s == CF_EMPTY) {
+                *slot = current;
+                return true;
+            }
+        }
+
+        for (slot, victim) in relocated.into_iter().rev() {
+            self.slots[slot] = victim;
+        }
+
+        false
+    }
+
+    fn count(&self, fp: Fingerprint, bucket_size: usize) -> u64 {
+        let b1 = self.bucket(fp.h1, bucket_size);
+        let b2 = self.bucket(fp.h2, bucket_size);
+
+        let mut count = self.slots[b1.clone()]
+            .iter()
+            .filter(|s| **s == fp.fp)
+            .count();
+        if b1 != b2 {
+            count += self.slots[b2].iter().filter(|s| **s == fp.fp).count();
+        }
+
+        count as u64
+    }
+
+    fn delete(&mut self, fp: Fingerprint, bucket_size: usize) -> bool {
+        for hash in [fp.h1, fp.h2] {
+            let bucket = self.bucket(hash, bucket_size);
+            if let Some(slot) = self.slots[bucket].iter_mut().find(|s| **s == fp.fp) {
+                *slot = CF_EMPTY;
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl DB {
+    /// Creates an empty Cuckoo filter at key.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the filter is to be created.
+    ///
+    /// * `capacity` - The number of items expected to be added.
+    ///
+    /// * `bucket_size` - Number of items in each bucket.
+    ///
+    /// * `max_iterations` - Number of attempts to relocate items, before the filter is expanded.
+    ///
+    /// * `expansion` - The growth rate of the sub-filters. 0 for non-scaling filters.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the filter was created.
+    /// * `Err(DBError)` - if key already exists.
+    pub fn cf_reserve(
+        &self,
+        k: &str,
+        capacity: u64,
+        bucket_size: usize,
+        max_iterations: u32,
+        expansion: u32,
+    ) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_some() {
+                return Err(DBError::Other(String::from("Item exists")));
+            }
+
+            let cf = CuckooFilter::new(capacity, bucket_size, max_iterations, expansion);
+            keyspace.insert(k, Value::CuckooFilter(cf));
+            keyspace.notify(NotifyClass::Module, "cf.reserve", k);
+            Ok(())
+        })
+    }
+
+    /// Adds the item to the Cuckoo filter stored at key. A filter with the default capacity is
+    /// created if the key doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the filter is stored.
+    ///
+    /// * `item` - The item to be added.
+    ///
+    /// * `nx` - Add the item only if it doesn't exist in the filter.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the item was added, `false` if it may exist already (with NX).
+    /// * `Err(DBError)` - if key already exists and is not a Cuckoo filter, or if the filter is full.
+    pub fn cf_add(&self, k: &str, item: &[u8], nx: bool) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_none() {
+                let cf = CuckooFilter::new(
+                    CF_DEFAULT_CAPACITY,
+                    CF_DEFAULT_BUCKET_SIZE,
+                    CF_DEFAULT_MAX_ITERATIONS,
+                    CF_DEFAULT_EXPANSION,
+                );
+                keyspace.insert(k, Value::CuckooFilter(cf));
+            }
+
+            let cf = match keyspace.get_mut(k) {
+                Some(Value::CuckooFilter(cf)) => cf,
+                _ => return Err(DBError::WrongType),
+            };
+
+            if nx && cf.contains(item) {
+                return Ok(false);
+            }
+
+            if !cf.add(item) {
+                return Err(DBError::Other(String::from("Filter is full")));
+            }
+            keyspace.notify(NotifyClass::Module, "cf.add", k);
+
+            Ok(true)
+        })
+    }
+
+    /// Checks if the item may have been added to the Cuckoo filter stored at key. `false` is
+    /// returned if the key doesn't exist.
+    pub fn cf_exists(&self, k: &str, item: &[u8]) -> Result<bool, DBError> {
+        self.read(|keyspace| Ok(cf_value(keyspace, k)?.is_some_and(|cf| cf.contains(item))))
+    }
+
+    /// Returns the number of times the item may have been added to the Cuckoo filter stored at
+    /// key. 0 is returned if the key doesn't exist.
+    pub fn cf_count(&self, k: &str, item: &[u8]) -> Result<u64, DBError> {
+        self.read(|keyspace| Ok(cf_value(keyspace, k)?.map_or(0, |cf| cf.count(item))))
+    }
+
+    /// Deletes one occurrence of the item from the Cuckoo filter stored at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the item was deleted, `false` if it's not found.
+    /// * `Err(DBError)` - if key doesn't exist or is not a Cuckoo filter.
+    pub fn cf_del(&self, k: &str, item: &[u8]) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            let deleted = match keyspace.get_mut(k) {
+                Some(Value::CuckooFilter(cf)) => cf.delete(item),
+                Some(_) => return Err(DBError::WrongType),
+                None => return Err(DBError::Other(String::from("Not found"))),
+            };
+            if deleted {
+                keyspace.notify(NotifyClass::Module, "cf.del", k);
+            }
+
+            Ok(deleted)
+        })
+    }
+}
+
+/// Returns the Cuckoo filter stored at key, if any.
+fn cf_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<Option<&'a CuckooFilter>, DBError> {
+    match keyspace.get(k) {
+        Some(Value::CuckooFilter(cf)) => Ok(Some(cf)),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(None),
+    }
+}
+
+/// Hashes the item into a non-zero fingerprint, and the hashes of its two candidate buckets.
+fn fingerprint(item: &[u8]) -> Fingerprint {
+    let h1 = murmurhash64a(item, 0);
+    let fp = (h1 % 255 + 1) as u8;
+
+    Fingerprint {
+        fp,
+        h1,
+        h2: alt_hash(h1, fp),
+    }
+}
+
+/// Returns the hash of the alternate bucket of a fingerprint. Since the number of buckets is a
+/// power of two, applying it twice returns the original bucket.
+fn alt_hash(hash: u64, fp: u8) -> u64 {
+    hash ^ (fp as u64).wrapping_mul(0x5bd1e995)
+}
+
+impl Dump for CuckooFilter {
+    fn dump(&self, enc: &mut Encoder) {
+        enc.size(self.bucket_size);
+        enc.u64(self.max_iterations as u64);
+        enc.u64(self.expansion as u64);
+        enc.size(self.filters.len());
+        for filter in self.filters.iter() {
+            enc.u64(filter.num_buckets);
+            enc.bytes(&filter.slots);
+        }
+    }
+
+    fn restore(dec: &mut Decoder) -> Result<CuckooFilter, DBError> {
+        let bucket_size = dec.size()?;
+        let max_iterations = u32::try_from(dec.u64()?).map_err(|_| bad_format_err())?;
+        let expansion = u32::try_from(dec.u64()?).map_err(|_| bad_format_err())?;
+
+        let mut filters = vec![];
+        for _ in 0..dec.size()? {
+            let num_buckets = dec.u64()?;
+            let slots = dec.bytes()?;
+
+            // buckets are located by masking the hashes, so their number must be a power of two
+            let expected_slots = (num_buckets as usize).checked_mul(bucket_size);
+            if !num_buckets.is_power_of_two() || expected_slots != Some(slots.len()) {
+                return Err(bad_format_err());
+            }
+            filters.push(SubFilter { slots, num_buckets });
+        }
+
+        if filters.is_empty() || bucket_size == 0 {
+            return Err(bad_format_err());
+        }
+
+        Ok(CuckooFilter {
+            bucket_size,
+            max_iterations,
+            expansion,
+            filters,
+        })
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     collections::{HashMap, VecDeque},
     fmt::Display,
     hash::Hash,
+    mem::size_of,
     sync::{Arc, RwLock},
 };
 
@@ -9,26 +10,69 @@ use log::error;
 use time::OffsetDateTime;
 use tokio::sync::broadcast::{self, Receiver, Sender};
 
-use super::{DBError, DBEvent};
+use crate::tracking::Tracking;
+
+use super::{
+    bloom::BloomFilter,
+    count_min_sketch::CountMinSketch,
+    cuckoo::CuckooFilter,
+    hash, json,
+    keys::ScanOrder,
+    lazyfree::LazyFree,
+    notify::{KeyspaceEvent, NotifyClass, NotifyFlags},
+    search::SearchIndexes,
+    sorted_set::SortedSet,
+    time_series::TimeSeries,
+    top_k::TopK,
+    DBError, DBEvent,
+};
 
-/// This struct contains the DB which is shared across all connections.
-#[derive(Debug, Clone)]
+/// This struct contains the numbered logical databases, which are shared across all
+/// connections. Each connection selects the database on which its commands are executed.
+#[derive(Debug)]
 pub struct Storage {
-    db: Arc<DB>,
+    /// The databases, indexed by their numbers. SWAPDB swaps the databases in place, so the
+    /// background tasks which hold a database keep working with the same data.
+    dbs: RwLock<Vec<Arc<DB>>>,
+    /// The keys cached by the clients, which are invalidated when the databases are swapped.
+    tracking: Arc<Tracking>,
 }
 
 /// This struct holds the data behind a RwLock.
 #[derive(Debug)]
 pub struct DB {
-    data: RwLock<HashMap<Key, Entry>>,
+    data: RwLock<Keyspace>,
+    events: Arc<Sender<DBEvent>>,
+    notifications: Arc<Sender<KeyspaceEvent>>,
+}
+
+/// This struct holds the keys and their values. Data types which are implemented outside
+/// this module access the keys through the methods of `Keyspace`, while the DB lock is held.
+#[derive(Debug)]
+pub struct Keyspace {
+    entries: HashMap<Key, Entry>,
+    /// The keys ordered by their scan hashes, which SCAN cursors walk through. Cursors stay
+    /// valid as the keys are added or removed, unlike positions in the map.
+    scan_order: ScanOrder,
     events: Arc<Sender<DBEvent>>,
+    /// The keyspace events, which are published as keyspace notifications.
+    notifications: Arc<Sender<KeyspaceEvent>>,
+    /// Search indexes over the hashes, which are updated whenever a key is written.
+    indexes: SearchIndexes,
+    /// Frees the values of the removed keys in the background.
+    lazy_free: LazyFree,
+    /// The classes of keyspace events for which notifications are sent.
+    notify_flags: NotifyFlags,
+    /// The keys cached by the clients, which are invalidated when they're modified.
+    tracking: Arc<Tracking>,
 }
 
 /// This struct represents the key in the database. It encloses the value for
 /// the key its expiry (optional).
 #[derive(Debug, Clone)]
 pub struct Key {
-    value: String,
+    /// The name of the key, which is shared with the scan order of the keyspace.
+    value: Arc<str>,
     expiry: Option<OffsetDateTime>,
 }
 
@@ -41,30 +85,169 @@ pub struct Entry {
 /// The type of data stored against a key.
 #[derive(Debug, Clone)]
 pub enum Value {
-    String(String),
+    /// Strings are binary safe, and are stored as raw bytes.
+    String(Vec<u8>),
     List(VecDeque<String>),
+    Hash(hash::Hash),
+    SortedSet(SortedSet),
+    Json(serde_json::Value),
+    BloomFilter(BloomFilter),
+    CuckooFilter(CuckooFilter),
+    CountMinSketch(CountMinSketch),
+    TopK(TopK),
+    TimeSeries(TimeSeries),
 }
 
 impl Storage {
-    /// Create a new instance of `Storage` which contains the DB.
-    pub fn new(db: DB) -> Storage {
-        Storage { db: Arc::new(db) }
+    /// Create a new instance of `Storage` with the given number of empty databases.
+    pub fn new(
+        num_dbs: usize,
+        lazy_free: LazyFree,
+        notify_flags: NotifyFlags,
+        tracking: Arc<Tracking>,
+    ) -> Storage {
+        let dbs = (0..num_dbs)
+            .map(|_| {
+                Arc::new(DB::new(
+                    lazy_free.clone(),
+                    notify_flags,
+                    Arc::clone(&tracking),
+                ))
+            })
+            .collect();
+        Storage {
+            dbs: RwLock::new(dbs),
+            tracking,
+        }
+    }
+
+    /// Get the database with the given number.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Arc<DB>)` - The shared database.
+    /// * `Err(DBError)` - if the number is out of range.
+    pub fn db(&self, index: usize) -> Result<Arc<DB>, DBError> {
+        let dbs = match self.dbs.read() {
+            Ok(dbs) => dbs,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        match dbs.get(index) {
+            Some(db) => Ok(db.clone()),
+            None => Err(db_index_err()),
+        }
+    }
+
+    /// Get all the databases, in the order of their numbers.
+    pub fn dbs(&self) -> Result<Vec<Arc<DB>>, DBError> {
+        match self.dbs.read() {
+            Ok(dbs) => Ok(dbs.clone()),
+            Err(e) => Err(DBError::Other(format!("{}", e))),
+        }
+    }
+
+    /// Get the current number of a database, which changes when the database is swapped.
+    pub fn index_of(&self, db: &Arc<DB>) -> Option<usize> {
+        let dbs = self.dbs.read().ok()?;
+        dbs.iter().position(|d| Arc::ptr_eq(d, db))
+    }
+
+    /// Swap the contents of two databases. Connections which have selected one of the
+    /// databases see the contents of the other one right away.
+    pub fn swap(&self, a: usize, b: usize) -> Result<(), DBError> {
+        let mut dbs = match self.dbs.write() {
+            Ok(dbs) => dbs,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        if a >= dbs.len() || b >= dbs.len() {
+            return Err(db_index_err());
+        }
+        dbs.swap(a, b);
+        self.tracking.invalidate_all();
+
+        Ok(())
     }
 
-    /// Get the shared database.
-    pub fn db(&self) -> Arc<DB> {
-        self.db.clone()
+    /// Acquires write locks on the keyspaces of two different databases and runs `f` against
+    /// them, so that keys are moved or copied between the databases atomically. The locks are
+    /// always acquired in the order of the database numbers, which prevents deadlocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The number of the database whose keyspace is passed first to `f`.
+    ///
+    /// * `dest` - The number of the database whose keyspace is passed second to `f`.
+    ///
+    /// # Returns
+    ///
+    /// The result of `f`, or a `DBError` if the numbers are the same or out of range.
+    pub fn write_pair<T>(
+        &self,
+        src: usize,
+        dest: usize,
+        f: impl FnOnce(&mut Keyspace, &mut Keyspace) -> Result<T, DBError>,
+    ) -> Result<T, DBError> {
+        if src == dest {
+            return Err(DBError::Other(String::from(
+                "source and destination objects are the same",
+            )));
+        }
+
+        // the databases must not be swapped while the keyspaces are locked
+        let dbs = match self.dbs.read() {
+            Ok(dbs) => dbs,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+        let (src_db, dest_db) = match (dbs.get(src), dbs.get(dest)) {
+            (Some(src_db), Some(dest_db)) => (src_db, dest_db),
+            _ => return Err(db_index_err()),
+        };
+
+        let (first, second) = if src < dest {
+            (src_db, dest_db)
+        } else {
+            (dest_db, src_db)
+        };
+        let mut first = match first.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+        let mut second = match second.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        if src < dest {
+            f(&mut first, &mut second)
+        } else {
+            f(&mut second, &mut first)
+        }
     }
 }
 
 impl DB {
     /// Create a new instance of DB.
-    pub fn new() -> DB {
+    pub fn new(lazy_free: LazyFree, notify_flags: NotifyFlags, tracking: Arc<Tracking>) -> DB {
+        let (tx, _) = broadcast::channel(1024);
+        let events = Arc::new(tx);
         let (tx, _) = broadcast::channel(1024);
+        let notifications = Arc::new(tx);
 
         DB {
-            data: RwLock::new(HashMap::new()),
-            events: Arc::new(tx),
+            data: RwLock::new(Keyspace {
+                entries: HashMap::new(),
+                scan_order: ScanOrder::default(),
+                events: events.clone(),
+                notifications: notifications.clone(),
+                indexes: SearchIndexes::default(),
+                lazy_free,
+                notify_flags,
+                tracking,
+            }),
+            events,
+            notifications,
         }
     }
 
@@ -76,24 +259,19 @@ impl DB {
     ///
     /// # Returns
     ///
-    /// * `Ok(Option<String>)` - `Some(String)` if key is found in DB, else `None`
+    /// * `Ok(Option<Vec<u8>>)` - `Some(Vec<u8>)` if key is found in DB, else `None`
     /// * `Err(DBError)` - if key already exists and has non-string data.
-    pub fn get(&self, k: String) -> Result<Option<String>, DBError> {
+    pub fn get(&self, k: String) -> Result<Option<Vec<u8>>, DBError> {
         let data = match self.data.read() {
             Ok(data) => data,
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let (_, entry) = match data.get_key_value(&k.into()) {
-            Some(pair) => pair,
-            None => return Ok(None),
-        };
-
-        if let Value::String(s) = &entry.value {
-            return Ok(Some(s.to_string()));
+        match data.get(&k) {
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(DBError::WrongType),
+            None => Ok(None),
         }
-
-        Err(DBError::WrongType)
     }
 
     /// Set a string value against a key.
@@ -121,17 +299,19 @@ impl DB {
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let key = Key::new(k.clone(), expiry_ts);
-        let existing_kv_pair = data.get_key_value(&key);
-
-        if let Some((_, entry)) = existing_kv_pair {
-            match entry.value {
-                Value::String(_) => {}
-                _ => return Err(DBError::WrongType),
-            }
+        match data.get(&k) {
+            Some(Value::String(_)) | None => {}
+            Some(_) => return Err(DBError::WrongType),
         }
 
-        data.insert(key, Entry::new(v));
+        // the existing key is removed first, since inserting into the map doesn't replace
+        // the key (and its expiry) if it already exists
+        match data.remove(&k) {
+            Some(old) => data.lazy_free.free_overwritten(old),
+            None => data.notify(NotifyClass::New, "new", &k),
+        }
+        data.insert_entry(Key::new(k.clone(), expiry_ts), Entry::new(v));
+        data.notify(NotifyClass::String, "set", &k);
 
         if let Some(expiry) = expiry_ts {
             let key = k.clone();
@@ -166,26 +346,24 @@ impl DB {
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let key: Key = k.into();
-        let entry = data.get_mut(&key);
-
-        match entry {
-            Some(e) => {
-                let val = &mut e.value;
-                match val {
-                    Value::List(l) => {
-                        for each in v.iter().cloned() {
-                            l.push_front(each);
-                        }
-                        Ok(l.len())
+        match data.get_mut(&k) {
+            Some(val) => match val {
+                Value::List(l) => {
+                    for each in v.iter().cloned() {
+                        l.push_front(each);
                     }
-                    _ => Err(DBError::WrongType),
+                    let l_len = l.len();
+                    data.notify(NotifyClass::List, "lpush", &k);
+
+                    Ok(l_len)
                 }
-            }
+                _ => Err(DBError::WrongType),
+            },
             None => {
                 let list = VecDeque::from(v);
                 let l_len = list.len();
-                data.insert(key, Entry::new(Value::List(list)));
+                data.insert(&k, Value::List(list));
+                data.notify(NotifyClass::List, "lpush", &k);
 
                 Ok(l_len)
             }
@@ -212,26 +390,24 @@ impl DB {
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let key: Key = k.into();
-        let entry = data.get_mut(&key);
-
-        match entry {
-            Some(e) => {
-                let val = &mut e.value;
-                match val {
-                    Value::List(l) => {
-                        for each in v.iter().cloned() {
-                            l.push_back(each);
-                        }
-                        Ok(l.len())
+        match data.get_mut(&k) {
+            Some(val) => match val {
+                Value::List(l) => {
+                    for each in v.iter().cloned() {
+                        l.push_back(each);
                     }
-                    _ => Err(DBError::WrongType),
+                    let l_len = l.len();
+                    data.notify(NotifyClass::List, "rpush", &k);
+
+                    Ok(l_len)
                 }
-            }
+                _ => Err(DBError::WrongType),
+            },
             None => {
                 let list = VecDeque::from(v);
                 let l_len = list.len();
-                data.insert(key, Entry::new(Value::List(list)));
+                data.insert(&k, Value::List(list));
+                data.notify(NotifyClass::List, "rpush", &k);
 
                 Ok(l_len)
             }
@@ -263,13 +439,12 @@ impl DB {
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        let key: Key = k.into();
-        let entry = match data.get(&key) {
-            Some(entry) => entry,
+        let value = match data.get(&k) {
+            Some(value) => value,
             None => return Ok(vec![]),
         };
 
-        match &entry.value {
+        match value {
             Value::List(l) => {
                 let l_len = l.len() as i64;
                 let (rounded_start_idx, rounded_stop_idx) =
@@ -282,7 +457,8 @@ impl DB {
         }
     }
 
-    /// Delete a key from the DB and return an Option containing its value.
+    /// Delete a key from the DB if it has expired. The key is retained if it was set again
+    /// since the expiry was tracked, with a later expiry or without one.
     ///
     /// # Arguments
     ///
@@ -290,15 +466,28 @@ impl DB {
     ///
     /// # Returns
     ///
-    /// * `Ok(Option<Entry>)` - An Option containing the value stored against the deleted key.
+    /// * `Ok(bool)` - `true` if the key was expired and deleted.
     /// * `Err(DBError)` - if key deletion fails.
-    pub fn del(&self, k: &str) -> Result<Option<Entry>, DBError> {
+    pub fn evict_expired(&self, k: &str) -> Result<bool, DBError> {
         let mut data = match self.data.write() {
             Ok(data) => data,
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
-        Ok(data.remove(&k.into()))
+        let now = OffsetDateTime::now_utc();
+        match data.entries.get_key_value(&k.into()) {
+            Some((key, _)) if key.is_expired(now) => {}
+            _ => return Ok(false),
+        }
+
+        // the expiry is no longer tracked by the evictor, so no event is sent
+        if let Some((_, entry)) = data.remove_entry(k) {
+            data.lazy_free.free_expired(entry.value);
+        }
+        data.reindex(k);
+        data.notify(NotifyClass::Expired, "expired", k);
+
+        Ok(true)
     }
 
     /// Delete a list of keys from the DB and return the number of keys deleted.
@@ -313,22 +502,45 @@ impl DB {
     /// * `Ok(usize)` - Number of keys deleted (which were present in the DB).
     /// * `Err(DBError)` - if key deletion fails.
     pub fn bulk_del(&self, keys: &[&str]) -> Result<usize, DBError> {
+        self.del_keys(keys, false)
+    }
+
+    /// Delete a list of keys from the DB like `bulk_del`, but the memory of large values is
+    /// freed in the background, so that the DB is not locked while the values are dropped.
+    pub fn unlink(&self, keys: &[&str]) -> Result<usize, DBError> {
+        self.del_keys(keys, true)
+    }
+
+    /// Delete a list of keys from the DB and return the number of keys deleted. The values
+    /// are freed lazily if `lazy` is set.
+    fn del_keys(&self, keys: &[&str], lazy: bool) -> Result<usize, DBError> {
         let mut data = match self.data.write() {
             Ok(data) => data,
             Err(e) => return Err(DBError::Other(format!("{}", e))),
         };
 
+        let now = OffsetDateTime::now_utc();
         let mut del_count: usize = 0;
         let mut del_keys_with_expiry: Vec<(OffsetDateTime, String)> = vec![];
 
         for k in keys {
-            let key = Key::from(*k);
-            let kv_pair = data.remove_entry(&key);
-            if let Some((k, _)) = kv_pair {
-                del_count += 1;
+            let kv_pair = data.remove_entry(k);
+            if let Some((k, entry)) = kv_pair {
+                // expired keys which are not evicted yet don't count as deleted
+                match k.is_expired(now) {
+                    true => data.notify(NotifyClass::Expired, "expired", &k.value),
+                    false => {
+                        del_count += 1;
+                        data.notify(NotifyClass::Generic, "del", &k.value);
+                    }
+                }
+                data.reindex(&k.value);
+                if lazy {
+                    data.lazy_free.free(entry.value);
+                }
 
                 if let Some(expiry_ts) = k.expiry {
-                    del_keys_with_expiry.push((expiry_ts, k.value));
+                    del_keys_with_expiry.push((expiry_ts, k.value.to_string()));
                 }
             }
         }
@@ -343,10 +555,109 @@ impl DB {
         Ok(del_count)
     }
 
+    /// Delete all the keys from the DB, along with the search indexes.
+    ///
+    /// # Arguments
+    ///
+    /// * `lazy` - Set to `true` if the memory of the deleted keys must be freed in the
+    ///   background.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the keys are deleted.
+    /// * `Err(DBError)` - if the DB lock cannot be acquired.
+    pub fn flush(&self, lazy: bool) -> Result<(), DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        let entries = std::mem::take(&mut data.entries);
+        let scan_order = std::mem::take(&mut data.scan_order);
+        let indexes = std::mem::take(&mut data.indexes);
+        let lazy_free = data.lazy_free.clone();
+        if !entries.is_empty() {
+            data.tracking.invalidate_all();
+        }
+        drop(data);
+
+        let del_keys_with_expiry: Vec<(OffsetDateTime, String)> = entries
+            .keys()
+            .filter_map(|k| k.expiry.map(|expiry_ts| (expiry_ts, k.value.to_string())))
+            .collect();
+        if !del_keys_with_expiry.is_empty() {
+            if let Err(e) = self.events.send(DBEvent::BulkDelKeys(del_keys_with_expiry)) {
+                error!("Failed to send bulk key deletion event: {}", e);
+            }
+        }
+
+        if lazy {
+            lazy_free.free_later((entries, scan_order, indexes));
+        }
+
+        Ok(())
+    }
+
+    /// Acquires a read lock on the keyspace and runs `f` against it.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The read-only operation to be performed on the keyspace.
+    ///
+    /// # Returns
+    ///
+    /// The result of `f`, or a `DBError` if the lock cannot be acquired.
+    pub fn read<T>(&self, f: impl FnOnce(&Keyspace) -> Result<T, DBError>) -> Result<T, DBError> {
+        let data = match self.data.read() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        f(&data)
+    }
+
+    /// Acquires a write lock on the keyspace and runs `f` against it. All the keys touched
+    /// by `f` are updated atomically.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The operation to be performed on the keyspace.
+    ///
+    /// # Returns
+    ///
+    /// The result of `f`, or a `DBError` if the lock cannot be acquired.
+    pub fn write<T>(
+        &self,
+        f: impl FnOnce(&mut Keyspace) -> Result<T, DBError>,
+    ) -> Result<T, DBError> {
+        let mut data = match self.data.write() {
+            Ok(data) => data,
+            Err(e) => return Err(DBError::Other(format!("{}", e))),
+        };
+
+        f(&mut data)
+    }
+
+    /// Returns the approximate number of bytes used by a key and its value, or `None` if the
+    /// key doesn't exist.
+    pub fn memory_usage(&self, k: &str) -> Result<Option<usize>, DBError> {
+        self.read(|keyspace| {
+            Ok(keyspace
+                .get(k)
+                .map(|v| size_of::<Key>() + size_of::<Entry>() + k.len() + v.memory_usage()))
+        })
+    }
+
     pub fn subscribe_events(&self) -> Receiver<DBEvent> {
         self.events.subscribe()
     }
 
+    /// Subscribes to the keyspace events of the DB, which are sent only for the classes of
+    /// events enabled by the `notify-keyspace-events` flags.
+    pub fn subscribe_notifications(&self) -> Receiver<KeyspaceEvent> {
+        self.notifications.subscribe()
+    }
+
     /// Round index to 0, if the given index value is less than zero.
     /// Round index to list length, if the given index value is greater then the list length.
     fn round_list_index(list_len: i64, idx: i64) -> usize {
@@ -385,6 +696,195 @@ impl DB {
     }
 }
 
+impl Keyspace {
+    /// Get the value stored against a key. Keys which have expired, but are not evicted yet,
+    /// are treated as missing. The key is remembered for the client reading it, if the client
+    /// tracks the keys it caches.
+    pub fn get(&self, k: &str) -> Option<&Value> {
+        self.tracking.remember(k);
+
+        let now = OffsetDateTime::now_utc();
+        self.entries
+            .get_key_value(&k.into())
+            .filter(|(key, _)| !key.is_expired(now))
+            .map(|(_, e)| &e.value)
+    }
+
+    /// Get a mutable reference to the value stored against a key. The expiry of the key
+    /// is retained when the value is modified. Expired keys are removed on access, so that
+    /// they are not modified.
+    pub fn get_mut(&mut self, k: &str) -> Option<&mut Value> {
+        if self.get(k).is_none() {
+            self.remove(k);
+            return None;
+        }
+
+        self.entries.get_mut(&k.into()).map(|e| &mut e.value)
+    }
+
+    /// Get the expiry of a key, if the key exists and has an expiry. The key is remembered
+    /// for the client reading it, like `get`.
+    pub fn expiry(&self, k: &str) -> Option<OffsetDateTime> {
+        self.tracking.remember(k);
+        self.peek_expiry(k)
+    }
+
+    /// Get the expiry of a key like `expiry`, without remembering the key for the client.
+    /// It's used when the keys are not read by a command, like when computing stats.
+    pub fn peek_expiry(&self, k: &str) -> Option<OffsetDateTime> {
+        let now = OffsetDateTime::now_utc();
+        self.entries
+            .get_key_value(&k.into())
+            .filter(|(key, _)| !key.is_expired(now))
+            .and_then(|(key, _)| key.expiry)
+    }
+
+    /// Set a value against a key, replacing the existing value (and its expiry) if any.
+    pub fn insert(&mut self, k: &str, v: Value) {
+        self.insert_with_expiry(k, v, None);
+    }
+
+    /// Set a value against a key along with its expiry, replacing the existing value (and its
+    /// expiry) if any.
+    pub fn insert_with_expiry(&mut self, k: &str, v: Value, expiry: Option<OffsetDateTime>) {
+        match self.remove(k) {
+            Some(old) => self.lazy_free.free_overwritten(old),
+            None => self.notify(NotifyClass::New, "new", k),
+        }
+        self.insert_entry(Key::new(k.to_string(), expiry), Entry::new(v));
+        self.reindex(k);
+
+        if let Some(expiry_ts) = expiry {
+            let evt = DBEvent::SetKeyExpiry((expiry_ts, k.to_string()));
+            if let Err(e) = self.events.send(evt) {
+                error!("Failed to send set expiry event: {}", e);
+            }
+        }
+    }
+
+    /// Remove a key and return its value. Nothing is returned if the key has expired.
+    pub fn remove(&mut self, k: &str) -> Option<Value> {
+        let (key, entry) = self.remove_entry(k)?;
+        let expired = key.is_expired(OffsetDateTime::now_utc());
+
+        // the key is no longer tracked for expiry once it's removed
+        if let Some(expiry_ts) = key.expiry {
+            let evt = DBEvent::BulkDelKeys(vec![(expiry_ts, key.value.to_string())]);
+            if let Err(e) = self.events.send(evt) {
+                error!("Failed to send key deletion event: {}", e);
+            }
+        }
+
+        self.indexes.update(k, None);
+        match expired {
+            true => {
+                self.lazy_free.free_expired(entry.value);
+                self.notify(NotifyClass::Expired, "expired", k);
+                None
+            }
+            false => Some(entry.value),
+        }
+    }
+
+    /// Update the search indexes with the current value of a key. This must be called
+    /// after the value of a key is modified in place.
+    pub fn reindex(&mut self, k: &str) {
+        let value = self.entries.get(&k.into()).map(|e| &e.value);
+        self.indexes.update(k, value);
+    }
+
+    /// Get the search indexes.
+    pub fn indexes(&self) -> &SearchIndexes {
+        &self.indexes
+    }
+
+    /// Get a mutable reference to the search indexes.
+    pub fn indexes_mut(&mut self) -> &mut SearchIndexes {
+        &mut self.indexes
+    }
+
+    /// Returns an iterator over the keys and their values, skipping the expired keys.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        let now = OffsetDateTime::now_utc();
+        self.entries
+            .iter()
+            .filter(move |(k, _)| !k.is_expired(now))
+            .map(|(k, e)| (k.value.as_ref(), &e.value))
+    }
+
+    /// Returns the keys (and their values) from the scan cursor onwards, in the order of
+    /// their scan hashes. Expired keys are skipped, but count towards `count`.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the next cursor, which is `0` once all the keys are scanned, and the
+    /// scanned keys.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&str, &Value)>) {
+        let now = OffsetDateTime::now_utc();
+        let (cursor, keys) = self.scan_order.walk(cursor, count);
+        let scanned = keys
+            .into_iter()
+            .filter_map(|k| {
+                let (key, entry) = self.entries.get_key_value(&k.into())?;
+                (!key.is_expired(now)).then_some((k, &entry.value))
+            })
+            .collect();
+
+        (cursor, scanned)
+    }
+
+    /// Sends a keyspace notification for an event on a key, if the class of the event is
+    /// enabled, and invalidates the key for the clients caching it. This must be called after
+    /// the key is modified.
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - The class of the event.
+    ///
+    /// * `event` - The name of the event, which is usually the name of the command.
+    ///
+    /// * `k` - The key on which the event occurred.
+    pub fn notify(&self, class: NotifyClass, event: &'static str, k: &str) {
+        // the key is modified by every event except the creation, which is followed by the
+        // event of the command which sets its value
+        if class != NotifyClass::New {
+            self.tracking.invalidate(k);
+        }
+
+        if !self.notify_flags.is_enabled(class) {
+            return;
+        }
+
+        if let Err(e) = self.notifications.send((event, k.to_string())) {
+            error!("Failed to send keyspace notification: {}", e);
+        }
+    }
+
+    /// Send an event to the background tasks which are subscribed to DB events.
+    pub fn send_event(&self, evt: DBEvent) -> Result<usize, broadcast::error::SendError<DBEvent>> {
+        self.events.send(evt)
+    }
+
+    /// Insert an entry, replacing the existing entry if any. The key itself is not replaced
+    /// if it already exists, so the existing key must be removed first to change its expiry.
+    fn insert_entry(&mut self, key: Key, entry: Entry) {
+        self.scan_order.insert(&key.value);
+        self.entries.insert(key, entry);
+    }
+
+    /// Remove the entry of a key, without updating the search indexes.
+    fn remove_entry(&mut self, k: &str) -> Option<(Key, Entry)> {
+        let (key, entry) = self.entries.remove_entry(&k.into())?;
+        self.scan_order.remove(&key.value);
+        Some((key, entry))
+    }
+}
+
+/// Builds the error returned when a database number is out of range.
+fn db_index_err() -> DBError {
+    DBError::Other(String::from("DB index is out of range"))
+}
+
 impl Display for Key {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.value)
@@ -394,7 +894,7 @@ impl Display for Key {
 impl From<String> for Key {
     fn from(value: String) -> Self {
         Key {
-            value,
+            value: Arc::from(value),
             expiry: None,
         }
     }
@@ -403,7 +903,7 @@ impl From<String> for Key {
 impl From<&str> for Key {
     fn from(s: &str) -> Self {
         Key {
-            value: s.to_string(),
+            value: Arc::from(s),
             expiry: None,
         }
     }
@@ -425,7 +925,15 @@ impl Eq for Key {}
 
 impl Key {
     pub fn new(value: String, expiry: Option<OffsetDateTime>) -> Key {
-        Key { value, expiry }
+        Key {
+            value: Arc::from(value),
+            expiry,
+        }
+    }
+
+    /// Returns `true` if the key has an expiry, which is at or before `now`.
+    fn is_expired(&self, now: OffsetDateTime) -> bool {
+        self.expiry.is_some_and(|expiry| expiry <= now)
     }
 }
 
@@ -434,3 +942,54 @@ impl Entry {
         Entry { value }
     }
 }
+
+impl Value {
+    /// Returns the name of the type of the value, as reported by the TYPE command. Types
+    /// which are provided by Redis modules are named after the module types.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::List(_) => "list",
+            Value::Hash(_) => "hash",
+            Value::SortedSet(_) => "zset",
+            Value::Json(_) => "ReJSON-RL",
+            Value::BloomFilter(_) => "MBbloom--",
+            Value::CuckooFilter(_) => "MBbloomCF",
+            Value::CountMinSketch(_) => "CMSk-TYPE",
+            Value::TopK(_) => "TopK-TYPE",
+            Value::TimeSeries(_) => "TSDB-TYPE",
+        }
+    }
+
+    /// Returns the effort to free the value, as the approximate number of allocations.
+    pub fn free_effort(&self) -> usize {
+        match self {
+            Value::List(l) => l.len(),
+            Value::Hash(h) => h.len(),
+            Value::SortedSet(zset) => zset.len(),
+            Value::Json(serde_json::Value::Array(arr)) => arr.len(),
+            Value::Json(serde_json::Value::Object(obj)) => obj.len(),
+            Value::TimeSeries(series) => series.total_samples(),
+            _ => 1,
+        }
+    }
+
+    /// Returns the approximate number of bytes used by the value, on the heap.
+    pub fn memory_usage(&self) -> usize {
+        match self {
+            Value::String(s) => s.capacity(),
+            Value::List(l) => l
+                .iter()
+                .map(|item| size_of::<String>() + item.capacity())
+                .sum(),
+            Value::Hash(h) => hash::memory_usage(h),
+            Value::SortedSet(zset) => zset.memory_usage(),
+            Value::Json(doc) => json::memory_usage(doc),
+            Value::BloomFilter(bf) => bf.memory_usage(),
+            Value::CuckooFilter(cf) => cf.memory_usage(),
+            Value::CountMinSketch(cms) => cms.memory_usage(),
+            Value::TopK(topk) => topk.memory_usage(),
+            Value::TimeSeries(series) => series.memory_usage(),
+        }
+    }
+}
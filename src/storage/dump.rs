@@ -0,0 +1,434 @@
+use std::collections::VecDeque;
+
+use time::{Duration, OffsetDateTime};
+
+use super::{
+    bloom::BloomFilter,
+    count_min_sketch::CountMinSketch,
+    cuckoo::CuckooFilter,
+    db::{Value, DB},
+    hash::Hash,
+    notify::NotifyClass,
+    sorted_set::SortedSet,
+    time_series::{track_retention, TimeSeries},
+    top_k::TopK,
+    DBError,
+};
+
+// DUMP serializes a value into a payload which RESTORE reads back, possibly on another
+// instance. The payload is laid out as:
+//
+//     <type tag: u8> <value> <format version: u16> <CRC-64 checksum: u64>
+//
+// Integers are little-endian, floats are stored as their bits, and variable length data
+// (bytes, strings and collections) is prefixed with its length as an u64. The checksum
+// covers everything before it, so that corrupted payloads are rejected. Payloads with a
+// newer format version are rejected too, since they may contain types or fields which are
+// not known to this version.
+
+/// Version of the payload format, which is bumped whenever the format changes.
+const DUMP_VERSION: u16 = 1;
+/// Number of bytes after the value, for the format version and the checksum.
+const DUMP_TRAILER_LEN: usize = 2 + 8;
+
+/// Polynomial of CRC-64/Jones (reflected), which is the checksum used by Redis too.
+const CRC64_POLY: u64 = 0x95ac9329ac4bc9b5;
+/// Lookup table for computing the checksum a byte at a time.
+const CRC64_TABLE: [u64; 256] = crc64_table();
+
+/// Types which can be serialized into a DUMP payload.
+pub trait Dump: Sized {
+    /// Writes the value into the payload.
+    fn dump(&self, enc: &mut Encoder);
+
+    /// Reads a value from the payload, which was written by `dump`. The value must be
+    /// validated, since the payload can be crafted.
+    fn restore(dec: &mut Decoder) -> Result<Self, DBError>;
+}
+
+/// Writes the fields of a value into a payload.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+/// The payload of a key serialized by DUMP, and the expiry of the key.
+pub type DumpedEntry = (Vec<u8>, Option<OffsetDateTime>);
+
+/// Reads the fields of a value from a payload.
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl DB {
+    /// Serializes the value stored at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<Vec<u8>>)` - The payload, or `None` if the key doesn't exist.
+    /// * `Err(DBError)` - if the DB lock cannot be acquired.
+    pub fn dump(&self, k: &str) -> Result<Option<Vec<u8>>, DBError> {
+        self.read(|keyspace| Ok(keyspace.get(k).map(dump_payload)))
+    }
+
+    /// Serializes the value stored at key, like `dump`, along with the expiry of the key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<DumpedEntry>)` - The payload and the expiry, or `None` if the key doesn't
+    ///   exist.
+    /// * `Err(DBError)` - if the DB lock cannot be acquired.
+    pub fn dump_with_expiry(&self, k: &str) -> Result<Option<DumpedEntry>, DBError> {
+        self.read(|keyspace| {
+            Ok(keyspace
+                .get(k)
+                .map(|value| (dump_payload(value), keyspace.expiry(k))))
+        })
+    }
+
+    /// Deletes a key, only if its value is still the one serialized into the payload. This
+    /// is used to delete a key after it's transferred to another instance, without losing
+    /// the writes made to it in the meantime.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the key is deleted.
+    /// * `Err(DBError)` - if the DB lock cannot be acquired.
+    pub fn del_if_dumped(&self, k: &str, payload: &[u8]) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).map(dump_payload).as_deref() != Some(payload) {
+                return Ok(false);
+            }
+
+            let deleted = keyspace.remove(k).is_some();
+            if deleted {
+                keyspace.notify(NotifyClass::Generic, "del", k);
+            }
+
+            Ok(deleted)
+        })
+    }
+
+    /// Creates a key with the value serialized by DUMP.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key to be created.
+    ///
+    /// * `payload` - The payload returned by DUMP.
+    ///
+    /// * `expiry` - Time at which the key expires, if any. The key is not created if the
+    ///   time has already passed.
+    ///
+    /// * `replace` - Set to `true` if the key must be replaced if it already exists.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the key is created.
+    /// * `Err(DBError)` - if the payload is not valid, or if the key already exists and
+    ///   `replace` is not set.
+    pub fn restore(
+        &self,
+        k: &str,
+        payload: &[u8],
+        expiry: Option<OffsetDateTime>,
+        replace: bool,
+    ) -> Result<(), DBError> {
+        let value = restore_payload(payload)?;
+
+        self.write(|keyspace| {
+            if !replace && keyspace.get(k).is_some() {
+                return Err(DBError::Other(String::from(
+                    "BUSYKEY Target key name already exists.",
+                )));
+            }
+
+            if expiry.is_some_and(|expiry| expiry <= OffsetDateTime::now_utc()) {
+                if keyspace.remove(k).is_some() {
+                    keyspace.notify(NotifyClass::Generic, "del", k);
+                }
+                return Ok(());
+            }
+
+            keyspace.insert_with_expiry(k, value, expiry);
+            track_retention(keyspace, k);
+            keyspace.notify(NotifyClass::Generic, "restore", k);
+
+            Ok(())
+        })
+    }
+}
+
+/// Serializes a value into a payload, along with the format version and the checksum.
+fn dump_payload(value: &Value) -> Vec<u8> {
+    let mut enc = Encoder::default();
+    value.dump(&mut enc);
+
+    let mut payload = enc.buf;
+    payload.extend_from_slice(&DUMP_VERSION.to_le_bytes());
+    let checksum = crc64(&payload);
+    payload.extend_from_slice(&checksum.to_le_bytes());
+
+    payload
+}
+
+/// Reads a value from a payload, after validating its format version and checksum.
+fn restore_payload(payload: &[u8]) -> Result<Value, DBError> {
+    let version_err = || DBError::Other(String::from("DUMP payload version or checksum are wrong"));
+
+    if payload.len() < DUMP_TRAILER_LEN {
+        return Err(version_err());
+    }
+    let (data, checksum) = payload.split_at(payload.len() - 8);
+    let (value, version) = data.split_at(data.len() - 2);
+
+    let version = u16::from_le_bytes([version[0], version[1]]);
+    let checksum = u64::from_le_bytes(checksum.try_into().map_err(|_| version_err())?);
+    if version > DUMP_VERSION || crc64(data) != checksum {
+        return Err(version_err());
+    }
+
+    let mut dec = Decoder { buf: value, pos: 0 };
+    let value = Value::restore(&mut dec)?;
+    // the whole payload must be read
+    if dec.pos != dec.buf.len() {
+        return Err(bad_format_err());
+    }
+
+    Ok(value)
+}
+
+impl Dump for Value {
+    fn dump(&self, enc: &mut Encoder) {
+        match self {
+            Value::String(s) => {
+                enc.u8(0);
+                enc.bytes(s);
+            }
+            Value::List(l) => {
+                enc.u8(1);
+                enc.size(l.len());
+                l.iter().for_each(|item| enc.string(item));
+            }
+            Value::Hash(h) => {
+                enc.u8(2);
+                enc.size(h.len());
+                for (field, value) in h.iter() {
+                    enc.string(field);
+                    enc.bytes(value);
+                }
+            }
+            Value::SortedSet(zset) => {
+                enc.u8(3);
+                enc.size(zset.len());
+                for (member, score) in zset.iter() {
+                    enc.string(member);
+                    enc.f64(score);
+                }
+            }
+            Value::Json(doc) => {
+                enc.u8(4);
+                enc.string(&doc.to_string());
+            }
+            Value::BloomFilter(bf) => {
+                enc.u8(5);
+                bf.dump(enc);
+            }
+            Value::CuckooFilter(cf) => {
+                enc.u8(6);
+                cf.dump(enc);
+            }
+            Value::CountMinSketch(cms) => {
+                enc.u8(7);
+                cms.dump(enc);
+            }
+            Value::TopK(topk) => {
+                enc.u8(8);
+                topk.dump(enc);
+            }
+            Value::TimeSeries(series) => {
+                enc.u8(9);
+                series.dump(enc);
+            }
+        }
+    }
+
+    fn restore(dec: &mut Decoder) -> Result<Value, DBError> {
+        let value = match dec.u8()? {
+            0 => Value::String(dec.bytes()?),
+            1 => {
+                let mut list = VecDeque::new();
+                for _ in 0..dec.size()? {
+                    list.push_back(dec.string()?);
+                }
+                Value::List(list)
+            }
+            2 => {
+                let mut hash = Hash::new();
+                for _ in 0..dec.size()? {
+                    let field = dec.string()?;
+                    hash.insert(field, dec.bytes()?);
+                }
+                Value::Hash(hash)
+            }
+            3 => {
+                let mut zset = SortedSet::new();
+                for _ in 0..dec.size()? {
+                    let member = dec.string()?;
+                    let score = dec.f64()?;
+                    if score.is_nan() {
+                        return Err(bad_format_err());
+                    }
+                    zset.insert(member, score);
+                }
+                Value::SortedSet(zset)
+            }
+            4 => {
+                let doc = serde_json::from_str(&dec.string()?).map_err(|_| bad_format_err())?;
+                Value::Json(doc)
+            }
+            5 => Value::BloomFilter(BloomFilter::restore(dec)?),
+            6 => Value::CuckooFilter(CuckooFilter::restore(dec)?),
+            7 => Value::CountMinSketch(CountMinSketch::restore(dec)?),
+            8 => Value::TopK(TopK::restore(dec)?),
+            9 => Value::TimeSeries(TimeSeries::restore(dec)?),
+            _ => return Err(bad_format_err()),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Encoder {
+    pub fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    pub fn u64(&mut self, v: u64) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    pub fn f64(&mut self, v: f64) {
+        self.u64(v.to_bits());
+    }
+
+    /// Writes the length of a collection.
+    pub fn size(&mut self, v: usize) {
+        self.u64(v as u64);
+    }
+
+    pub fn bytes(&mut self, v: &[u8]) {
+        self.size(v.len());
+        self.buf.extend_from_slice(v);
+    }
+
+    pub fn string(&mut self, v: &str) {
+        self.bytes(v.as_bytes());
+    }
+}
+
+impl Decoder<'_> {
+    pub fn u8(&mut self) -> Result<u8, DBError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u64(&mut self) -> Result<u64, DBError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(
+            bytes.try_into().map_err(|_| bad_format_err())?,
+        ))
+    }
+
+    pub fn f64(&mut self) -> Result<f64, DBError> {
+        Ok(f64::from_bits(self.u64()?))
+    }
+
+    /// Reads the length of a collection. Each element takes at least a byte, so lengths
+    /// beyond the remaining data are rejected, before anything is allocated for them.
+    pub fn size(&mut self) -> Result<usize, DBError> {
+        let size = self.u64()?;
+        if size > (self.buf.len() - self.pos) as u64 {
+            return Err(bad_format_err());
+        }
+        Ok(size as usize)
+    }
+
+    pub fn bytes(&mut self) -> Result<Vec<u8>, DBError> {
+        let len = self.size()?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    pub fn string(&mut self) -> Result<String, DBError> {
+        String::from_utf8(self.bytes()?).map_err(|_| bad_format_err())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], DBError> {
+        let end = self.pos.checked_add(len).ok_or_else(bad_format_err)?;
+        let bytes = self.buf.get(self.pos..end).ok_or_else(bad_format_err)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+}
+
+/// Builds the error returned when a payload can't be read, despite a valid checksum.
+pub fn bad_format_err() -> DBError {
+    DBError::Other(String::from("Bad data format"))
+}
+
+/// Converts the TTL given to RESTORE into the expiry time of the key.
+///
+/// # Arguments
+///
+/// * `ttl` - TTL in milliseconds, or `0` if the key doesn't expire.
+///
+/// * `absolute` - Set to `true` if the TTL is a Unix time in milliseconds, at which the key
+///   expires.
+///
+/// # Returns
+///
+/// * `Ok(Option<OffsetDateTime>)` - The expiry time of the key, if any.
+/// * `Err(DBError)` - If the expiry time is out of the supported range.
+pub fn restore_expiry(ttl: u64, absolute: bool) -> Result<Option<OffsetDateTime>, DBError> {
+    if ttl == 0 {
+        return Ok(None);
+    }
+
+    let base = match absolute {
+        true => OffsetDateTime::UNIX_EPOCH,
+        false => OffsetDateTime::now_utc(),
+    };
+    i64::try_from(ttl)
+        .ok()
+        .and_then(|ttl| base.checked_add(Duration::milliseconds(ttl)))
+        .map(Some)
+        .ok_or_else(|| DBError::Other(String::from("Invalid TTL value")))
+}
+
+/// Computes the CRC-64/Jones checksum of the data.
+fn crc64(data: &[u8]) -> u64 {
+    data.iter().fold(0, |crc, b| {
+        CRC64_TABLE[((crc ^ *b as u64) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+const fn crc64_table() -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC64_POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
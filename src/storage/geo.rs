@@ -0,0 +1,659 @@
+use std::f64::consts::FRAC_PI_2;
+
+use super::{
+    db::{Keyspace, Value, DB},
+    notify::NotifyClass,
+    sorted_set::SortedSet,
+    DBError,
+};
+
+// Locations are stored in a sorted set, with a 52 bit geohash of the location as the score of
+// the member. The geohash is computed the same way as Redis, hence the scores are compatible.
+//
+// The geohash is obtained by dividing the longitude and latitude ranges into 2^26 cells each,
+// and interleaving the bits of the longitude cell index and latitude cell index. The latitude
+// bits take the even positions, and the longitude bits take the odd positions. Locations that
+// are close to each other share the prefix of their geohash, so a cell of any size maps to a
+// contiguous range of scores.
+//
+// The latitude range is limited to the one supported by the Web Mercator projection, so
+// that the locations close to the poles can still be indexed.
+
+const GEO_LONG_MIN: f64 = -180.0;
+const GEO_LONG_MAX: f64 = 180.0;
+const GEO_LAT_MIN: f64 = -85.051_128_78;
+const GEO_LAT_MAX: f64 = 85.051_128_78;
+
+/// Number of bits used for each of longitude and latitude in the geohash.
+const GEO_STEP_MAX: u32 = 26;
+/// Earth's quadratic mean radius for WGS-84. This is same as the one used by Redis.
+const EARTH_RADIUS_IN_METERS: f64 = 6_372_797.560_856;
+const MERCATOR_MAX: f64 = 20_037_726.37;
+/// Alphabet used for the standard base32 geohash strings.
+const GEOHASH_ALPHABET: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// Unit of the distances used in the geo commands.
+#[derive(Debug, Clone, Copy)]
+pub enum GeoUnit {
+    Meters,
+    Kilometers,
+    Miles,
+    Feet,
+}
+
+/// The center of a geo search.
+#[derive(Debug, Clone)]
+pub enum GeoOrigin {
+    /// Position of an existing member in the sorted set.
+    Member(String),
+    /// Longitude and latitude.
+    LonLat(f64, f64),
+}
+
+/// The area covered by a geo search.
+#[derive(Debug, Clone, Copy)]
+pub enum GeoShape {
+    /// A circle with the given radius.
+    Radius(f64),
+    /// An axis aligned rectangle with the given width and height.
+    Box { width: f64, height: f64 },
+}
+
+/// Order of the results of a geo search, based on the distance from the center.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeoSort {
+    None,
+    Asc,
+    Desc,
+}
+
+/// Options for a geo search.
+#[derive(Debug, Clone)]
+pub struct GeoSearch {
+    pub origin: GeoOrigin,
+    pub shape: GeoShape,
+    /// Unit of the dimensions of the shape.
+    pub unit: GeoUnit,
+    pub sort: GeoSort,
+    /// Maximum number of results to be returned.
+    pub count: Option<usize>,
+    /// If set, search stops as soon as `count` matches are found. Hence the results are not
+    /// necessarily the closest ones.
+    pub any: bool,
+}
+
+/// A member which matches a geo search.
+#[derive(Debug, Clone)]
+pub struct GeoMatch {
+    pub member: String,
+    /// Distance from the center of the search, in meters.
+    pub dist: f64,
+    pub hash: u64,
+    pub lon: f64,
+    pub lat: f64,
+}
+
+impl GeoUnit {
+    /// Returns the number of meters in one unit.
+    pub fn to_meters(self) -> f64 {
+        match self {
+            GeoUnit::Meters => 1.0,
+            GeoUnit::Kilometers => 1000.0,
+            GeoUnit::Miles => 1609.34,
+            GeoUnit::Feet => 0.3048,
+        }
+    }
+}
+
+impl DB {
+    /// Adds the locations to the sorted set stored at key. An empty sorted set is created if the
+    /// key doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the sorted set is stored.
+    ///
+    /// * `locations` - List of longitude, latitude and member name.
+    ///
+    /// * `nx` - Only add new members, don't update the existing ones.
+    ///
+    /// * `xx` - Only update the existing members, don't add new ones.
+    ///
+    /// * `ch` - Count the updated members along with the added ones.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of members added (and updated, if `ch` is set).
+    /// * `Err(DBError)` - if key already exists and has non sorted set data.
+    pub fn geoadd(
+        &self,
+        k: &str,
+        locations: &[(f64, f64, String)],
+        nx: bool,
+        xx: bool,
+        ch: bool,
+    ) -> Result<usize, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_none() {
+                if xx {
+                    return Ok(0);
+                }
+                keyspace.insert(k, Value::SortedSet(SortedSet::new()));
+            }
+
+            let zset = match zset_value_mut(keyspace, k)? {
+                Some(zset) => zset,
+                None => return Ok(0),
+            };
+
+            let mut added = 0;
+            let mut updated = 0;
+            for (lon, lat, member) in locations {
+                let score = encode(*lon, *lat, GEO_STEP_MAX, GEO_LAT_MIN, GEO_LAT_MAX) as f64;
+                match zset.score(member) {
+                    Some(_) if nx => {}
+                    Some(old) => {
+                        if old != score {
+                            zset.insert(member.clone(), score);
+                            updated += 1;
+                        }
+                    }
+                    None if xx => {}
+                    None => {
+                        zset.insert(member.clone(), score);
+                        added += 1;
+                    }
+                }
+            }
+            if added + updated > 0 {
+                keyspace.notify(NotifyClass::ZSet, "zadd", k);
+            }
+
+            Ok(if ch { added + updated } else { added })
+        })
+    }
+
+    /// Returns the longitude and latitude of the members of the sorted set stored at key.
+    /// `None` is returned for the members which don't exist.
+    pub fn geopos(&self, k: &str, members: &[String]) -> Result<Vec<Option<(f64, f64)>>, DBError> {
+        self.read(|keyspace| {
+            let zset = match zset_value(keyspace, k)? {
+                Some(zset) => zset,
+                None => return Ok(vec![None; members.len()]),
+            };
+
+            Ok(members
+                .iter()
+                .map(|m| zset.score(m).map(|score| decode(score as u64)))
+                .collect())
+        })
+    }
+
+    /// Returns the distance between two members of the sorted set stored at key, in meters.
+    /// `None` is returned if either of the members doesn't exist.
+    pub fn geodist(&self, k: &str, member1: &str, member2: &str) -> Result<Option<f64>, DBError> {
+        self.read(|keyspace| {
+            let zset = match zset_value(keyspace, k)? {
+                Some(zset) => zset,
+                None => return Ok(None),
+            };
+
+            match (zset.score(member1), zset.score(member2)) {
+                (Some(score1), Some(score2)) => {
+                    let (lon1, lat1) = decode(score1 as u64);
+                    let (lon2, lat2) = decode(score2 as u64);
+                    Ok(Some(distance(lon1, lat1, lon2, lat2)))
+                }
+                _ => Ok(None),
+            }
+        })
+    }
+
+    /// Returns the standard 11 character geohash strings of the members of the sorted set stored
+    /// at key. `None` is returned for the members which don't exist.
+    pub fn geohash(&self, k: &str, members: &[String]) -> Result<Vec<Option<String>>, DBError> {
+        self.read(|keyspace| {
+            let zset = match zset_value(keyspace, k)? {
+                Some(zset) => zset,
+                None => return Ok(vec![None; members.len()]),
+            };
+
+            Ok(members
+                .iter()
+                .map(|m| zset.score(m).map(|score| geohash_string(score as u64)))
+                .collect())
+        })
+    }
+
+    /// Returns the members of the sorted set stored at key, which are within the area
+    /// specified by the search.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<GeoMatch>)` - The matching members. Empty if the key doesn't exist.
+    /// * `Err(DBError)` - if key has non sorted set data, or if the member used as the
+    ///   center of the search doesn't exist.
+    pub fn geosearch(&self, k: &str, search: &GeoSearch) -> Result<Vec<GeoMatch>, DBError> {
+        self.read(|keyspace| search_members(keyspace, k, search))
+    }
+
+    /// Same as `geosearch`, but the results are stored in a sorted set at the destination key.
+    /// The destination key is deleted if there are no matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `dest` - The key on which the results are to be stored.
+    ///
+    /// * `src` - The key on which the searched sorted set is stored.
+    ///
+    /// * `search` - Options for the search.
+    ///
+    /// * `store_dist` - If set, the distances (in the unit of the search) are stored as the
+    ///   scores, instead of the geohashes.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of members in the resulting sorted set.
+    /// * `Err(DBError)` - If the search fails.
+    pub fn geosearchstore(
+        &self,
+        dest: &str,
+        src: &str,
+        search: &GeoSearch,
+        store_dist: bool,
+    ) -> Result<usize, DBError> {
+        self.write(|keyspace| {
+            let matches = search_members(keyspace, src, search)?;
+
+            let mut zset = SortedSet::new();
+            for m in matches {
+                let score = if store_dist {
+                    m.dist / search.unit.to_meters()
+                } else {
+                    m.hash as f64
+                };
+                zset.insert(m.member, score);
+            }
+
+            if zset.is_empty() {
+                if keyspace.remove(dest).is_some() {
+                    keyspace.notify(NotifyClass::Generic, "del", dest);
+                }
+                return Ok(0);
+            }
+
+            let len = zset.len();
+            keyspace.insert(dest, Value::SortedSet(zset));
+            keyspace.notify(NotifyClass::ZSet, "geosearchstore", dest);
+
+            Ok(len)
+        })
+    }
+}
+
+/// Checks if the longitude and latitude can be indexed.
+pub fn is_valid_location(lon: f64, lat: f64) -> bool {
+    (GEO_LONG_MIN..=GEO_LONG_MAX).contains(&lon) && (GEO_LAT_MIN..=GEO_LAT_MAX).contains(&lat)
+}
+
+/// Returns the sorted set stored at key, if any.
+fn zset_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<Option<&'a SortedSet>, DBError> {
+    match keyspace.get(k) {
+        Some(Value::SortedSet(zset)) => Ok(Some(zset)),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(None),
+    }
+}
+
+/// Returns the mutable sorted set stored at key, if any.
+fn zset_value_mut<'a>(
+    keyspace: &'a mut Keyspace,
+    k: &str,
+) -> Result<Option<&'a mut SortedSet>, DBError> {
+    match keyspace.get_mut(k) {
+        Some(Value::SortedSet(zset)) => Ok(Some(zset)),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(None),
+    }
+}
+
+/// Finds the members within the search area. Only the cells around the center, which cover the
+/// search area, are scanned.
+fn search_members(
+    keyspace: &Keyspace,
+    k: &str,
+    search: &GeoSearch,
+) -> Result<Vec<GeoMatch>, DBError> {
+    let zset = match zset_value(keyspace, k)? {
+        Some(zset) => zset,
+        None => return Ok(vec![]),
+    };
+
+    let (lon, lat) = match &search.origin {
+        GeoOrigin::LonLat(lon, lat) => (*lon, *lat),
+        GeoOrigin::Member(m) => match zset.score(m) {
+            Some(score) => decode(score as u64),
+            None => {
+                return Err(DBError::Other(String::from(
+                    "could not decode requested zset member",
+                )))
+            }
+        },
+    };
+
+    let shape = match search.shape {
+        GeoShape::Radius(r) => GeoShape::Radius(r * search.unit.to_meters()),
+        GeoShape::Box { width, height } => GeoShape::Box {
+            width: width * search.unit.to_meters(),
+            height: height * search.unit.to_meters(),
+        },
+    };
+
+    // with ANY, search stops at the first `count` matches
+    let limit = if search.any { search.count } else { None };
+
+    let mut matches = vec![];
+    'cells: for (min, max) in search_ranges(lon, lat, shape) {
+        for (member, score) in zset.range_by_score(min as f64, max as f64) {
+            let hash = score as u64;
+            let (member_lon, member_lat) = decode(hash);
+            if let Some(dist) = distance_in_shape(lon, lat, member_lon, member_lat, shape) {
+                matches.push(GeoMatch {
+                    member: member.to_string(),
+                    dist,
+                    hash,
+                    lon: member_lon,
+                    lat: member_lat,
+                });
+
+                if limit.is_some_and(|l| matches.len() >= l) {
+                    break 'cells;
+                }
+            }
+        }
+    }
+
+    // results are sorted by default when COUNT is specified, so that the closest ones are returned
+    let sort = match search.sort {
+        GeoSort::None if search.count.is_some() && !search.any => GeoSort::Asc,
+        sort => sort,
+    };
+    match sort {
+        GeoSort::Asc => matches.sort_by(|a, b| a.dist.total_cmp(&b.dist)),
+        GeoSort::Desc => matches.sort_by(|a, b| b.dist.total_cmp(&a.dist)),
+        GeoSort::None => {}
+    }
+
+    if let Some(count) = search.count {
+        matches.truncate(count);
+    }
+
+    Ok(matches)
+}
+
+/// Returns the score ranges of the cell containing the center of the search and its 8
+/// neighbours. The size of the cells is chosen such that these cells cover the entire search area.
+/// Dimensions of the shape are in meters.
+fn search_ranges(lon: f64, lat: f64, shape: GeoShape) -> Vec<(u64, u64)> {
+    let (min_lon, min_lat, max_lon, max_lat) = bounding_box(lon, lat, shape);
+    let radius = match shape {
+        GeoShape::Radius(r) => r,
+        GeoShape::Box { width, height } => (width / 2.0).hypot(height / 2.0),
+    };
+
+    // the search area covers all the longitudes when it crosses a pole
+    if max_lat >= 90.0 || min_lat <= -90.0 {
+        return vec![(0, 1 << (2 * GEO_STEP_MAX))];
+    }
+
+    let mut step = estimate_steps(radius, lat);
+    let mut center = cell_index(lon, lat, step);
+
+    // The estimated step may not be small enough when the center is close to the edge of its
+    // cell. Use bigger cells until the neighbouring cells cover the bounding box.
+    while step > 1 {
+        let (lat_idx, lon_idx) = center;
+        let (_, _, north_lat, _) = cell_bounds(lat_idx + 1, lon_idx, step);
+        let (south_lat, _, _, _) = cell_bounds(lat_idx - 1, lon_idx, step);
+        let (_, _, _, east_lon) = cell_bounds(lat_idx, lon_idx + 1, step);
+        let (_, west_lon, _, _) = cell_bounds(lat_idx, lon_idx - 1, step);
+        if north_lat >= max_lat
+            && south_lat <= min_lat
+            && east_lon >= max_lon
+            && west_lon <= min_lon
+        {
+            break;
+        }
+
+        step -= 1;
+        center = cell_index(lon, lat, step);
+    }
+
+    let cells = 1i64 << step;
+    let shift = 2 * (GEO_STEP_MAX - step);
+    let mut ranges = vec![];
+    for lat_delta in -1..=1 {
+        let lat_idx = center.0 + lat_delta;
+        if lat_idx < 0 || lat_idx >= cells {
+            continue;
+        }
+
+        for lon_delta in -1..=1 {
+            // longitudes wrap around the anti-meridian
+            let lon_idx = (center.1 + lon_delta).rem_euclid(cells);
+            let hash = interleave(lat_idx as u32, lon_idx as u32);
+            ranges.push((hash << shift, (hash + 1) << shift));
+        }
+    }
+
+    // small number of cells may have the same neighbours on either side
+    ranges.sort_unstable();
+    ranges.dedup();
+
+    ranges
+}
+
+/// Estimates the number of bits (per coordinate) of the cells, so that the area of the given
+/// radius is covered by a cell and its neighbours.
+fn estimate_steps(radius: f64, lat: f64) -> u32 {
+    if radius == 0.0 {
+        return GEO_STEP_MAX;
+    }
+
+    let mut range = radius;
+    let mut step: i32 = 1;
+    while range < MERCATOR_MAX {
+        range *= 2.0;
+        step += 1;
+    }
+    step -= 2;
+
+    // cells are narrower towards the poles
+    if !(-66.0..=66.0).contains(&lat) {
+        step -= 1;
+        if !(-80.0..=80.0).contains(&lat) {
+            step -= 1;
+        }
+    }
+
+    step.clamp(1, GEO_STEP_MAX as i32) as u32
+}
+
+/// Returns the bounding box (min longitude, min latitude, max longitude, max latitude) of the
+/// search area. Dimensions of the shape are in meters.
+fn bounding_box(lon: f64, lat: f64, shape: GeoShape) -> (f64, f64, f64, f64) {
+    let (half_width, half_height) = match shape {
+        GeoShape::Radius(r) => (r, r),
+        GeoShape::Box { width, height } => (width / 2.0, height / 2.0),
+    };
+
+    let lat_delta = (half_height / EARTH_RADIUS_IN_METERS).to_degrees();
+
+    // The longitude span is derived by inverting the haversine formula, since the distances
+    // are great circle distances. All the longitudes are covered when the ratio reaches 1.
+    let lon_delta = match shape {
+        GeoShape::Radius(_) => {
+            let angle = (half_width / EARTH_RADIUS_IN_METERS).min(FRAC_PI_2);
+            let ratio = angle.sin() / lat.to_radians().cos();
+            if ratio < 1.0 {
+                ratio.asin().to_degrees()
+            } else {
+                180.0
+            }
+        }
+        GeoShape::Box { .. } => {
+            // points closer to the pole can be farther apart in longitude, for the same distance
+            let polar_lat = lat.abs() + lat_delta;
+            let angle = (half_width / (2.0 * EARTH_RADIUS_IN_METERS)).min(FRAC_PI_2);
+            let ratio = angle.sin() / polar_lat.to_radians().cos();
+            if ratio < 1.0 {
+                2.0 * ratio.asin().to_degrees()
+            } else {
+                180.0
+            }
+        }
+    };
+
+    (
+        lon - lon_delta,
+        lat - lat_delta,
+        lon + lon_delta,
+        lat + lat_delta,
+    )
+}
+
+/// Returns the distance of the point from the center, if the point is within the search area.
+/// Dimensions of the shape are in meters.
+fn distance_in_shape(lon: f64, lat: f64, p_lon: f64, p_lat: f64, shape: GeoShape) -> Option<f64> {
+    match shape {
+        GeoShape::Radius(r) => {
+            let dist = distance(lon, lat, p_lon, p_lat);
+            (dist <= r).then_some(dist)
+        }
+        GeoShape::Box { width, height } => {
+            let lat_dist = EARTH_RADIUS_IN_METERS * (p_lat.to_radians() - lat.to_radians()).abs();
+            if lat_dist > height / 2.0 {
+                return None;
+            }
+
+            let lon_dist = distance(p_lon, p_lat, lon, p_lat);
+            if lon_dist > width / 2.0 {
+                return None;
+            }
+
+            Some(distance(lon, lat, p_lon, p_lat))
+        }
+    }
+}
+
+/// Returns the distance between two points in meters, using the haversine formula.
+fn distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let u = ((lat2 - lat1) / 2.0).sin();
+    let v = ((lon2.to_radians() - lon1.to_radians()) / 2.0).sin();
+
+    2.0 * EARTH_RADIUS_IN_METERS * (u * u + lat1.cos() * lat2.cos() * v * v).sqrt().asin()
+}
+
+/// Returns the (latitude, longitude) index of the cell containing the point.
+fn cell_index(lon: f64, lat: f64, step: u32) -> (i64, i64) {
+    let cells = (1u64 << step) as f64;
+    let lat_offset = (lat - GEO_LAT_MIN) / (GEO_LAT_MAX - GEO_LAT_MIN) * cells;
+    let lon_offset = (lon - GEO_LONG_MIN) / (GEO_LONG_MAX - GEO_LONG_MIN) * cells;
+
+    let max_idx = (1i64 << step) - 1;
+    (
+        (lat_offset as i64).clamp(0, max_idx),
+        (lon_offset as i64).clamp(0, max_idx),
+    )
+}
+
+/// Returns the bounds (min latitude, min longitude, max latitude, max longitude) of a cell.
+/// The indices may be out of range, in which case the bounds are extrapolated.
+fn cell_bounds(lat_idx: i64, lon_idx: i64, step: u32) -> (f64, f64, f64, f64) {
+    let cells = (1u64 << step) as f64;
+    let lat_scale = GEO_LAT_MAX - GEO_LAT_MIN;
+    let lon_scale = GEO_LONG_MAX - GEO_LONG_MIN;
+
+    (
+        GEO_LAT_MIN + (lat_idx as f64 / cells) * lat_scale,
+        GEO_LONG_MIN + (lon_idx as f64 / cells) * lon_scale,
+        GEO_LAT_MIN + ((lat_idx + 1) as f64 / cells) * lat_scale,
+        GEO_LONG_MIN + ((lon_idx + 1) as f64 / cells) * lon_scale,
+    )
+}
+
+/// Computes the geohash of a point, with `step` bits for each coordinate.
+fn encode(lon: f64, lat: f64, step: u32, lat_min: f64, lat_max: f64) -> u64 {
+    let cells = (1u64 << step) as f64;
+    let max_idx = (1u64 << step) - 1;
+    let lat_offset = (lat - lat_min) / (lat_max - lat_min) * cells;
+    let lon_offset = (lon - GEO_LONG_MIN) / (GEO_LONG_MAX - GEO_LONG_MIN) * cells;
+
+    interleave(
+        (lat_offset as u64).min(max_idx) as u32,
+        (lon_offset as u64).min(max_idx) as u32,
+    )
+}
+
+/// Returns the longitude and latitude of the center of the cell of a 52 bit geohash.
+fn decode(hash: u64) -> (f64, f64) {
+    let (lat_idx, lon_idx) = deinterleave(hash);
+    let (min_lat, min_lon, max_lat, max_lon) =
+        cell_bounds(lat_idx as i64, lon_idx as i64, GEO_STEP_MAX);
+
+    (
+        ((min_lon + max_lon) / 2.0).clamp(GEO_LONG_MIN, GEO_LONG_MAX),
+        ((min_lat + max_lat) / 2.0).clamp(GEO_LAT_MIN, GEO_LAT_MAX),
+    )
+}
+
+/// Returns the standard geohash string (which uses the full latitude range) of a 52 bit geohash.
+fn geohash_string(hash: u64) -> String {
+    let (lon, lat) = decode(hash);
+    let hash = encode(lon, lat, GEO_STEP_MAX, -90.0, 90.0);
+
+    // Each character encodes 5 bits. The 11th character would need 55 bits, while the hash has
+    // only 52 bits, hence it's always '0'.
+    (0..11)
+        .map(|i| {
+            let idx = if i == 10 {
+                0
+            } else {
+                (hash >> (52 - (i + 1) * 5)) & 0x1f
+            };
+            GEOHASH_ALPHABET[idx as usize] as char
+        })
+        .collect()
+}
+
+/// Interleaves the bits of latitude and longitude indices. Latitude bits take the even
+/// positions and longitude bits take the odd positions.
+fn interleave(lat_idx: u32, lon_idx: u32) -> u64 {
+    spread(lat_idx) | (spread(lon_idx) << 1)
+}
+
+/// Reverse of `interleave`. Returns the (latitude, longitude) indices.
+fn deinterleave(hash: u64) -> (u32, u32) {
+    (squash(hash), squash(hash >> 1))
+}
+
+/// Spreads the bits of `v` to the even positions of a 64 bit integer.
+fn spread(v: u32) -> u64 {
+    let mut x = v as u64;
+    x = (x | (x << 16)) & 0x0000_ffff_0000_ffff;
+    x = (x | (x << 8)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x << 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & 0x5555_5555_5555_5555
+}
+
+/// Collects the bits at the even positions of `x`.
+fn squash(x: u64) -> u32 {
+    let mut x = x & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0f0f_0f0f_0f0f_0f0f;
+    x = (x | (x >> 4)) & 0x00ff_00ff_00ff_00ff;
+    x = (x | (x >> 8)) & 0x0000_ffff_0000_ffff;
+    ((x | (x >> 16)) & 0x0000_0000_ffff_ffff) as u32
+}
@@ -0,0 +1,194 @@
+use std::{collections::HashMap, mem::size_of, sync::Arc};
+
+use super::{
+    db::{Keyspace, Value, DB},
+    keys::ScanOrder,
+    notify::NotifyClass,
+    DBError,
+};
+
+/// A hash maps field names to binary safe values.
+///
+/// The fields are also kept in the order of their scan hashes, which HSCAN cursors walk
+/// through. The names of the fields are shared with the scan order.
+#[derive(Debug, Clone, Default)]
+pub struct Hash {
+    fields: HashMap<Arc<str>, Vec<u8>>,
+    scan_order: ScanOrder,
+}
+
+impl DB {
+    /// Sets the fields of the hash stored at key. The hash is created if the key doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of fields which were added (and not updated).
+    /// * `Err(DBError)` - if key already exists and is not a hash.
+    pub fn hset(&self, k: &str, fields: &[(String, Vec<u8>)]) -> Result<usize, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_none() {
+                keyspace.insert(k, Value::Hash(Hash::new()));
+            }
+
+            let hash = match keyspace.get_mut(k) {
+                Some(Value::Hash(hash)) => hash,
+                _ => return Err(DBError::WrongType),
+            };
+
+            let added = fields
+                .iter()
+                .filter(|(field, value)| hash.insert(field.clone(), value.clone()).is_none())
+                .count();
+
+            keyspace.reindex(k);
+            keyspace.notify(NotifyClass::Hash, "hset", k);
+            Ok(added)
+        })
+    }
+
+    /// Returns the value of a field of the hash stored at key.
+    pub fn hget(&self, k: &str, field: &str) -> Result<Option<Vec<u8>>, DBError> {
+        self.read(|keyspace| Ok(hash_value(keyspace, k)?.and_then(|h| h.get(field).cloned())))
+    }
+
+    /// Returns all the fields and values of the hash stored at key.
+    pub fn hgetall(&self, k: &str) -> Result<Vec<(String, Vec<u8>)>, DBError> {
+        self.read(|keyspace| {
+            Ok(hash_value(keyspace, k)?
+                .map(|h| h.iter().map(|(f, v)| (f.to_string(), v.clone())).collect())
+                .unwrap_or_default())
+        })
+    }
+
+    /// Removes the fields from the hash stored at key. The key is deleted once the hash is
+    /// empty.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of fields which were removed.
+    /// * `Err(DBError)` - if key already exists and is not a hash.
+    pub fn hdel(&self, k: &str, fields: &[String]) -> Result<usize, DBError> {
+        self.write(|keyspace| {
+            let hash = match keyspace.get_mut(k) {
+                Some(Value::Hash(hash)) => hash,
+                Some(_) => return Err(DBError::WrongType),
+                None => return Ok(0),
+            };
+
+            let removed = fields
+                .iter()
+                .filter(|field| hash.remove(field.as_str()).is_some())
+                .count();
+
+            let emptied = hash.is_empty();
+            if removed > 0 {
+                keyspace.notify(NotifyClass::Hash, "hdel", k);
+            }
+            if emptied {
+                keyspace.remove(k);
+                keyspace.notify(NotifyClass::Generic, "del", k);
+            } else {
+                keyspace.reindex(k);
+            }
+            Ok(removed)
+        })
+    }
+}
+
+impl Hash {
+    /// Creates an empty hash.
+    pub fn new() -> Hash {
+        Hash::default()
+    }
+
+    /// Returns the value of a field.
+    pub fn get(&self, field: &str) -> Option<&Vec<u8>> {
+        self.fields.get(field)
+    }
+
+    /// Sets the value of a field.
+    ///
+    /// # Returns
+    ///
+    /// The previous value of the field, if it was already present.
+    pub fn insert(&mut self, field: String, value: Vec<u8>) -> Option<Vec<u8>> {
+        if let Some(old) = self.fields.get_mut(field.as_str()) {
+            return Some(std::mem::replace(old, value));
+        }
+
+        let field: Arc<str> = Arc::from(field);
+        self.scan_order.insert(&field);
+        self.fields.insert(field, value);
+        None
+    }
+
+    /// Removes a field.
+    ///
+    /// # Returns
+    ///
+    /// The value of the field, if it was present.
+    pub fn remove(&mut self, field: &str) -> Option<Vec<u8>> {
+        let (field, value) = self.fields.remove_entry(field)?;
+        self.scan_order.remove(&field);
+        Some(value)
+    }
+
+    /// Returns the fields along with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Vec<u8>)> {
+        self.fields.iter().map(|(f, v)| (f.as_ref(), v))
+    }
+
+    /// Returns the names of the fields, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.fields.keys().map(|f| f.as_ref())
+    }
+
+    /// Returns the number of fields in the hash.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Checks if the hash has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the fields (and their values) from the scan cursor onwards, in the order of
+    /// their scan hashes.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the next cursor, which is `0` once all the fields are scanned, and the
+    /// scanned fields.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&str, &Vec<u8>)>) {
+        let (cursor, fields) = self.scan_order.walk(cursor, count);
+        let fields = fields
+            .into_iter()
+            .filter_map(|f| Some((f, self.fields.get(f)?)))
+            .collect();
+
+        (cursor, fields)
+    }
+}
+
+/// Returns the approximate number of bytes used by the hash, including the scan order of its
+/// fields.
+pub fn memory_usage(hash: &Hash) -> usize {
+    hash.iter()
+        .map(|(f, v)| {
+            size_of::<(Arc<str>, Vec<u8>)>()
+                + f.len()
+                + v.capacity()
+                + ScanOrder::element_memory_usage()
+        })
+        .sum()
+}
+
+/// Returns the hash stored at key, if any.
+fn hash_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<Option<&'a Hash>, DBError> {
+    match keyspace.get(k) {
+        Some(Value::Hash(hash)) => Ok(Some(hash)),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(None),
+    }
+}
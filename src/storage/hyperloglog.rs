@@ -0,0 +1,632 @@
+use super::{
+    db::{Keyspace, Value, DB},
+    notify::NotifyClass,
+    DBError,
+};
+
+// HyperLogLogs are stored as string values, using the same encoding as Redis. Hence the values
+// can be moved between Nimblecache and Redis as-is.
+//
+// The string starts with a 16 byte header:
+//
+// ```
+// +------+---+-----+----------+
+// | HYLL | E | N/U | Cardin.  |
+// +------+---+-----+----------+
+// ```
+//
+// - 4 bytes of magic (`HYLL`).
+// - 1 byte for the encoding (0 = dense, 1 = sparse).
+// - 3 unused bytes.
+// - 8 bytes of cached cardinality (little endian). The most significant bit of the last
+//   byte is set if the cached value is invalid (ie, registers were modified after caching).
+//
+// The header is followed by 16384 registers, in either dense or sparse encoding.
+//
+// Dense encoding: Each register takes 6 bits, packed from the least significant bit of each byte.
+//
+// Sparse encoding: A run length encoding with the below opcodes:
+//
+// - ZERO (`00xxxxxx`): Next `xxxxxx + 1` registers (1-64) are set to 0.
+// - XZERO (`01xxxxxx yyyyyyyy`): Next `xxxxxxyyyyyyyy + 1` registers (1-16384) are set to 0.
+// - VAL (`1vvvvvxx`): Next `xx + 1` registers (1-4) are set to `vvvvv + 1` (1-32).
+
+/// Number of bits of the hash used for selecting the register.
+const HLL_P: u32 = 14;
+/// Number of bits of the hash used for counting the leading zeros.
+const HLL_Q: u32 = 64 - HLL_P;
+const HLL_REGISTERS: usize = 1 << HLL_P;
+const HLL_BITS: usize = 6;
+const HLL_REGISTER_MAX: u8 = (1 << HLL_BITS) - 1;
+const HLL_HDR_SIZE: usize = 16;
+const HLL_DENSE_SIZE: usize = HLL_HDR_SIZE + (HLL_REGISTERS * HLL_BITS).div_ceil(8);
+const HLL_MAGIC: &[u8] = b"HYLL";
+const HLL_DENSE: u8 = 0;
+const HLL_SPARSE: u8 = 1;
+
+const HLL_SPARSE_VAL_MAX_VALUE: u8 = 32;
+const HLL_SPARSE_VAL_MAX_LEN: usize = 4;
+const HLL_SPARSE_ZERO_MAX_LEN: usize = 64;
+const HLL_SPARSE_XZERO_MAX_LEN: usize = 16384;
+/// Sparse HyperLogLogs are converted to dense encoding once they grow beyond this size.
+const HLL_SPARSE_MAX_BYTES: usize = 3000;
+
+/// Seed used for hashing the elements. This is same as the one used by Redis.
+const HLL_HASH_SEED: u64 = 0xadc83b19;
+/// 0.5 / ln(2)
+const HLL_ALPHA_INF: f64 = 0.721_347_520_444_481_7;
+
+impl DB {
+    /// Adds the elements to the HyperLogLog stored at key. An empty HyperLogLog is created
+    /// if the key doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the HyperLogLog is stored.
+    ///
+    /// * `elements` - The elements to be added.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the HyperLogLog was created, or if at least one register was altered.
+    /// * `Err(DBError)` - if key already exists and is not a valid HyperLogLog.
+    pub fn pfadd(&self, k: &str, elements: &[Vec<u8>]) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            let mut created = false;
+            if keyspace.get(k).is_none() {
+                keyspace.insert(k, Value::String(new_sparse()));
+                created = true;
+            }
+
+            let hll = hll_value_mut(keyspace, k)?;
+            let updated = add_elements(hll, elements)?;
+            if created || updated {
+                keyspace.notify(NotifyClass::String, "pfadd", k);
+            }
+
+            Ok(created || updated)
+        })
+    }
+
+    /// Returns the approximated cardinality of the HyperLogLog stored at key. If multiple keys
+    /// are specified, the cardinality of the union of the HyperLogLogs is returned.
+    /// Missing keys are considered as empty HyperLogLogs.
+    ///
+    /// The cardinality of a single HyperLogLog is cached in its header, so that the registers are
+    /// scanned only if the HyperLogLog was modified after the last count.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - The approximated number of unique elements.
+    /// * `Err(DBError)` - if any of the keys is not a valid HyperLogLog.
+    pub fn pfcount(&self, keys: &[String]) -> Result<u64, DBError> {
+        if keys.len() == 1 {
+            let k = keys[0].as_str();
+            return self.write(|keyspace| {
+                if keyspace.get(k).is_none() {
+                    return Ok(0);
+                }
+
+                let hll = hll_value_mut(keyspace, k)?;
+                if let Some(card) = cached_cardinality(hll) {
+                    return Ok(card);
+                }
+
+                let card = estimate(&registers(hll)?);
+                hll[8..HLL_HDR_SIZE].copy_from_slice(&card.to_le_bytes());
+
+                Ok(card)
+            });
+        }
+
+        self.read(|keyspace| Ok(estimate(&merged_registers(keyspace, keys)?)))
+    }
+
+    /// Merges the HyperLogLogs stored at the source keys into the destination key. The
+    /// destination key is created if it doesn't exist, and if it exists, it's treated as one of
+    /// the source HyperLogLogs. The merged HyperLogLog is always stored in dense encoding.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If the HyperLogLogs are merged successfully.
+    /// * `Err(DBError)` - if any of the keys is not a valid HyperLogLog.
+    pub fn pfmerge(&self, dest: &str, keys: &[String]) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            let mut all_keys = vec![dest.to_string()];
+            all_keys.extend(keys.iter().cloned());
+
+            let registers = merged_registers(keyspace, &all_keys)?;
+            let hll = encode_dense(&registers);
+
+            match keyspace.get_mut(dest) {
+                // retain the expiry of destination key, if it already exists
+                Some(v) => *v = Value::String(hll),
+                None => keyspace.insert(dest, Value::String(hll)),
+            }
+            keyspace.notify(NotifyClass::String, "pfadd", dest);
+
+            Ok(())
+        })
+    }
+}
+
+/// Adds the elements to the HyperLogLog, and invalidates its cached cardinality if any
+/// register was altered. A sparse HyperLogLog is promoted to dense encoding once it can't be
+/// represented in sparse encoding.
+///
+/// # Returns
+///
+/// * `Ok(bool)` - `true` if at least one register was altered.
+/// * `Err(DBError)` - if the sparse registers are corrupted.
+fn add_elements(hll: &mut Vec<u8>, elements: &[Vec<u8>]) -> Result<bool, DBError> {
+    let updated = match hll[4] {
+        HLL_DENSE => {
+            let mut updated = false;
+            for element in elements {
+                let (idx, count) = hash_element(element);
+                if dense_get(hll, idx) < count {
+                    dense_set(hll, idx, count);
+                    updated = true;
+                }
+            }
+            updated
+        }
+        _ => {
+            let mut registers = registers(hll)?;
+            let mut updated = false;
+            for element in elements {
+                let (idx, count) = hash_element(element);
+                if registers[idx] < count {
+                    registers[idx] = count;
+                    updated = true;
+                }
+            }
+
+            if updated {
+                let cached_card = hll[8..HLL_HDR_SIZE].to_vec();
+                *hll = match encode_sparse(&registers) {
+                    Some(sparse) => sparse,
+                    None => encode_dense(&registers),
+                };
+                hll[8..HLL_HDR_SIZE].copy_from_slice(&cached_card);
+            }
+            updated
+        }
+    };
+
+    if updated {
+        invalidate_cache(hll);
+    }
+    Ok(updated)
+}
+
+/// Returns the HyperLogLog stored at key, after validating its header.
+fn hll_value_mut<'a>(keyspace: &'a mut Keyspace, k: &str) -> Result<&'a mut Vec<u8>, DBError> {
+    match keyspace.get_mut(k) {
+        Some(Value::String(s)) if is_valid_header(s) => Ok(s),
+        Some(Value::String(_)) => Err(invalid_hll_err()),
+        Some(_) => Err(DBError::WrongType),
+        None => Err(DBError::Other(String::from("Key not found"))),
+    }
+}
+
+/// Merges the registers of all the HyperLogLogs stored at the given keys, by taking the
+/// maximum value of each register. Missing keys are skipped.
+fn merged_registers(keyspace: &Keyspace, keys: &[String]) -> Result<Vec<u8>, DBError> {
+    let mut merged = vec![0u8; HLL_REGISTERS];
+
+    for k in keys {
+        let hll = match keyspace.get(k) {
+            Some(Value::String(s)) if is_valid_header(s) => s,
+            Some(Value::String(_)) => return Err(invalid_hll_err()),
+            Some(_) => return Err(DBError::WrongType),
+            None => continue,
+        };
+
+        merge_registers(&mut merged, hll)?;
+    }
+
+    Ok(merged)
+}
+
+/// Merges the registers of a HyperLogLog in either encoding into the merged registers, by
+/// taking the maximum value of each register.
+fn merge_registers(merged: &mut [u8], hll: &[u8]) -> Result<(), DBError> {
+    for (m, r) in merged.iter_mut().zip(registers(hll)?) {
+        *m = (*m).max(r);
+    }
+
+    Ok(())
+}
+
+fn invalid_hll_err() -> DBError {
+    DBError::Other(String::from(
+        "WRONGTYPE Key is not a valid HyperLogLog string value.",
+    ))
+}
+
+fn corrupted_hll_err() -> DBError {
+    DBError::Other(String::from("INVALIDOBJ Corrupted HLL object detected"))
+}
+
+/// Checks the magic bytes and encoding in the header, and the length of dense HyperLogLogs.
+fn is_valid_header(hll: &[u8]) -> bool {
+    if hll.len() < HLL_HDR_SIZE || &hll[..4] != HLL_MAGIC {
+        return false;
+    }
+
+    match hll[4] {
+        HLL_DENSE => hll.len() == HLL_DENSE_SIZE,
+        HLL_SPARSE => true,
+        _ => false,
+    }
+}
+
+/// Creates an empty HyperLogLog in sparse encoding.
+fn new_sparse() -> Vec<u8> {
+    encode_sparse(&[0; HLL_REGISTERS]).unwrap_or_default()
+}
+
+/// Builds the header for the given encoding, with the cached cardinality set to 0.
+fn header(encoding: u8) -> Vec<u8> {
+    let mut hdr = HLL_MAGIC.to_vec();
+    hdr.extend_from_slice(&[encoding, 0, 0, 0]);
+    hdr.extend_from_slice(&[0; 8]);
+    hdr
+}
+
+/// Returns the cached cardinality, if it's valid.
+fn cached_cardinality(hll: &[u8]) -> Option<u64> {
+    if hll[15] & (1 << 7) != 0 {
+        return None;
+    }
+
+    let mut card = [0u8; 8];
+    card.copy_from_slice(&hll[8..HLL_HDR_SIZE]);
+    Some(u64::from_le_bytes(card))
+}
+
+fn invalidate_cache(hll: &mut [u8]) {
+    hll[15] |= 1 << 7;
+}
+
+/// Hashes the element and returns the register index, and the number of trailing zeros
+/// (plus one) in the remaining bits of the hash.
+fn hash_element(element: &[u8]) -> (usize, u8) {
+    let hash = murmurhash64a(element, HLL_HASH_SEED);
+    let idx = (hash & (HLL_REGISTERS as u64 - 1)) as usize;
+
+    // Set the Q-th bit so that the count is at most Q + 1
+    let hash = (hash >> HLL_P) | (1 << HLL_Q);
+
+    (idx, hash.trailing_zeros() as u8 + 1)
+}
+
+/// MurmurHash2, 64-bit version by Austin Appleby.
+/// The input is always read as little endian, so that the hash is portable.
+pub fn murmurhash64a(key: &[u8], seed: u64) -> u64 {
+    const M: u64 = 0xc6a4a7935bd1e995;
+    const R: u32 = 47;
+
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(M);
+
+    let mut chunks = key.chunks_exact(8);
+    for chunk in chunks.by_ref() {
+        let mut k = u64::from_le_bytes(chunk.try_into().unwrap_or([0; 8]));
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+
+        h ^= k;
+        h = h.wrapping_mul(M);
+    }
+
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        for (i, b) in remainder.iter().enumerate() {
+            h ^= (*b as u64) << (8 * i);
+        }
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> R;
+    h = h.wrapping_mul(M);
+    h ^= h >> R;
+
+    h
+}
+
+/// Returns the value of a register in a dense HyperLogLog.
+fn dense_get(hll: &[u8], idx: usize) -> u8 {
+    let regs = &hll[HLL_HDR_SIZE..];
+    let byte = idx * HLL_BITS / 8;
+    let fb = (idx * HLL_BITS) & 7;
+
+    let b0 = regs[byte] as u16;
+    let b1 = *regs.get(byte + 1).unwrap_or(&0) as u16;
+
+    (((b0 >> fb) | (b1 << (8 - fb))) & HLL_REGISTER_MAX as u16) as u8
+}
+
+/// Sets the value of a register in a dense HyperLogLog.
+fn dense_set(hll: &mut [u8], idx: usize, val: u8) {
+    let regs = &mut hll[HLL_HDR_SIZE..];
+    let byte = idx * HLL_BITS / 8;
+    let fb = (idx * HLL_BITS) & 7;
+    let val = val as u16;
+    let max = HLL_REGISTER_MAX as u16;
+
+    regs[byte] &= !((max << fb) as u8);
+    regs[byte] |= (val << fb) as u8;
+    if let Some(next) = regs.get_mut(byte + 1) {
+        *next &= !((max >> (8 - fb)) as u8);
+        *next |= (val >> (8 - fb)) as u8;
+    }
+}
+
+/// Decodes the registers of a HyperLogLog in either encoding.
+fn registers(hll: &[u8]) -> Result<Vec<u8>, DBError> {
+    if hll[4] == HLL_DENSE {
+        return Ok((0..HLL_REGISTERS).map(|idx| dense_get(hll, idx)).collect());
+    }
+
+    let mut registers = Vec::with_capacity(HLL_REGISTERS);
+    let mut ops = hll[HLL_HDR_SIZE..].iter();
+
+    while let Some(op) = ops.next() {
+        let (val, len) = match op >> 6 {
+            // ZERO
+            0b00 => (0, (op & 0x3f) as usize + 1),
+            // XZERO
+            0b01 => match ops.next() {
+                Some(next) => (0, ((((op & 0x3f) as usize) << 8) | *next as usize) + 1),
+                None => return Err(corrupted_hll_err()),
+            },
+            // VAL
+            _ => (((op >> 2) & 0x1f) + 1, (op & 0x3) as usize + 1),
+        };
+
+        if registers.len() + len > HLL_REGISTERS {
+            return Err(corrupted_hll_err());
+        }
+        registers.resize(registers.len() + len, val);
+    }
+
+    if registers.len() != HLL_REGISTERS {
+        return Err(corrupted_hll_err());
+    }
+
+    Ok(registers)
+}
+
+/// Encodes the registers in dense encoding.
+fn encode_dense(registers: &[u8]) -> Vec<u8> {
+    let mut hll = header(HLL_DENSE);
+    hll.resize(HLL_DENSE_SIZE, 0);
+
+    for (idx, val) in registers.iter().enumerate() {
+        dense_set(&mut hll, idx, *val);
+    }
+    invalidate_cache(&mut hll);
+
+    hll
+}
+
+/// Encodes the registers in sparse encoding. `None` is returned if the registers can't be
+/// represented in sparse encoding, or if the sparse encoding grows beyond the size limit.
+fn encode_sparse(registers: &[u8]) -> Option<Vec<u8>> {
+    let mut hll = header(HLL_SPARSE);
+
+    let mut idx = 0;
+    while idx < registers.len() {
+        let val = registers[idx];
+        if val > HLL_SPARSE_VAL_MAX_VALUE {
+            return None;
+        }
+
+        let run_len = registers[idx..].iter().take_while(|r| **r == val).count();
+        let mut remaining = run_len;
+        while remaining > 0 {
+            if val != 0 {
+                let len = remaining.min(HLL_SPARSE_VAL_MAX_LEN);
+                hll.push(0x80 | ((val - 1) << 2) | (len - 1) as u8);
+                remaining -= len;
+            } else if remaining > HLL_SPARSE_ZERO_MAX_LEN {
+                let len = remaining.min(HLL_SPARSE_XZERO_MAX_LEN) - 1;
+                hll.push(0x40 | (len >> 8) as u8);
+                hll.push((len & 0xff) as u8);
+                remaining -= len + 1;
+            } else {
+                hll.push((remaining - 1) as u8);
+                remaining = 0;
+            }
+        }
+
+        idx += run_len;
+    }
+
+    if hll.len() > HLL_SPARSE_MAX_BYTES {
+        return None;
+    }
+
+    Some(hll)
+}
+
+/// Estimates the cardinality from the registers, using the improved estimator described in
+/// "New cardinality estimation algorithms for HyperLogLog sketches" by Otmar Ertl (arXiv:1702.01284).
+fn estimate(registers: &[u8]) -> u64 {
+    let m = HLL_REGISTERS as f64;
+    let q = HLL_Q as usize;
+
+    let mut histogram = [0u32; 64];
+    for r in registers {
+        histogram[*r as usize] += 1;
+    }
+
+    let mut z = m * tau((m - histogram[q + 1] as f64) / m);
+    for j in (1..=q).rev() {
+        z += histogram[j] as f64;
+        z *= 0.5;
+    }
+    z += m * sigma(histogram[0] as f64 / m);
+
+    (HLL_ALPHA_INF * m * m / z).round() as u64
+}
+
+fn sigma(x: f64) -> f64 {
+    if x == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let mut x = x;
+    let mut y = 1.0;
+    let mut z = x;
+    loop {
+        x *= x;
+        let z_prev = z;
+        z += x * y;
+        y += y;
+        if z_prev == z {
+            return z;
+        }
+    }
+}
+
+fn tau(x: f64) -> f64 {
+    if x == 0.0 || x == 1.0 {
+        return 0.0;
+    }
+
+    let mut x = x;
+    let mut y = 1.0;
+    let mut z = 1.0 - x;
+    loop {
+        x = x.sqrt();
+        let z_prev = z;
+        y *= 0.5;
+        z -= (1.0 - x).powi(2) * y;
+        if z_prev == z {
+            return z / 3.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a sparse HyperLogLog with the elements `prefix:0`, `prefix:1`, ... added in
+    /// batches, the same way PFADD adds them.
+    fn hll_with(prefix: &str, count: usize) -> Vec<u8> {
+        let mut hll = new_sparse();
+        let elements: Vec<Vec<u8>> = (0..count)
+            .map(|i| format!("{}:{}", prefix, i).into_bytes())
+            .collect();
+        for batch in elements.chunks(1000) {
+            add_elements(&mut hll, batch).unwrap();
+        }
+
+        hll
+    }
+
+    fn assert_estimate(registers: &[u8], expected: usize) {
+        let card = estimate(registers) as f64;
+        let error = (card - expected as f64).abs() / expected as f64;
+        assert!(
+            error < 0.02,
+            "estimated {} for {} elements ({:.2}% error)",
+            card,
+            expected,
+            error * 100.0
+        );
+    }
+
+    #[test]
+    fn sparse_dense_round_trip() {
+        let sparse = hll_with("e", 500);
+        assert_eq!(sparse[4], HLL_SPARSE);
+        let regs = registers(&sparse).unwrap();
+        assert!(regs.iter().any(|r| *r > 0));
+
+        let dense = encode_dense(&regs);
+        assert!(is_valid_header(&dense));
+        assert_eq!(dense[4], HLL_DENSE);
+        assert_eq!(registers(&dense).unwrap(), regs);
+
+        let sparse_again = encode_sparse(&registers(&dense).unwrap()).unwrap();
+        assert_eq!(sparse_again[HLL_HDR_SIZE..], sparse[HLL_HDR_SIZE..]);
+        assert_eq!(registers(&sparse_again).unwrap(), regs);
+    }
+
+    #[test]
+    fn sparse_encoding_of_runs() {
+        // the runs are longer than the max length of each opcode
+        let mut regs = vec![0u8; HLL_REGISTERS];
+        regs[100..110].fill(3);
+        regs[HLL_REGISTERS - 1] = HLL_SPARSE_VAL_MAX_VALUE;
+
+        let sparse = encode_sparse(&regs).unwrap();
+        assert_eq!(registers(&sparse).unwrap(), regs);
+
+        regs[0] = HLL_SPARSE_VAL_MAX_VALUE + 1;
+        assert_eq!(encode_sparse(&regs), None);
+    }
+
+    #[test]
+    fn promotion_to_dense() {
+        let mut hll = new_sparse();
+        let mut i = 0;
+        while hll[4] == HLL_SPARSE {
+            let sparse_len = hll.len();
+            let regs = registers(&hll).unwrap();
+
+            add_elements(&mut hll, &[format!("e:{}", i).into_bytes()]).unwrap();
+            i += 1;
+
+            if hll[4] == HLL_DENSE {
+                // the last element grew the sparse encoding past the limit
+                assert!(sparse_len <= HLL_SPARSE_MAX_BYTES);
+                let mut new_regs = regs.clone();
+                merge_registers(&mut new_regs, &hll).unwrap();
+                assert!(encode_sparse(&new_regs).is_none());
+            } else {
+                assert!(hll.len() <= HLL_SPARSE_MAX_BYTES);
+            }
+        }
+
+        assert!(is_valid_header(&hll));
+        assert_eq!(hll.len(), HLL_DENSE_SIZE);
+        assert_eq!(cached_cardinality(&hll), None);
+        assert_eq!(
+            registers(&hll).unwrap(),
+            registers(&hll_with("e", i)).unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_sparse_with_dense() {
+        let sparse = hll_with("a", 100);
+        let dense = hll_with("b", 20_000);
+        assert_eq!(sparse[4], HLL_SPARSE);
+        assert_eq!(dense[4], HLL_DENSE);
+
+        let mut merged = vec![0u8; HLL_REGISTERS];
+        merge_registers(&mut merged, &sparse).unwrap();
+        merge_registers(&mut merged, &dense).unwrap();
+
+        let mut union = dense.clone();
+        let elements: Vec<Vec<u8>> = (0..100).map(|i| format!("a:{}", i).into_bytes()).collect();
+        add_elements(&mut union, &elements).unwrap();
+
+        assert_eq!(merged, registers(&union).unwrap());
+        assert_estimate(&merged, 20_100);
+    }
+
+    #[test]
+    fn count_error() {
+        for count in [10_000, 100_000, 1_000_000] {
+            let hll = hll_with("count", count);
+            assert_estimate(&registers(&hll).unwrap(), count);
+        }
+    }
+}
@@ -0,0 +1,1194 @@
+use std::{fmt::Display, mem::size_of};
+
+use serde_json::{Map, Number, Value as JsonValue};
+
+use super::{
+    db::{Keyspace, Value, DB},
+    notify::NotifyClass,
+    DBError,
+};
+
+// JSON documents are queried and updated using paths. Two syntaxes are supported, same as RedisJSON:
+//
+// - JSONPath, which starts with `$`. Eg: `$.store.book[0].title`, `$..price`, `$.book[?(@.price < 10)]`.
+//   Commands return a result for each value matched by the path.
+// - Legacy paths, which don't start with `$`. Eg: `.`, `.store.book[0]`, `store.book`. These
+//   must match a single value, and commands return the result for that value alone.
+//
+// Supported JSONPath syntax:
+//
+// - `.name`, `['name']`, `["name"]` - Child member of an object.
+// - `[n]` - Array element. Negative indices count from the end of the array.
+// - `[start:end:step]` - Array slice.
+// - `.*`, `[*]` - All the children.
+// - `..name`, `..*`, `..[...]` - Recursive descent.
+// - `[a,b]` - Union of the selectors.
+// - `[?(expr)]` - Children for which the filter expression is true. The expression compares
+//   paths relative to the child (`@.price`) with literals, using `==`, `!=`, `<`, `<=`, `>`, `>=`.
+//   Comparisons can be combined using `&&`, `||` and parentheses. A relative path without a
+//   comparison checks if the path exists.
+
+/// A parsed JSON path.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    raw: String,
+    segments: Vec<Segment>,
+    legacy: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    /// Selects from the children of the current values.
+    Child(Selector),
+    /// Selects from the children of the current values and all their descendants.
+    Descendant(Selector),
+}
+
+#[derive(Debug, Clone)]
+enum Selector {
+    Key(String),
+    Wildcard,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, i64),
+    Union(Vec<Selector>),
+    Filter(Filter),
+}
+
+#[derive(Debug, Clone)]
+enum Filter {
+    Or(Box<Filter>, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Exists(Vec<Segment>),
+    Compare(Operand, CompareOp, Operand),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    /// A path relative to the value being filtered.
+    Current(Vec<Segment>),
+    Literal(JsonValue),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A step on the way from the root of a document to a value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+/// Parser for the JSON paths and filter expressions.
+struct PathParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl JsonPath {
+    /// Parses a JSONPath or a legacy path.
+    pub fn parse(path: &str) -> Result<JsonPath, DBError> {
+        let (expr, legacy) = match path.strip_prefix('$') {
+            Some(expr) => (expr.to_string(), false),
+            None if path == "." => (String::new(), true),
+            None if path.starts_with('.') || path.starts_with('[') => (path.to_string(), true),
+            None => (format!(".{}", path), true),
+        };
+
+        let mut parser = PathParser {
+            chars: expr.chars().collect(),
+            pos: 0,
+        };
+        let segments = parser.segments()?;
+        if parser.peek().is_some() {
+            return Err(parser.error());
+        }
+
+        Ok(JsonPath {
+            raw: path.to_string(),
+            segments,
+            legacy,
+        })
+    }
+
+    /// Checks if the path points to the root of the document.
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Checks if the path uses the legacy syntax.
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+}
+
+impl Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl DB {
+    /// Sets the JSON value at path. For the values that don't exist, the value is added only if
+    /// the parent of the path is an object. New keys can be created only at the root path.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the JSON document is stored.
+    ///
+    /// * `path` - The path at which the value is to be set.
+    ///
+    /// * `value` - The value to be set.
+    ///
+    /// * `nx` - Only set the value if the path doesn't exist.
+    ///
+    /// * `xx` - Only set the value if the path already exists.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the value was set.
+    /// * `Err(DBError)` - if key already exists and has non JSON data.
+    pub fn json_set(
+        &self,
+        k: &str,
+        path: &JsonPath,
+        value: JsonValue,
+        nx: bool,
+        xx: bool,
+    ) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            let updated = set_value(keyspace, k, path, value, nx, xx)?;
+            if updated {
+                keyspace.notify(NotifyClass::Module, "json.set", k);
+            }
+            Ok(updated)
+        })
+    }
+
+    /// Returns the values matched by each of the paths. `None` is returned if the key doesn't exist.
+    pub fn json_get(
+        &self,
+        k: &str,
+        paths: &[JsonPath],
+    ) -> Result<Option<Vec<Vec<JsonValue>>>, DBError> {
+        self.read(|keyspace| {
+            let doc = match json_value(keyspace, k)? {
+                Some(doc) => doc,
+                None => return Ok(None),
+            };
+
+            Ok(Some(
+                paths
+                    .iter()
+                    .map(|path| matched_values(doc, &path.segments))
+                    .collect(),
+            ))
+        })
+    }
+
+    /// Returns the values matched by the path, from each of the keys. `None` is returned for the
+    /// keys that don't exist or don't hold JSON documents.
+    pub fn json_mget(
+        &self,
+        keys: &[String],
+        path: &JsonPath,
+    ) -> Result<Vec<Option<Vec<JsonValue>>>, DBError> {
+        self.read(|keyspace| {
+            Ok(keys
+                .iter()
+                .map(|k| match json_value(keyspace, k) {
+                    Ok(Some(doc)) => Some(matched_values(doc, &path.segments)),
+                    _ => None,
+                })
+                .collect())
+        })
+    }
+
+    /// Deletes the values matched by the path. The key is deleted if the path is the root.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - Number of values deleted.
+    /// * `Err(DBError)` - if key has non JSON data.
+    pub fn json_del(&self, k: &str, path: &JsonPath) -> Result<usize, DBError> {
+        self.write(|keyspace| {
+            let doc = match json_value_mut(keyspace, k)? {
+                Some(doc) => doc,
+                None => return Ok(0),
+            };
+
+            let deleted = match path.is_root() {
+                true => {
+                    keyspace.remove(k);
+                    1
+                }
+                false => delete_paths(doc, matched_paths(doc, &path.segments)),
+            };
+            if deleted > 0 {
+                keyspace.notify(NotifyClass::Module, "json.del", k);
+            }
+
+            Ok(deleted)
+        })
+    }
+
+    /// Merges the value into the values matched by the path, as per RFC 7396 (JSON Merge Patch).
+    /// Members with `null` values in the patch are deleted from the target. If the path doesn't
+    /// exist, the value is added like `json_set`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the document was updated.
+    /// * `Err(DBError)` - if key has non JSON data, or if the key doesn't exist and the
+    ///   path is not the root.
+    pub fn json_merge(&self, k: &str, path: &JsonPath, patch: JsonValue) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            let updated = merge_value(keyspace, k, path, patch)?;
+            if updated {
+                keyspace.notify(NotifyClass::Module, "json.merge", k);
+            }
+            Ok(updated)
+        })
+    }
+
+    /// Increments the numbers matched by the path.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<Number>>)` - The new value of each matched value. `None` for the values
+    ///   which are not numbers.
+    /// * `Err(DBError)` - if key doesn't exist or has non JSON data, or if the result is not a
+    ///   finite number.
+    pub fn json_numincrby(
+        &self,
+        k: &str,
+        path: &JsonPath,
+        incr: &Number,
+    ) -> Result<Vec<Option<Number>>, DBError> {
+        let check = |v: &JsonValue| match v {
+            JsonValue::Number(n) if add_numbers(n, incr).is_none() => Err(DBError::Other(
+                String::from("Result is not a finite number"),
+            )),
+            _ => Ok(()),
+        };
+
+        self.update_matches(k, "json.numincrby", path, check, |v| match v {
+            JsonValue::Number(n) => {
+                *n = add_numbers(n, incr)?;
+                Some(n.clone())
+            }
+            _ => None,
+        })
+    }
+
+    /// Appends to the strings matched by the path.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<usize>>)` - The new length of each matched value. `None` for the values
+    ///   which are not strings.
+    /// * `Err(DBError)` - if key doesn't exist or has non JSON data.
+    pub fn json_strappend(
+        &self,
+        k: &str,
+        path: &JsonPath,
+        s: &str,
+    ) -> Result<Vec<Option<usize>>, DBError> {
+        self.update_matches(k, "json.strappend", path, no_check, |v| match v {
+            JsonValue::String(existing) => {
+                existing.push_str(s);
+                Some(existing.chars().count())
+            }
+            _ => None,
+        })
+    }
+
+    /// Appends the values to the arrays matched by the path.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<usize>>)` - The new length of each matched value. `None` for the values
+    ///   which are not arrays.
+    /// * `Err(DBError)` - if key doesn't exist or has non JSON data.
+    pub fn json_arrappend(
+        &self,
+        k: &str,
+        path: &JsonPath,
+        values: &[JsonValue],
+    ) -> Result<Vec<Option<usize>>, DBError> {
+        self.update_matches(k, "json.arrappend", path, no_check, |v| match v {
+            JsonValue::Array(arr) => {
+                arr.extend_from_slice(values);
+                Some(arr.len())
+            }
+            _ => None,
+        })
+    }
+
+    /// Inserts the values before the index, in the arrays matched by the path. Negative index
+    /// counts from the end of the array.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<usize>>)` - The new length of each matched value. `None` for the values
+    ///   which are not arrays.
+    /// * `Err(DBError)` - if key doesn't exist or has non JSON data, or if the index is out of
+    ///   the range of an array.
+    pub fn json_arrinsert(
+        &self,
+        k: &str,
+        path: &JsonPath,
+        index: i64,
+        values: &[JsonValue],
+    ) -> Result<Vec<Option<usize>>, DBError> {
+        let insert_idx = |len: usize| {
+            let len = len as i64;
+            let idx = if index < 0 { len + index } else { index };
+            (0..=len).contains(&idx).then_some(idx as usize)
+        };
+        let check = |v: &JsonValue| match v {
+            JsonValue::Array(arr) if insert_idx(arr.len()).is_none() => {
+                Err(DBError::Other(String::from("Index out of bounds")))
+            }
+            _ => Ok(()),
+        };
+
+        self.update_matches(k, "json.arrinsert", path, check, |v| match v {
+            JsonValue::Array(arr) => {
+                let idx = insert_idx(arr.len())?;
+                arr.splice(idx..idx, values.iter().cloned());
+                Some(arr.len())
+            }
+            _ => None,
+        })
+    }
+
+    /// Removes and returns the element at the index, from the arrays matched by the path.
+    /// Negative index counts from the end of the array. Out of range indices are rounded to
+    /// the first or last element.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<Option<JsonValue>>>)` - The removed element of each matched value, or
+    ///   `Some(None)` if the array is empty. `None` for the values which are not arrays.
+    /// * `Err(DBError)` - if key doesn't exist or has non JSON data.
+    pub fn json_arrpop(
+        &self,
+        k: &str,
+        path: &JsonPath,
+        index: i64,
+    ) -> Result<Vec<Option<Option<JsonValue>>>, DBError> {
+        self.update_matches(k, "json.arrpop", path, no_check, |v| match v {
+            JsonValue::Array(arr) if arr.is_empty() => Some(None),
+            JsonValue::Array(arr) => {
+                let len = arr.len() as i64;
+                let idx = if index < 0 { len + index } else { index };
+                let idx = idx.clamp(0, len - 1) as usize;
+                Some(Some(arr.remove(idx)))
+            }
+            _ => None,
+        })
+    }
+
+    /// Returns the length of the arrays matched by the path. `None` is returned if the key doesn't
+    /// exist, and for the values which are not arrays.
+    pub fn json_arrlen(
+        &self,
+        k: &str,
+        path: &JsonPath,
+    ) -> Result<Option<Vec<Option<usize>>>, DBError> {
+        self.read_matches(k, path, |v| v.as_array().map(|arr| arr.len()))
+    }
+
+    /// Returns the keys of the objects matched by the path. `None` is returned if the key doesn't
+    /// exist, and for the values which are not objects.
+    pub fn json_objkeys(
+        &self,
+        k: &str,
+        path: &JsonPath,
+    ) -> Result<Option<Vec<Option<Vec<String>>>>, DBError> {
+        self.read_matches(k, path, |v| {
+            v.as_object().map(|obj| obj.keys().cloned().collect())
+        })
+    }
+
+    /// Returns the type of the values matched by the path. `None` is returned if the key doesn't exist.
+    pub fn json_type(
+        &self,
+        k: &str,
+        path: &JsonPath,
+    ) -> Result<Option<Vec<Option<&'static str>>>, DBError> {
+        self.read_matches(k, path, |v| Some(type_name(v)))
+    }
+
+    /// Runs `f` on each value matched by the path.
+    fn read_matches<T>(
+        &self,
+        k: &str,
+        path: &JsonPath,
+        f: impl Fn(&JsonValue) -> Option<T>,
+    ) -> Result<Option<Vec<Option<T>>>, DBError> {
+        self.read(|keyspace| {
+            let doc = match json_value(keyspace, k)? {
+                Some(doc) => doc,
+                None => return Ok(None),
+            };
+
+            let mut matches = vec![];
+            select(doc, &path.segments, &mut |_, v| matches.push(f(v)));
+            Ok(Some(matches))
+        })
+    }
+
+    /// Runs `f` on each value matched by the path, allowing it to modify the value in place.
+    /// All the matched values are validated using `check` before any of them are modified, so
+    /// that the document is left unmodified if the operation fails. The keyspace event is
+    /// sent if any of the values is modified.
+    fn update_matches<T>(
+        &self,
+        k: &str,
+        event: &'static str,
+        path: &JsonPath,
+        check: impl Fn(&JsonValue) -> Result<(), DBError>,
+        mut f: impl FnMut(&mut JsonValue) -> Option<T>,
+    ) -> Result<Vec<Option<T>>, DBError> {
+        self.write(|keyspace| {
+            let doc = match json_value_mut(keyspace, k)? {
+                Some(doc) => doc,
+                None => {
+                    return Err(DBError::Other(String::from(
+                        "Could not perform this operation on a key that doesn't exist",
+                    )))
+                }
+            };
+
+            let mut paths = vec![];
+            let mut result = Ok(());
+            select(doc, &path.segments, &mut |p, v| {
+                if result.is_ok() {
+                    result = check(v);
+                }
+                paths.push(p.to_vec());
+            });
+            result?;
+
+            let results: Vec<Option<T>> = paths
+                .iter()
+                .map(|p| value_at_mut(doc, p).and_then(&mut f))
+                .collect();
+            if results.iter().any(|r| r.is_some()) {
+                keyspace.notify(NotifyClass::Module, event, k);
+            }
+
+            Ok(results)
+        })
+    }
+}
+
+/// Validation for the operations that can't fail.
+fn no_check(_: &JsonValue) -> Result<(), DBError> {
+    Ok(())
+}
+
+/// Returns the approximate number of bytes used by the JSON value.
+pub fn memory_usage(v: &JsonValue) -> usize {
+    let children = match v {
+        JsonValue::String(s) => s.len(),
+        JsonValue::Array(arr) => arr.iter().map(memory_usage).sum(),
+        JsonValue::Object(obj) => obj
+            .iter()
+            .map(|(k, v)| size_of::<String>() + k.len() + memory_usage(v))
+            .sum(),
+        _ => 0,
+    };
+
+    size_of::<JsonValue>() + children
+}
+
+/// Returns the name of the type of the JSON value.
+fn type_name(v: &JsonValue) -> &'static str {
+    match v {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(n) if n.is_f64() => "number",
+        JsonValue::Number(_) => "integer",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "array",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+/// Returns the JSON document stored at key, if any.
+fn json_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<Option<&'a JsonValue>, DBError> {
+    match keyspace.get(k) {
+        Some(Value::Json(doc)) => Ok(Some(doc)),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(None),
+    }
+}
+
+/// Returns the mutable JSON document stored at key, if any.
+fn json_value_mut<'a>(
+    keyspace: &'a mut Keyspace,
+    k: &str,
+) -> Result<Option<&'a mut JsonValue>, DBError> {
+    match keyspace.get_mut(k) {
+        Some(Value::Json(doc)) => Ok(Some(doc)),
+        Some(_) => Err(DBError::WrongType),
+        None => Ok(None),
+    }
+}
+
+/// Sets the value at the path of the JSON document stored at key, as in `json_set`.
+fn set_value(
+    keyspace: &mut Keyspace,
+    k: &str,
+    path: &JsonPath,
+    value: JsonValue,
+    nx: bool,
+    xx: bool,
+) -> Result<bool, DBError> {
+    let doc = match json_value_mut(keyspace, k)? {
+        Some(doc) => doc,
+        None => {
+            if !path.is_root() {
+                return Err(DBError::Other(String::from(
+                    "New objects must be created at the root",
+                )));
+            }
+            if xx {
+                return Ok(false);
+            }
+
+            keyspace.insert(k, Value::Json(value));
+            return Ok(true);
+        }
+    };
+
+    let paths = matched_paths(doc, &path.segments);
+    if !paths.is_empty() {
+        if nx {
+            return Ok(false);
+        }
+
+        for p in paths {
+            if let Some(v) = value_at_mut(doc, &p) {
+                *v = value.clone();
+            }
+        }
+        return Ok(true);
+    }
+
+    if xx {
+        return Ok(false);
+    }
+
+    Ok(add_member(doc, &path.segments, &value))
+}
+
+/// Merges the patch into the JSON document stored at key, as in `json_merge`.
+fn merge_value(
+    keyspace: &mut Keyspace,
+    k: &str,
+    path: &JsonPath,
+    patch: JsonValue,
+) -> Result<bool, DBError> {
+    let doc = match json_value_mut(keyspace, k)? {
+        Some(doc) => doc,
+        None => {
+            if !path.is_root() {
+                return Err(DBError::Other(String::from(
+                    "New objects must be created at the root",
+                )));
+            }
+
+            let mut doc = JsonValue::Null;
+            merge_patch(&mut doc, &patch);
+            keyspace.insert(k, Value::Json(doc));
+            return Ok(true);
+        }
+    };
+
+    if patch.is_null() {
+        if path.is_root() {
+            keyspace.remove(k);
+            return Ok(true);
+        }
+        return Ok(delete_paths(doc, matched_paths(doc, &path.segments)) > 0);
+    }
+
+    let paths = matched_paths(doc, &path.segments);
+    if paths.is_empty() {
+        let mut value = JsonValue::Null;
+        merge_patch(&mut value, &patch);
+        return Ok(add_member(doc, &path.segments, &value));
+    }
+
+    for p in paths {
+        if let Some(v) = value_at_mut(doc, &p) {
+            merge_patch(v, &patch);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Adds the value to the objects matched by the parent of the path, if the last segment of the
+/// path is a member name.
+///
+/// # Returns
+///
+/// `true` if the value was added to at least one object.
+fn add_member(doc: &mut JsonValue, segments: &[Segment], value: &JsonValue) -> bool {
+    let (key, parent) = match segments.split_last() {
+        Some((Segment::Child(Selector::Key(key)), parent)) => (key, parent),
+        _ => return false,
+    };
+
+    let mut added = false;
+    for p in matched_paths(doc, parent) {
+        if let Some(JsonValue::Object(obj)) = value_at_mut(doc, &p) {
+            obj.insert(key.clone(), value.clone());
+            added = true;
+        }
+    }
+
+    added
+}
+
+/// Deletes the values at the given paths, and returns the number of values deleted.
+fn delete_paths(doc: &mut JsonValue, mut paths: Vec<Vec<Step>>) -> usize {
+    // Values inside a deleted value needn't be deleted separately. Array elements are deleted
+    // starting from the end, so that the indices of the remaining elements stay valid.
+    paths.sort();
+    paths.dedup();
+    let mut roots: Vec<Vec<Step>> = vec![];
+    for p in paths {
+        if !roots.iter().any(|r| p.starts_with(r)) {
+            roots.push(p);
+        }
+    }
+
+    let mut deleted = 0;
+    for p in roots.iter().rev() {
+        let (last, parent) = match p.split_last() {
+            Some(pair) => pair,
+            None => continue,
+        };
+
+        let removed = match (value_at_mut(doc, parent), last) {
+            (Some(JsonValue::Object(obj)), Step::Key(key)) => obj.shift_remove(key).is_some(),
+            (Some(JsonValue::Array(arr)), Step::Index(idx)) if *idx < arr.len() => {
+                arr.remove(*idx);
+                true
+            }
+            _ => false,
+        };
+
+        if removed {
+            deleted += 1;
+        }
+    }
+
+    deleted
+}
+
+/// Applies the merge patch to the target, as per RFC 7396.
+fn merge_patch(target: &mut JsonValue, patch: &JsonValue) {
+    let patch = match patch {
+        JsonValue::Object(patch) => patch,
+        _ => {
+            *target = patch.clone();
+            return;
+        }
+    };
+
+    if !target.is_object() {
+        *target = JsonValue::Object(Map::new());
+    }
+
+    if let JsonValue::Object(obj) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                obj.shift_remove(key);
+            } else {
+                merge_patch(obj.entry(key.clone()).or_insert(JsonValue::Null), value);
+            }
+        }
+    }
+}
+
+/// Adds two numbers. Integers are added without loss of precision, unless the sum overflows.
+/// Returns `None` if the result is not a finite number.
+fn add_numbers(a: &Number, b: &Number) -> Option<Number> {
+    if let (Some(x), Some(y)) = (a.as_i64(), b.as_i64()) {
+        if let Some(sum) = x.checked_add(y) {
+            return Some(Number::from(sum));
+        }
+    }
+
+    Number::from_f64(a.as_f64()? + b.as_f64()?)
+}
+
+/// Returns the paths of the values matched by the segments.
+fn matched_paths(doc: &JsonValue, segments: &[Segment]) -> Vec<Vec<Step>> {
+    let mut paths = vec![];
+    select(doc, segments, &mut |p, _| paths.push(p.to_vec()));
+    paths
+}
+
+/// Returns the values matched by the segments.
+fn matched_values(doc: &JsonValue, segments: &[Segment]) -> Vec<JsonValue> {
+    let mut values = vec![];
+    select(doc, segments, &mut |_, v| values.push(v.clone()));
+    values
+}
+
+/// Returns the value at the given path.
+fn value_at_mut<'a>(doc: &'a mut JsonValue, path: &[Step]) -> Option<&'a mut JsonValue> {
+    path.iter().try_fold(doc, |v, step| match (v, step) {
+        (JsonValue::Object(obj), Step::Key(key)) => obj.get_mut(key),
+        (JsonValue::Array(arr), Step::Index(idx)) => arr.get_mut(*idx),
+        _ => None,
+    })
+}
+
+/// Calls `f` with the path and value of each value matched by the segments, in document order.
+fn select(doc: &JsonValue, segments: &[Segment], f: &mut dyn FnMut(&[Step], &JsonValue)) {
+    let mut path = vec![];
+    select_from(doc, &mut path, segments, f);
+}
+
+fn select_from(
+    value: &JsonValue,
+    path: &mut Vec<Step>,
+    segments: &[Segment],
+    f: &mut dyn FnMut(&[Step], &JsonValue),
+) {
+    let (segment, rest) = match segments.split_first() {
+        Some(pair) => pair,
+        None => {
+            f(path, value);
+            return;
+        }
+    };
+
+    match segment {
+        Segment::Child(selector) => {
+            for (step, child) in select_children(value, selector) {
+                path.push(step);
+                select_from(child, path, rest, f);
+                path.pop();
+            }
+        }
+        Segment::Descendant(selector) => {
+            for (step, child) in select_children(value, selector) {
+                path.push(step);
+                select_from(child, path, rest, f);
+                path.pop();
+            }
+
+            for (step, child) in children(value) {
+                path.push(step);
+                select_from(child, path, segments, f);
+                path.pop();
+            }
+        }
+    }
+}
+
+/// Returns all the children of an object or an array.
+fn children(value: &JsonValue) -> Vec<(Step, &JsonValue)> {
+    match value {
+        JsonValue::Object(obj) => obj
+            .iter()
+            .map(|(key, v)| (Step::Key(key.clone()), v))
+            .collect(),
+        JsonValue::Array(arr) => arr
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| (Step::Index(idx), v))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the children of a value which are matched by the selector.
+fn select_children<'a>(value: &'a JsonValue, selector: &Selector) -> Vec<(Step, &'a JsonValue)> {
+    match (selector, value) {
+        (Selector::Key(key), JsonValue::Object(obj)) => match obj.get(key) {
+            Some(v) => vec![(Step::Key(key.clone()), v)],
+            None => vec![],
+        },
+        (Selector::Wildcard, _) => children(value),
+        (Selector::Index(idx), JsonValue::Array(arr)) => {
+            let len = arr.len() as i64;
+            let idx = if *idx < 0 { len + idx } else { *idx };
+            if idx < 0 || idx >= len {
+                return vec![];
+            }
+            vec![(Step::Index(idx as usize), &arr[idx as usize])]
+        }
+        (Selector::Slice(start, end, step), JsonValue::Array(arr)) => {
+            if *step <= 0 {
+                return vec![];
+            }
+
+            let len = arr.len() as i64;
+            let bound = |idx: i64| {
+                let idx = if idx < 0 { len + idx } else { idx };
+                idx.clamp(0, len) as usize
+            };
+            let start = bound(start.unwrap_or(0));
+            let end = bound(end.unwrap_or(len));
+
+            (start..end.max(start))
+                .step_by(*step as usize)
+                .map(|idx| (Step::Index(idx), &arr[idx]))
+                .collect()
+        }
+        (Selector::Union(selectors), _) => selectors
+            .iter()
+            .flat_map(|s| select_children(value, s))
+            .collect(),
+        (Selector::Filter(filter), _) => children(value)
+            .into_iter()
+            .filter(|(_, child)| filter.matches(child))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+impl Filter {
+    /// Evaluates the filter expression against a value.
+    fn matches(&self, value: &JsonValue) -> bool {
+        match self {
+            Filter::Or(a, b) => a.matches(value) || b.matches(value),
+            Filter::And(a, b) => a.matches(value) && b.matches(value),
+            Filter::Exists(segments) => !matched_paths(value, segments).is_empty(),
+            Filter::Compare(lhs, op, rhs) => match (lhs.resolve(value), rhs.resolve(value)) {
+                (Some(a), Some(b)) => op.compare(&a, &b),
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Operand {
+    /// Returns the value of the operand. Relative paths resolve to the first matched value.
+    fn resolve(&self, value: &JsonValue) -> Option<JsonValue> {
+        match self {
+            Operand::Literal(v) => Some(v.clone()),
+            Operand::Current(segments) => matched_values(value, segments).into_iter().next(),
+        }
+    }
+}
+
+impl CompareOp {
+    fn compare(self, a: &JsonValue, b: &JsonValue) -> bool {
+        let ordering = match (a, b) {
+            (JsonValue::Number(x), JsonValue::Number(y)) => match (x.as_f64(), y.as_f64()) {
+                (Some(x), Some(y)) => x.partial_cmp(&y),
+                _ => None,
+            },
+            (JsonValue::String(x), JsonValue::String(y)) => Some(x.cmp(y)),
+            _ if a == b => Some(std::cmp::Ordering::Equal),
+            _ => None,
+        };
+
+        match (self, ordering) {
+            (CompareOp::Eq, o) => o.is_some_and(|o| o.is_eq()),
+            (CompareOp::Ne, o) => !o.is_some_and(|o| o.is_eq()),
+            (CompareOp::Lt, o) => o.is_some_and(|o| o.is_lt()),
+            (CompareOp::Le, o) => o.is_some_and(|o| o.is_le()),
+            (CompareOp::Gt, o) => o.is_some_and(|o| o.is_gt()),
+            (CompareOp::Ge, o) => o.is_some_and(|o| o.is_ge()),
+        }
+    }
+}
+
+impl PathParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), DBError> {
+        if self.peek() != Some(c) {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn error(&self) -> DBError {
+        DBError::Other(format!("Invalid JSON path at offset {}", self.pos))
+    }
+
+    /// Parses a sequence of child and descendant segments.
+    fn segments(&mut self) -> Result<Vec<Segment>, DBError> {
+        let mut segments = vec![];
+        loop {
+            if self.starts_with("..") {
+                self.pos += 2;
+                let selector = match self.peek() {
+                    Some('[') => self.bracket()?,
+                    Some('*') => {
+                        self.pos += 1;
+                        Selector::Wildcard
+                    }
+                    _ => Selector::Key(self.name()?),
+                };
+                segments.push(Segment::Descendant(selector));
+            } else if self.peek() == Some('.') {
+                self.pos += 1;
+                let selector = match self.peek() {
+                    Some('*') => {
+                        self.pos += 1;
+                        Selector::Wildcard
+                    }
+                    _ => Selector::Key(self.name()?),
+                };
+                segments.push(Segment::Child(selector));
+            } else if self.peek() == Some('[') {
+                segments.push(Segment::Child(self.bracket()?));
+            } else {
+                break;
+            }
+        }
+
+        Ok(segments)
+    }
+
+    /// Parses an unquoted member name.
+    fn name(&mut self) -> Result<String, DBError> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == '.' || c == '[' || c.is_whitespace() || "=!<>&|)".contains(c) {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        if self.pos == start {
+            return Err(self.error());
+        }
+
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    /// Parses a quoted string. Both single and double quotes are supported.
+    fn quoted(&mut self) -> Result<String, DBError> {
+        let quote = match self.peek() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return Err(self.error()),
+        };
+        self.pos += 1;
+
+        let mut s = String::new();
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(c) => s.push(c),
+                        None => return Err(self.error()),
+                    }
+                }
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(s);
+                }
+                Some(c) => s.push(c),
+                None => return Err(self.error()),
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Parses an optionally signed integer.
+    fn integer(&mut self) -> Result<Option<i64>, DBError> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.pos += 1;
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        if self.pos == start {
+            return Ok(None);
+        }
+
+        let s: String = self.chars[start..self.pos].iter().collect();
+        s.parse::<i64>().map(Some).map_err(|_| self.error())
+    }
+
+    /// Parses the selectors within brackets.
+    fn bracket(&mut self) -> Result<Selector, DBError> {
+        self.expect('[')?;
+        self.skip_whitespace();
+
+        if self.peek() == Some('?') {
+            self.pos += 1;
+            self.skip_whitespace();
+            self.expect('(')?;
+            let filter = self.filter_or()?;
+            self.skip_whitespace();
+            self.expect(')')?;
+            self.skip_whitespace();
+            self.expect(']')?;
+            return Ok(Selector::Filter(filter));
+        }
+
+        let mut selectors = vec![];
+        loop {
+            self.skip_whitespace();
+            let selector = match self.peek() {
+                Some('*') => {
+                    self.pos += 1;
+                    Selector::Wildcard
+                }
+                Some('\'' | '"') => Selector::Key(self.quoted()?),
+                _ => {
+                    let start = self.integer()?;
+                    self.skip_whitespace();
+                    if self.peek() == Some(':') {
+                        self.pos += 1;
+                        self.skip_whitespace();
+                        let end = self.integer()?;
+                        self.skip_whitespace();
+                        let mut step = 1;
+                        if self.peek() == Some(':') {
+                            self.pos += 1;
+                            self.skip_whitespace();
+                            step = self.integer()?.unwrap_or(1);
+                        }
+                        Selector::Slice(start, end, step)
+                    } else {
+                        Selector::Index(start.ok_or_else(|| self.error())?)
+                    }
+                }
+            };
+            selectors.push(selector);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => return Err(self.error()),
+            }
+        }
+
+        if selectors.len() == 1 {
+            return Ok(selectors.remove(0));
+        }
+        Ok(Selector::Union(selectors))
+    }
+
+    fn filter_or(&mut self) -> Result<Filter, DBError> {
+        let mut filter = self.filter_and()?;
+        loop {
+            self.skip_whitespace();
+            if !self.starts_with("||") {
+                return Ok(filter);
+            }
+            self.pos += 2;
+            filter = Filter::Or(Box::new(filter), Box::new(self.filter_and()?));
+        }
+    }
+
+    fn filter_and(&mut self) -> Result<Filter, DBError> {
+        let mut filter = self.filter_atom()?;
+        loop {
+            self.skip_whitespace();
+            if !self.starts_with("&&") {
+                return Ok(filter);
+            }
+            self.pos += 2;
+            filter = Filter::And(Box::new(filter), Box::new(self.filter_atom()?));
+        }
+    }
+
+    fn filter_atom(&mut self) -> Result<Filter, DBError> {
+        self.skip_whitespace();
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            let filter = self.filter_or()?;
+            self.skip_whitespace();
+            self.expect(')')?;
+            return Ok(filter);
+        }
+
+        let lhs = self.operand()?;
+        self.skip_whitespace();
+
+        let op = [
+            ("==", CompareOp::Eq),
+            ("!=", CompareOp::Ne),
+            ("<=", CompareOp::Le),
+            (">=", CompareOp::Ge),
+            ("<", CompareOp::Lt),
+            (">", CompareOp::Gt),
+        ]
+        .into_iter()
+        .find(|(s, _)| self.starts_with(s));
+
+        match (op, lhs) {
+            (Some((s, op)), lhs) => {
+                self.pos += s.len();
+                self.skip_whitespace();
+                let rhs = self.operand()?;
+                Ok(Filter::Compare(lhs, op, rhs))
+            }
+            (None, Operand::Current(segments)) => Ok(Filter::Exists(segments)),
+            (None, Operand::Literal(_)) => Err(self.error()),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand, DBError> {
+        match self.peek() {
+            Some('@') => {
+                self.pos += 1;
+                Ok(Operand::Current(self.segments()?))
+            }
+            Some('\'' | '"') => Ok(Operand::Literal(JsonValue::String(self.quoted()?))),
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| !c.is_whitespace() && !"=!<>&|)".contains(c))
+                {
+                    self.pos += 1;
+                }
+
+                let literal: String = self.chars[start..self.pos].iter().collect();
+                match serde_json::from_str::<JsonValue>(&literal) {
+                    Ok(v) if !v.is_object() && !v.is_array() => Ok(Operand::Literal(v)),
+                    _ => Err(self.error()),
+                }
+            }
+        }
+    }
+}
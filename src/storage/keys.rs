@@ -0,0 +1,488 @@
+use std::{
+    collections::BTreeSet,
+    hash::{DefaultHasher, Hash, Hasher},
+    mem::size_of,
+    sync::Arc,
+};
+
+use rand::seq::IteratorRandom;
+
+use super::{
+    db::{Storage, Value, DB},
+    notify::NotifyClass,
+    time_series::track_retention,
+    DBError,
+};
+
+// Commands which operate on the keyspace, regardless of the type of the values. Keys which
+// have expired, but are not evicted yet, are never returned or counted.
+//
+// SCAN cursors are stateless. Keys (and the elements of collections) are walked in the order
+// of their scan hashes, and a cursor is the scan hash to resume from. Hence an element which
+// exists for the whole iteration is returned at least once, regardless of the elements which
+// are added or removed in between. Elements which are added during the iteration may or may
+// not be returned.
+
+impl DB {
+    /// Returns the number of keys which exist, out of the given keys. Keys are counted as
+    /// many times as they're repeated.
+    pub fn exists(&self, keys: &[String]) -> Result<usize, DBError> {
+        self.read(|keyspace| Ok(keys.iter().filter(|k| keyspace.get(k).is_some()).count()))
+    }
+
+    /// Returns the name of the type of the value stored at key, or `None` if the key doesn't
+    /// exist.
+    pub fn key_type(&self, k: &str) -> Result<Option<&'static str>, DBError> {
+        self.read(|keyspace| Ok(keyspace.get(k).map(|v| v.type_name())))
+    }
+
+    /// Returns the number of keys in the DB.
+    pub fn dbsize(&self) -> Result<usize, DBError> {
+        self.read(|keyspace| Ok(keyspace.iter().count()))
+    }
+
+    /// Returns the keys matching the glob-style pattern, in no particular order.
+    pub fn keys(&self, pattern: &str) -> Result<Vec<String>, DBError> {
+        self.read(|keyspace| {
+            Ok(keyspace
+                .iter()
+                .filter(|(k, _)| glob_match(pattern.as_bytes(), k.as_bytes()))
+                .map(|(k, _)| k.to_string())
+                .collect())
+        })
+    }
+
+    /// Incrementally iterates over the keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor` - The cursor returned by the previous call, or `0` to start an iteration.
+    ///
+    /// * `opts` - The number of keys to be walked, and the filters.
+    ///
+    /// * `type_name` - Only the keys holding values of the type (as named by TYPE) are
+    ///   returned, if given.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ScanPage<String>)` - The cursor for the next call (`0` once the iteration is
+    ///   complete), and the keys which were walked and matched the filters.
+    /// * `Err(DBError)` - if the DB lock cannot be acquired.
+    pub fn scan(
+        &self,
+        cursor: u64,
+        opts: &ScanOptions,
+        type_name: Option<&str>,
+    ) -> Result<ScanPage<String>, DBError> {
+        self.read(|keyspace| {
+            let (cursor, keys) = keyspace.scan(cursor, opts.count);
+            let keys = keys
+                .into_iter()
+                .filter(|(_, v)| type_name.is_none_or(|t| v.type_name().eq_ignore_ascii_case(t)))
+                .filter(|(k, _)| opts.matches(k))
+                .map(|(k, _)| k.to_string())
+                .collect();
+
+            Ok((cursor, keys))
+        })
+    }
+
+    /// Incrementally iterates over the fields of the hash stored at key. The iteration is
+    /// complete right away if the key doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ScanPage<(String, Vec<u8>)>)` - The cursor for the next call, and the fields
+    ///   (and their values) which were walked and matched the pattern.
+    /// * `Err(DBError)` - if key already exists and is not a hash.
+    pub fn hscan(
+        &self,
+        k: &str,
+        cursor: u64,
+        opts: &ScanOptions,
+    ) -> Result<ScanPage<(String, Vec<u8>)>, DBError> {
+        self.read(|keyspace| {
+            let hash = match keyspace.get(k) {
+                Some(Value::Hash(hash)) => hash,
+                Some(_) => return Err(DBError::WrongType),
+                None => return Ok((0, vec![])),
+            };
+
+            let (cursor, fields) = hash.scan(cursor, opts.count);
+            Ok((
+                cursor,
+                fields
+                    .into_iter()
+                    .filter(|(f, _)| opts.matches(f))
+                    .map(|(f, v)| (f.to_string(), v.clone()))
+                    .collect(),
+            ))
+        })
+    }
+
+    /// Incrementally iterates over the members of the sorted set stored at key. The iteration
+    /// is complete right away if the key doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ScanPage<(String, f64)>)` - The cursor for the next call, and the members (and
+    ///   their scores) which were walked and matched the pattern.
+    /// * `Err(DBError)` - if key already exists and is not a sorted set.
+    pub fn zscan(
+        &self,
+        k: &str,
+        cursor: u64,
+        opts: &ScanOptions,
+    ) -> Result<ScanPage<(String, f64)>, DBError> {
+        self.read(|keyspace| {
+            let zset = match keyspace.get(k) {
+                Some(Value::SortedSet(zset)) => zset,
+                Some(_) => return Err(DBError::WrongType),
+                None => return Ok((0, vec![])),
+            };
+
+            let (cursor, members) = zset.scan(cursor, opts.count);
+            Ok((
+                cursor,
+                members
+                    .into_iter()
+                    .filter(|(m, _)| opts.matches(m))
+                    .map(|(m, score)| (m.to_string(), score))
+                    .collect(),
+            ))
+        })
+    }
+
+    /// Renames a key, along with its expiry. The destination key is overwritten if it
+    /// already exists, unless `nx` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `src` - The key to be renamed.
+    ///
+    /// * `dest` - The new name of the key.
+    ///
+    /// * `nx` - Set to `true` if the key must be renamed only when the destination key doesn't
+    ///   exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the key was renamed, or `false` if the destination key exists
+    ///   and `nx` is set.
+    /// * `Err(DBError)` - if the source key doesn't exist.
+    pub fn rename(&self, src: &str, dest: &str, nx: bool) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(src).is_none() {
+                return Err(DBError::Other(String::from("no such key")));
+            }
+            if nx && keyspace.get(dest).is_some() {
+                return Ok(false);
+            }
+            if src == dest {
+                return Ok(true);
+            }
+
+            let expiry = keyspace.expiry(src);
+            if let Some(value) = keyspace.remove(src) {
+                keyspace.insert_with_expiry(dest, value, expiry);
+                track_retention(keyspace, dest);
+                keyspace.notify(NotifyClass::Generic, "rename_from", src);
+                keyspace.notify(NotifyClass::Generic, "rename_to", dest);
+            }
+
+            Ok(true)
+        })
+    }
+
+    /// Copies the value stored at the source key, along with its expiry, to the destination
+    /// key. The destination key is overwritten if it already exists, only if `replace` is set.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the value was copied, or `false` if the source key doesn't
+    ///   exist or the destination key exists.
+    /// * `Err(DBError)` - if the source and destination keys are the same.
+    pub fn copy(&self, src: &str, dest: &str, replace: bool) -> Result<bool, DBError> {
+        if src == dest {
+            return Err(DBError::Other(String::from(
+                "source and destination objects are the same",
+            )));
+        }
+
+        self.write(|keyspace| {
+            let value = match keyspace.get(src) {
+                Some(value) => value.clone(),
+                None => return Ok(false),
+            };
+            if !replace && keyspace.get(dest).is_some() {
+                return Ok(false);
+            }
+
+            let expiry = keyspace.expiry(src);
+            keyspace.insert_with_expiry(dest, value, expiry);
+            track_retention(keyspace, dest);
+            keyspace.notify(NotifyClass::Generic, "copy_to", dest);
+
+            Ok(true)
+        })
+    }
+
+    /// Returns the number of keys, and the number of keys with an expiry.
+    pub fn keyspace_stats(&self) -> Result<(usize, usize), DBError> {
+        self.read(|keyspace| {
+            let (mut keys, mut expires) = (0, 0);
+            for (k, _) in keyspace.iter() {
+                keys += 1;
+                if keyspace.peek_expiry(k).is_some() {
+                    expires += 1;
+                }
+            }
+
+            Ok((keys, expires))
+        })
+    }
+
+    /// Returns a random key, or `None` if the DB is empty.
+    pub fn random_key(&self) -> Result<Option<String>, DBError> {
+        self.read(|keyspace| {
+            Ok(keyspace
+                .iter()
+                .choose(&mut rand::thread_rng())
+                .map(|(k, _)| k.to_string()))
+        })
+    }
+}
+
+impl Storage {
+    /// Moves a key, along with its expiry, to another database.
+    ///
+    /// # Arguments
+    ///
+    /// * `src_db` - The number of the database which holds the key.
+    ///
+    /// * `k` - The key to be moved.
+    ///
+    /// * `dest_db` - The number of the database to which the key is moved.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the key was moved, or `false` if the key doesn't exist or the
+    ///   destination database already has the key.
+    /// * `Err(DBError)` - if the databases are the same, or out of range.
+    pub fn move_key(&self, src_db: usize, k: &str, dest_db: usize) -> Result<bool, DBError> {
+        self.write_pair(src_db, dest_db, |src, dest| {
+            if src.get(k).is_none() || dest.get(k).is_some() {
+                return Ok(false);
+            }
+
+            let expiry = src.expiry(k);
+            if let Some(value) = src.remove(k) {
+                dest.insert_with_expiry(k, value, expiry);
+                track_retention(dest, k);
+                src.notify(NotifyClass::Generic, "move_from", k);
+                dest.notify(NotifyClass::Generic, "move_to", k);
+            }
+
+            Ok(true)
+        })
+    }
+
+    /// Copies the value stored at the source key, along with its expiry, to the destination
+    /// key in another database. See `DB::copy` for copying within a database.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `true` if the value was copied, or `false` if the source key doesn't
+    ///   exist or the destination key exists.
+    /// * `Err(DBError)` - if the databases are the same, or out of range.
+    pub fn copy_to(
+        &self,
+        src_db: usize,
+        src: &str,
+        dest_db: usize,
+        dest: &str,
+        replace: bool,
+    ) -> Result<bool, DBError> {
+        self.write_pair(src_db, dest_db, |src_keyspace, dest_keyspace| {
+            let value = match src_keyspace.get(src) {
+                Some(value) => value.clone(),
+                None => return Ok(false),
+            };
+            if !replace && dest_keyspace.get(dest).is_some() {
+                return Ok(false);
+            }
+
+            let expiry = src_keyspace.expiry(src);
+            dest_keyspace.insert_with_expiry(dest, value, expiry);
+            track_retention(dest_keyspace, dest);
+            dest_keyspace.notify(NotifyClass::Generic, "copy_to", dest);
+
+            Ok(true)
+        })
+    }
+}
+
+/// A page of a SCAN iteration, with the cursor for the next call and the elements.
+pub type ScanPage<T> = (u64, Vec<T>);
+
+/// Options of the SCAN family of commands.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Only the elements matching the glob-style pattern are returned, if given.
+    pub pattern: Option<String>,
+    /// Number of elements to be walked by a call. Fewer elements can be returned, since the
+    /// filters are applied after the elements are walked.
+    pub count: usize,
+}
+
+impl ScanOptions {
+    fn matches(&self, s: &str) -> bool {
+        self.pattern
+            .as_ref()
+            .is_none_or(|p| glob_match(p.as_bytes(), s.as_bytes()))
+    }
+}
+
+/// Returns the scan hash of a key (or an element of a collection), which orders the elements
+/// walked by SCAN cursors.
+pub fn scan_hash(s: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The elements of a collection (or the keys) ordered by their scan hashes, which the SCAN
+/// cursors walk through. It's kept alongside the collection and updated as the elements are
+/// added or removed, so that a cursor can resume without sorting the whole collection.
+///
+/// The elements are shared with the collection, which must store them as `Arc<str>` too, so
+/// that the strings aren't duplicated.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOrder {
+    elements: BTreeSet<(u64, Arc<str>)>,
+}
+
+impl ScanOrder {
+    /// Adds an element, if it's not present already.
+    pub fn insert(&mut self, e: &Arc<str>) {
+        self.elements.insert((scan_hash(e), Arc::clone(e)));
+    }
+
+    /// Removes an element, if it's present.
+    pub fn remove(&mut self, e: &Arc<str>) {
+        self.elements.remove(&(scan_hash(e), Arc::clone(e)));
+    }
+
+    /// Returns the approximate number of bytes used for an element, apart from the element
+    /// itself which is shared with the collection.
+    pub fn element_memory_usage() -> usize {
+        size_of::<(u64, Arc<str>)>()
+    }
+
+    /// Walks the elements from the scan cursor onwards, in the order of their scan hashes.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the next cursor, which is `0` once all the elements are walked, and the
+    /// walked elements.
+    pub fn walk(&self, cursor: u64, count: usize) -> (u64, Vec<&str>) {
+        let mut walked = vec![];
+        let mut last_hash = None;
+
+        for (hash, e) in self.elements.range((cursor, Arc::from(""))..) {
+            // elements with the same hash are walked together, since the cursor can't point
+            // between them
+            if walked.len() >= count && last_hash != Some(*hash) {
+                return (*hash, walked);
+            }
+            last_hash = Some(*hash);
+            walked.push(e.as_ref());
+        }
+
+        (0, walked)
+    }
+}
+
+/// Matches the string against a glob-style pattern.
+///
+/// * `*` matches any number of characters, and `?` matches a single character.
+/// * `[abc]` matches any of the characters, `[a-z]` matches a range of characters, and
+///   `[^abc]` matches any character except the ones in the brackets.
+/// * `\` escapes the next character, so that it's matched literally.
+pub fn glob_match(pattern: &[u8], s: &[u8]) -> bool {
+    let (mut p, mut i) = (0, 0);
+    // positions in the pattern and the string after the last `*`, to backtrack to when a
+    // character doesn't match
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while i < s.len() {
+        let next = match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p + 1, i));
+                p += 1;
+                continue;
+            }
+            Some(b'?') => Some(p + 1),
+            Some(b'[') => match match_class(pattern, p, s[i]) {
+                Some((true, end)) => Some(end),
+                Some((false, _)) => None,
+                // an unterminated class is matched literally
+                None => (s[i] == b'[').then_some(p + 1),
+            },
+            Some(b'\\') if p + 1 < pattern.len() => (pattern[p + 1] == s[i]).then_some(p + 2),
+            Some(c) => (*c == s[i]).then_some(p + 1),
+            None => None,
+        };
+
+        match (next, backtrack) {
+            (Some(next), _) => {
+                p = next;
+                i += 1;
+            }
+            // the last `*` absorbs one more character
+            (None, Some((star_p, star_i))) => {
+                p = star_p;
+                i = star_i + 1;
+                backtrack = Some((star_p, star_i + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// Matches a character against the class starting at `start` (the position of `[`).
+///
+/// # Returns
+///
+/// `Some((bool, usize))` with `true` if the character matches the class, along with the
+/// position after the class, or `None` if the class is not terminated.
+fn match_class(pattern: &[u8], start: usize, c: u8) -> Option<(bool, usize)> {
+    let mut p = start + 1;
+    let negated = pattern.get(p) == Some(&b'^');
+    if negated {
+        p += 1;
+    }
+
+    let mut matched = false;
+    loop {
+        match *pattern.get(p)? {
+            b']' => return Some((matched != negated, p + 1)),
+            b'\\' => {
+                matched |= *pattern.get(p + 1)? == c;
+                p += 2;
+            }
+            lo if pattern.get(p + 1) == Some(&b'-') && pattern.get(p + 2) != Some(&b']') => {
+                let hi = *pattern.get(p + 2)?;
+                let (lo, hi) = (lo.min(hi), lo.max(hi));
+                matched |= lo <= c && c <= hi;
+                p += 3;
+            }
+            other => {
+                matched |= other == c;
+                p += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use log::error;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use super::db::Value;
+
+/// Values with more allocations than this are freed in the background, when they're freed
+/// lazily. Smaller values are cheaper to free right away, than to send to the background task.
+const LAZYFREE_THRESHOLD: usize = 64;
+
+/// Specifies the implicit deletions which free the values lazily. Explicit deletions with
+/// UNLINK, or FLUSHDB and FLUSHALL with ASYNC, always free the values lazily.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LazyFreeConfig {
+    /// Free the values of expired keys lazily.
+    pub lazy_expire: bool,
+    /// Free the values which are overwritten (by SET, RENAME etc.) lazily.
+    pub lazy_server_del: bool,
+}
+
+/// Garbage which is dropped in the background.
+type Garbage = Box<dyn Send>;
+
+/// A handle to free values lazily. Values are removed from the DB synchronously, but their
+/// memory is reclaimed by `LazyFreer` on the background tasks runtime, so that the DB is not
+/// locked while large values are dropped.
+#[derive(Debug, Clone)]
+pub struct LazyFree {
+    tx: UnboundedSender<Garbage>,
+    config: LazyFreeConfig,
+}
+
+/// Background task which drops the values freed lazily.
+pub struct LazyFreer {
+    rx: UnboundedReceiver<Garbage>,
+}
+
+impl LazyFree {
+    /// Creates a new `LazyFree` handle, along with the `LazyFreer` task which drops the
+    /// values sent through the handle.
+    pub fn new(config: LazyFreeConfig) -> (LazyFree, LazyFreer) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (LazyFree { tx, config }, LazyFreer { rx })
+    }
+
+    /// Frees a value in the background if it's large, or right away otherwise.
+    pub fn free(&self, value: Value) {
+        if value.free_effort() > LAZYFREE_THRESHOLD {
+            self.free_later(value);
+        }
+    }
+
+    /// Frees the value of an expired key, lazily if configured.
+    pub fn free_expired(&self, value: Value) {
+        if self.config.lazy_expire {
+            self.free(value);
+        }
+    }
+
+    /// Frees a value which is overwritten, lazily if configured.
+    pub fn free_overwritten(&self, value: Value) {
+        if self.config.lazy_server_del {
+            self.free(value);
+        }
+    }
+
+    /// Frees anything in the background, regardless of its size.
+    pub fn free_later<T: Send + 'static>(&self, garbage: T) {
+        // if the background task is gone, the garbage is dropped right away along with the error
+        if self.tx.send(Box::new(garbage)).is_err() {
+            error!("Failed to free memory in the background");
+        }
+    }
+}
+
+impl LazyFreer {
+    /// Runs the background task, which drops the values as they are received.
+    pub async fn run(&mut self) {
+        while let Some(garbage) = self.rx.recv().await {
+            drop(garbage);
+        }
+    }
+}
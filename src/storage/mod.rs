@@ -1,7 +1,26 @@
 use time::OffsetDateTime;
 
+pub mod bitmap;
+pub mod bloom;
+pub mod count_min_sketch;
+pub mod cuckoo;
 pub mod db;
+pub mod dump;
+pub mod geo;
+pub mod hash;
+pub mod hyperloglog;
+pub mod json;
+pub mod keys;
+pub mod lazyfree;
+pub mod notify;
+pub mod search;
+pub mod search_fields;
+pub mod sort;
+pub mod sorted_set;
+pub mod time_series;
+pub mod top_k;
 pub mod ttl;
+pub mod vector;
 
 /// Represents database events that can occur in the system.
 #[derive(Debug, Clone)]
@@ -18,6 +37,11 @@ pub enum DBEvent {
     /// - `OffsetDateTime`: The expiration time for the key.
     /// - `String`: The key for which the expiry is set.
     BulkDelKeys(Vec<(OffsetDateTime, String)>),
+    /// Event triggered when a time series with a retention period is created, so that its
+    /// expired samples are trimmed periodically.
+    ///
+    /// Contains the key of the time series.
+    TrackSeriesRetention(String),
 }
 
 /// Represents errors that can occur during DB operations.
@@ -29,6 +53,8 @@ pub enum DBError {
     Other(String),
 }
 
+impl std::error::Error for DBError {}
+
 impl std::fmt::Display for DBError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,129 @@
+// Keyspace notifications are published over pub/sub whenever a key is modified, so that the
+// clients can react to the changes (for example, to invalidate their local caches).
+//
+// Each event is published to 2 channels: `__keyspace@<db>__:<key>` with the event as the
+// message, and `__keyevent@<db>__:<event>` with the key as the message. The events are sent
+// while the DB lock is held, and are published by the background task of the DB, hence
+// they're delivered in the order in which the keys are modified.
+//
+// The events are sent through a channel of their own, rather than along with the `DBEvent`s,
+// so that a burst of notifications can't make the key evictor miss the expiries of the keys.
+
+/// A keyspace event, which is the name of the event (like `set` or `expired`) and the key on
+/// which the event occurred.
+pub type KeyspaceEvent = (&'static str, String);
+
+/// The classes of keyspace events. Notifications are sent only for the classes enabled by
+/// the `notify-keyspace-events` flag string.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotifyClass {
+    /// Commands which apply to any type, like DEL, RENAME and RESTORE (`g`).
+    Generic,
+    /// Commands on strings (`$`).
+    String,
+    /// Commands on lists (`l`).
+    List,
+    /// Commands on hashes (`h`).
+    Hash,
+    /// Commands on sorted sets (`z`).
+    ZSet,
+    /// Keys which are deleted as they expire (`x`).
+    Expired,
+    /// Commands on the types implemented as modules in Redis, like JSON, probabilistic
+    /// filters and time series (`d`).
+    Module,
+    /// Keys which are created (`n`).
+    New,
+}
+
+/// The classes of keyspace events enabled by the `notify-keyspace-events` flag string, and
+/// the channels to which they're published.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NotifyFlags(u32);
+
+const KEYSPACE: u32 = 1 << 0;
+const KEYEVENT: u32 = 1 << 1;
+const GENERIC: u32 = 1 << 2;
+const STRING: u32 = 1 << 3;
+const LIST: u32 = 1 << 4;
+const SET: u32 = 1 << 5;
+const HASH: u32 = 1 << 6;
+const ZSET: u32 = 1 << 7;
+const EXPIRED: u32 = 1 << 8;
+const EVICTED: u32 = 1 << 9;
+const STREAM: u32 = 1 << 10;
+const KEY_MISS: u32 = 1 << 11;
+const MODULE: u32 = 1 << 12;
+const NEW: u32 = 1 << 13;
+/// All the classes of events (`A`), except the key misses and the new keys.
+const ALL: u32 = GENERIC | STRING | LIST | SET | HASH | ZSET | EXPIRED | EVICTED | STREAM | MODULE;
+
+impl NotifyFlags {
+    /// Parses the `notify-keyspace-events` flag string. Every character enables a class of
+    /// events, or the channels to which the events are published:
+    ///
+    /// - `K`: Keyspace events, published to `__keyspace@<db>__:<key>`.
+    /// - `E`: Keyevent events, published to `__keyevent@<db>__:<event>`.
+    /// - `g`, `$`, `l`, `s`, `h`, `z`, `x`, `e`, `t`, `m`, `d`, `n`: The classes of events,
+    ///   as in Redis. `A` is an alias for `g$lshzxetd`.
+    ///
+    /// Classes of types which are not supported (sets and streams), evictions and key misses
+    /// are accepted, but no events are sent for them. An empty string disables the
+    /// notifications.
+    ///
+    /// # Returns
+    ///
+    /// The flags, or an error if the string contains an unknown character.
+    pub fn parse(s: &str) -> Result<NotifyFlags, String> {
+        let mut flags = 0;
+        for c in s.chars() {
+            flags |= match c {
+                'K' => KEYSPACE,
+                'E' => KEYEVENT,
+                'g' => GENERIC,
+                '$' => STRING,
+                'l' => LIST,
+                's' => SET,
+                'h' => HASH,
+                'z' => ZSET,
+                'x' => EXPIRED,
+                'e' => EVICTED,
+                't' => STREAM,
+                'm' => KEY_MISS,
+                'd' => MODULE,
+                'n' => NEW,
+                'A' => ALL,
+                _ => return Err(format!("Invalid notify-keyspace-events flag: '{}'", c)),
+            };
+        }
+
+        Ok(NotifyFlags(flags))
+    }
+
+    /// Returns `true` if notifications are sent for the class of events, to at least one of
+    /// the keyspace and keyevent channels.
+    pub fn is_enabled(&self, class: NotifyClass) -> bool {
+        let class = match class {
+            NotifyClass::Generic => GENERIC,
+            NotifyClass::String => STRING,
+            NotifyClass::List => LIST,
+            NotifyClass::Hash => HASH,
+            NotifyClass::ZSet => ZSET,
+            NotifyClass::Expired => EXPIRED,
+            NotifyClass::Module => MODULE,
+            NotifyClass::New => NEW,
+        };
+
+        self.0 & class != 0 && self.0 & (KEYSPACE | KEYEVENT) != 0
+    }
+
+    /// Returns `true` if the events are published to the keyspace channels.
+    pub fn keyspace(&self) -> bool {
+        self.0 & KEYSPACE != 0
+    }
+
+    /// Returns `true` if the events are published to the keyevent channels.
+    pub fn keyevent(&self) -> bool {
+        self.0 & KEYEVENT != 0
+    }
+}
@@ -0,0 +1,857 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Bound,
+};
+
+use super::{
+    db::{Keyspace, Value, DB},
+    hash::Hash,
+    notify::NotifyClass,
+    search_fields::{NumericIndex, TagIndex, TextIndex},
+    vector::{VectorIndex, VectorParams},
+    DBError,
+};
+
+// Search indexes are defined over the hashes whose keys start with any of the index's
+// prefixes. They are part of the keyspace, and are maintained incrementally: every write to a
+// key (and its deletion) reindexes the key, while the DB lock is held. Hence the results of a
+// search are always consistent with the keyspace.
+//
+// A query is evaluated to the set of matching documents. Text queries rank the documents by
+// TF-IDF: each query term scores its number of occurrences in the document, weighted by the
+// field's weight and by how rare the term is across the documents. Vector queries rank the
+// documents matching the filter by their distances from the query vector.
+
+/// The search indexes, by their names.
+#[derive(Debug, Default)]
+pub struct SearchIndexes {
+    indexes: BTreeMap<String, SearchIndex>,
+}
+
+/// A search index over hashes.
+#[derive(Debug, Clone)]
+pub struct SearchIndex {
+    prefixes: Vec<String>,
+    fields: Vec<IndexedField>,
+    /// Keys of the indexed hashes.
+    docs: HashSet<String>,
+}
+
+/// Definition of a field in an index schema.
+#[derive(Debug, Clone)]
+pub struct FieldSchema {
+    /// Name of the hash field.
+    pub name: String,
+    /// Name by which the field is referred to in queries. Defaults to the hash field's name.
+    pub alias: Option<String>,
+    pub kind: FieldKind,
+    /// Accepted for compatibility. Results can be sorted by any field.
+    pub sortable: bool,
+}
+
+/// Type of an indexed field.
+#[derive(Debug, Clone)]
+pub enum FieldKind {
+    /// Full-text field, whose terms are scored with the weight.
+    Text {
+        weight: f64,
+    },
+    /// Exact-match field, holding a list of tags split by the separator.
+    Tag {
+        separator: char,
+        case_sensitive: bool,
+    },
+    Numeric,
+    Vector(VectorParams),
+}
+
+#[derive(Debug, Clone)]
+struct IndexedField {
+    schema: FieldSchema,
+    index: FieldIndex,
+}
+
+#[derive(Debug, Clone)]
+enum FieldIndex {
+    Text(TextIndex),
+    Tag(TagIndex),
+    Numeric(NumericIndex),
+    Vector(VectorIndex),
+}
+
+/// A node of a parsed search query. Text nodes search the given field, or all the TEXT fields
+/// if no field is given.
+#[derive(Debug, Clone)]
+pub enum QueryNode {
+    /// Matches all the documents.
+    All,
+    Term(Option<String>, String),
+    /// Matches the documents with any term starting with the prefix.
+    Prefix(Option<String>, String),
+    /// Matches the documents with the terms next to each other, in the same order.
+    Phrase(Option<String>, Vec<String>),
+    /// Matches the documents with a value in the range, in a NUMERIC field.
+    Numeric(String, Bound<f64>, Bound<f64>),
+    /// Matches the documents with any of the tags, in a TAG field.
+    Tag(String, Vec<String>),
+    And(Vec<QueryNode>),
+    Or(Vec<QueryNode>),
+    Not(Box<QueryNode>),
+}
+
+/// A K nearest neighbours query on a vector field.
+#[derive(Debug, Clone)]
+pub struct KnnQuery {
+    /// Name (or alias) of the vector field.
+    pub field: String,
+    pub k: usize,
+    /// The query vector as a FLOAT32 blob.
+    pub vector: Vec<u8>,
+    pub ef_runtime: Option<usize>,
+}
+
+/// A search query, along with the order and the page of the results.
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pub filter: QueryNode,
+    /// Finds the nearest neighbours among the documents matching the filter.
+    pub knn: Option<KnnQuery>,
+    /// Sorts the results by the field, in the ascending order if `true`.
+    pub sort_by: Option<(String, bool)>,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// A document matched by a search.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub key: String,
+    /// The distance from the query vector for KNN queries, or the TF-IDF score otherwise.
+    pub score: f64,
+    /// All the fields of the hash.
+    pub fields: Vec<(String, Vec<u8>)>,
+}
+
+/// A step of the FT.AGGREGATE pipeline.
+#[derive(Debug, Clone)]
+pub enum AggregateStep {
+    /// Groups the rows by the values of the fields, and reduces each group to a single row.
+    GroupBy {
+        fields: Vec<String>,
+        reducers: Vec<Reducer>,
+    },
+    /// Sorts the rows by the fields, each in the ascending order if `true`, and keeps the
+    /// first `max` rows.
+    SortBy {
+        fields: Vec<(String, bool)>,
+        max: Option<usize>,
+    },
+    Limit {
+        offset: usize,
+        num: usize,
+    },
+}
+
+/// Reduces the rows of a group to a value, which is set as the `alias` field of the group.
+#[derive(Debug, Clone)]
+pub struct Reducer {
+    pub function: ReduceFunction,
+    /// The field whose values are reduced. COUNT doesn't take a field.
+    pub field: Option<String>,
+    pub alias: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReduceFunction {
+    Count,
+    CountDistinct,
+    Sum,
+    Min,
+    Max,
+    Avg,
+    ToList,
+}
+
+/// A value in a row of the FT.AGGREGATE pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowValue {
+    Value(String),
+    List(Vec<String>),
+}
+
+/// A row of the FT.AGGREGATE pipeline, with the values of its fields.
+pub type Row = Vec<(String, RowValue)>;
+
+/// Details of an index, as returned by FT.INFO.
+#[derive(Debug, Clone)]
+pub struct IndexInfo {
+    pub prefixes: Vec<String>,
+    pub schema: Vec<FieldSchema>,
+    pub num_docs: usize,
+    /// Number of distinct terms across the TEXT fields.
+    pub num_terms: usize,
+    /// Number of indexed terms, tags, numbers and vectors across the documents.
+    pub num_records: usize,
+}
+
+impl SearchIndexes {
+    /// Reindexes the key in all the indexes whose prefixes match the key. `value` is the
+    /// current value of the key, or `None` if it was deleted.
+    pub fn update(&mut self, k: &str, value: Option<&Value>) {
+        for index in self.indexes.values_mut() {
+            index.update(k, value);
+        }
+    }
+}
+
+impl SearchIndex {
+    fn new(prefixes: Vec<String>, schema: Vec<FieldSchema>) -> SearchIndex {
+        let fields = schema
+            .into_iter()
+            .map(|schema| {
+                let index = match &schema.kind {
+                    FieldKind::Text { .. } => FieldIndex::Text(TextIndex::default()),
+                    FieldKind::Tag {
+                        separator,
+                        case_sensitive,
+                    } => FieldIndex::Tag(TagIndex::new(*separator, *case_sensitive)),
+                    FieldKind::Numeric => FieldIndex::Numeric(NumericIndex::default()),
+                    FieldKind::Vector(params) => FieldIndex::Vector(VectorIndex::new(params)),
+                };
+                IndexedField { schema, index }
+            })
+            .collect();
+
+        SearchIndex {
+            prefixes,
+            fields,
+            docs: HashSet::new(),
+        }
+    }
+
+    fn update(&mut self, k: &str, value: Option<&Value>) {
+        if !self.prefixes.iter().any(|p| k.starts_with(p.as_str())) {
+            return;
+        }
+
+        if self.docs.remove(k) {
+            for field in self.fields.iter_mut() {
+                match &mut field.index {
+                    FieldIndex::Text(index) => index.remove(k),
+                    FieldIndex::Tag(index) => index.remove(k),
+                    FieldIndex::Numeric(index) => index.remove(k),
+                    FieldIndex::Vector(index) => index.remove(k),
+                }
+            }
+        }
+
+        let hash = match value {
+            Some(Value::Hash(hash)) => hash,
+            _ => return,
+        };
+
+        self.docs.insert(k.to_string());
+        for field in self.fields.iter_mut() {
+            let value = match hash.get(&field.schema.name) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            // values which are not valid for the field (like non-numeric values of NUMERIC
+            // fields) are left out of the field's index, but the document is still indexed
+            match &mut field.index {
+                FieldIndex::Text(index) => index.insert(k, &String::from_utf8_lossy(value)),
+                FieldIndex::Tag(index) => index.insert(k, &String::from_utf8_lossy(value)),
+                FieldIndex::Numeric(index) => {
+                    index.insert(k, &String::from_utf8_lossy(value));
+                }
+                FieldIndex::Vector(index) => {
+                    index.insert(k, value);
+                }
+            }
+        }
+    }
+
+    /// Returns the field with the name (or alias).
+    fn field(&self, name: &str) -> Result<&IndexedField, DBError> {
+        self.fields
+            .iter()
+            .find(|f| f.schema.attribute() == name)
+            .ok_or_else(|| DBError::Other(format!("Unknown field '{}'", name)))
+    }
+
+    /// Returns the name of the hash field referred to by the name (or alias). Names which are
+    /// not in the schema are taken as hash fields.
+    fn hash_field<'a>(&'a self, name: &'a str) -> &'a str {
+        self.fields
+            .iter()
+            .find(|f| f.schema.attribute() == name)
+            .map_or(name, |f| f.schema.name.as_str())
+    }
+
+    /// Returns the indexes of the TEXT fields searched by a text node.
+    fn text_indexes(&self, field: &Option<String>) -> Result<Vec<&TextIndex>, DBError> {
+        match field {
+            Some(name) => match &self.field(name)?.index {
+                FieldIndex::Text(index) => Ok(vec![index]),
+                _ => Err(wrong_field_type_err(name, "TEXT")),
+            },
+            None => Ok(self
+                .fields
+                .iter()
+                .filter_map(|f| match &f.index {
+                    FieldIndex::Text(index) => Some(index),
+                    _ => None,
+                })
+                .collect()),
+        }
+    }
+
+    /// Returns the documents matching the query node.
+    fn eval(&self, node: &QueryNode) -> Result<HashSet<String>, DBError> {
+        let docs = match node {
+            QueryNode::All => self.docs.clone(),
+            QueryNode::Term(field, term) => self
+                .text_indexes(field)?
+                .iter()
+                .flat_map(|index| index.term(term))
+                .collect(),
+            QueryNode::Prefix(field, prefix) => self
+                .text_indexes(field)?
+                .iter()
+                .flat_map(|index| index.prefix(prefix))
+                .collect(),
+            QueryNode::Phrase(field, terms) => self
+                .text_indexes(field)?
+                .iter()
+                .flat_map(|index| index.phrase(terms))
+                .collect(),
+            QueryNode::Numeric(name, min, max) => match &self.field(name)?.index {
+                FieldIndex::Numeric(index) => index.range(*min, *max),
+                _ => return Err(wrong_field_type_err(name, "NUMERIC")),
+            },
+            QueryNode::Tag(name, tags) => match &self.field(name)?.index {
+                FieldIndex::Tag(index) => index.any(tags),
+                _ => return Err(wrong_field_type_err(name, "TAG")),
+            },
+            QueryNode::And(nodes) => {
+                let mut docs: Option<HashSet<String>> = None;
+                for node in nodes.iter() {
+                    let matched = self.eval(node)?;
+                    docs = Some(match docs {
+                        Some(docs) => docs.intersection(&matched).cloned().collect(),
+                        None => matched,
+                    });
+                }
+                docs.unwrap_or_default()
+            }
+            QueryNode::Or(nodes) => {
+                let mut docs = HashSet::new();
+                for node in nodes.iter() {
+                    docs.extend(self.eval(node)?);
+                }
+                docs
+            }
+            QueryNode::Not(node) => {
+                let excluded = self.eval(node)?;
+                self.docs.difference(&excluded).cloned().collect()
+            }
+        };
+
+        Ok(docs)
+    }
+
+    /// Returns the TF-IDF score of the document, for the terms of a query.
+    fn score(&self, doc: &str, terms: &[(Option<String>, String)]) -> f64 {
+        let num_docs = self.docs.len() as f64;
+
+        let mut score = 0.0;
+        for (field, term) in terms.iter() {
+            for f in self.fields.iter() {
+                if field
+                    .as_ref()
+                    .is_some_and(|name| f.schema.attribute() != name)
+                {
+                    continue;
+                }
+                if let (FieldIndex::Text(index), FieldKind::Text { weight }) =
+                    (&f.index, &f.schema.kind)
+                {
+                    let tf = index.term_frequency(term, doc) as f64;
+                    let df = index.doc_frequency(term).max(1) as f64;
+                    score += tf * weight * (1.0 + num_docs / df).ln();
+                }
+            }
+        }
+
+        score
+    }
+
+    fn info(&self) -> IndexInfo {
+        let mut num_terms = 0;
+        let mut num_records = 0;
+        for field in self.fields.iter() {
+            num_records += match &field.index {
+                FieldIndex::Text(index) => {
+                    num_terms += index.num_terms();
+                    index.num_records()
+                }
+                FieldIndex::Tag(index) => index.num_records(),
+                FieldIndex::Numeric(index) => index.num_records(),
+                FieldIndex::Vector(index) => index.num_vectors(),
+            };
+        }
+
+        IndexInfo {
+            prefixes: self.prefixes.clone(),
+            schema: self.fields.iter().map(|f| f.schema.clone()).collect(),
+            num_docs: self.docs.len(),
+            num_terms,
+            num_records,
+        }
+    }
+}
+
+impl FieldSchema {
+    /// Returns the name by which the field is referred to in queries.
+    pub fn attribute(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl QueryNode {
+    /// Collects the terms which score the matched documents, with the fields they're searched
+    /// in. Negated terms don't score.
+    fn terms(&self, terms: &mut Vec<(Option<String>, String)>) {
+        match self {
+            QueryNode::Term(field, term) => terms.push((field.clone(), term.clone())),
+            QueryNode::Phrase(field, phrase) => {
+                terms.extend(phrase.iter().map(|t| (field.clone(), t.clone())))
+            }
+            QueryNode::And(nodes) | QueryNode::Or(nodes) => {
+                nodes.iter().for_each(|node| node.terms(terms))
+            }
+            _ => {}
+        }
+    }
+}
+
+impl DB {
+    /// Creates a search index over the hashes whose keys start with any of the prefixes. The
+    /// existing hashes are indexed right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the index.
+    ///
+    /// * `prefixes` - Key prefixes of the hashes to be indexed. An empty prefix matches all
+    ///   the keys.
+    ///
+    /// * `schema` - The fields to be indexed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the index was created.
+    /// * `Err(DBError)` - if an index with the name already exists.
+    pub fn ft_create(
+        &self,
+        name: &str,
+        prefixes: Vec<String>,
+        schema: Vec<FieldSchema>,
+    ) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            if keyspace.indexes().indexes.contains_key(name) {
+                return Err(DBError::Other(String::from("Index already exists")));
+            }
+
+            let mut index = SearchIndex::new(prefixes, schema);
+            for (k, v) in keyspace.iter() {
+                index.update(k, Some(v));
+            }
+
+            keyspace
+                .indexes_mut()
+                .indexes
+                .insert(name.to_string(), index);
+            Ok(())
+        })
+    }
+
+    /// Drops a search index.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the index.
+    ///
+    /// * `delete_docs` - Set to `true` to delete the indexed hashes as well.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the index was dropped.
+    /// * `Err(DBError)` - if the index doesn't exist.
+    pub fn ft_dropindex(&self, name: &str, delete_docs: bool) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            let index = keyspace
+                .indexes_mut()
+                .indexes
+                .remove(name)
+                .ok_or_else(unknown_index_err)?;
+
+            if delete_docs {
+                for k in index.docs.iter() {
+                    if keyspace.remove(k).is_some() {
+                        keyspace.notify(NotifyClass::Generic, "del", k);
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Searches the documents of an index.
+    ///
+    /// Documents are ordered by their distances from the query vector for KNN queries, and by
+    /// their scores (highest first) otherwise, unless a sort field is given. Documents without
+    /// the sort field are ordered last.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok((usize, Vec<SearchHit>))` - Number of matched documents, and the documents in the
+    ///   requested page.
+    /// * `Err(DBError)` - if the index or a queried field doesn't exist, or if a field is
+    ///   queried as another type.
+    pub fn ft_search(
+        &self,
+        name: &str,
+        query: &SearchQuery,
+    ) -> Result<(usize, Vec<SearchHit>), DBError> {
+        self.read(|keyspace| {
+            let index = search_index(keyspace, name)?;
+            let docs = index.eval(&query.filter)?;
+
+            let mut hits: Vec<(String, f64)> = match &query.knn {
+                Some(knn) => {
+                    let vectors = match &index.field(&knn.field)?.index {
+                        FieldIndex::Vector(vectors) => vectors,
+                        _ => return Err(wrong_field_type_err(&knn.field, "VECTOR")),
+                    };
+                    let filter = match query.filter {
+                        QueryNode::All => None,
+                        _ => Some(&docs),
+                    };
+
+                    vectors
+                        .knn(&knn.vector, knn.k, knn.ef_runtime, filter)?
+                        .into_iter()
+                        .map(|(key, distance)| (key, distance as f64))
+                        .collect()
+                }
+                None => {
+                    let mut terms = vec![];
+                    query.filter.terms(&mut terms);
+
+                    let mut hits: Vec<(String, f64)> = docs
+                        .into_iter()
+                        .map(|doc| {
+                            let score = index.score(&doc, &terms);
+                            (doc, score)
+                        })
+                        .collect();
+                    hits.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                    hits
+                }
+            };
+
+            if let Some((field, asc)) = &query.sort_by {
+                let field = index.hash_field(field);
+                let values: HashMap<String, String> = hits
+                    .iter()
+                    .filter_map(|(key, _)| {
+                        let value = hash_field_value(keyspace, key, field)?;
+                        Some((key.clone(), value))
+                    })
+                    .collect();
+
+                // the sort is stable, so ties keep their order by score
+                hits.sort_by(|a, b| {
+                    compare_values(
+                        values.get(&a.0).map(|v| v.as_str()),
+                        values.get(&b.0).map(|v| v.as_str()),
+                        *asc,
+                    )
+                });
+            }
+
+            let total = hits.len();
+            let page = hits
+                .into_iter()
+                .skip(query.offset)
+                .take(query.limit)
+                .map(|(key, score)| {
+                    let fields = match keyspace.get(&key) {
+                        Some(Value::Hash(hash)) => hash
+                            .iter()
+                            .map(|(f, v)| (f.to_string(), v.clone()))
+                            .collect(),
+                        _ => vec![],
+                    };
+                    SearchHit { key, score, fields }
+                })
+                .collect();
+
+            Ok((total, page))
+        })
+    }
+
+    /// Runs an aggregation pipeline over the documents matching the query. Each document is
+    /// loaded as a row, which goes through the steps of the pipeline in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the index.
+    ///
+    /// * `filter` - The query which selects the documents.
+    ///
+    /// * `load` - Fields to be loaded into the rows, along with the fields used by the
+    ///   pipeline. `*` loads all the fields.
+    ///
+    /// * `steps` - The steps of the pipeline.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Row>)` - The rows resulting from the pipeline.
+    /// * `Err(DBError)` - if the index or a queried field doesn't exist, or if a field is
+    ///   queried as another type.
+    pub fn ft_aggregate(
+        &self,
+        name: &str,
+        filter: &QueryNode,
+        load: &[String],
+        steps: &[AggregateStep],
+    ) -> Result<Vec<Row>, DBError> {
+        self.read(|keyspace| {
+            let index = search_index(keyspace, name)?;
+
+            let mut docs: Vec<String> = index.eval(filter)?.into_iter().collect();
+            docs.sort();
+
+            // steps after the first GROUPBY only see the fields of the groups
+            let mut fields: Vec<&str> = load.iter().map(|f| f.as_str()).collect();
+            for step in steps.iter() {
+                match step {
+                    AggregateStep::GroupBy {
+                        fields: by,
+                        reducers,
+                    } => {
+                        fields.extend(by.iter().map(|f| f.as_str()));
+                        fields.extend(reducers.iter().filter_map(|r| r.field.as_deref()));
+                        break;
+                    }
+                    AggregateStep::SortBy { fields: by, .. } => {
+                        fields.extend(by.iter().map(|(f, _)| f.as_str()))
+                    }
+                    AggregateStep::Limit { .. } => {}
+                }
+            }
+
+            let mut rows: Vec<Row> = docs
+                .iter()
+                .filter_map(|doc| match keyspace.get(doc) {
+                    Some(Value::Hash(hash)) => Some(load_row(index, hash, &fields)),
+                    _ => None,
+                })
+                .collect();
+
+            for step in steps.iter() {
+                rows = match step {
+                    AggregateStep::GroupBy { fields, reducers } => group_by(rows, fields, reducers),
+                    AggregateStep::SortBy { fields, max } => {
+                        rows.sort_by(|a, b| {
+                            fields
+                                .iter()
+                                .map(|(f, asc)| {
+                                    compare_values(row_value(a, f), row_value(b, f), *asc)
+                                })
+                                .find(|o| o.is_ne())
+                                .unwrap_or(Ordering::Equal)
+                        });
+                        rows.truncate(max.unwrap_or(usize::MAX));
+                        rows
+                    }
+                    AggregateStep::Limit { offset, num } => {
+                        rows.into_iter().skip(*offset).take(*num).collect()
+                    }
+                };
+            }
+
+            Ok(rows)
+        })
+    }
+
+    /// Returns the details of an index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IndexInfo)` - The details of the index.
+    /// * `Err(DBError)` - if the index doesn't exist.
+    pub fn ft_info(&self, name: &str) -> Result<IndexInfo, DBError> {
+        self.read(|keyspace| Ok(search_index(keyspace, name)?.info()))
+    }
+}
+
+/// Loads the fields of a document into a row. Fields are referred to by their names (or
+/// aliases).
+fn load_row(index: &SearchIndex, hash: &Hash, fields: &[&str]) -> Row {
+    let mut row: Row = vec![];
+
+    let mut names: Vec<&str> = vec![];
+    if fields.contains(&"*") {
+        let mut all: Vec<&str> = hash.keys().collect();
+        all.sort();
+        names.extend(all);
+    }
+    names.extend(fields.iter().filter(|f| **f != "*"));
+
+    for name in names {
+        if row.iter().any(|(f, _)| f == name) {
+            continue;
+        }
+        if let Some(value) = hash.get(index.hash_field(name)) {
+            let value = String::from_utf8_lossy(value).to_string();
+            row.push((name.to_string(), RowValue::Value(value)));
+        }
+    }
+
+    row
+}
+
+/// Groups the rows by the values of the fields, and reduces each group to a row. Groups are
+/// returned in the order they're first seen.
+fn group_by(rows: Vec<Row>, fields: &[String], reducers: &[Reducer]) -> Vec<Row> {
+    let mut groups: Vec<(Vec<Option<String>>, Vec<Row>)> = vec![];
+    let mut group_ids: HashMap<Vec<Option<String>>, usize> = HashMap::new();
+
+    for row in rows {
+        let key: Vec<Option<String>> = fields
+            .iter()
+            .map(|f| row_value(&row, f).map(|v| v.to_string()))
+            .collect();
+
+        match group_ids.get(&key) {
+            Some(id) => groups[*id].1.push(row),
+            None => {
+                group_ids.insert(key.clone(), groups.len());
+                groups.push((key, vec![row]));
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, rows)| {
+            let mut row: Row = fields
+                .iter()
+                .zip(key)
+                .filter_map(|(f, v)| Some((f.clone(), RowValue::Value(v?))))
+                .collect();
+
+            for reducer in reducers.iter() {
+                row.push((reducer.alias.clone(), reduce(&rows, reducer)));
+            }
+            row
+        })
+        .collect()
+}
+
+/// Reduces the rows of a group. Values which are not numbers are skipped by the numeric
+/// reducers.
+fn reduce(rows: &[Row], reducer: &Reducer) -> RowValue {
+    let values: Vec<&str> = match &reducer.field {
+        Some(field) => rows
+            .iter()
+            .filter_map(|row| row_value(row, field))
+            .collect(),
+        None => vec![],
+    };
+    let numbers = || values.iter().filter_map(|v| v.parse::<f64>().ok());
+
+    let result = match reducer.function {
+        ReduceFunction::Count => rows.len() as f64,
+        ReduceFunction::CountDistinct => values.iter().collect::<HashSet<_>>().len() as f64,
+        ReduceFunction::Sum => numbers().sum(),
+        ReduceFunction::Min => numbers().fold(f64::INFINITY, f64::min),
+        ReduceFunction::Max => numbers().fold(f64::NEG_INFINITY, f64::max),
+        ReduceFunction::Avg => match numbers().count() {
+            0 => 0.0,
+            count => numbers().sum::<f64>() / count as f64,
+        },
+        ReduceFunction::ToList => {
+            let mut list: Vec<String> = vec![];
+            for value in values {
+                if !list.iter().any(|v| v == value) {
+                    list.push(value.to_string());
+                }
+            }
+            return RowValue::List(list);
+        }
+    };
+
+    RowValue::Value(result.to_string())
+}
+
+/// Returns the value of a field of the row, unless it's a list.
+fn row_value<'a>(row: &'a Row, field: &str) -> Option<&'a str> {
+    row.iter()
+        .find(|(f, _)| f == field)
+        .and_then(|(_, value)| match value {
+            RowValue::Value(value) => Some(value.as_str()),
+            RowValue::List(_) => None,
+        })
+}
+
+/// Compares the values as numbers if both of them are numbers, else as strings. Missing
+/// values are ordered last, in either order.
+fn compare_values(a: Option<&str>, b: Option<&str>, asc: bool) -> Ordering {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (a, b) => return Reverse(a.is_some()).cmp(&Reverse(b.is_some())),
+    };
+
+    let ordering = match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.total_cmp(&y),
+        _ => a.cmp(b),
+    };
+
+    if asc {
+        ordering
+    } else {
+        ordering.reverse()
+    }
+}
+
+/// Returns the value of a field of the hash stored at key, as a string.
+fn hash_field_value(keyspace: &Keyspace, k: &str, field: &str) -> Option<String> {
+    match keyspace.get(k) {
+        Some(Value::Hash(hash)) => hash
+            .get(field)
+            .map(|v| String::from_utf8_lossy(v).to_string()),
+        _ => None,
+    }
+}
+
+fn search_index<'a>(keyspace: &'a Keyspace, name: &str) -> Result<&'a SearchIndex, DBError> {
+    keyspace
+        .indexes()
+        .indexes
+        .get(name)
+        .ok_or_else(unknown_index_err)
+}
+
+fn unknown_index_err() -> DBError {
+    DBError::Other(String::from("Unknown Index name"))
+}
+
+fn wrong_field_type_err(field: &str, kind: &str) -> DBError {
+    DBError::Other(format!("Field '{}' is not a {} field", field, kind))
+}
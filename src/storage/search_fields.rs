@@ -0,0 +1,266 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    ops::Bound,
+};
+
+// Indexes of the TEXT, TAG and NUMERIC fields of a search index. Each index tracks the values
+// it indexed for every document, so that a document can be removed without reading its
+// previous value.
+
+/// An inverted index over the terms of a TEXT field.
+#[derive(Debug, Clone, Default)]
+pub struct TextIndex {
+    /// Positions of each term in the documents, by the term and the document's key. Terms are
+    /// ordered, so that the terms with a prefix can be looked up as a range.
+    postings: BTreeMap<String, HashMap<String, Vec<u32>>>,
+    doc_terms: HashMap<String, Vec<String>>,
+}
+
+/// An index over the tags of a TAG field.
+#[derive(Debug, Clone)]
+pub struct TagIndex {
+    separator: char,
+    case_sensitive: bool,
+    docs: HashMap<String, HashSet<String>>,
+    doc_tags: HashMap<String, Vec<String>>,
+}
+
+/// An index over the values of a NUMERIC field.
+#[derive(Debug, Clone, Default)]
+pub struct NumericIndex {
+    /// Documents ordered by their values. Values are stored as ordered bits.
+    values: BTreeSet<(u64, String)>,
+    doc_values: HashMap<String, f64>,
+}
+
+impl TextIndex {
+    /// Indexes the terms of the text for the document.
+    pub fn insert(&mut self, doc: &str, text: &str) {
+        let mut terms = vec![];
+        for (pos, term) in tokenize(text).into_iter().enumerate() {
+            let positions = self
+                .postings
+                .entry(term.clone())
+                .or_default()
+                .entry(doc.to_string())
+                .or_default();
+            if positions.is_empty() {
+                terms.push(term);
+            }
+            positions.push(pos as u32);
+        }
+
+        self.doc_terms.insert(doc.to_string(), terms);
+    }
+
+    pub fn remove(&mut self, doc: &str) {
+        for term in self.doc_terms.remove(doc).unwrap_or_default() {
+            if let Some(docs) = self.postings.get_mut(&term) {
+                docs.remove(doc);
+                if docs.is_empty() {
+                    self.postings.remove(&term);
+                }
+            }
+        }
+    }
+
+    /// Returns the documents containing the term.
+    pub fn term(&self, term: &str) -> HashSet<String> {
+        self.postings
+            .get(term)
+            .map(|docs| docs.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the documents containing any term with the prefix.
+    pub fn prefix(&self, prefix: &str) -> HashSet<String> {
+        self.postings
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(|(term, _)| term.starts_with(prefix))
+            .flat_map(|(_, docs)| docs.keys().cloned())
+            .collect()
+    }
+
+    /// Returns the documents containing the terms next to each other, in the same order.
+    pub fn phrase(&self, terms: &[String]) -> HashSet<String> {
+        let postings: Vec<&HashMap<String, Vec<u32>>> =
+            match terms.iter().map(|t| self.postings.get(t)).collect() {
+                Some(postings) => postings,
+                None => return HashSet::new(),
+            };
+
+        let (first, rest) = match postings.split_first() {
+            Some(split) => split,
+            None => return HashSet::new(),
+        };
+
+        first
+            .iter()
+            .filter(|(doc, positions)| {
+                positions.iter().any(|&start| {
+                    rest.iter().enumerate().all(|(i, docs)| {
+                        docs.get(*doc)
+                            .is_some_and(|p| p.contains(&(start + i as u32 + 1)))
+                    })
+                })
+            })
+            .map(|(doc, _)| doc.clone())
+            .collect()
+    }
+
+    /// Returns the number of occurrences of the term in the document.
+    pub fn term_frequency(&self, term: &str, doc: &str) -> usize {
+        self.postings
+            .get(term)
+            .and_then(|docs| docs.get(doc))
+            .map_or(0, |p| p.len())
+    }
+
+    /// Returns the number of documents containing the term.
+    pub fn doc_frequency(&self, term: &str) -> usize {
+        self.postings.get(term).map_or(0, |docs| docs.len())
+    }
+
+    /// Returns the number of distinct terms.
+    pub fn num_terms(&self) -> usize {
+        self.postings.len()
+    }
+
+    /// Returns the number of (term, document) pairs.
+    pub fn num_records(&self) -> usize {
+        self.doc_terms.values().map(|t| t.len()).sum()
+    }
+}
+
+impl TagIndex {
+    pub fn new(separator: char, case_sensitive: bool) -> TagIndex {
+        TagIndex {
+            separator,
+            case_sensitive,
+            docs: HashMap::new(),
+            doc_tags: HashMap::new(),
+        }
+    }
+
+    /// Indexes the tags of the value, split by the separator, for the document.
+    pub fn insert(&mut self, doc: &str, value: &str) {
+        let mut tags = vec![];
+        for tag in value.split(self.separator) {
+            let tag = self.normalize(tag);
+            if tag.is_empty() || tags.contains(&tag) {
+                continue;
+            }
+            self.docs
+                .entry(tag.clone())
+                .or_default()
+                .insert(doc.to_string());
+            tags.push(tag);
+        }
+
+        self.doc_tags.insert(doc.to_string(), tags);
+    }
+
+    pub fn remove(&mut self, doc: &str) {
+        for tag in self.doc_tags.remove(doc).unwrap_or_default() {
+            if let Some(docs) = self.docs.get_mut(&tag) {
+                docs.remove(doc);
+                if docs.is_empty() {
+                    self.docs.remove(&tag);
+                }
+            }
+        }
+    }
+
+    /// Returns the documents with any of the tags.
+    pub fn any(&self, tags: &[String]) -> HashSet<String> {
+        tags.iter()
+            .filter_map(|tag| self.docs.get(&self.normalize(tag)))
+            .flat_map(|docs| docs.iter().cloned())
+            .collect()
+    }
+
+    pub fn num_records(&self) -> usize {
+        self.doc_tags.values().map(|t| t.len()).sum()
+    }
+
+    fn normalize(&self, tag: &str) -> String {
+        let tag = tag.trim();
+        if self.case_sensitive {
+            tag.to_string()
+        } else {
+            tag.to_lowercase()
+        }
+    }
+}
+
+impl NumericIndex {
+    /// Indexes the value for the document. Returns `false` if the value is not a number.
+    pub fn insert(&mut self, doc: &str, value: &str) -> bool {
+        let value = match value.trim().parse::<f64>() {
+            Ok(value) if !value.is_nan() => value,
+            _ => return false,
+        };
+
+        self.values.insert((ordered_bits(value), doc.to_string()));
+        self.doc_values.insert(doc.to_string(), value);
+        true
+    }
+
+    pub fn remove(&mut self, doc: &str) {
+        if let Some(value) = self.doc_values.remove(doc) {
+            self.values.remove(&(ordered_bits(value), doc.to_string()));
+        }
+    }
+
+    /// Returns the documents with values in the range.
+    pub fn range(&self, min: Bound<f64>, max: Bound<f64>) -> HashSet<String> {
+        let start = match min {
+            Bound::Included(min) | Bound::Excluded(min) => ordered_bits(min),
+            Bound::Unbounded => 0,
+        };
+
+        self.values
+            .range((start, String::new())..)
+            .map(|(bits, doc)| (from_ordered_bits(*bits), doc))
+            .skip_while(|(value, _)| matches!(min, Bound::Excluded(min) if *value <= min))
+            .take_while(|(value, _)| match max {
+                Bound::Included(max) => *value <= max,
+                Bound::Excluded(max) => *value < max,
+                Bound::Unbounded => true,
+            })
+            .map(|(_, doc)| doc.clone())
+            .collect()
+    }
+
+    pub fn num_records(&self) -> usize {
+        self.doc_values.len()
+    }
+}
+
+/// Splits the text into lowercase terms. Terms are the runs of alphanumeric characters (and
+/// underscores).
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Maps a float to bits which sort in the same order as the floats.
+fn ordered_bits(value: f64) -> u64 {
+    // -0.0 is sorted as 0.0
+    let bits = (value + 0.0).to_bits();
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
+fn from_ordered_bits(bits: u64) -> f64 {
+    if bits >> 63 == 1 {
+        f64::from_bits(bits & !(1 << 63))
+    } else {
+        f64::from_bits(!bits)
+    }
+}
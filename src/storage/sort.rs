@@ -0,0 +1,211 @@
+use std::cmp::Ordering;
+
+use super::{
+    db::{Keyspace, Value, DB},
+    notify::NotifyClass,
+    DBError,
+};
+
+// SORT sorts the elements of a list, or the members of a sorted set. By default, the elements
+// are compared as numbers, or as binary strings with ALPHA.
+//
+// Patterns: The BY and GET patterns are keys in which the first `*` is substituted with the
+// element. A pattern may refer to a field of a hash with `key->field`. A BY pattern without
+// `*` skips the sorting, and the GET pattern `#` returns the element itself. Missing keys (or
+// keys holding other types) are treated as missing values. A missing weight is 0, or sorts
+// first with ALPHA.
+
+/// Options of the SORT command.
+#[derive(Debug, Clone, Default)]
+pub struct SortOptions {
+    /// The pattern of the keys holding the weights of the elements.
+    pub by: Option<String>,
+    /// Offset and count of the elements to be returned. A negative count returns all the
+    /// elements after the offset.
+    pub limit: Option<(i64, i64)>,
+    /// The patterns of the keys whose values are returned, instead of the elements.
+    pub get: Vec<String>,
+    pub desc: bool,
+    pub alpha: bool,
+}
+
+/// An element being sorted, along with its weight.
+struct SortItem {
+    element: String,
+    weight: SortWeight,
+}
+
+/// The weight by which an element is sorted.
+enum SortWeight {
+    None,
+    Number(f64),
+    Bytes(Vec<u8>),
+}
+
+impl DB {
+    /// Sorts the elements of the list or sorted set stored at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<Vec<u8>>>)` - The sorted elements, or the values of the GET patterns
+    ///   for each element. Missing values are `None`.
+    /// * `Err(DBError)` - if key holds another type, or if a weight is not a number when
+    ///   sorting numerically.
+    pub fn sort(&self, k: &str, opts: &SortOptions) -> Result<Vec<Option<Vec<u8>>>, DBError> {
+        self.read(|keyspace| sort_values(keyspace, k, opts))
+    }
+
+    /// Sorts the elements like `sort`, and stores the result as a list at the destination
+    /// key. Missing values are stored as empty strings. The destination key is deleted if
+    /// the result is empty.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)` - The number of elements in the stored list.
+    /// * `Err(DBError)` - if the elements can't be sorted.
+    pub fn sort_store(&self, k: &str, opts: &SortOptions, dest: &str) -> Result<usize, DBError> {
+        self.write(|keyspace| {
+            let list = sort_values(keyspace, k, opts)?
+                .into_iter()
+                .map(|v| String::from_utf8_lossy(&v.unwrap_or_default()).to_string())
+                .collect::<Vec<String>>();
+
+            let len = list.len();
+            match len {
+                0 => {
+                    if keyspace.remove(dest).is_some() {
+                        keyspace.notify(NotifyClass::Generic, "del", dest);
+                    }
+                }
+                _ => {
+                    keyspace.insert(dest, Value::List(list.into()));
+                    keyspace.notify(NotifyClass::List, "sortstore", dest);
+                }
+            }
+
+            Ok(len)
+        })
+    }
+}
+
+/// Sorts the elements of the list or sorted set stored at key, and returns the elements or
+/// the values of the GET patterns.
+fn sort_values(
+    keyspace: &Keyspace,
+    k: &str,
+    opts: &SortOptions,
+) -> Result<Vec<Option<Vec<u8>>>, DBError> {
+    let elements: Vec<String> = match keyspace.get(k) {
+        Some(Value::List(list)) => list.iter().cloned().collect(),
+        Some(Value::SortedSet(zset)) => zset.iter().map(|(m, _)| m.to_string()).collect(),
+        Some(_) => return Err(DBError::WrongType),
+        None => vec![],
+    };
+
+    let sort = opts.by.as_ref().is_none_or(|by| by.contains('*'));
+    let mut items = elements
+        .into_iter()
+        .map(|element| {
+            let weight = match (&opts.by, sort) {
+                (_, false) => SortWeight::None,
+                (Some(by), true) => sort_weight(lookup(keyspace, by, &element), opts.alpha)?,
+                (None, true) => sort_weight(Some(element.clone().into_bytes()), opts.alpha)?,
+            };
+            Ok(SortItem { element, weight })
+        })
+        .collect::<Result<Vec<SortItem>, DBError>>()?;
+
+    if sort {
+        items.sort_by(|a, b| {
+            // ties are broken by the elements, so that the order is deterministic
+            let ord = cmp_weights(&a.weight, &b.weight)
+                .then_with(|| a.element.as_bytes().cmp(b.element.as_bytes()));
+            if opts.desc {
+                ord.reverse()
+            } else {
+                ord
+            }
+        });
+    } else if opts.desc && matches!(keyspace.get(k), Some(Value::SortedSet(_))) {
+        // unsorted members of a sorted set are returned in the reverse order of scores
+        items.reverse();
+    }
+
+    let (offset, count) = opts.limit.unwrap_or((0, -1));
+    let offset = offset.max(0) as usize;
+    let count = match count {
+        c if c < 0 => usize::MAX,
+        c => c as usize,
+    };
+
+    let items = items.into_iter().skip(offset).take(count);
+    if opts.get.is_empty() {
+        return Ok(items.map(|item| Some(item.element.into_bytes())).collect());
+    }
+
+    Ok(items
+        .flat_map(|item| {
+            opts.get
+                .iter()
+                .map(|pattern| match pattern.as_str() {
+                    "#" => Some(item.element.clone().into_bytes()),
+                    _ => lookup(keyspace, pattern, &item.element),
+                })
+                .collect::<Vec<Option<Vec<u8>>>>()
+        })
+        .collect())
+}
+
+/// Returns the value of the key (or hash field) referred to by the pattern, after
+/// substituting the first `*` with the element.
+fn lookup(keyspace: &Keyspace, pattern: &str, element: &str) -> Option<Vec<u8>> {
+    let pattern = pattern.replacen('*', element, 1);
+
+    // the hash field is separated by the last `->`, unless it's at the end of the pattern
+    let (key, field) = match pattern.rsplit_once("->") {
+        Some((key, field)) if !field.is_empty() => (key, Some(field)),
+        _ => (pattern.as_str(), None),
+    };
+
+    match (keyspace.get(key), field) {
+        (Some(Value::String(s)), None) => Some(s.clone()),
+        (Some(Value::Hash(hash)), Some(field)) => hash.get(field).cloned(),
+        _ => None,
+    }
+}
+
+/// Converts the value by which an element is sorted into its weight.
+fn sort_weight(value: Option<Vec<u8>>, alpha: bool) -> Result<SortWeight, DBError> {
+    let value = match (value, alpha) {
+        (Some(value), _) => value,
+        (None, true) => return Ok(SortWeight::None),
+        (None, false) => return Ok(SortWeight::Number(0.0)),
+    };
+
+    if alpha {
+        return Ok(SortWeight::Bytes(value));
+    }
+
+    std::str::from_utf8(&value)
+        .ok()
+        .and_then(|s| s.trim().parse::<f64>().ok())
+        .filter(|n| !n.is_nan())
+        .map(SortWeight::Number)
+        .ok_or_else(|| {
+            DBError::Other(String::from(
+                "One or more scores can't be converted into double",
+            ))
+        })
+}
+
+/// Compares two weights. Missing weights are lower than any other weight.
+fn cmp_weights(a: &SortWeight, b: &SortWeight) -> Ordering {
+    match (a, b) {
+        (SortWeight::Number(a), SortWeight::Number(b)) => a.total_cmp(b),
+        (SortWeight::Bytes(a), SortWeight::Bytes(b)) => a.cmp(b),
+        (SortWeight::None, SortWeight::None) => Ordering::Equal,
+        (SortWeight::None, _) => Ordering::Less,
+        (_, SortWeight::None) => Ordering::Greater,
+        _ => Ordering::Equal,
+    }
+}
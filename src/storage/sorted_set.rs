@@ -0,0 +1,158 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap},
+    mem::size_of,
+    ops::Bound,
+    sync::Arc,
+};
+
+use super::{
+    db::{Value, DB},
+    keys::ScanOrder,
+    DBError,
+};
+
+/// A set of unique members, ordered by the score associated with each member.
+/// Members with the same score are ordered lexicographically.
+///
+/// The score of a member is looked up from a `HashMap`, while the members are kept ordered in a
+/// `BTreeSet`, so that range queries on the scores are efficient. The members are also kept in
+/// the order of their scan hashes, which ZSCAN cursors walk through. Each member is stored
+/// once, and shared by the three.
+#[derive(Debug, Clone, Default)]
+pub struct SortedSet {
+    scores: HashMap<Arc<str>, f64>,
+    ordered: BTreeSet<(Score, Arc<str>)>,
+    scan_order: ScanOrder,
+}
+
+impl DB {
+    /// Returns the score of a member of the sorted set stored at key. `None` is returned if
+    /// either the key or the member doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Option<f64>)` - The score of the member.
+    /// * `Err(DBError)` - if key already exists and is not a sorted set.
+    pub fn zscore(&self, k: &str, member: &str) -> Result<Option<f64>, DBError> {
+        self.read(|keyspace| match keyspace.get(k) {
+            Some(Value::SortedSet(zset)) => Ok(zset.score(member)),
+            Some(_) => Err(DBError::WrongType),
+            None => Ok(None),
+        })
+    }
+}
+
+/// A wrapper over the score, which provides total ordering for the scores.
+#[derive(Debug, Clone, Copy)]
+struct Score(f64);
+
+impl SortedSet {
+    /// Creates an empty sorted set.
+    pub fn new() -> SortedSet {
+        SortedSet::default()
+    }
+
+    /// Adds a member to the sorted set, or updates the score of an existing member.
+    ///
+    /// # Returns
+    ///
+    /// The previous score of the member, if it was already present.
+    pub fn insert(&mut self, member: String, score: f64) -> Option<f64> {
+        let (member, old_score) = match self.scores.get_key_value(member.as_str()) {
+            Some((member, old)) => (Arc::clone(member), Some(*old)),
+            None => (Arc::from(member), None),
+        };
+        match old_score {
+            Some(old) => {
+                self.ordered.remove(&(Score(old), Arc::clone(&member)));
+            }
+            None => self.scan_order.insert(&member),
+        }
+        self.ordered.insert((Score(score), Arc::clone(&member)));
+        self.scores.insert(member, score);
+
+        old_score
+    }
+
+    /// Returns the score of a member.
+    pub fn score(&self, member: &str) -> Option<f64> {
+        self.scores.get(member).copied()
+    }
+
+    /// Returns the number of members in the sorted set.
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Checks if the sorted set has no members.
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+
+    /// Returns the members along with their scores, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.scores.iter().map(|(m, score)| (m.as_ref(), *score))
+    }
+
+    /// Returns the members (and their scores) from the scan cursor onwards, in the order of
+    /// their scan hashes.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with the next cursor, which is `0` once all the members are scanned, and the
+    /// scanned members.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<(&str, f64)>) {
+        let (cursor, members) = self.scan_order.walk(cursor, count);
+        let members = members
+            .into_iter()
+            .filter_map(|m| Some((m, self.score(m)?)))
+            .collect();
+
+        (cursor, members)
+    }
+
+    /// Returns the members with scores in the range `min <= score < max`, in the order of their scores.
+    pub fn range_by_score(&self, min: f64, max: f64) -> impl Iterator<Item = (&str, f64)> {
+        let lower = Bound::Included((Score(min), Arc::from("")));
+        let upper = Bound::Excluded((Score(max), Arc::from("")));
+
+        self.ordered
+            .range((lower, upper))
+            .map(|(score, member)| (member.as_ref(), score.0))
+    }
+
+    /// Returns the approximate number of bytes used by the sorted set. Each member is stored
+    /// once, and shared by the score lookup, the ordered set and the scan order.
+    pub fn memory_usage(&self) -> usize {
+        self.scores
+            .keys()
+            .map(|member| {
+                member.len()
+                    + size_of::<(Arc<str>, f64)>()
+                    + size_of::<(Score, Arc<str>)>()
+                    + ScanOrder::element_memory_usage()
+            })
+            .sum()
+    }
+}
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
@@ -0,0 +1,797 @@
+use std::{collections::BTreeMap, mem::size_of};
+
+use log::error;
+
+use super::{
+    db::{Keyspace, Value, DB},
+    dump::{bad_format_err, Decoder, Dump, Encoder},
+    notify::NotifyClass,
+    DBError, DBEvent,
+};
+
+// A time series holds samples (a timestamp in milliseconds and a float value) ordered by their
+// timestamps, along with a set of labels which are used to select the series in TS.MRANGE.
+//
+// Retention: Samples older than the retention period, counting back from the latest sample,
+// are trimmed periodically by the `KeyEvictor`. Series with a retention period are registered
+// with it through `DBEvent::TrackSeriesRetention`. Samples which are already out of the
+// retention period are rejected by TS.ADD.
+//
+// Compaction: A series can have rules which downsample its samples into other series. Each rule
+// aggregates the samples of the current bucket, and once a sample from a later bucket arrives,
+// the aggregate is added to the destination series with the bucket's start as the timestamp.
+// Samples added to a bucket which is already closed are not compacted.
+
+/// The policy for handling a sample with the same timestamp as an existing sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicatePolicy {
+    /// Reject the new sample.
+    Block,
+    /// Keep the existing sample.
+    First,
+    /// Replace with the new sample.
+    Last,
+    /// Keep the lower value.
+    Min,
+    /// Keep the higher value.
+    Max,
+    /// Add the new value to the existing value.
+    Sum,
+}
+
+/// The aggregation applied to the samples of a bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregation {
+    Avg,
+    Sum,
+    Min,
+    Max,
+    Count,
+    First,
+    Last,
+}
+
+/// Options of a time series, given when it's created explicitly or implicitly.
+#[derive(Debug, Clone, Default)]
+pub struct SeriesOptions {
+    /// Retention period in milliseconds. 0 retains the samples forever.
+    pub retention: Option<u64>,
+    pub duplicate_policy: Option<DuplicatePolicy>,
+    pub labels: Option<Vec<(String, String)>>,
+}
+
+/// A query on the samples of a time series.
+#[derive(Debug, Clone)]
+pub struct RangeQuery {
+    /// Start timestamp (inclusive).
+    pub from: u64,
+    /// End timestamp (inclusive).
+    pub to: u64,
+    /// Return the samples in the descending order of timestamps.
+    pub rev: bool,
+    /// Only the samples with these timestamps are returned.
+    pub filter_by_ts: Option<Vec<u64>>,
+    /// Only the samples with values in this range (inclusive) are returned.
+    pub filter_by_value: Option<(f64, f64)>,
+    /// Max number of samples (or buckets, when aggregated) to be returned.
+    pub count: Option<usize>,
+    pub aggregation: Option<RangeAggregation>,
+}
+
+/// Aggregation of the samples in a range query.
+#[derive(Debug, Clone, Copy)]
+pub struct RangeAggregation {
+    pub aggregation: Aggregation,
+    /// Duration of each bucket in milliseconds.
+    pub bucket_duration: u64,
+    /// Buckets start at this timestamp, plus multiples of the bucket duration.
+    pub align: u64,
+}
+
+/// A filter which selects the time series by their labels, in TS.MRANGE.
+#[derive(Debug, Clone)]
+pub enum LabelFilter {
+    /// The label's value must be one of the values. An empty value matches series without
+    /// the label.
+    Eq(String, Vec<String>),
+    /// The label's value must not be any of the values. An empty value matches series
+    /// with the label.
+    NotEq(String, Vec<String>),
+}
+
+/// A time series.
+#[derive(Debug, Clone)]
+pub struct TimeSeries {
+    samples: BTreeMap<u64, f64>,
+    retention: u64,
+    duplicate_policy: DuplicatePolicy,
+    labels: Vec<(String, String)>,
+    rules: Vec<CompactionRule>,
+    /// The series whose rule compacts into this series, if any.
+    source: Option<String>,
+}
+
+/// A rule which downsamples a series into another series.
+#[derive(Debug, Clone)]
+struct CompactionRule {
+    dest: String,
+    aggregation: RangeAggregation,
+    /// Start of the bucket being aggregated, and its aggregator.
+    current: Option<(u64, Aggregator)>,
+}
+
+/// Accumulates the samples of a bucket, to compute any of the aggregations.
+#[derive(Debug, Clone, Copy)]
+struct Aggregator {
+    sum: f64,
+    min: f64,
+    max: f64,
+    count: u64,
+    first: f64,
+    last: f64,
+}
+
+impl TimeSeries {
+    fn new(opts: &SeriesOptions) -> TimeSeries {
+        TimeSeries {
+            samples: BTreeMap::new(),
+            retention: opts.retention.unwrap_or(0),
+            duplicate_policy: opts.duplicate_policy.unwrap_or(DuplicatePolicy::Block),
+            labels: opts.labels.clone().unwrap_or_default(),
+            rules: vec![],
+            source: None,
+        }
+    }
+
+    /// Adds a sample, applying the duplicate policy if a sample with the same timestamp exists.
+    /// Returns the value stored against the timestamp.
+    fn upsert(&mut self, ts: u64, value: f64, policy: DuplicatePolicy) -> Result<f64, DBError> {
+        if let Some(last_ts) = self.last_timestamp() {
+            if self.retention > 0 && ts < last_ts.saturating_sub(self.retention) {
+                return Err(DBError::Other(String::from(
+                    "TSDB: Timestamp is older than retention",
+                )));
+            }
+        }
+
+        let new_value = match self.samples.get(&ts) {
+            None => value,
+            Some(existing) => match policy {
+                DuplicatePolicy::Block => {
+                    return Err(DBError::Other(String::from(
+                        "TSDB: Error at upsert, update is not supported when DUPLICATE_POLICY is set to BLOCK mode",
+                    )))
+                }
+                DuplicatePolicy::First => *existing,
+                DuplicatePolicy::Last => value,
+                DuplicatePolicy::Min => existing.min(value),
+                DuplicatePolicy::Max => existing.max(value),
+                DuplicatePolicy::Sum => existing + value,
+            },
+        };
+
+        self.samples.insert(ts, new_value);
+        Ok(new_value)
+    }
+
+    fn last_timestamp(&self) -> Option<u64> {
+        self.samples.last_key_value().map(|(ts, _)| *ts)
+    }
+
+    /// Feeds the sample to the compaction rules. Returns the aggregated samples of the closed
+    /// buckets, which are to be added to the destination series.
+    fn compact(&mut self, ts: u64, value: f64) -> Vec<(String, u64, f64)> {
+        let mut compacted = vec![];
+
+        for rule in self.rules.iter_mut() {
+            let bucket = bucket_start(ts, rule.aggregation.bucket_duration, rule.aggregation.align);
+            match rule.current.as_mut() {
+                Some((start, agg)) if *start == bucket => agg.add(value),
+                Some((start, _)) if *start > bucket => {}
+                Some((start, agg)) => {
+                    compacted.push((
+                        rule.dest.clone(),
+                        *start,
+                        agg.result(rule.aggregation.aggregation),
+                    ));
+                    rule.current = Some((bucket, Aggregator::new(value)));
+                }
+                None => rule.current = Some((bucket, Aggregator::new(value))),
+            }
+        }
+
+        compacted
+    }
+
+    /// Returns the samples matching the query.
+    fn range(&self, query: &RangeQuery) -> Vec<(u64, f64)> {
+        if query.from > query.to {
+            return vec![];
+        }
+
+        let samples = self
+            .samples
+            .range(query.from..=query.to)
+            .filter(|(ts, value)| {
+                let ts_match = match &query.filter_by_ts {
+                    Some(timestamps) => timestamps.contains(ts),
+                    None => true,
+                };
+                let value_match = match query.filter_by_value {
+                    Some((min, max)) => (min..=max).contains(*value),
+                    None => true,
+                };
+                ts_match && value_match
+            });
+
+        let mut results: Vec<(u64, f64)> = match query.aggregation {
+            Some(agg) => {
+                let mut buckets: Vec<(u64, Aggregator)> = vec![];
+                for (ts, value) in samples {
+                    let bucket = bucket_start(*ts, agg.bucket_duration, agg.align);
+                    match buckets.last_mut() {
+                        Some((start, aggregator)) if *start == bucket => aggregator.add(*value),
+                        _ => buckets.push((bucket, Aggregator::new(*value))),
+                    }
+                }
+
+                buckets
+                    .into_iter()
+                    .map(|(start, aggregator)| (start, aggregator.result(agg.aggregation)))
+                    .collect()
+            }
+            None => samples.map(|(ts, value)| (*ts, *value)).collect(),
+        };
+
+        if query.rev {
+            results.reverse();
+        }
+        if let Some(count) = query.count {
+            results.truncate(count);
+        }
+
+        results
+    }
+
+    /// Checks if the labels of the series match all the filters.
+    fn matches(&self, filters: &[LabelFilter]) -> bool {
+        let label_value = |name: &str| {
+            self.labels
+                .iter()
+                .find(|(l, _)| l == name)
+                .map_or("", |(_, v)| v.as_str())
+        };
+
+        filters.iter().all(|filter| match filter {
+            LabelFilter::Eq(name, values) => values.iter().any(|v| v == label_value(name)),
+            LabelFilter::NotEq(name, values) => values.iter().all(|v| v != label_value(name)),
+        })
+    }
+
+    /// Returns the number of samples in the series.
+    pub fn total_samples(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns the approximate number of bytes used by the series.
+    pub fn memory_usage(&self) -> usize {
+        size_of::<TimeSeries>()
+            + self.samples.len() * (size_of::<u64>() + size_of::<f64>())
+            + self
+                .labels
+                .iter()
+                .map(|(l, v)| 2 * size_of::<String>() + l.len() + v.len())
+                .sum::<usize>()
+            + self
+                .rules
+                .iter()
+                .map(|r| size_of::<CompactionRule>() + r.dest.len())
+                .sum::<usize>()
+    }
+}
+
+impl Aggregator {
+    fn new(value: f64) -> Aggregator {
+        Aggregator {
+            sum: value,
+            min: value,
+            max: value,
+            count: 1,
+            first: value,
+            last: value,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.count += 1;
+        self.last = value;
+    }
+
+    fn result(&self, aggregation: Aggregation) -> f64 {
+        match aggregation {
+            Aggregation::Avg => self.sum / self.count as f64,
+            Aggregation::Sum => self.sum,
+            Aggregation::Min => self.min,
+            Aggregation::Max => self.max,
+            Aggregation::Count => self.count as f64,
+            Aggregation::First => self.first,
+            Aggregation::Last => self.last,
+        }
+    }
+}
+
+impl DB {
+    /// Creates an empty time series at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the series was created.
+    /// * `Err(DBError)` - if key already exists.
+    pub fn ts_create(&self, k: &str, opts: &SeriesOptions) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_some() {
+                return Err(DBError::Other(String::from("TSDB: key already exists")));
+            }
+
+            create_series(keyspace, k, opts);
+            keyspace.notify(NotifyClass::Module, "ts.create", k);
+            Ok(())
+        })
+    }
+
+    /// Adds a sample to the time series stored at key. The series is created with the given
+    /// options if the key doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The key on which the series is stored.
+    ///
+    /// * `ts` - Timestamp of the sample in milliseconds.
+    ///
+    /// * `value` - Value of the sample.
+    ///
+    /// * `opts` - Options for creating the series.
+    ///
+    /// * `on_duplicate` - Overrides the duplicate policy of the series, for this sample.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Timestamp of the sample.
+    /// * `Err(DBError)` - if key already exists and is not a time series, or if the sample is
+    ///   rejected.
+    pub fn ts_add(
+        &self,
+        k: &str,
+        ts: u64,
+        value: f64,
+        opts: &SeriesOptions,
+        on_duplicate: Option<DuplicatePolicy>,
+    ) -> Result<u64, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_none() {
+                create_series(keyspace, k, opts);
+            }
+
+            add_sample(keyspace, k, ts, value, on_duplicate)?;
+            keyspace.notify(NotifyClass::Module, "ts.add", k);
+            Ok(ts)
+        })
+    }
+
+    /// Adds the samples to the time series stored at the keys. Each item of `samples` is a
+    /// tuple of the key, timestamp and value.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Result<u64, DBError>>)` - The timestamp of each sample, or the error if the
+    ///   sample is rejected or the key doesn't exist.
+    pub fn ts_madd(
+        &self,
+        samples: &[(String, u64, f64)],
+    ) -> Result<Vec<Result<u64, DBError>>, DBError> {
+        self.write(|keyspace| {
+            Ok(samples
+                .iter()
+                .map(|(k, ts, value)| {
+                    add_sample(keyspace, k, *ts, *value, None)?;
+                    keyspace.notify(NotifyClass::Module, "ts.add", k);
+                    Ok(*ts)
+                })
+                .collect())
+        })
+    }
+
+    /// Increments the value of the latest sample of the time series stored at key. If the
+    /// timestamp is later than the latest sample, a new sample is added with the incremented
+    /// value. The series is created with the given options if the key doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Timestamp of the sample.
+    /// * `Err(DBError)` - if key already exists and is not a time series, or if the timestamp
+    ///   is earlier than the latest sample.
+    pub fn ts_incrby(
+        &self,
+        k: &str,
+        ts: u64,
+        incr: f64,
+        opts: &SeriesOptions,
+    ) -> Result<u64, DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_none() {
+                create_series(keyspace, k, opts);
+            }
+
+            let series = series_value_mut(keyspace, k)?;
+            let (last_ts, last_value) = match series.samples.last_key_value() {
+                Some((last_ts, last_value)) => (*last_ts, *last_value),
+                None => (0, 0.0),
+            };
+
+            if ts < last_ts {
+                return Err(DBError::Other(String::from(
+                    "TSDB: timestamp must be equal to or higher than the maximum existing timestamp",
+                )));
+            }
+
+            add_sample(
+                keyspace,
+                k,
+                ts,
+                last_value + incr,
+                Some(DuplicatePolicy::Last),
+            )?;
+            keyspace.notify(NotifyClass::Module, "ts.incrby", k);
+            Ok(ts)
+        })
+    }
+
+    /// Returns the samples of the time series stored at key, which match the query.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(u64, f64)>)` - The timestamp and value of the samples.
+    /// * `Err(DBError)` - if key doesn't exist or is not a time series.
+    pub fn ts_range(&self, k: &str, query: &RangeQuery) -> Result<Vec<(u64, f64)>, DBError> {
+        self.read(|keyspace| Ok(series_value(keyspace, k)?.range(query)))
+    }
+
+    /// Returns the samples of all the time series whose labels match the filters, in the
+    /// order of their keys.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<(String, Vec<(String, String)>, Vec<(u64, f64)>)>)` - The key, labels and
+    ///   samples of each matched series.
+    #[allow(clippy::type_complexity)]
+    pub fn ts_mrange(
+        &self,
+        filters: &[LabelFilter],
+        query: &RangeQuery,
+    ) -> Result<Vec<(String, Vec<(String, String)>, Vec<(u64, f64)>)>, DBError> {
+        self.read(|keyspace| {
+            let mut results: Vec<_> = keyspace
+                .iter()
+                .filter_map(|(k, v)| match v {
+                    Value::TimeSeries(series) if series.matches(filters) => {
+                        Some((k.to_string(), series.labels.clone(), series.range(query)))
+                    }
+                    _ => None,
+                })
+                .collect();
+
+            results.sort_by(|a, b| a.0.cmp(&b.0));
+            Ok(results)
+        })
+    }
+
+    /// Creates a compaction rule, which downsamples the samples added to the source series
+    /// into the destination series.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the rule was created.
+    /// * `Err(DBError)` - if either of the keys doesn't exist or is not a time series, or if
+    ///   the destination is already compacted from another series, or if the source is a
+    ///   destination of another rule.
+    pub fn ts_createrule(
+        &self,
+        src: &str,
+        dest: &str,
+        aggregation: RangeAggregation,
+    ) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            if src == dest {
+                return Err(DBError::Other(String::from(
+                    "TSDB: the source key and destination key should be different",
+                )));
+            }
+
+            if series_value(keyspace, src)?.source.is_some() {
+                return Err(DBError::Other(String::from(
+                    "TSDB: the source key is a compaction destination",
+                )));
+            }
+
+            let dest_series = series_value_mut(keyspace, dest)?;
+            if dest_series.source.is_some() {
+                return Err(DBError::Other(String::from(
+                    "TSDB: the destination key already has a src rule",
+                )));
+            }
+            if !dest_series.rules.is_empty() {
+                return Err(DBError::Other(String::from(
+                    "TSDB: the destination key is a compaction source",
+                )));
+            }
+            dest_series.source = Some(src.to_string());
+
+            series_value_mut(keyspace, src)?.rules.push(CompactionRule {
+                dest: dest.to_string(),
+                aggregation,
+                current: None,
+            });
+            keyspace.notify(NotifyClass::Module, "ts.createrule:src", src);
+            keyspace.notify(NotifyClass::Module, "ts.createrule:dest", dest);
+            Ok(())
+        })
+    }
+
+    /// Deletes the compaction rule from the source series to the destination series.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the rule was deleted.
+    /// * `Err(DBError)` - if the source key doesn't exist or is not a time series, or if the
+    ///   rule doesn't exist.
+    pub fn ts_deleterule(&self, src: &str, dest: &str) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            let src_series = series_value_mut(keyspace, src)?;
+            let pos = src_series
+                .rules
+                .iter()
+                .position(|r| r.dest == dest)
+                .ok_or_else(|| {
+                    DBError::Other(String::from("TSDB: compaction rule does not exist"))
+                })?;
+            src_series.rules.remove(pos);
+
+            if let Some(Value::TimeSeries(dest_series)) = keyspace.get_mut(dest) {
+                dest_series.source = None;
+            }
+            keyspace.notify(NotifyClass::Module, "ts.deleterule:src", src);
+            keyspace.notify(NotifyClass::Module, "ts.deleterule:dest", dest);
+            Ok(())
+        })
+    }
+
+    /// Removes the samples of the time series stored at key, which are older than its
+    /// retention period.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - `false` if the key no longer holds a time series with a retention period,
+    ///   so that it need not be trimmed anymore.
+    pub fn ts_trim(&self, k: &str) -> Result<bool, DBError> {
+        self.write(|keyspace| {
+            let series = match keyspace.get_mut(k) {
+                Some(Value::TimeSeries(series)) if series.retention > 0 => series,
+                _ => return Ok(false),
+            };
+
+            if let Some(last_ts) = series.last_timestamp() {
+                let min_ts = last_ts.saturating_sub(series.retention);
+                series.samples = series.samples.split_off(&min_ts);
+            }
+            Ok(true)
+        })
+    }
+}
+
+/// Creates an empty time series at key, and registers it for trimming if it has a retention
+/// period.
+fn create_series(keyspace: &mut Keyspace, k: &str, opts: &SeriesOptions) {
+    keyspace.insert(k, Value::TimeSeries(TimeSeries::new(opts)));
+    track_retention(keyspace, k);
+}
+
+/// Registers the time series stored at key for trimming, if it has a retention period. This
+/// is required whenever a series is placed at a key, other than by creating it.
+pub fn track_retention(keyspace: &Keyspace, k: &str) {
+    if let Some(Value::TimeSeries(series)) = keyspace.get(k) {
+        if series.retention == 0 {
+            return;
+        }
+        if let Err(e) = keyspace.send_event(DBEvent::TrackSeriesRetention(k.to_string())) {
+            error!("Failed to send series retention event: {}", e);
+        }
+    }
+}
+
+/// Adds a sample to the time series stored at key, and to the destinations of its compaction
+/// rules if a bucket is closed.
+fn add_sample(
+    keyspace: &mut Keyspace,
+    k: &str,
+    ts: u64,
+    value: f64,
+    on_duplicate: Option<DuplicatePolicy>,
+) -> Result<(), DBError> {
+    let series = series_value_mut(keyspace, k)?;
+    let policy = on_duplicate.unwrap_or(series.duplicate_policy);
+    let value = series.upsert(ts, value, policy)?;
+
+    for (dest, bucket_ts, agg_value) in series.compact(ts, value) {
+        if let Some(Value::TimeSeries(dest_series)) = keyspace.get_mut(&dest) {
+            if let Err(e) = dest_series.upsert(bucket_ts, agg_value, DuplicatePolicy::Last) {
+                error!("Failed to add compacted sample to '{}': {}", dest, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the start of the bucket which contains the timestamp.
+fn bucket_start(ts: u64, bucket_duration: u64, align: u64) -> u64 {
+    let offset = (ts as i128 - align as i128).rem_euclid(bucket_duration as i128);
+    (ts as i128 - offset).max(0) as u64
+}
+
+/// Returns the time series stored at key.
+fn series_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<&'a TimeSeries, DBError> {
+    match keyspace.get(k) {
+        Some(Value::TimeSeries(series)) => Ok(series),
+        Some(_) => Err(DBError::WrongType),
+        None => Err(key_not_found_err()),
+    }
+}
+
+/// Returns the mutable time series stored at key.
+fn series_value_mut<'a>(
+    keyspace: &'a mut Keyspace,
+    k: &str,
+) -> Result<&'a mut TimeSeries, DBError> {
+    match keyspace.get_mut(k) {
+        Some(Value::TimeSeries(series)) => Ok(series),
+        Some(_) => Err(DBError::WrongType),
+        None => Err(key_not_found_err()),
+    }
+}
+
+fn key_not_found_err() -> DBError {
+    DBError::Other(String::from("TSDB: the key does not exist"))
+}
+
+impl Dump for TimeSeries {
+    fn dump(&self, enc: &mut Encoder) {
+        enc.size(self.samples.len());
+        for (ts, value) in self.samples.iter() {
+            enc.u64(*ts);
+            enc.f64(*value);
+        }
+        enc.u64(self.retention);
+        enc.u8(self.duplicate_policy as u8);
+        enc.size(self.labels.len());
+        for (name, value) in self.labels.iter() {
+            enc.string(name);
+            enc.string(value);
+        }
+        enc.size(self.rules.len());
+        for rule in self.rules.iter() {
+            enc.string(&rule.dest);
+            enc.u8(rule.aggregation.aggregation as u8);
+            enc.u64(rule.aggregation.bucket_duration);
+            enc.u64(rule.aggregation.align);
+            match &rule.current {
+                Some((bucket_ts, agg)) => {
+                    enc.u8(1);
+                    enc.u64(*bucket_ts);
+                    enc.f64(agg.sum);
+                    enc.f64(agg.min);
+                    enc.f64(agg.max);
+                    enc.u64(agg.count);
+                    enc.f64(agg.first);
+                    enc.f64(agg.last);
+                }
+                None => enc.u8(0),
+            }
+        }
+        match &self.source {
+            Some(source) => {
+                enc.u8(1);
+                enc.string(source);
+            }
+            None => enc.u8(0),
+        }
+    }
+
+    fn restore(dec: &mut Decoder) -> Result<TimeSeries, DBError> {
+        let mut samples = BTreeMap::new();
+        for _ in 0..dec.size()? {
+            let ts = dec.u64()?;
+            samples.insert(ts, dec.f64()?);
+        }
+        let retention = dec.u64()?;
+        let duplicate_policy = match dec.u8()? {
+            0 => DuplicatePolicy::Block,
+            1 => DuplicatePolicy::First,
+            2 => DuplicatePolicy::Last,
+            3 => DuplicatePolicy::Min,
+            4 => DuplicatePolicy::Max,
+            5 => DuplicatePolicy::Sum,
+            _ => return Err(bad_format_err()),
+        };
+
+        let mut labels = vec![];
+        for _ in 0..dec.size()? {
+            let name = dec.string()?;
+            labels.push((name, dec.string()?));
+        }
+
+        let mut rules = vec![];
+        for _ in 0..dec.size()? {
+            let dest = dec.string()?;
+            let aggregation = match dec.u8()? {
+                0 => Aggregation::Avg,
+                1 => Aggregation::Sum,
+                2 => Aggregation::Min,
+                3 => Aggregation::Max,
+                4 => Aggregation::Count,
+                5 => Aggregation::First,
+                6 => Aggregation::Last,
+                _ => return Err(bad_format_err()),
+            };
+            let bucket_duration = dec.u64()?;
+            let align = dec.u64()?;
+            if bucket_duration == 0 {
+                return Err(bad_format_err());
+            }
+
+            let current = match dec.u8()? {
+                0 => None,
+                1 => {
+                    let bucket_ts = dec.u64()?;
+                    let agg = Aggregator {
+                        sum: dec.f64()?,
+                        min: dec.f64()?,
+                        max: dec.f64()?,
+                        count: dec.u64()?,
+                        first: dec.f64()?,
+                        last: dec.f64()?,
+                    };
+                    Some((bucket_ts, agg))
+                }
+                _ => return Err(bad_format_err()),
+            };
+
+            rules.push(CompactionRule {
+                dest,
+                aggregation: RangeAggregation {
+                    aggregation,
+                    bucket_duration,
+                    align,
+                },
+                current,
+            });
+        }
+
+        let source = match dec.u8()? {
+            0 => None,
+            1 => Some(dec.string()?),
+            _ => return Err(bad_format_err()),
+        };
+
+        Ok(TimeSeries {
+            samples,
+            retention,
+            duplicate_policy,
+            labels,
+            rules,
+            source,
+        })
+    }
+}
@@ -0,0 +1,304 @@
+use std::{cmp::Reverse, mem::size_of};
+
+use super::{
+    db::{Keyspace, Value, DB},
+    dump::{bad_format_err, Decoder, Dump, Encoder},
+    hyperloglog::murmurhash64a,
+    notify::NotifyClass,
+    DBError,
+};
+
+// Top-K is implemented with the HeavyKeeper algorithm. A matrix of `depth` rows of `width`
+// buckets counts the items, where each bucket holds the fingerprint of an item and its count.
+// When an item hashes to a bucket held by another item, the count of the bucket decays with the
+// probability `decay ^ count`, and the item takes over the bucket once the count drops to 0.
+// Hence the buckets are eventually held by the frequent items.
+//
+// The top K items are tracked in a list, ordered by the count estimated by the buckets. The
+// decay uses a pseudo random generator with a fixed seed, so that replicas which replay the
+// same commands end up with identical structures.
+
+/// Number of buckets in each row, unless specified by TOPK.RESERVE.
+pub const TOPK_DEFAULT_WIDTH: usize = 8;
+/// Number of rows, unless specified by TOPK.RESERVE.
+pub const TOPK_DEFAULT_DEPTH: usize = 7;
+/// Decay probability base, unless specified by TOPK.RESERVE.
+pub const TOPK_DEFAULT_DECAY: f64 = 0.9;
+/// Seed used for hashing the fingerprint of items.
+const TOPK_FP_SEED: u64 = 0x5bd1e995;
+/// Seed of the random generator used for the decay.
+const TOPK_RNG_SEED: u64 = 0x2545f4914f6cdd1d;
+
+/// A Top-K structure.
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    width: usize,
+    depth: usize,
+    decay: f64,
+    buckets: Vec<Bucket>,
+    /// The top K items with their counts, in the descending order of the counts.
+    heap: Vec<(Vec<u8>, u64)>,
+    rng: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    fp: u64,
+    count: u64,
+}
+
+impl TopK {
+    /// Creates an empty Top-K structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - Number of top items to be tracked.
+    ///
+    /// * `width` - Number of buckets in each row.
+    ///
+    /// * `depth` - Number of rows.
+    ///
+    /// * `decay` - The probability of decaying a bucket held by another item is `decay ^ count`.
+    pub fn new(k: usize, width: usize, depth: usize, decay: f64) -> TopK {
+        TopK {
+            k,
+            width,
+            depth,
+            decay,
+            buckets: vec![Bucket::default(); width * depth],
+            heap: vec![],
+            rng: TOPK_RNG_SEED,
+        }
+    }
+
+    /// Increments the count of the item, and returns the item which was expelled from the
+    /// top K list, if any.
+    fn incr(&mut self, item: &[u8], incr: u64) -> Option<Vec<u8>> {
+        let fp = murmurhash64a(item, TOPK_FP_SEED);
+        let mut max_count = 0;
+
+        for row in 0..self.depth {
+            let idx =
+                row * self.width + (murmurhash64a(item, row as u64) % self.width as u64) as usize;
+            let mut bucket = self.buckets[idx];
+
+            if bucket.count == 0 {
+                bucket = Bucket { fp, count: incr };
+            } else if bucket.fp == fp {
+                bucket.count = bucket.count.saturating_add(incr);
+            } else {
+                let mut remaining = incr;
+                while remaining > 0 {
+                    let decay = self.decay.powf(bucket.count as f64);
+                    if self.next_random() < decay {
+                        bucket.count -= 1;
+                        if bucket.count == 0 {
+                            bucket = Bucket {
+                                fp,
+                                count: remaining,
+                            };
+                            break;
+                        }
+                    }
+                    remaining -= 1;
+                }
+            }
+
+            if bucket.fp == fp {
+                max_count = max_count.max(bucket.count);
+            }
+            self.buckets[idx] = bucket;
+        }
+
+        self.update_heap(item, max_count)
+    }
+
+    /// Updates the count of the item in the top K list, or adds the item if its count is higher
+    /// than the least frequent item. The expelled item is returned.
+    fn update_heap(&mut self, item: &[u8], count: u64) -> Option<Vec<u8>> {
+        let mut expelled = None;
+
+        match self.heap.iter().position(|(i, _)| i == item) {
+            Some(pos) => {
+                let (_, c) = &mut self.heap[pos];
+                *c = (*c).max(count);
+            }
+            None if self.heap.len() < self.k => self.heap.push((item.to_vec(), count)),
+            None => {
+                let min = self.heap.last().map_or(0, |(_, c)| *c);
+                if count <= min {
+                    return None;
+                }
+                expelled = self.heap.pop().map(|(i, _)| i);
+                self.heap.push((item.to_vec(), count));
+            }
+        }
+
+        // stable sort keeps the older items ahead among the items with the same count
+        self.heap.sort_by_key(|(_, count)| Reverse(*count));
+        expelled
+    }
+
+    /// Returns a pseudo random number in the range [0, 1), using xorshift.
+    fn next_random(&mut self) -> f64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        (self.rng >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns the approximate number of bytes used by the structure.
+    pub fn memory_usage(&self) -> usize {
+        size_of::<TopK>()
+            + self.buckets.len() * size_of::<Bucket>()
+            + self
+                .heap
+                .iter()
+                .map(|(item, _)| size_of::<(Vec<u8>, u64)>() + item.len())
+                .sum::<usize>()
+    }
+}
+
+impl DB {
+    /// Creates an empty Top-K structure at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - if the structure was created.
+    /// * `Err(DBError)` - if key already exists.
+    pub fn topk_reserve(
+        &self,
+        k: &str,
+        topk: usize,
+        width: usize,
+        depth: usize,
+        decay: f64,
+    ) -> Result<(), DBError> {
+        self.write(|keyspace| {
+            if keyspace.get(k).is_some() {
+                return Err(DBError::Other(String::from("TopK: key already exists")));
+            }
+
+            keyspace.insert(k, Value::TopK(TopK::new(topk, width, depth, decay)));
+            keyspace.notify(NotifyClass::Module, "topk.reserve", k);
+            Ok(())
+        })
+    }
+
+    /// Increments the count of the items in the Top-K structure stored at key.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<Vec<u8>>>)` - For each item, the item expelled from the top K list.
+    /// * `Err(DBError)` - if key doesn't exist or is not a Top-K structure.
+    pub fn topk_incrby(
+        &self,
+        k: &str,
+        items: &[(Vec<u8>, u64)],
+    ) -> Result<Vec<Option<Vec<u8>>>, DBError> {
+        self.write(|keyspace| {
+            let topk = match keyspace.get_mut(k) {
+                Some(Value::TopK(topk)) => topk,
+                Some(_) => return Err(DBError::WrongType),
+                None => return Err(key_not_found_err()),
+            };
+
+            let expelled = items
+                .iter()
+                .map(|(item, incr)| topk.incr(item, *incr))
+                .collect();
+            keyspace.notify(NotifyClass::Module, "topk.incrby", k);
+            Ok(expelled)
+        })
+    }
+
+    /// Checks if the items are in the top K list of the structure stored at key.
+    pub fn topk_query(&self, k: &str, items: &[Vec<u8>]) -> Result<Vec<bool>, DBError> {
+        self.read(|keyspace| {
+            let topk = topk_value(keyspace, k)?;
+            Ok(items
+                .iter()
+                .map(|item| topk.heap.iter().any(|(i, _)| i == item))
+                .collect())
+        })
+    }
+
+    /// Returns the top K items with their counts, in the descending order of the counts.
+    pub fn topk_list(&self, k: &str) -> Result<Vec<(Vec<u8>, u64)>, DBError> {
+        self.read(|keyspace| Ok(topk_value(keyspace, k)?.heap.clone()))
+    }
+}
+
+/// Returns the Top-K structure stored at key.
+fn topk_value<'a>(keyspace: &'a Keyspace, k: &str) -> Result<&'a TopK, DBError> {
+    match keyspace.get(k) {
+        Some(Value::TopK(topk)) => Ok(topk),
+        Some(_) => Err(DBError::WrongType),
+        None => Err(key_not_found_err()),
+    }
+}
+
+fn key_not_found_err() -> DBError {
+    DBError::Other(String::from("TopK: key does not exist"))
+}
+
+impl Dump for TopK {
+    fn dump(&self, enc: &mut Encoder) {
+        enc.size(self.k);
+        enc.size(self.width);
+        enc.size(self.depth);
+        enc.f64(self.decay);
+        enc.size(self.buckets.len());
+        for bucket in self.buckets.iter() {
+            enc.u64(bucket.fp);
+            enc.u64(bucket.count);
+        }
+        enc.size(self.heap.len());
+        for (item, count) in self.heap.iter() {
+            enc.bytes(item);
+            enc.u64(*count);
+        }
+        enc.u64(self.rng);
+    }
+
+    fn restore(dec: &mut Decoder) -> Result<TopK, DBError> {
+        let k = dec.size()?;
+        let width = dec.size()?;
+        let depth = dec.size()?;
+        let decay = dec.f64()?;
+
+        let mut buckets = vec![];
+        for _ in 0..dec.size()? {
+            let fp = dec.u64()?;
+            let count = dec.u64()?;
+            buckets.push(Bucket { fp, count });
+        }
+        let mut heap = vec![];
+        for _ in 0..dec.size()? {
+            let item = dec.bytes()?;
+            heap.push((item, dec.u64()?));
+        }
+        let rng = dec.u64()?;
+
+        if k == 0
+            || width == 0
+            || depth == 0
+            || width.checked_mul(depth) != Some(buckets.len())
+            || heap.len() > k
+            || !(decay > 0.0 && decay <= 1.0)
+        {
+            return Err(bad_format_err());
+        }
+
+        Ok(TopK {
+            k,
+            width,
+            depth,
+            decay,
+            buckets,
+            heap,
+            rng,
+        })
+    }
+}
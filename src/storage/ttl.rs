@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeSet, HashSet},
     sync::{Arc, Mutex},
     time::Duration,
 };
@@ -12,6 +12,10 @@ use crate::storage::DBEvent;
 
 use super::{db::DB, DBError};
 
+/// Interval between the runs of the job which trims the samples of time series, which are
+/// older than their retention period.
+const SERIES_TRIM_INTERVAL: Duration = Duration::from_secs(1);
+
 /// KeyEvictor maintains the TTL (Time To Live) for each key and runs a job to evict expired keys.
 pub struct KeyEvictor {
     /// Stores key-expiry pairs in a BTreeSet, sorted in ascending order by expiry time.
@@ -20,6 +24,8 @@ pub struct KeyEvictor {
     db: Arc<DB>,
     /// Notifier for triggering key eviction jobs.
     eviction_notifier: Arc<Notify>,
+    /// Keys of the time series which have a retention period.
+    series: Arc<Mutex<HashSet<String>>>,
 }
 
 impl KeyEvictor {
@@ -34,6 +40,7 @@ impl KeyEvictor {
             expiries: Arc::new(Mutex::new(BTreeSet::new())),
             db,
             eviction_notifier,
+            series: Arc::new(Mutex::new(HashSet::new())),
         }
     }
 
@@ -44,6 +51,7 @@ impl KeyEvictor {
 
         let expiries_arc = self.expiries.clone();
         let eviction_notifier_arc = self.eviction_notifier.clone();
+        let series_arc = self.series.clone();
 
         // Listen to DB events (like setting expiry to a key) and update the TTL of keys if required.
         tokio::spawn(async move {
@@ -67,6 +75,11 @@ impl KeyEvictor {
 
                             eviction_notifier.notify_one();
                         }
+                        DBEvent::TrackSeriesRetention(key) => {
+                            if let Err(e) = Self::track_series(key, series_arc.clone()) {
+                                error!("Error while tracking series retention: {}", e);
+                            }
+                        }
                     },
                     Err(e) => {
                         error!("Error while receiving DB events: {}", e);
@@ -75,6 +88,19 @@ impl KeyEvictor {
             }
         });
 
+        // Trims the expired samples of time series periodically.
+        let db = self.db.clone();
+        let series = self.series.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(SERIES_TRIM_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = Self::trim_series(&db, series.clone()) {
+                    error!("Time series trimming failed due to: {}", e);
+                }
+            }
+        });
+
         // Runs key eviction job.
         // This function sleeps until its time for a key to be expired.
         loop {
@@ -130,7 +156,7 @@ impl KeyEvictor {
                 return Ok(Some(when));
             }
 
-            self.db.del(key.as_str())?;
+            self.db.evict_expired(key.as_str())?;
             expiries.remove(&(when, key));
         }
 
@@ -193,4 +219,64 @@ impl KeyEvictor {
 
         Ok(())
     }
+
+    /// Adds the key of a time series to the set of series which are trimmed periodically.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the time series.
+    /// * `series` - Arc reference to the Mutex-protected set of time series keys.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or a DBError if the operation fails.
+    fn track_series(key: String, series: Arc<Mutex<HashSet<String>>>) -> Result<(), DBError> {
+        let mut series = match series.lock() {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Failed to track series retention: {}", e);
+                return Err(DBError::Other(
+                    "Failed to track series retention".to_string(),
+                ));
+            }
+        };
+
+        series.insert(key);
+
+        Ok(())
+    }
+
+    /// Trims the samples of the tracked time series, which are older than their retention
+    /// period. Keys which no longer hold a time series with a retention period are untracked.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Reference to the database.
+    /// * `series` - Arc reference to the Mutex-protected set of time series keys.
+    ///
+    /// # Returns
+    ///
+    /// A Result indicating success or a DBError if the operation fails.
+    fn trim_series(db: &DB, series: Arc<Mutex<HashSet<String>>>) -> Result<(), DBError> {
+        let mut series = match series.lock() {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Failed to trim time series: {}", e);
+                return Err(DBError::Other("Failed to trim time series".to_string()));
+            }
+        };
+
+        let mut untracked = vec![];
+        for key in series.iter() {
+            if !db.ts_trim(key)? {
+                untracked.push(key.clone());
+            }
+        }
+
+        for key in untracked {
+            series.remove(&key);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,539 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+    mem::size_of,
+};
+
+use super::DBError;
+
+// Vector indexes find the K nearest neighbours of a query vector, among the vectors stored in
+// the hash fields of the indexed keys. Vectors are FLOAT32 blobs (little endian), with the
+// number of dimensions declared by the index. Blobs of any other size are not indexed.
+//
+// FLAT indexes compare the query with every vector, and always return the exact neighbours.
+//
+// HNSW indexes build a Hierarchical Navigable Small World graph. Each vector is a node, which
+// is linked to its closest nodes on each of the layers it's assigned to. The number of layers
+// of a node is random, with exponentially fewer nodes on the upper layers. A query descends
+// greedily from the entry point (a node on the top layer) to the bottom layer, and then
+// explores the neighbourhood of the closest nodes found. Results are approximate, and their
+// accuracy is traded for speed with EF_RUNTIME.
+//
+// When a node is deleted, its neighbours on each layer are linked among themselves, so that
+// the graph stays navigable.
+
+/// Max number of links of a node on the upper layers of HNSW, unless specified.
+pub const HNSW_DEFAULT_M: usize = 16;
+/// Number of candidates explored while linking a new node, unless specified.
+pub const HNSW_DEFAULT_EF_CONSTRUCTION: usize = 200;
+/// Number of candidates explored by a query, unless specified.
+pub const HNSW_DEFAULT_EF_RUNTIME: usize = 10;
+/// Seed of the random generator used for the layers of the nodes.
+const HNSW_RNG_SEED: u64 = 0x9e3779b97f4a7c15;
+
+/// The metric used to compute the distance between vectors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistanceMetric {
+    /// Squared euclidean distance.
+    L2,
+    /// `1 - inner product`.
+    Ip,
+    /// `1 - cosine similarity`.
+    Cosine,
+}
+
+/// The algorithm of a vector index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorAlgorithm {
+    Flat,
+    Hnsw {
+        m: usize,
+        ef_construction: usize,
+        ef_runtime: usize,
+    },
+}
+
+/// Parameters of a vector field in an index schema.
+#[derive(Debug, Clone, Copy)]
+pub struct VectorParams {
+    pub algorithm: VectorAlgorithm,
+    pub dim: usize,
+    pub metric: DistanceMetric,
+}
+
+/// An index over the vectors stored in a hash field.
+#[derive(Debug, Clone)]
+pub struct VectorIndex {
+    dim: usize,
+    metric: DistanceMetric,
+    store: VectorStore,
+}
+
+#[derive(Debug, Clone)]
+enum VectorStore {
+    Flat(HashMap<String, Vec<f32>>),
+    Hnsw(Hnsw),
+}
+
+#[derive(Debug, Clone)]
+struct Hnsw {
+    metric: DistanceMetric,
+    /// Max number of links of a node on the upper layers. Nodes have up to `2 * m` links on
+    /// the bottom layer.
+    m: usize,
+    ef_construction: usize,
+    ef_runtime: usize,
+    /// Nodes by their ids. Ids of deleted nodes are reused.
+    nodes: Vec<Option<HnswNode>>,
+    free_ids: Vec<usize>,
+    ids: HashMap<String, usize>,
+    entry_point: Option<usize>,
+    rng: u64,
+}
+
+#[derive(Debug, Clone)]
+struct HnswNode {
+    key: String,
+    vector: Vec<f32>,
+    /// Ids of the linked nodes, on each layer the node is assigned to.
+    links: Vec<Vec<usize>>,
+}
+
+/// A node found by a search, ordered by its distance from the query.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    distance: f32,
+    id: usize,
+}
+
+impl VectorIndex {
+    /// Creates an empty vector index.
+    pub fn new(params: &VectorParams) -> VectorIndex {
+        let store = match params.algorithm {
+            VectorAlgorithm::Flat => VectorStore::Flat(HashMap::new()),
+            VectorAlgorithm::Hnsw {
+                m,
+                ef_construction,
+                ef_runtime,
+            } => VectorStore::Hnsw(Hnsw {
+                metric: params.metric,
+                m,
+                ef_construction,
+                ef_runtime,
+                nodes: vec![],
+                free_ids: vec![],
+                ids: HashMap::new(),
+                entry_point: None,
+                rng: HNSW_RNG_SEED,
+            }),
+        };
+
+        VectorIndex {
+            dim: params.dim,
+            metric: params.metric,
+            store,
+        }
+    }
+
+    /// Indexes the vector of the key, replacing its previous vector if any. Returns `false`
+    /// if the blob is not a valid vector for the index.
+    pub fn insert(&mut self, key: &str, blob: &[u8]) -> bool {
+        self.remove(key);
+
+        let vector = match self.to_vector(blob) {
+            Some(vector) => vector,
+            None => return false,
+        };
+
+        match &mut self.store {
+            VectorStore::Flat(vectors) => {
+                vectors.insert(key.to_string(), vector);
+            }
+            VectorStore::Hnsw(hnsw) => hnsw.insert(key, vector),
+        }
+        true
+    }
+
+    /// Removes the vector of the key from the index.
+    pub fn remove(&mut self, key: &str) {
+        match &mut self.store {
+            VectorStore::Flat(vectors) => {
+                vectors.remove(key);
+            }
+            VectorStore::Hnsw(hnsw) => hnsw.remove(key),
+        }
+    }
+
+    /// Returns the keys of the K nearest vectors to the query blob, with their distances,
+    /// ordered by the distances.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query vector as a FLOAT32 blob.
+    ///
+    /// * `k` - Number of neighbours to be returned.
+    ///
+    /// * `ef_runtime` - Overrides the EF_RUNTIME of HNSW indexes.
+    ///
+    /// * `filter` - Only the keys in the filter are considered. The filtered keys are compared
+    ///   exhaustively (even for HNSW indexes), so the results are exact.
+    pub fn knn(
+        &self,
+        query: &[u8],
+        k: usize,
+        ef_runtime: Option<usize>,
+        filter: Option<&HashSet<String>>,
+    ) -> Result<Vec<(String, f32)>, DBError> {
+        let query = self.to_vector(query).ok_or_else(|| {
+            DBError::Other(format!(
+                "Error parsing vector similarity query: query vector blob size ({}) does not match index's expected size ({})",
+                query.len(),
+                self.dim * size_of::<f32>()
+            ))
+        })?;
+
+        if k == 0 {
+            return Ok(vec![]);
+        }
+
+        let vectors: Box<dyn Iterator<Item = (&String, &Vec<f32>)>> = match (&self.store, filter) {
+            (VectorStore::Flat(vectors), None) => Box::new(vectors.iter()),
+            (VectorStore::Flat(vectors), Some(keys)) => {
+                Box::new(keys.iter().filter_map(|k| vectors.get_key_value(k)))
+            }
+            (VectorStore::Hnsw(hnsw), None) => return Ok(hnsw.knn(&query, k, ef_runtime)),
+            (VectorStore::Hnsw(hnsw), Some(keys)) => Box::new(
+                keys.iter()
+                    .filter_map(|k| hnsw.ids.get(k))
+                    .map(|id| hnsw.node(*id))
+                    .map(|n| (&n.key, &n.vector)),
+            ),
+        };
+
+        let mut nearest: BinaryHeap<(Candidate, &String)> = BinaryHeap::new();
+        for (key, vector) in vectors {
+            let candidate = Candidate {
+                distance: distance(self.metric, &query, vector),
+                id: 0,
+            };
+            if nearest.len() < k {
+                nearest.push((candidate, key));
+            } else if nearest.peek().is_some_and(|(c, _)| candidate < *c) {
+                nearest.pop();
+                nearest.push((candidate, key));
+            }
+        }
+
+        Ok(nearest
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(c, key)| (key.clone(), c.distance))
+            .collect())
+    }
+
+    /// Returns the number of indexed vectors.
+    pub fn num_vectors(&self) -> usize {
+        match &self.store {
+            VectorStore::Flat(vectors) => vectors.len(),
+            VectorStore::Hnsw(hnsw) => hnsw.ids.len(),
+        }
+    }
+
+    /// Reads a FLOAT32 blob as a vector, normalized for the cosine metric.
+    fn to_vector(&self, blob: &[u8]) -> Option<Vec<f32>> {
+        let floats = blob.chunks_exact(size_of::<f32>());
+        if floats.len() != self.dim || !floats.remainder().is_empty() {
+            return None;
+        }
+
+        let mut vector: Vec<f32> = floats
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+
+        if self.metric == DistanceMetric::Cosine {
+            let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                vector.iter_mut().for_each(|x| *x /= norm);
+            }
+        }
+
+        Some(vector)
+    }
+}
+
+impl Hnsw {
+    fn insert(&mut self, key: &str, vector: Vec<f32>) {
+        let level = self.random_level();
+        let node = HnswNode {
+            key: key.to_string(),
+            vector,
+            links: vec![vec![]; level + 1],
+        };
+
+        let id = match self.free_ids.pop() {
+            Some(id) => {
+                self.nodes[id] = Some(node);
+                id
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.ids.insert(key.to_string(), id);
+
+        let entry_point = match self.entry_point {
+            Some(ep) => ep,
+            None => {
+                self.entry_point = Some(id);
+                return;
+            }
+        };
+
+        let query = self.node(id).vector.clone();
+        let top_level = self.node(entry_point).links.len() - 1;
+
+        // descend greedily through the layers above the node's top layer
+        let mut ep = entry_point;
+        for layer in (level + 1..=top_level).rev() {
+            ep = self.search_layer(&query, &[ep], 1, layer)[0].id;
+        }
+
+        let mut eps = vec![ep];
+        for layer in (0..=level.min(top_level)).rev() {
+            let found = self.search_layer(&query, &eps, self.ef_construction, layer);
+            let neighbours: Vec<usize> = found
+                .iter()
+                .filter(|c| c.id != id)
+                .take(self.max_links(layer))
+                .map(|c| c.id)
+                .collect();
+
+            for &n in neighbours.iter() {
+                self.link(n, &[id], layer);
+            }
+            self.node_mut(id).links[layer] = neighbours;
+
+            eps = found.into_iter().map(|c| c.id).collect();
+        }
+
+        if level > top_level {
+            self.entry_point = Some(id);
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        let id = match self.ids.remove(key) {
+            Some(id) => id,
+            None => return,
+        };
+        let node = match self.nodes[id].take() {
+            Some(node) => node,
+            None => return,
+        };
+        self.free_ids.push(id);
+
+        for (layer, links) in node.links.iter().enumerate() {
+            for &n in links.iter() {
+                let n_links = match self.nodes[n].as_mut().and_then(|n| n.links.get_mut(layer)) {
+                    Some(n_links) => n_links,
+                    None => continue,
+                };
+                n_links.retain(|&l| l != id);
+
+                // link the orphaned neighbours among themselves
+                self.link(n, links, layer);
+            }
+        }
+
+        // links from the nodes which aren't linked back are removed lazily, as the id is
+        // skipped by the searches until it's reused
+        if self.entry_point == Some(id) {
+            self.entry_point = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter_map(|(i, n)| n.as_ref().map(|n| (i, n.links.len())))
+                .max_by_key(|(_, levels)| *levels)
+                .map(|(i, _)| i);
+        }
+    }
+
+    fn knn(&self, query: &[f32], k: usize, ef_runtime: Option<usize>) -> Vec<(String, f32)> {
+        let entry_point = match self.entry_point {
+            Some(ep) => ep,
+            None => return vec![],
+        };
+
+        let mut ep = entry_point;
+        for layer in (1..self.node(entry_point).links.len()).rev() {
+            ep = self.search_layer(query, &[ep], 1, layer)[0].id;
+        }
+
+        let ef = ef_runtime.unwrap_or(self.ef_runtime).max(k);
+        self.search_layer(query, &[ep], ef, 0)
+            .into_iter()
+            .take(k)
+            .map(|c| (self.node(c.id).key.clone(), c.distance))
+            .collect()
+    }
+
+    /// Returns up to `ef` closest nodes to the query on the layer, ordered by their distances,
+    /// exploring the graph from the entry points.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[usize],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        // closest candidate first
+        let mut candidates: BinaryHeap<std::cmp::Reverse<Candidate>> = BinaryHeap::new();
+        // farthest result first
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &id in entry_points.iter() {
+            let c = Candidate {
+                distance: distance(self.metric, query, &self.node(id).vector),
+                id,
+            };
+            candidates.push(std::cmp::Reverse(c));
+            results.push(c);
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(std::cmp::Reverse(c)) = candidates.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|f| c.distance > f.distance) {
+                break;
+            }
+
+            let links = match self.node(c.id).links.get(layer) {
+                Some(links) => links,
+                None => continue,
+            };
+            for &n in links.iter() {
+                // deleted nodes, and reused ids which aren't on this layer, are skipped
+                let node = match &self.nodes[n] {
+                    Some(node) if node.links.len() > layer => node,
+                    _ => continue,
+                };
+                if !visited.insert(n) {
+                    continue;
+                }
+
+                let candidate = Candidate {
+                    distance: distance(self.metric, query, &node.vector),
+                    id: n,
+                };
+                if results.len() < ef || results.peek().is_some_and(|f| candidate < *f) {
+                    candidates.push(std::cmp::Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Links the node to the other nodes on the layer. If the node exceeds its max links, only
+    /// the closest links are retained.
+    fn link(&mut self, id: usize, others: &[usize], layer: usize) {
+        let max_links = self.max_links(layer);
+        let metric = self.metric;
+
+        let mut links = self.node(id).links[layer].clone();
+        for &other in others.iter() {
+            if other != id && self.nodes[other].is_some() && !links.contains(&other) {
+                links.push(other);
+            }
+        }
+
+        if links.len() > max_links {
+            let vector = &self.node(id).vector;
+            let mut by_distance: Vec<Candidate> = links
+                .iter()
+                .filter_map(|&l| {
+                    self.nodes[l].as_ref().map(|n| Candidate {
+                        distance: distance(metric, vector, &n.vector),
+                        id: l,
+                    })
+                })
+                .collect();
+            by_distance.sort();
+            links = by_distance
+                .into_iter()
+                .take(max_links)
+                .map(|c| c.id)
+                .collect();
+        }
+
+        self.node_mut(id).links[layer] = links;
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        if layer == 0 {
+            2 * self.m
+        } else {
+            self.m
+        }
+    }
+
+    /// Returns a random layer for a new node, such that each layer has `1 / m` times the
+    /// nodes of the layer below.
+    fn random_level(&mut self) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let r = ((self.rng >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+
+        let level_mult = 1.0 / (self.m.max(2) as f64).ln();
+        (-r.ln() * level_mult) as usize
+    }
+
+    fn node(&self, id: usize) -> &HnswNode {
+        self.nodes[id].as_ref().expect("HNSW node must exist")
+    }
+
+    fn node_mut(&mut self, id: usize) -> &mut HnswNode {
+        self.nodes[id].as_mut().expect("HNSW node must exist")
+    }
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.id.cmp(&other.id))
+    }
+}
+
+/// Returns the distance between the vectors. Vectors are already normalized for the cosine
+/// metric.
+fn distance(metric: DistanceMetric, a: &[f32], b: &[f32]) -> f32 {
+    match metric {
+        DistanceMetric::L2 => a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum(),
+        DistanceMetric::Ip | DistanceMetric::Cosine => {
+            1.0 - a.iter().zip(b).map(|(x, y)| x * y).sum::<f32>()
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use bytes::Bytes;
+use log::error;
+
+use crate::{
+    pubsub::PubSub,
+    resp::types::{ProtocolVersion, RespType},
+};
+
+/// The channel to which the invalidation messages are published, for the RESP2 clients to
+/// which the messages are redirected.
+pub const INVALIDATE_CHANNEL: &str = "__redis__:invalidate";
+
+/// Default max number of keys remembered for the clients in the default tracking mode.
+pub const DEFAULT_TRACKING_TABLE_MAX_KEYS: usize = 1_000_000;
+
+tokio::task_local! {
+    /// The client whose command is being executed by the current task.
+    static CLIENT: ClientContext;
+}
+
+/// The state of the client connection which is handled by the current task, which is
+/// consulted when the keys are read or modified.
+#[derive(Debug)]
+struct ClientContext {
+    id: u64,
+    /// Set to `true` if the keys read by the command are to be remembered.
+    tracks_reads: Cell<bool>,
+    /// Set to `true` if the command being executed doesn't modify the keys.
+    read_only: Cell<bool>,
+}
+
+/// Tracks the keys cached by the clients (client side caching), and sends invalidation
+/// messages to the clients when the keys are modified, expire or are deleted. The messages
+/// are pushed to the RESP3 clients, and published to the invalidation channel for the RESP2
+/// clients.
+///
+/// In the default mode, the keys read by a client are remembered, and the client is notified
+/// once when any of them is modified. In the broadcasting mode (BCAST), the clients are
+/// notified of the modifications to all the keys matching their prefixes, without the keys
+/// being remembered.
+#[derive(Debug)]
+pub struct Tracking {
+    pubsub: Arc<PubSub>,
+    /// Max number of keys remembered. Keys are invalidated proactively once there are more,
+    /// so that the table doesn't grow unbounded. `0` means there's no limit.
+    max_keys: usize,
+    /// The number of clients with tracking enabled, so that the table isn't locked when no
+    /// client is tracking the keys.
+    num_clients: AtomicUsize,
+    table: Mutex<TrackingTable>,
+}
+
+#[derive(Debug, Default)]
+struct TrackingTable {
+    /// The versions of RESP used by the connected clients, by their ids.
+    connected: HashMap<u64, ProtocolVersion>,
+    /// The tracking options of the clients which have tracking enabled, by their ids.
+    clients: HashMap<u64, TrackingOptions>,
+    /// The clients which have read a key, by the keys (default mode).
+    keys: HashMap<String, HashSet<u64>>,
+    /// The clients which are notified of the modifications to the keys with a prefix, by the
+    /// prefixes (broadcasting mode).
+    prefixes: HashMap<String, HashSet<u64>>,
+}
+
+/// The options of CLIENT TRACKING.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackingOptions {
+    /// The id of the client to which the invalidation messages are sent, instead of the
+    /// tracking client.
+    pub redirect: Option<u64>,
+    /// Enables the broadcasting mode.
+    pub bcast: bool,
+    /// The prefixes of the keys for which the invalidation messages are broadcast. All the
+    /// keys are matched if there's none.
+    pub prefixes: Vec<String>,
+    /// Keys are remembered only for the commands right after CLIENT CACHING yes.
+    pub optin: bool,
+    /// Keys are remembered except for the commands right after CLIENT CACHING no.
+    pub optout: bool,
+    /// The client isn't notified of the modifications made by its own commands.
+    pub noloop: bool,
+}
+
+impl Tracking {
+    /// Creates a new `Tracking` instance, which sends the invalidation messages through the
+    /// pub/sub.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubsub` - The subscriptions of all the clients, used for redirected invalidations.
+    ///
+    /// * `max_keys` - Max number of keys remembered, or `0` for no limit.
+    pub fn new(pubsub: Arc<PubSub>, max_keys: usize) -> Arc<Tracking> {
+        Arc::new(Tracking {
+            pubsub,
+            max_keys,
+            num_clients: AtomicUsize::new(0),
+            table: Mutex::new(TrackingTable::default()),
+        })
+    }
+
+    /// Registers a connected client, so that other clients can redirect the invalidation
+    /// messages to it. This is called again when the client switches the version of RESP,
+    /// which decides how the messages are delivered to it.
+    pub fn connect(&self, id: u64, version: ProtocolVersion) {
+        if let Some(mut table) = self.lock() {
+            table.connected.insert(id, version);
+        }
+    }
+
+    /// Unregisters a client once its connection is closed, disabling tracking for it.
+    pub fn disconnect(&self, id: u64) {
+        if let Some(mut table) = self.lock() {
+            table.connected.remove(&id);
+            self.remove_client(&mut table, id);
+        }
+    }
+
+    /// Enables tracking for a client (CLIENT TRACKING on). If tracking is already enabled,
+    /// the options are updated and the prefixes are added to the existing ones, but the mode
+    /// can't be changed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - If tracking is enabled.
+    /// * `Err(String)` - If the options are invalid, or the redirect client doesn't exist.
+    pub fn enable(&self, id: u64, options: TrackingOptions) -> Result<(), String> {
+        if !options.bcast && !options.prefixes.is_empty() {
+            return Err(String::from(
+                "PREFIX option requires BCAST mode to be enabled",
+            ));
+        }
+        if options.optin && options.optout {
+            return Err(String::from("You can't use both OPTIN and OPTOUT"));
+        }
+        if options.bcast && (options.optin || options.optout) {
+            return Err(String::from(
+                "OPTIN and OPTOUT are not compatible with BCAST",
+            ));
+        }
+
+        let mut table = match self.lock() {
+            Some(table) => table,
+            None => return Err(String::from("tracking table is unavailable")),
+        };
+
+        if let Some(redirect) = options.redirect {
+            if !table.connected.contains_key(&redirect) {
+                return Err(String::from(
+                    "The client ID you want redirect to does not exist",
+                ));
+            }
+        }
+
+        let mut prefixes = vec![];
+        if let Some(existing) = table.clients.get(&id) {
+            if existing.bcast != options.bcast {
+                return Err(String::from(
+                    "You can't switch BCAST mode on/off before disabling tracking for this client, and then re-enabling it with a different mode.",
+                ));
+            }
+            if existing.optin != options.optin || existing.optout != options.optout {
+                return Err(String::from(
+                    "You can't switch OPTIN/OPTOUT mode before disabling tracking for this client, and then re-enabling it with a different mode.",
+                ));
+            }
+            prefixes.clone_from(&existing.prefixes);
+        }
+
+        for prefix in options.prefixes.iter() {
+            if prefixes.contains(prefix) {
+                continue;
+            }
+            if let Some(other) = prefixes
+                .iter()
+                .find(|p| p.starts_with(prefix.as_str()) || prefix.starts_with(p.as_str()))
+            {
+                return Err(format!(
+                    "Prefix '{}' overlaps with an existing prefix '{}'. Prefixes for a single client must not overlap.",
+                    prefix, other
+                ));
+            }
+            prefixes.push(prefix.clone());
+        }
+
+        if options.bcast {
+            if prefixes.is_empty() {
+                table.prefixes.entry(String::new()).or_default().insert(id);
+            }
+            for prefix in prefixes.iter() {
+                table.prefixes.entry(prefix.clone()).or_default().insert(id);
+            }
+        }
+
+        let options = TrackingOptions {
+            prefixes,
+            ..options
+        };
+        if table.clients.insert(id, options).is_none() {
+            self.num_clients.fetch_add(1, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    /// Disables tracking for a client (CLIENT TRACKING off).
+    pub fn disable(&self, id: u64) {
+        if let Some(mut table) = self.lock() {
+            self.remove_client(&mut table, id);
+        }
+    }
+
+    /// Returns the tracking options of a client, or `None` if tracking isn't enabled.
+    pub fn options(&self, id: u64) -> Option<TrackingOptions> {
+        if self.num_clients.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+
+        self.lock()?.clients.get(&id).cloned()
+    }
+
+    /// Returns `true` if a client is connected.
+    pub fn is_connected(&self, id: u64) -> bool {
+        self.lock()
+            .is_some_and(|table| table.connected.contains_key(&id))
+    }
+
+    /// Returns `true` if the keys read by the next command of a client are to be
+    /// remembered, given the flag set by CLIENT CACHING before the command (if any).
+    pub fn tracks_reads(&self, id: u64, caching: Option<bool>) -> bool {
+        match self.options(id) {
+            Some(options) if options.bcast => false,
+            Some(options) if options.optin => caching == Some(true),
+            Some(options) if options.optout => caching != Some(false),
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    /// Remembers that a key is read by the client whose command is being executed, if the
+    /// command is read-only and the client is tracking its reads.
+    pub fn remember(&self, k: &str) {
+        if self.num_clients.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let id = match current_client() {
+            Some((id, true)) => id,
+            _ => return,
+        };
+
+        let mut table = match self.lock() {
+            Some(table) => table,
+            None => return,
+        };
+        match table.keys.get_mut(k) {
+            Some(clients) => {
+                clients.insert(id);
+            }
+            None => {
+                table.keys.insert(k.to_string(), HashSet::from([id]));
+            }
+        }
+
+        // arbitrary keys are invalidated to make room for the new ones
+        while self.max_keys > 0 && table.keys.len() > self.max_keys {
+            let evicted = match table.keys.keys().find(|key| key.as_str() != k) {
+                Some(key) => key.clone(),
+                None => break,
+            };
+            if let Some(clients) = table.keys.remove(&evicted) {
+                let payload = invalidated_keys(&evicted);
+                for client in clients {
+                    self.send(&table, client, payload.clone());
+                }
+            }
+        }
+    }
+
+    /// Sends the invalidation messages for a modified key to the clients which have read it,
+    /// and to the clients which are notified of the modifications to its prefix. The key is
+    /// forgotten, until it's read again.
+    pub fn invalidate(&self, k: &str) {
+        if self.num_clients.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let mut table = match self.lock() {
+            Some(table) => table,
+            None => return,
+        };
+
+        // a client with NOLOOP isn't notified of its own modifications
+        let modifier = current_client().map(|(id, _)| id);
+        let notified = |table: &TrackingTable, id: u64| {
+            Some(id) != modifier || table.clients.get(&id).is_none_or(|opts| !opts.noloop)
+        };
+
+        let payload = invalidated_keys(k);
+        if let Some(clients) = table.keys.remove(k) {
+            for id in clients {
+                if notified(&table, id) {
+                    self.send(&table, id, payload.clone());
+                }
+            }
+        }
+
+        for (prefix, clients) in table.prefixes.iter() {
+            if !k.starts_with(prefix.as_str()) {
+                continue;
+            }
+            for id in clients.iter() {
+                if notified(&table, *id) {
+                    self.send(&table, *id, payload.clone());
+                }
+            }
+        }
+    }
+
+    /// Sends an invalidation message for all the keys to all the tracking clients, and
+    /// forgets the keys. This is used when the databases are flushed or swapped.
+    pub fn invalidate_all(&self) {
+        if self.num_clients.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let mut table = match self.lock() {
+            Some(table) => table,
+            None => return,
+        };
+
+        table.keys.clear();
+        for id in table.clients.keys() {
+            self.send(&table, *id, RespType::NullArray);
+        }
+    }
+
+    /// Sends an invalidation message to a tracking client, or to the client to which its
+    /// messages are redirected. The message is pushed to a RESP3 client, and published to a
+    /// RESP2 client if it's subscribed to the invalidation channel. There's no way to notify
+    /// a RESP2 client which doesn't redirect the messages, so they're dropped.
+    ///
+    /// If the client to which the messages are redirected is disconnected, a RESP3 tracking
+    /// client is notified that the redirection is broken instead.
+    fn send(&self, table: &TrackingTable, id: u64, payload: RespType) {
+        let options = match table.clients.get(&id) {
+            Some(options) => options,
+            // tracking was disabled after the key was read
+            None => return,
+        };
+
+        let target = options.redirect.unwrap_or(id);
+        match table.connected.get(&target) {
+            Some(ProtocolVersion::Resp3) => {
+                let msg = RespType::Push(vec![
+                    RespType::BulkString(Bytes::from("invalidate")),
+                    payload,
+                ]);
+                self.pubsub.push_to(target, msg);
+            }
+            Some(ProtocolVersion::Resp2) if options.redirect.is_some() => {
+                self.pubsub.send_to(target, INVALIDATE_CHANNEL, payload);
+            }
+            None if table.connected.get(&id) == Some(&ProtocolVersion::Resp3) => {
+                let msg = RespType::Push(vec![
+                    RespType::BulkString(Bytes::from("tracking-redir-broken")),
+                    RespType::Integer(target as i64),
+                ]);
+                self.pubsub.push_to(id, msg);
+            }
+            _ => {}
+        }
+    }
+
+    /// Removes a client from the table, along with its prefixes. The keys which were read by
+    /// the client are not searched, and are skipped when they're invalidated.
+    fn remove_client(&self, table: &mut TrackingTable, id: u64) {
+        let options = match table.clients.remove(&id) {
+            Some(options) => options,
+            None => return,
+        };
+        self.num_clients.fetch_sub(1, Ordering::Relaxed);
+
+        if options.bcast {
+            table.prefixes.retain(|_, clients| {
+                clients.remove(&id);
+                !clients.is_empty()
+            });
+        }
+    }
+
+    fn lock(&self) -> Option<MutexGuard<'_, TrackingTable>> {
+        match self.table.lock() {
+            Ok(table) => Some(table),
+            Err(e) => {
+                error!("Failed to lock the tracking table: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Runs a future as the task of a client connection, so that the keys read and modified by
+/// its commands are attributed to the client.
+pub async fn scope<F: Future>(id: u64, f: F) -> F::Output {
+    let ctx = ClientContext {
+        id,
+        tracks_reads: Cell::new(false),
+        read_only: Cell::new(false),
+    };
+    CLIENT.scope(ctx, f).await
+}
+
+/// Sets whether the keys read by the next command of the current client are remembered. The
+/// command is assumed to modify the keys, until `set_read_only` is called.
+pub fn track_reads(tracks_reads: bool) {
+    let _ = CLIENT.try_with(|ctx| {
+        ctx.tracks_reads.set(tracks_reads);
+        ctx.read_only.set(false);
+    });
+}
+
+/// Sets whether the command which is about to be executed by the current client is
+/// read-only. Only the keys read by read-only commands are remembered.
+pub fn set_read_only(read_only: bool) {
+    let _ = CLIENT.try_with(|ctx| ctx.read_only.set(read_only));
+}
+
+/// Returns the id of the current client, and whether the keys read by its command are to
+/// be remembered.
+fn current_client() -> Option<(u64, bool)> {
+    CLIENT
+        .try_with(|ctx| (ctx.id, ctx.tracks_reads.get() && ctx.read_only.get()))
+        .ok()
+}
+
+/// Builds the payload of the invalidation message for a key.
+fn invalidated_keys(k: &str) -> RespType {
+    RespType::Array(vec![RespType::BulkString(Bytes::from(k.to_string()))])
+}